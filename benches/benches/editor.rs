@@ -0,0 +1,137 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use edtui::actions::{Execute, InsertChar, SwitchMode};
+use edtui::{EditorEventHandler, EditorMode, EditorState, EditorView, Index2, LineStorage};
+use edtui_benches::{long_line, state, state_with_history, wrapped_text};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+/// Typing into the middle of a large text.
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    group.throughput(Throughput::Elements(1000));
+    group.bench_function("1000 chars into 10k lines", |b| {
+        b.iter_batched(
+            || {
+                let mut state = state(10_000);
+                state.cursor = Index2::new(5_000, 40);
+                SwitchMode(EditorMode::Insert).execute(&mut state);
+                state
+            },
+            |mut state| {
+                for _ in 0..1000 {
+                    InsertChar('x').execute(&mut state);
+                }
+                state
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+/// Handling key events in a very large text, where every key must only cost
+/// as much as the rows it changes.
+fn keys(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keys");
+    group.throughput(Throughput::Elements(100));
+    group.bench_function("100 chars into 200k lines", |b| {
+        b.iter_batched(
+            || {
+                let mut state = state(200_000);
+                state.cursor = Index2::new(100_000, 40);
+                let mut handler = EditorEventHandler::default();
+                handler.feed_keys("i", &mut state).unwrap();
+                (state, handler)
+            },
+            |(mut state, mut handler)| {
+                for _ in 0..100 {
+                    handler.feed_keys("x", &mut state).unwrap();
+                }
+                state
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("100 downs in 200k lines", |b| {
+        b.iter_batched(
+            || {
+                let mut state = state(200_000);
+                state.cursor = Index2::new(100_000, 40);
+                (state, EditorEventHandler::default())
+            },
+            |(mut state, mut handler)| {
+                for _ in 0..100 {
+                    handler.feed_keys("j", &mut state).unwrap();
+                }
+                state
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    for storage in [LineStorage::Contiguous, LineStorage::GapBuffer] {
+        let name = format!("100 chars into a 1M chars line ({storage:?})");
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || {
+                    let mut state = EditorState::new(long_line(1_000_000));
+                    state.set_line_storage(storage);
+                    state.cursor = Index2::new(0, 500_000);
+                    let mut handler = EditorEventHandler::default();
+                    handler.feed_keys("i", &mut state).unwrap();
+                    (state, handler)
+                },
+                |(mut state, mut handler)| {
+                    for _ in 0..100 {
+                        handler.feed_keys("x", &mut state).unwrap();
+                    }
+                    state
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Rendering a frame of a large text with wrapped lines.
+fn render(c: &mut Criterion) {
+    let area = Rect::new(0, 0, 120, 40);
+    let mut state = edtui::EditorState::new(wrapped_text(100_000));
+    state.cursor = Index2::new(50_000, 0);
+
+    c.bench_function("render wrapped 100k lines", |b| {
+        b.iter(|| {
+            let mut buf = Buffer::empty(area);
+            EditorView::new(&mut state).wrap(true).render(area, &mut buf);
+            buf
+        });
+    });
+}
+
+/// Finding all matches in a large text.
+fn search(c: &mut Criterion) {
+    let mut state = state(100_000);
+    c.bench_function("search 100k lines", |b| {
+        b.iter(|| {
+            state.set_search_pattern("result");
+            state.search_matches().count()
+        });
+    });
+}
+
+/// Undoing and redoing changes in a large text.
+fn undo(c: &mut Criterion) {
+    c.bench_function("undo 100 changes in 10k lines", |b| {
+        b.iter_batched(
+            || state_with_history(10_000, 100),
+            |mut state| {
+                while state.undo() {}
+                while state.redo() {}
+                state
+            },
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+criterion_group!(benches, insert, keys, render, search, undo);
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+//! Fixtures for the edtui benchmarks.
+//!
+//! The fixtures are deterministic, so that numbers of different runs and
+//! machines can be compared. Contributors can use them to reproduce a
+//! performance regression in their own benchmarks.
+//!
+//! run: cargo bench --manifest-path benches/Cargo.toml
+use edtui::actions::{Execute, InsertChar, SwitchMode};
+use edtui::{EditorMode, EditorState, Lines};
+
+/// A line of source code like text with words of different lengths.
+const WORDS: [&str; 8] = [
+    "let", "value", "=", "compute(left,", "right);", "// the", "result", "is",
+];
+
+/// Returns a text with `rows` lines of roughly `width` characters.
+#[must_use]
+pub fn text(rows: usize, width: usize) -> Lines {
+    let lines: Vec<Vec<char>> = (0..rows)
+        .map(|row| {
+            let mut line = String::new();
+            for word in WORDS.iter().cycle().skip(row % WORDS.len()) {
+                if line.len() + word.len() >= width {
+                    break;
+                }
+                line.push_str(word);
+                line.push(' ');
+            }
+            line.trim_end().chars().collect()
+        })
+        .collect();
+    Lines::new(lines)
+}
+
+/// Returns a text with `rows` lines that are much wider than a terminal,
+/// so that every line wraps onto several display rows.
+#[must_use]
+pub fn wrapped_text(rows: usize) -> Lines {
+    text(rows, 400)
+}
+
+/// Returns a text with a single line of `width` characters, like a log line
+/// or minified JSON.
+#[must_use]
+pub fn long_line(width: usize) -> Lines {
+    text(1, width)
+}
+
+/// Returns a state with a text of `rows` lines of 80 characters.
+#[must_use]
+pub fn state(rows: usize) -> EditorState {
+    EditorState::new(text(rows, 80))
+}
+
+/// Returns a state whose undo history contains `changes` changes, each
+/// inserting a single character.
+#[must_use]
+pub fn state_with_history(rows: usize, changes: usize) -> EditorState {
+    let mut state = state(rows);
+    state.set_undo_limit(changes);
+    SwitchMode(EditorMode::Insert).execute(&mut state);
+    for i in 0..changes {
+        state.capture();
+        InsertChar(char::from(b'a' + (i % 26) as u8)).execute(&mut state);
+    }
+    SwitchMode(EditorMode::Normal).execute(&mut state);
+    state
+}
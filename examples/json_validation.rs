@@ -0,0 +1,187 @@
+use edtui::validation::{Validate, ValidationError};
+use edtui::{EditorEventHandler, EditorState, EditorTheme, EditorView, Index2, Lines};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyModifiers},
+    widgets::{Block, Widget},
+    DefaultTerminal,
+};
+use std::{error::Error, time::Duration};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+    let mut state = EditorState::new(Lines::from(
+        "{\n  \"name\": \"edtui\",\n  \"version\": 0.10,\n  \"tags\": [\"tui\" \"editor\"]\n}",
+    ));
+    state.set_validator(JsonValidator, Duration::from_millis(300));
+    let mut event_handler = EditorEventHandler::default();
+
+    loop {
+        terminal.draw(|frame| {
+            let block = Block::bordered().title(" JSON (]d: next error, Ctrl+c: quit) ");
+            EditorView::new(&mut state)
+                .theme(EditorTheme::default().block(block))
+                .render(frame.area(), frame.buffer_mut());
+        })?;
+
+        // Redraw periodically, so that the debounced validation runs.
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let event = event::read()?;
+        if let Event::Key(key) = &event {
+            if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+                break;
+            }
+        }
+        event_handler.on_event(event, &mut state);
+    }
+    Ok(())
+}
+
+/// Validates JSON text. Reports the first syntax error.
+struct JsonValidator;
+
+impl Validate for JsonValidator {
+    fn validate(&self, lines: &Lines) -> Vec<ValidationError> {
+        let chars: Vec<(char, Index2)> = lines
+            .iter()
+            .map(|(ch, index)| (ch.copied().unwrap_or('\n'), index))
+            .collect();
+        let mut parser = Parser { chars, pos: 0 };
+
+        let result = parser.value().and_then(|()| {
+            parser.skip_whitespace();
+            match parser.peek() {
+                None => Ok(()),
+                Some(_) => Err(String::from("Unexpected text after the value")),
+            }
+        });
+        match result {
+            Ok(()) => Vec::new(),
+            Err(message) => {
+                let index = parser.index();
+                vec![ValidationError::new(index, index, message)]
+            }
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<(char, Index2)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(ch, _)| *ch)
+    }
+
+    /// The position of the current character, or of the last character at
+    /// the end of the text.
+    fn index(&self) -> Index2 {
+        let pos = self.pos.min(self.chars.len().saturating_sub(1));
+        self.chars
+            .get(pos)
+            .map_or(Index2::new(0, 0), |(_, index)| *index)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("Expected `{expected}`"))
+        }
+    }
+
+    fn value(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.sequence('}', |parser| {
+                parser.skip_whitespace();
+                parser.string()?;
+                parser.expect(':')?;
+                parser.value()
+            }),
+            Some('[') => self.sequence(']', Self::value),
+            Some('"') => self.string(),
+            Some('-' | '0'..='9') => {
+                while self
+                    .peek()
+                    .is_some_and(|ch| ch.is_ascii_digit() || "+-.eE".contains(ch))
+                {
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+            Some(_) => ["true", "false", "null"]
+                .into_iter()
+                .find(|literal| {
+                    let end = self.pos + literal.len();
+                    self.chars
+                        .get(self.pos..end)
+                        .is_some_and(|chars| chars.iter().map(|(ch, _)| *ch).eq(literal.chars()))
+                })
+                .map(|literal| self.pos += literal.len())
+                .ok_or_else(|| String::from("Expected a value")),
+            None => Err(String::from("Unexpected end of text")),
+        }
+    }
+
+    /// Parses the items of an object or array, separated by commas.
+    fn sequence(
+        &mut self,
+        close: char,
+        item: impl Fn(&mut Self) -> Result<(), String>,
+    ) -> Result<(), String> {
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(ch) if ch == close => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(format!("Expected `,` or `{close}`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<(), String> {
+        if self.peek() != Some('"') {
+            return Err(String::from("Expected a string"));
+        }
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => self.pos += 2,
+                Some('\n') | None => return Err(String::from("Unterminated string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+}
@@ -1,14 +1,23 @@
 //! Editor actions such as move, insert, delete
+pub mod bookmark;
+pub mod command;
 pub mod cpaste;
 pub mod delete;
+pub mod exit;
+pub mod field;
+mod info;
 pub mod insert;
+pub mod location;
+pub mod macros;
 pub mod motion;
+pub mod operator;
 pub mod search;
 pub mod select;
 #[cfg(feature = "system-editor")]
 pub mod system_editor;
+use crate::state::notification::Notification;
 use crate::state::selection::Selection;
-use crate::{EditorMode, EditorState};
+use crate::{EditorMode, EditorState, Lines, SetContentsOptions};
 use cpaste::PasteOverSelection;
 use delete::DeleteToEndOfLine;
 use enum_dispatch::enum_dispatch;
@@ -16,25 +25,38 @@ use motion::{MoveToFirstRow, MoveToLastRow};
 #[cfg(feature = "system-editor")]
 pub use system_editor::OpenSystemEditor;
 
-pub use self::cpaste::{CopyLine, CopySelection, Paste};
+pub use self::bookmark::{NextBookmark, PrevBookmark, ToggleBookmark};
+pub use self::command::{AppendCharToCommand, RemoveCharFromCommand, RunCommand};
+pub use self::cpaste::{CopyLine, CopySelection, Paste, PasteCycleNext, SelectRegister};
 pub use self::delete::{
-    DeleteChar, DeleteCharForward, DeleteLine, DeleteSelection, DeleteToFirstCharOfLine,
-    JoinLineWithLineBelow, RemoveChar, ReplaceChar,
+    DeleteChar, DeleteCharForward, DeleteLine, DeleteMatchingLines, DeleteSelection,
+    DeleteToFirstCharOfLine, JoinLineWithLineBelow, KeepMatchingLines, RemoveChar, ReplaceChar,
 };
-pub use self::insert::{AppendNewline, InsertChar, InsertNewline, LineBreak};
+pub use self::exit::{PushIntent, RequestExit};
+pub use self::field::{NextField, PreviousField};
+pub use self::info::{ActionCategory, ActionInfo};
+pub use self::insert::{AppendNewline, InsertChar, InsertLastInserted, InsertNewline, LineBreak};
+pub use self::location::{NextLocation, OpenFileUnderCursor, PrevLocation};
+pub use self::macros::{PlayMacro, StartRecordingMacro, StopRecordingMacro};
 pub use self::motion::{
-    MoveBackward, MoveDown, MoveForward, MoveHalfPageDown, MoveHalfPageUp, MoveToEndOfLine,
-    MoveToFirst, MoveToMatchinBracket, MoveToStartOfLine, MoveUp, MoveWordBackward,
-    MoveWordForward, MoveWordForwardToEndOfWord,
+    MoveBackward, MoveBigWordBackwardToEndOfWord, MoveDown, MoveForward, MoveHalfPageDown,
+    MoveHalfPageUp, MovePageDown, MovePageUp, MoveSubwordBackward, MoveSubwordForward,
+    MoveSubwordForwardToEnd, MoveToEndOfLine, MoveToFirst, MoveToMatchinBracket, MoveToPercent,
+    MoveToStartOfLine, MoveUp, MoveWordBackward, MoveWordBackwardToEndOfWord, MoveWordForward,
+    MoveWordForwardToEndOfWord, NextError, SmartMoveToStart,
 };
+pub use self::operator::{Operator, OperatorMotion};
 use self::search::StartSearch;
 pub use self::search::{
-    AppendCharToSearch, FindFirst, FindNext, FindPrevious, RemoveCharFromSearch,
-    SelectCurrentSearch, StopSearch,
+    AppendCharToSearch, FindFirst, FindNext, FindPrevious, RemoveCharFromSearch, ReplaceAll,
+    ReplaceNext, SelectCurrentSearch, StartSearchOffset, StopSearch,
 };
 pub use self::select::{
-    ChangeInnerBetween, ChangeInnerWord, ChangeSelection, SelectInnerBetween, SelectInnerWord,
-    SelectLine,
+    AlignSelection, AppendBlock, ChangeInnerBetween, ChangeInnerWord, ChangeSelection, DedentLines,
+    DedentSelection, ExpandSelection, IndentLines, IndentSelection, InsertBlock,
+    LowercaseSelection, ReplaceSelection, ReselectLastVisual, SelectAroundIndent, SelectBlock,
+    SelectEntireBuffer, SelectInnerBetween, SelectInnerIndent, SelectInnerSubword, SelectInnerWord,
+    SelectLine, SelectTextObject, ShrinkSelection, SwapSelectionEnds, UppercaseSelection,
 };
 
 #[enum_dispatch(Execute)]
@@ -48,15 +70,26 @@ pub enum Action {
     MoveWordForward(MoveWordForward),
     MoveWordForwardToEndOfWord(MoveWordForwardToEndOfWord),
     MoveWordBackward(MoveWordBackward),
+    MoveWordBackwardToEndOfWord(MoveWordBackwardToEndOfWord),
+    MoveBigWordBackwardToEndOfWord(MoveBigWordBackwardToEndOfWord),
+    MoveSubwordForward(MoveSubwordForward),
+    MoveSubwordForwardToEnd(MoveSubwordForwardToEnd),
+    MoveSubwordBackward(MoveSubwordBackward),
     MoveToStartOfLine(MoveToStartOfLine),
     MoveToFirst(MoveToFirst),
+    SmartMoveToStart(SmartMoveToStart),
     MoveToEndOfLine(MoveToEndOfLine),
     MoveToFirstRow(MoveToFirstRow),
     MoveToLastRow(MoveToLastRow),
     MoveToMatchingBracket(MoveToMatchinBracket),
+    MoveToPercent(MoveToPercent),
+    NextError(NextError),
     MoveHalfPageDown(MoveHalfPageDown),
     MoveHalfPageUp(MoveHalfPageUp),
+    MovePageDown(MovePageDown),
+    MovePageUp(MovePageUp),
     InsertChar(InsertChar),
+    InsertLastInserted(InsertLastInserted),
     LineBreak(LineBreak),
     AppendNewline(AppendNewline),
     InsertNewline(InsertNewline),
@@ -65,35 +98,120 @@ pub enum Action {
     DeleteChar(DeleteChar),
     DeleteCharForward(DeleteCharForward),
     DeleteLine(DeleteLine),
+    DeleteMatchingLines(DeleteMatchingLines),
+    KeepMatchingLines(KeepMatchingLines),
     DeleteToFirstCharOfLine(DeleteToFirstCharOfLine),
     DeleteToEndOfLine(DeleteToEndOfLine),
     DeleteSelection(DeleteSelection),
     JoinLineWithLineBelow(JoinLineWithLineBelow),
     SelectInnerBetween(SelectInnerBetween),
     SelectInnerWord(SelectInnerWord),
+    SelectInnerSubword(SelectInnerSubword),
     ChangeInnerBetween(ChangeInnerBetween),
     ChangeInnerWord(ChangeInnerWord),
     ChangeSelection(ChangeSelection),
     SelectLine(SelectLine),
+    SelectBlock(SelectBlock),
+    InsertBlock(InsertBlock),
+    AppendBlock(AppendBlock),
+    AppendCharToCommand(AppendCharToCommand),
+    RemoveCharFromCommand(RemoveCharFromCommand),
+    RunCommand(RunCommand),
+    SelectEntireBuffer(SelectEntireBuffer),
+    SelectTextObject(SelectTextObject),
+    SelectInnerIndent(SelectInnerIndent),
+    SelectAroundIndent(SelectAroundIndent),
+    SwapSelectionEnds(SwapSelectionEnds),
+    ReselectLastVisual(ReselectLastVisual),
+    ExpandSelection(ExpandSelection),
+    ShrinkSelection(ShrinkSelection),
+    UppercaseSelection(UppercaseSelection),
+    LowercaseSelection(LowercaseSelection),
+    ReplaceSelection(ReplaceSelection),
+    AlignSelection(AlignSelection),
+    IndentSelection(IndentSelection),
+    DedentSelection(DedentSelection),
+    IndentLines(IndentLines),
+    DedentLines(DedentLines),
+    NextField(NextField),
+    ToggleBookmark(ToggleBookmark),
+    NextBookmark(NextBookmark),
+    PrevBookmark(PrevBookmark),
+    NextLocation(NextLocation),
+    OpenFileUnderCursor(OpenFileUnderCursor),
+    PrevLocation(PrevLocation),
+    PreviousField(PreviousField),
     Undo(Undo),
     Redo(Redo),
+    UndoN(UndoN),
+    RedoN(RedoN),
+    RepeatLastChange(RepeatLastChange),
+    StartRecordingMacro(StartRecordingMacro),
+    StopRecordingMacro(StopRecordingMacro),
+    PlayMacro(PlayMacro),
+    ShowCursorInfo(ShowCursorInfo),
+    SetContents(SetContents),
+    RequestExit(RequestExit),
+    PushIntent(PushIntent),
     Paste(Paste),
+    PasteCycleNext(PasteCycleNext),
+    SelectRegister(SelectRegister),
     PasteOverSelection(PasteOverSelection),
     CopySelection(CopySelection),
     CopyLine(CopyLine),
     Composed(Composed),
+    OperatorMotion(OperatorMotion),
+    Conditional(Conditional),
     StartSearch(StartSearch),
     StopSearch(StopSearch),
+    ReplaceAll(ReplaceAll),
+    ReplaceNext(ReplaceNext),
     FindFirst(FindFirst),
     FindNext(FindNext),
     FindPrevious(FindPrevious),
     SelectCurrentSearch(SelectCurrentSearch),
     AppendCharToSearch(AppendCharToSearch),
     RemoveCharFromSearch(RemoveCharFromSearch),
+    StartSearchOffset(StartSearchOffset),
     #[cfg(feature = "system-editor")]
     OpenSystemEditor(OpenSystemEditor),
 }
 
+impl Action {
+    /// Returns whether the action is a `T`, or a composed or conditional
+    /// action with a `T` step.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{Action, Composed, DeleteLine, MoveDown, SwitchMode};
+    /// use edtui::EditorMode;
+    ///
+    /// let action: Action = Composed::new(DeleteLine(1))
+    ///     .chain(SwitchMode(EditorMode::Insert))
+    ///     .into();
+    /// assert!(action.contains::<DeleteLine>());
+    /// assert!(!action.contains::<MoveDown>());
+    /// ```
+    #[must_use]
+    pub fn contains<T>(&self) -> bool
+    where
+        Action: TryInto<T>,
+    {
+        match self {
+            Self::Composed(composed) => composed.0.iter().any(Self::contains::<T>),
+            Self::Conditional(conditional) => {
+                conditional.then.contains::<T>()
+                    || conditional
+                        .otherwise
+                        .as_ref()
+                        .is_some_and(|otherwise| otherwise.contains::<T>())
+            }
+            _ => self.clone().try_into().is_ok(),
+        }
+    }
+}
+
 #[enum_dispatch]
 pub trait Execute {
     fn execute(&mut self, state: &mut EditorState);
@@ -105,8 +223,15 @@ pub struct SwitchMode(pub EditorMode);
 impl Execute for SwitchMode {
     fn execute(&mut self, state: &mut EditorState) {
         state.clamp_column();
+        if state.mode == EditorMode::Insert && self.0 != EditorMode::Insert {
+            let inserted = std::mem::take(&mut state.inserted);
+            state.finish_change_insert(&inserted);
+            if !inserted.is_empty() {
+                state.last_inserted = inserted;
+            }
+        }
         match self.0 {
-            EditorMode::Normal => {
+            EditorMode::Normal | EditorMode::Custom(_) => {
                 state.selection = None;
             }
             EditorMode::Visual => {
@@ -115,29 +240,122 @@ impl Execute for SwitchMode {
             EditorMode::Insert => {
                 if ![EditorMode::Insert, EditorMode::Search].contains(&state.mode) {
                     state.capture();
+                    state.inserted.clear();
                 }
             }
             EditorMode::Search => {}
+            EditorMode::Command => {
+                state.command_line.clear();
+                if state.mode == EditorMode::Visual {
+                    state.command_line.push_str("'<,'>");
+                    state.selection = None;
+                }
+            }
         }
         state.mode = self.0;
     }
 }
 
+/// Undoes the last change, placing the cursor where it occurred.
 #[derive(Clone, Debug)]
 pub struct Undo;
 
 impl Execute for Undo {
     fn execute(&mut self, state: &mut EditorState) {
-        state.undo();
+        UndoN(1).execute(state);
     }
 }
 
+/// Redoes the last undone change, placing the cursor where it occurred.
 #[derive(Clone, Debug)]
 pub struct Redo;
 
 impl Execute for Redo {
     fn execute(&mut self, state: &mut EditorState) {
-        state.redo();
+        RedoN(1).execute(state);
+    }
+}
+
+/// Undoes the given number of changes, placing the cursor where the last
+/// undone change occurred.
+#[derive(Clone, Debug)]
+pub struct UndoN(pub usize);
+
+impl Execute for UndoN {
+    fn execute(&mut self, state: &mut EditorState) {
+        let undone = (0..self.0).take_while(|_| state.undo()).count();
+        notify_history(state, undone, "undone", "Already at oldest change");
+    }
+}
+
+/// Redoes the given number of undone changes, placing the cursor where the
+/// last redone change occurred.
+#[derive(Clone, Debug)]
+pub struct RedoN(pub usize);
+
+impl Execute for RedoN {
+    fn execute(&mut self, state: &mut EditorState) {
+        let redone = (0..self.0).take_while(|_| state.redo()).count();
+        notify_history(state, redone, "redone", "Already at newest change");
+    }
+}
+
+/// Reports how many changes were undone or redone.
+fn notify_history(state: &mut EditorState, count: usize, verb: &str, none: &str) {
+    match count {
+        0 => state.notify(Notification::warning(none)),
+        1 => state.notify(format!("1 change {verb}")),
+        _ => state.notify(format!("{count} changes {verb}")),
+    }
+}
+
+/// Repeats the last change made in normal mode at the cursor, like `.` in
+/// vim. A change that entered insert mode, such as `ciw` or `A`, is repeated
+/// together with the text typed before insert mode was left. See
+/// [`EditorState::last_change`].
+#[derive(Clone, Debug, Copy)]
+pub struct RepeatLastChange(pub usize);
+
+impl Execute for RepeatLastChange {
+    fn execute(&mut self, state: &mut EditorState) {
+        // Taken while repeating, so that the change cannot repeat itself
+        let Some(change) = state.last_change.take() else {
+            return;
+        };
+        for _ in 0..self.0 {
+            change.action.clone().execute(state);
+            if state.mode == EditorMode::Insert {
+                for ch in change.inserted.chars() {
+                    InsertChar(ch).execute(state);
+                }
+                SwitchMode(EditorMode::Normal).execute(state);
+            }
+        }
+        state.last_change = Some(change);
+    }
+}
+
+/// Replaces the entire text of the editor, see
+/// [`EditorState::set_contents`]. Executing the action directly does not
+/// run the action callbacks, whatever [`SetContentsOptions::run_hooks`]
+/// is set to.
+#[derive(Clone, Debug)]
+pub struct SetContents(pub Lines, pub SetContentsOptions);
+
+impl Execute for SetContents {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.replace_contents(self.0.clone(), self.1);
+    }
+}
+
+/// Shows the position of the cursor in the status line, like `g Ctrl+g`
+/// in vim. See [`EditorState::cursor_info`].
+#[derive(Clone, Debug, Copy)]
+pub struct ShowCursorInfo;
+
+impl Execute for ShowCursorInfo {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.notify(state.cursor_info().to_string());
     }
 }
 
@@ -156,6 +374,29 @@ impl Composed {
         self.0.push(action.into());
         self
     }
+
+    /// Chains an action that is only executed if `condition` is met when
+    /// the step is reached.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{Composed, Condition, DeleteSelection, SelectInnerWord};
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// // Deletes the selection, or the word under the cursor if nothing is selected
+    /// let mut delete = Composed(Vec::new())
+    ///     .chain_if(Condition::Not(Box::new(Condition::HasSelection)), SelectInnerWord)
+    ///     .chain(DeleteSelection);
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello World"));
+    /// state.execute(delete);
+    /// assert_eq!(state.lines, Lines::from(" World"));
+    /// ```
+    #[must_use]
+    pub fn chain_if<A: Into<Action>>(self, condition: Condition, action: A) -> Self {
+        self.chain(Conditional::new(condition, action))
+    }
 }
 
 impl Execute for Composed {
@@ -166,10 +407,76 @@ impl Execute for Composed {
     }
 }
 
+/// A query on the editor state, see [`Conditional`].
+#[derive(Clone, Debug)]
+pub enum Condition {
+    /// Text is selected.
+    HasSelection,
+    /// The editor is in the given mode.
+    Mode(EditorMode),
+    /// The line under the cursor is empty.
+    EmptyLine,
+    /// The inner condition is not met.
+    Not(Box<Condition>),
+    /// A custom query.
+    Custom(fn(&EditorState) -> bool),
+}
+
+impl Condition {
+    /// Returns whether the condition is met.
+    #[must_use]
+    pub fn is_met(&self, state: &EditorState) -> bool {
+        match self {
+            Self::HasSelection => state.selection.is_some(),
+            Self::Mode(mode) => state.mode == *mode,
+            Self::EmptyLine => state.lines.len_col(state.cursor.row).unwrap_or_default() == 0,
+            Self::Not(condition) => !condition.is_met(state),
+            Self::Custom(query) => query(state),
+        }
+    }
+}
+
+/// Executes an action if a [`Condition`] is met, or an optional other action
+/// if it is not.
+#[derive(Clone, Debug)]
+pub struct Conditional {
+    condition: Condition,
+    then: Box<Action>,
+    otherwise: Option<Box<Action>>,
+}
+
+impl Conditional {
+    #[must_use]
+    pub fn new<A: Into<Action>>(condition: Condition, then: A) -> Self {
+        Self {
+            condition,
+            then: Box::new(then.into()),
+            otherwise: None,
+        }
+    }
+
+    /// Sets the action that is executed if the condition is not met.
+    #[must_use]
+    pub fn otherwise<A: Into<Action>>(mut self, otherwise: A) -> Self {
+        self.otherwise = Some(Box::new(otherwise.into()));
+        self
+    }
+}
+
+impl Execute for Conditional {
+    fn execute(&mut self, state: &mut EditorState) {
+        if self.condition.is_met(state) {
+            self.then.execute(state);
+        } else if let Some(otherwise) = &mut self.otherwise {
+            otherwise.execute(state);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::clipboard::InternalClipboard;
-    use crate::Lines;
+    use crate::{Index2, Lines};
 
     use super::*;
     fn test_state() -> EditorState {
@@ -189,4 +496,18 @@ mod tests {
         SwitchMode(EditorMode::Visual).execute(&mut state);
         assert_eq!(state.mode, EditorMode::Visual);
     }
+
+    #[test]
+    fn test_conditional() {
+        let mut state = test_state();
+        let mut action =
+            Conditional::new(Condition::EmptyLine, InsertChar('x')).otherwise(MoveDown(1));
+
+        action.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        SwitchMode(EditorMode::Insert).execute(&mut state);
+        action.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("Hello World!\nx\n123."));
+    }
 }
@@ -0,0 +1,56 @@
+use super::Execute;
+use crate::{EditorState, Index2};
+
+/// Adds a bookmark to the cursor line, or removes it if the line is
+/// bookmarked.
+#[derive(Clone, Debug, Copy)]
+pub struct ToggleBookmark;
+
+impl Execute for ToggleBookmark {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.toggle_bookmark(state.cursor.row);
+    }
+}
+
+/// Moves the cursor to the next bookmarked line, wrapping around to the
+/// first bookmark.
+#[derive(Clone, Debug, Copy)]
+pub struct NextBookmark;
+
+impl Execute for NextBookmark {
+    fn execute(&mut self, state: &mut EditorState) {
+        let row = state.cursor.row;
+        let next = state
+            .bookmarks
+            .range(row + 1..)
+            .next()
+            .or_else(|| state.bookmarks.first());
+        if let Some(&next) = next {
+            jump_to_bookmark(state, next);
+        }
+    }
+}
+
+/// Moves the cursor to the previous bookmarked line, wrapping around to the
+/// last bookmark.
+#[derive(Clone, Debug, Copy)]
+pub struct PrevBookmark;
+
+impl Execute for PrevBookmark {
+    fn execute(&mut self, state: &mut EditorState) {
+        let row = state.cursor.row;
+        let previous = state
+            .bookmarks
+            .range(..row)
+            .next_back()
+            .or_else(|| state.bookmarks.last());
+        if let Some(&previous) = previous {
+            jump_to_bookmark(state, previous);
+        }
+    }
+}
+
+fn jump_to_bookmark(state: &mut EditorState, row: usize) {
+    state.push_jump(state.cursor);
+    state.cursor = state.clamp_index(Index2::new(row, 0));
+}
@@ -0,0 +1,38 @@
+use super::{Execute, SwitchMode};
+use crate::{EditorMode, EditorState};
+
+/// Appends a character to the command line.
+#[derive(Clone, Debug, Copy)]
+pub struct AppendCharToCommand(pub char);
+
+impl Execute for AppendCharToCommand {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.command_line.push(self.0);
+    }
+}
+
+/// Removes the last character from the command line. Leaves the command
+/// line if it is empty, like backspace in vim.
+#[derive(Clone, Debug, Copy)]
+pub struct RemoveCharFromCommand;
+
+impl Execute for RemoveCharFromCommand {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.command_line.pop().is_none() {
+            SwitchMode(EditorMode::Normal).execute(state);
+        }
+    }
+}
+
+/// Switches to normal mode and runs the command typed into the command
+/// line. See [`EditorState::run_command`].
+#[derive(Clone, Debug, Copy)]
+pub struct RunCommand;
+
+impl Execute for RunCommand {
+    fn execute(&mut self, state: &mut EditorState) {
+        let command = std::mem::take(&mut state.command_line);
+        SwitchMode(EditorMode::Normal).execute(state);
+        state.run_command(&command);
+    }
+}
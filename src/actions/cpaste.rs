@@ -3,8 +3,8 @@ use std::cmp::min;
 use jagged::{index::RowIndex, Index2};
 
 use crate::{
-    clipboard::ClipboardTrait,
     helper::{append_str, insert_str, max_row},
+    state::selection::Selection,
     EditorState,
 };
 
@@ -18,27 +18,60 @@ pub struct Paste;
 
 impl Execute for Paste {
     fn execute(&mut self, state: &mut EditorState) {
-        let s = state.clip.get_text();
+        let s = state.paste_text();
         if s.is_empty() {
             return;
         }
+        let cursor = state.cursor;
+        paste(state, &s);
+        let index = state.yank_ring.position(&s);
+        state
+            .yank_ring
+            .pasted(index, cursor, (state.undo.len(), state.cursor));
+    }
+}
 
-        state.capture();
-        state.clamp_column();
+/// Replaces the text of a paste with the previous text of the yank ring, if
+/// the paste was the last command. Cycles through the ring when repeated,
+/// like `yank-pop` in emacs.
+#[derive(Clone, Debug)]
+pub struct PasteCycleNext;
 
-        let s = if let Some(stripped) = s.strip_prefix('\n') {
-            state.cursor = Index2::new(min(max_row(state), state.cursor.row + 1), 0);
-            state.lines.insert(RowIndex::new(state.cursor.row), vec![]);
-            stripped
-        } else {
-            state.clamp_column();
-            &s
+impl Execute for PasteCycleNext {
+    fn execute(&mut self, state: &mut EditorState) {
+        let after = (state.undo.len(), state.cursor);
+        let Some((text, index, cursor)) = state.yank_ring.cycle(after) else {
+            return;
         };
-
-        append_str(&mut state.lines, &mut state.cursor, s);
+        state.undo();
+        state.cursor = cursor;
+        paste(state, &text);
+        state
+            .yank_ring
+            .pasted(Some(index), cursor, (state.undo.len(), state.cursor));
     }
 }
 
+/// Pastes `s` after the cursor, or below the current line if it starts
+/// with a newline.
+fn paste(state: &mut EditorState, s: &str) {
+    state.capture();
+    state.clamp_column();
+
+    let s = if let Some(stripped) = s.strip_prefix('\n') {
+        state.cursor = Index2::new(min(max_row(state), state.cursor.row + 1), 0);
+        state.touch_rows(state.cursor.row..state.cursor.row);
+        state.lines.insert(RowIndex::new(state.cursor.row), vec![]);
+        stripped
+    } else {
+        state.clamp_column();
+        state.touch_row(state.cursor.row);
+        s
+    };
+
+    append_str(&mut state.lines, &mut state.cursor, s);
+}
+
 #[derive(Clone, Debug)]
 pub struct PasteOverSelection;
 
@@ -48,19 +81,37 @@ impl Execute for PasteOverSelection {
             state.capture();
             state.clamp_column();
             let _ = delete_selection(state, &selection);
-            insert_str(&mut state.lines, &mut state.cursor, &state.clip.get_text());
+            let text = state.paste_text();
+            state.touch_row(state.cursor.row);
+            insert_str(&mut state.lines, &mut state.cursor, &text);
         }
     }
 }
 
+/// Selects the register used by the next yank, deletion or paste, like `"`
+/// in vim: `'1'` to `'9'` and `'-'` paste earlier deletions, `'a'` to `'z'`
+/// are named registers. See [`EditorState::register`].
+#[derive(Clone, Debug, Copy)]
+pub struct SelectRegister(pub char);
+
+impl Execute for SelectRegister {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.registers.selected = Some(self.0);
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CopySelection;
 
 impl Execute for CopySelection {
     fn execute(&mut self, state: &mut EditorState) {
-        if let Some(s) = &state.selection {
-            state.clip.set_text(s.copy_from(&state.lines).into());
-            state.selection = None;
+        if let Some(s) = state.selection.take() {
+            state.yank(s.copy_from(&state.lines).into());
+            let num_lines = s.end().row - s.start().row + 1;
+            if num_lines > 2 {
+                state.notify(format!("{num_lines} lines yanked"));
+            }
+            state.yank_flash.flash(s);
         }
     }
 }
@@ -72,7 +123,9 @@ impl Execute for CopyLine {
     fn execute(&mut self, state: &mut EditorState) {
         if let Some(line) = state.lines.get(RowIndex::new(state.cursor.row)) {
             let text = String::from('\n') + &line.iter().collect::<String>();
-            state.clip.set_text(text);
+            state.yank(text);
+            let row = Index2::new(state.cursor.row, 0);
+            state.yank_flash.flash(Selection::new(row, row).line_mode());
         }
     }
 }
@@ -80,8 +133,8 @@ impl Execute for CopyLine {
 #[cfg(test)]
 mod tests {
     use crate::actions::Undo;
+    use crate::clipboard::ClipboardTrait;
     use crate::clipboard::InternalClipboard;
-    use crate::state::selection::Selection;
     use crate::Index2;
     use crate::Lines;
 
@@ -135,4 +188,30 @@ mod tests {
         assert_eq!(state.lines, Lines::from("Hello World!\n\n123."));
         assert_eq!(state.mode, EditorMode::Visual);
     }
+
+    #[test]
+    fn test_paste_cycle_next() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+        state.set_clipboard(InternalClipboard::default());
+        CopyLine.execute(&mut state);
+        state.cursor = Index2::new(1, 0);
+        CopyLine.execute(&mut state);
+        assert_eq!(state.yank_ring().collect::<Vec<_>>(), vec!["\nb", "\na"]);
+
+        state.cursor = Index2::new(0, 0);
+        Paste.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\nb\nb\nc"));
+
+        PasteCycleNext.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\na\nb\nc"));
+        PasteCycleNext.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\nb\nb\nc"));
+
+        state.cursor = Index2::new(3, 0);
+        PasteCycleNext.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\nb\nb\nc"));
+
+        Undo.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\nb\nc"));
+    }
 }
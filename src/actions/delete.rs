@@ -2,10 +2,9 @@ use jagged::index::RowIndex;
 
 use super::Execute;
 use crate::{
-    clipboard::ClipboardTrait,
     helper::{is_out_of_bounds, max_col_insert},
     state::selection::Selection,
-    EditorState, Index2, Lines,
+    EditorMode, EditorState, Index2, Lines,
 };
 
 /// Deletes a character at the current cursor position. Does not
@@ -18,6 +17,7 @@ impl Execute for RemoveChar {
     fn execute(&mut self, state: &mut EditorState) {
         state.capture();
         state.clamp_column();
+        state.touch_row(state.cursor.row);
         for _ in 0..self.0 {
             let lines = &mut state.lines;
             let index = &mut state.cursor;
@@ -49,6 +49,7 @@ impl Execute for ReplaceChar {
             return;
         }
         state.capture();
+        state.touch_row(index.row);
         if let Some(ch) = state.lines.get_mut(index) {
             *ch = self.0;
         };
@@ -65,7 +66,12 @@ impl Execute for DeleteChar {
     fn execute(&mut self, state: &mut EditorState) {
         state.capture();
         for _ in 0..self.0 {
+            // Deleting the line break joins the line with the line above
+            state.touch_rows(state.cursor.row.saturating_sub(1)..state.cursor.row + 1);
             delete_char(&mut state.lines, &mut state.cursor);
+            if state.mode == EditorMode::Insert {
+                state.inserted.pop();
+            }
         }
     }
 }
@@ -112,6 +118,7 @@ impl Execute for DeleteCharForward {
         state.capture();
         state.clamp_column();
         for _ in 0..self.0 {
+            state.touch_rows(state.cursor.row..state.cursor.row + 2);
             delete_char_forward(&mut state.lines, &mut state.cursor);
         }
     }
@@ -144,19 +151,84 @@ pub struct DeleteLine(pub usize);
 impl Execute for DeleteLine {
     fn execute(&mut self, state: &mut EditorState) {
         state.capture();
+        let mut deleted = String::new();
         for _ in 0..self.0 {
             if state.cursor.row >= state.lines.len() {
                 break;
             }
+            state.touch_row(state.cursor.row);
             let row_index = RowIndex::new(state.cursor.row);
-            let deleted_line = state.lines.remove(row_index).iter().collect::<String>();
-            state.clip.set_text(String::from('\n') + &deleted_line);
+            deleted.push('\n');
+            deleted.extend(state.lines.remove(row_index));
             state.cursor.col = 0;
             state.cursor.row = state.cursor.row.min(state.lines.len().saturating_sub(1));
         }
+        if !deleted.is_empty() {
+            state.yank_deleted(deleted);
+        }
+    }
+}
+
+/// Deletes all lines that contain a pattern, like `:g/pattern/d` in vim.
+/// The pattern is matched literally. All deletions are a single undo step.
+#[derive(Clone, Debug)]
+pub struct DeleteMatchingLines(pub String);
+
+impl Execute for DeleteMatchingLines {
+    fn execute(&mut self, state: &mut EditorState) {
+        delete_lines_where(state, &self.0, true);
+    }
+}
+
+/// Keeps only the lines that contain a pattern and deletes all others, like
+/// `:v/pattern/d` in vim. The pattern is matched literally. All deletions are
+/// a single undo step.
+#[derive(Clone, Debug)]
+pub struct KeepMatchingLines(pub String);
+
+impl Execute for KeepMatchingLines {
+    fn execute(&mut self, state: &mut EditorState) {
+        delete_lines_where(state, &self.0, false);
     }
 }
 
+/// Deletes the lines for which containing `pattern` equals `matching`. The
+/// cursor stays on its line, or moves to the next remaining line if its line
+/// was deleted.
+fn delete_lines_where(state: &mut EditorState, pattern: &str, matching: bool) {
+    let pattern: Vec<char> = pattern.chars().collect();
+    if pattern.is_empty() {
+        return;
+    }
+    let rows: Vec<usize> = (0..state.lines.len())
+        .filter(|&row| {
+            state.lines.get(RowIndex::new(row)).is_some_and(|line| {
+                line.windows(pattern.len()).any(|window| window == pattern) == matching
+            })
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+
+    state.capture();
+    state.touch_rows(rows[0]..rows[rows.len() - 1] + 1);
+    for &row in rows.iter().rev() {
+        let _ = state.lines.remove(RowIndex::new(row));
+    }
+    if state.lines.is_empty() {
+        state.lines = Lines::from("");
+    }
+
+    let cursor = state.cursor;
+    let deleted_above = rows.iter().filter(|&&row| row < cursor.row).count();
+    state.cursor.row = (cursor.row - deleted_above).min(state.lines.last_row_index());
+    if rows.contains(&cursor.row) {
+        state.cursor.col = 0;
+    }
+    state.clamp_column();
+}
+
 /// Deletes from the current cursor position to the first non-whitespace character of the line
 #[derive(Clone, Debug, Copy)]
 pub struct DeleteToFirstCharOfLine;
@@ -164,6 +236,7 @@ pub struct DeleteToFirstCharOfLine;
 impl Execute for DeleteToFirstCharOfLine {
     fn execute(&mut self, state: &mut EditorState) {
         state.capture();
+        state.touch_row(state.cursor.row);
 
         let row_index = RowIndex::new(state.cursor.row);
         let Some(row) = state.lines.get_mut(row_index) else {
@@ -181,7 +254,7 @@ impl Execute for DeleteToFirstCharOfLine {
 
         if anchor < col && col <= row.len() {
             let deleted = row.drain(anchor..col).collect();
-            state.clip.set_text(deleted);
+            state.yank_deleted(deleted);
         }
 
         state.cursor.col = anchor;
@@ -198,12 +271,13 @@ impl Execute for DeleteToEndOfLine {
             return;
         }
         state.capture();
+        state.touch_row(state.cursor.row);
         let Some(row) = state.lines.get_mut(RowIndex::new(state.cursor.row)) else {
             return;
         };
-        let deleted_chars = row.drain(state.cursor.col..);
+        let deleted: String = row.drain(state.cursor.col..).collect();
         state.cursor.col = state.cursor.col.saturating_sub(1);
-        state.clip.set_text(deleted_chars.collect());
+        state.yank_deleted(deleted);
     }
 }
 
@@ -216,16 +290,18 @@ impl Execute for DeleteSelection {
         if let Some(selection) = state.selection.take() {
             state.capture();
             let drained = delete_selection(state, &selection);
-            state.clip.set_text(drained.into());
+            state.yank_deleted(drained.into());
         }
         state.selection = None;
     }
 }
 
 pub(crate) fn delete_selection(state: &mut EditorState, selection: &Selection) -> Lines {
-    state.cursor = selection.start();
+    state.touch_rows(selection.start().row..selection.end().row + 1);
+    let extracted = selection.extract_from(&mut state.lines);
+    state.cursor = state.clamp_index(selection.start());
     state.clamp_column();
-    selection.extract_from(&mut state.lines)
+    extracted
 }
 
 /// Joins line below to the current line.
@@ -238,12 +314,14 @@ impl Execute for JoinLineWithLineBelow {
             return;
         }
         state.capture();
+        state.touch_rows(state.cursor.row..state.cursor.row + 2);
         state.lines.join_lines(state.cursor.row);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::clipboard::ClipboardTrait;
     use crate::state::selection::Selection;
     use crate::EditorMode;
     use crate::Index2;
@@ -340,6 +418,23 @@ mod tests {
         assert_eq!(state.lines, Lines::from(""));
     }
 
+    #[test]
+    fn test_delete_matching_lines() {
+        let mut state = EditorState::new(Lines::from("INFO a\nDEBUG b\nINFO c\nDEBUG d"));
+        state.cursor = Index2::new(2, 3);
+
+        state.execute(DeleteMatchingLines(String::from("DEBUG")));
+        assert_eq!(state.lines, Lines::from("INFO a\nINFO c"));
+        assert_eq!(state.cursor, Index2::new(1, 3));
+
+        state.execute(KeepMatchingLines(String::from("a")));
+        assert_eq!(state.lines, Lines::from("INFO a"));
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        state.execute(KeepMatchingLines(String::from("x")));
+        assert_eq!(state.lines, Lines::from(""));
+    }
+
     #[test]
     fn test_delete_to_first_char_of_line() {
         let mut state = EditorState::new(Lines::from("  Hello World!"));
@@ -430,4 +525,22 @@ mod tests {
         assert_eq!(state.cursor, Index2::new(0, 10));
         assert_eq!(state.lines, Lines::from("HelloWorld"));
     }
+
+    #[test]
+    fn test_delete_selection_in_line_mode() {
+        let mut state = test_state();
+        state.cursor = Index2::new(1, 0);
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(1, 0)).line_mode());
+
+        DeleteSelection.execute(&mut state);
+
+        assert_eq!(state.lines, Lines::from("123."));
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        assert_eq!(state.clip.get_text(), "\nHello World!\n");
+
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(0, 3)).line_mode());
+        DeleteSelection.execute(&mut state);
+        assert_eq!(state.lines.len(), 1);
+        assert_eq!(state.lines.len_col(0), Some(0));
+    }
 }
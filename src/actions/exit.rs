@@ -0,0 +1,36 @@
+use super::Execute;
+use crate::{EditorIntent, EditorState, ExitRequest};
+
+/// Asks the app to close the editor, with or without saving the text. The
+/// request is passed to the callback registered with
+/// [`EditorState::on_exit_request`]. Without a callback, the matching
+/// [`EditorIntent`]s are pushed to the intent queue. The text is not changed.
+#[derive(Clone, Debug, Copy)]
+pub struct RequestExit(pub ExitRequest);
+
+impl Execute for RequestExit {
+    fn execute(&mut self, state: &mut EditorState) {
+        match state.exit_hook.clone() {
+            Some(hook) => {
+                state.touch_all_rows();
+                hook(state, self.0);
+            }
+            None => {
+                for intent in EditorIntent::from_exit(self.0) {
+                    state.push_intent(intent);
+                }
+            }
+        }
+    }
+}
+
+/// Pushes an intent to the queue that the app drains with
+/// [`EditorState::drain_intents`], e.g. to bind a key to an app command.
+#[derive(Clone, Debug)]
+pub struct PushIntent(pub EditorIntent);
+
+impl Execute for PushIntent {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.push_intent(self.0.clone());
+    }
+}
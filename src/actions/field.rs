@@ -0,0 +1,117 @@
+use super::insert::tab_chars;
+use super::{Execute, InsertChar};
+use crate::{EditorMode, EditorState, Index2};
+
+/// Moves the cursor to the next field, wrapping around to the first field.
+/// Without fields, a tab is inserted in insert mode, so that `Tab` can be
+/// bound to this action in insert mode.
+#[derive(Clone, Debug, Copy)]
+pub struct NextField;
+
+impl Execute for NextField {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.fields().is_empty() {
+            if state.mode == EditorMode::Insert {
+                for ch in tab_chars(state) {
+                    InsertChar(ch).execute(state);
+                }
+            }
+            return;
+        }
+        let fields = sorted_fields(state);
+        let next = match current_field(&fields, state.cursor) {
+            Some(i) => (i + 1) % fields.len(),
+            None => 0,
+        };
+        if let Some(&(start, end)) = fields.get(next) {
+            move_to_field(state, start, end);
+        }
+    }
+}
+
+/// Moves the cursor to the previous field, wrapping around to the last field.
+#[derive(Clone, Debug, Copy)]
+pub struct PreviousField;
+
+impl Execute for PreviousField {
+    fn execute(&mut self, state: &mut EditorState) {
+        let fields = sorted_fields(state);
+        let previous = match current_field(&fields, state.cursor) {
+            Some(i) if state.cursor <= fields[i].1 => (i + fields.len() - 1) % fields.len(),
+            Some(i) => i,
+            None => fields.len().saturating_sub(1),
+        };
+        if let Some(&(start, end)) = fields.get(previous) {
+            move_to_field(state, start, end);
+        }
+    }
+}
+
+/// Returns the start and end of all fields, ordered by their start.
+fn sorted_fields(state: &EditorState) -> Vec<(Index2, Index2)> {
+    let mut fields: Vec<_> = state
+        .fields()
+        .iter()
+        .map(|field| (field.start, field.end))
+        .collect();
+    fields.sort_by_key(|(start, end)| ((start.row, start.col), (end.row, end.col)));
+    fields
+}
+
+/// Returns the index of the last field that starts at or before the cursor.
+fn current_field(fields: &[(Index2, Index2)], cursor: Index2) -> Option<usize> {
+    fields.iter().rposition(|(start, _)| *start <= cursor)
+}
+
+/// In insert mode the cursor is placed at the end of the field, ready to type,
+/// otherwise at its start.
+fn move_to_field(state: &mut EditorState, start: Index2, end: Index2) {
+    if state.mode == EditorMode::Insert {
+        state.cursor = end;
+    } else {
+        state.cursor = start;
+        state.clamp_column();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, Lines};
+
+    fn test_state() -> EditorState {
+        let mut state = EditorState::new(Lines::from("Name: Bo\nAge: 42"));
+        state.set_fields(vec![
+            Field::new("age", Index2::new(1, 5), Index2::new(1, 7)),
+            Field::new("name", Index2::new(0, 6), Index2::new(0, 8)),
+        ]);
+        state
+    }
+
+    #[test]
+    fn test_next_field() {
+        let mut state = test_state();
+
+        NextField.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 6));
+
+        NextField.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 5));
+
+        NextField.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 6));
+    }
+
+    #[test]
+    fn test_previous_field() {
+        let mut state = test_state();
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(1, 6);
+
+        PreviousField.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 8));
+
+        PreviousField.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 7));
+    }
+}
@@ -0,0 +1,932 @@
+//! Introspection of actions, e.g. for config UIs or which-key popups.
+use std::fmt;
+
+#[cfg(feature = "system-editor")]
+use super::OpenSystemEditor;
+use super::{
+    cpaste::PasteOverSelection,
+    delete::DeleteToEndOfLine,
+    motion::{MoveToFirstRow, MoveToLastRow},
+    search::StartSearch,
+    *,
+};
+use crate::{EditorIntent, EditorMode, ExitRequest};
+
+/// Describes a built-in action.
+#[derive(Clone, Debug)]
+pub struct ActionInfo {
+    /// The name of the action, e.g. `"MoveForward"`.
+    pub name: &'static str,
+    /// A short, human readable description.
+    pub description: &'static str,
+    /// The category of the action.
+    pub category: ActionCategory,
+    /// Creates the action with default parameters, e.g. a count of 1.
+    pub create: fn() -> Action,
+}
+
+/// The category of an action, e.g. to group actions in a help screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ActionCategory {
+    /// Switching between modes.
+    Mode,
+    /// Moving the cursor.
+    Motion,
+    /// Selecting text.
+    Selection,
+    /// Changing the text, including the clipboard and the undo history.
+    Edit,
+    /// Searching and replacing text.
+    Search,
+    /// Everything else.
+    Other,
+}
+
+impl fmt::Display for ActionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+const fn info(
+    name: &'static str,
+    description: &'static str,
+    category: ActionCategory,
+    create: fn() -> Action,
+) -> ActionInfo {
+    ActionInfo {
+        name,
+        description,
+        category,
+        create,
+    }
+}
+
+/// The built-in actions. The combinators [`Composed`] and [`Conditional`]
+/// are not included, nor is [`SetContents`], which carries a whole text.
+const BUILTIN_ACTIONS: &[ActionInfo] = &[
+    info(
+        "SwitchMode",
+        "Switch the editor mode",
+        ActionCategory::Mode,
+        || SwitchMode(EditorMode::Normal).into(),
+    ),
+    info(
+        "MoveForward",
+        "Move the cursor forward",
+        ActionCategory::Motion,
+        || MoveForward(1).into(),
+    ),
+    info(
+        "MoveBackward",
+        "Move the cursor backward",
+        ActionCategory::Motion,
+        || MoveBackward(1).into(),
+    ),
+    info(
+        "MoveUp",
+        "Move the cursor up",
+        ActionCategory::Motion,
+        || MoveUp(1).into(),
+    ),
+    info(
+        "MoveDown",
+        "Move the cursor down",
+        ActionCategory::Motion,
+        || MoveDown(1).into(),
+    ),
+    info(
+        "MoveWordForward",
+        "Move to the start of the next word",
+        ActionCategory::Motion,
+        || MoveWordForward(1).into(),
+    ),
+    info(
+        "MoveWordForwardToEndOfWord",
+        "Move to the end of the next word",
+        ActionCategory::Motion,
+        || MoveWordForwardToEndOfWord(1).into(),
+    ),
+    info(
+        "MoveWordBackward",
+        "Move to the start of the previous word",
+        ActionCategory::Motion,
+        || MoveWordBackward(1).into(),
+    ),
+    info(
+        "MoveWordBackwardToEndOfWord",
+        "Move to the end of the previous word",
+        ActionCategory::Motion,
+        || MoveWordBackwardToEndOfWord(1).into(),
+    ),
+    info(
+        "MoveBigWordBackwardToEndOfWord",
+        "Move to the end of the previous WORD",
+        ActionCategory::Motion,
+        || MoveBigWordBackwardToEndOfWord(1).into(),
+    ),
+    info(
+        "MoveSubwordForward",
+        "Move to the start of the next sub-word",
+        ActionCategory::Motion,
+        || MoveSubwordForward(1).into(),
+    ),
+    info(
+        "MoveSubwordForwardToEnd",
+        "Move to the end of the next sub-word",
+        ActionCategory::Motion,
+        || MoveSubwordForwardToEnd(1).into(),
+    ),
+    info(
+        "MoveSubwordBackward",
+        "Move to the start of the previous sub-word",
+        ActionCategory::Motion,
+        || MoveSubwordBackward(1).into(),
+    ),
+    info(
+        "MoveToStartOfLine",
+        "Move to the start of the line",
+        ActionCategory::Motion,
+        || MoveToStartOfLine().into(),
+    ),
+    info(
+        "MoveToFirst",
+        "Move to the first non-whitespace character of the line",
+        ActionCategory::Motion,
+        || MoveToFirst().into(),
+    ),
+    info(
+        "SmartMoveToStart",
+        "Toggle between the first non-whitespace character and the start of the line",
+        ActionCategory::Motion,
+        || SmartMoveToStart().into(),
+    ),
+    info(
+        "MoveToEndOfLine",
+        "Move to the end of the line",
+        ActionCategory::Motion,
+        || MoveToEndOfLine().into(),
+    ),
+    info(
+        "MoveToFirstRow",
+        "Move to the first row",
+        ActionCategory::Motion,
+        || MoveToFirstRow().into(),
+    ),
+    info(
+        "MoveToLastRow",
+        "Move to the last row",
+        ActionCategory::Motion,
+        || MoveToLastRow().into(),
+    ),
+    info(
+        "MoveToMatchingBracket",
+        "Move to the matching bracket",
+        ActionCategory::Motion,
+        || MoveToMatchinBracket().into(),
+    ),
+    info(
+        "MoveToPercent",
+        "Move to a percentage of the text",
+        ActionCategory::Motion,
+        || MoveToPercent(50).into(),
+    ),
+    info(
+        "NextError",
+        "Jump to the next validation error",
+        ActionCategory::Motion,
+        || NextError.into(),
+    ),
+    info(
+        "MoveHalfPageDown",
+        "Scroll half a page down",
+        ActionCategory::Motion,
+        || MoveHalfPageDown().into(),
+    ),
+    info(
+        "MoveHalfPageUp",
+        "Scroll half a page up",
+        ActionCategory::Motion,
+        || MoveHalfPageUp().into(),
+    ),
+    info(
+        "MovePageDown",
+        "Scroll a page down",
+        ActionCategory::Motion,
+        || MovePageDown().into(),
+    ),
+    info(
+        "MovePageUp",
+        "Scroll a page up",
+        ActionCategory::Motion,
+        || MovePageUp().into(),
+    ),
+    info(
+        "InsertChar",
+        "Insert a character",
+        ActionCategory::Edit,
+        || InsertChar(' ').into(),
+    ),
+    info(
+        "InsertLastInserted",
+        "Insert the text of the last insert session",
+        ActionCategory::Edit,
+        || InsertLastInserted.into(),
+    ),
+    info(
+        "LineBreak",
+        "Break the line at the cursor",
+        ActionCategory::Edit,
+        || LineBreak(1).into(),
+    ),
+    info(
+        "AppendNewline",
+        "Append a new line below the current line",
+        ActionCategory::Edit,
+        || AppendNewline(1).into(),
+    ),
+    info(
+        "InsertNewline",
+        "Insert a new line above the current line",
+        ActionCategory::Edit,
+        || InsertNewline(1).into(),
+    ),
+    info(
+        "ReplaceChar",
+        "Replace the character under the cursor",
+        ActionCategory::Edit,
+        || ReplaceChar(' ').into(),
+    ),
+    info(
+        "RemoveChar",
+        "Delete the character under the cursor",
+        ActionCategory::Edit,
+        || RemoveChar(1).into(),
+    ),
+    info(
+        "DeleteChar",
+        "Delete the character before the cursor",
+        ActionCategory::Edit,
+        || DeleteChar(1).into(),
+    ),
+    info(
+        "DeleteCharForward",
+        "Delete the character after the cursor",
+        ActionCategory::Edit,
+        || DeleteCharForward(1).into(),
+    ),
+    info(
+        "DeleteLine",
+        "Delete the current line",
+        ActionCategory::Edit,
+        || DeleteLine(1).into(),
+    ),
+    info(
+        "DeleteMatchingLines",
+        "Delete all lines that contain a pattern",
+        ActionCategory::Edit,
+        || DeleteMatchingLines(String::new()).into(),
+    ),
+    info(
+        "KeepMatchingLines",
+        "Delete all lines that do not contain a pattern",
+        ActionCategory::Edit,
+        || KeepMatchingLines(String::new()).into(),
+    ),
+    info(
+        "DeleteToFirstCharOfLine",
+        "Delete to the first non-whitespace character of the line",
+        ActionCategory::Edit,
+        || DeleteToFirstCharOfLine.into(),
+    ),
+    info(
+        "DeleteToEndOfLine",
+        "Delete to the end of the line",
+        ActionCategory::Edit,
+        || DeleteToEndOfLine.into(),
+    ),
+    info(
+        "DeleteSelection",
+        "Delete the selection",
+        ActionCategory::Edit,
+        || DeleteSelection.into(),
+    ),
+    info(
+        "JoinLineWithLineBelow",
+        "Join the current line with the line below",
+        ActionCategory::Edit,
+        || JoinLineWithLineBelow.into(),
+    ),
+    info(
+        "SelectInnerBetween",
+        "Select the text between two delimiters",
+        ActionCategory::Selection,
+        || SelectInnerBetween::new('(', ')').into(),
+    ),
+    info(
+        "SelectInnerWord",
+        "Select the word under the cursor",
+        ActionCategory::Selection,
+        || SelectInnerWord.into(),
+    ),
+    info(
+        "SelectInnerSubword",
+        "Select the sub-word under the cursor",
+        ActionCategory::Selection,
+        || SelectInnerSubword.into(),
+    ),
+    info(
+        "ChangeInnerBetween",
+        "Change the text between two delimiters",
+        ActionCategory::Edit,
+        || ChangeInnerBetween::new('(', ')').into(),
+    ),
+    info(
+        "ChangeInnerWord",
+        "Change the word under the cursor",
+        ActionCategory::Edit,
+        || ChangeInnerWord.into(),
+    ),
+    info(
+        "ChangeSelection",
+        "Change the selection",
+        ActionCategory::Edit,
+        || ChangeSelection.into(),
+    ),
+    info(
+        "SelectLine",
+        "Select the current line",
+        ActionCategory::Selection,
+        || SelectLine.into(),
+    ),
+    info(
+        "SelectBlock",
+        "Select a block of columns",
+        ActionCategory::Selection,
+        || SelectBlock.into(),
+    ),
+    info(
+        "InsertBlock",
+        "Insert text in front of the selection on every row",
+        ActionCategory::Edit,
+        || InsertBlock.into(),
+    ),
+    info(
+        "AppendBlock",
+        "Append text after the selection on every row",
+        ActionCategory::Edit,
+        || AppendBlock.into(),
+    ),
+    info(
+        "SelectEntireBuffer",
+        "Select the entire text",
+        ActionCategory::Selection,
+        || SelectEntireBuffer.into(),
+    ),
+    info(
+        "SelectTextObject",
+        "Select a custom text object",
+        ActionCategory::Selection,
+        || SelectTextObject('n').into(),
+    ),
+    info(
+        "SelectInnerIndent",
+        "Select the lines at the indentation level of the cursor",
+        ActionCategory::Selection,
+        || SelectInnerIndent.into(),
+    ),
+    info(
+        "SelectAroundIndent",
+        "Select the lines at the indentation level of the cursor and the line above",
+        ActionCategory::Selection,
+        || SelectAroundIndent.into(),
+    ),
+    info(
+        "SwapSelectionEnds",
+        "Move the cursor to the other end of the selection",
+        ActionCategory::Selection,
+        || SwapSelectionEnds.into(),
+    ),
+    info(
+        "ReselectLastVisual",
+        "Reselect the last visual selection",
+        ActionCategory::Selection,
+        || ReselectLastVisual.into(),
+    ),
+    info(
+        "ExpandSelection",
+        "Expand the selection to the enclosing text object",
+        ActionCategory::Selection,
+        || ExpandSelection.into(),
+    ),
+    info(
+        "ShrinkSelection",
+        "Shrink the selection to the previous text object",
+        ActionCategory::Selection,
+        || ShrinkSelection.into(),
+    ),
+    info(
+        "UppercaseSelection",
+        "Convert the selection to uppercase",
+        ActionCategory::Edit,
+        || UppercaseSelection.into(),
+    ),
+    info(
+        "LowercaseSelection",
+        "Convert the selection to lowercase",
+        ActionCategory::Edit,
+        || LowercaseSelection.into(),
+    ),
+    info(
+        "ReplaceSelection",
+        "Replace each selected character",
+        ActionCategory::Edit,
+        || ReplaceSelection(' ').into(),
+    ),
+    info(
+        "AlignSelection",
+        "Align the selected lines on a delimiter",
+        ActionCategory::Edit,
+        || AlignSelection('=').into(),
+    ),
+    info(
+        "IndentSelection",
+        "Indent the selected lines",
+        ActionCategory::Edit,
+        || IndentSelection(1).into(),
+    ),
+    info(
+        "DedentSelection",
+        "Dedent the selected lines",
+        ActionCategory::Edit,
+        || DedentSelection(1).into(),
+    ),
+    info(
+        "IndentLines",
+        "Indent lines from the cursor by one level",
+        ActionCategory::Edit,
+        || IndentLines(1).into(),
+    ),
+    info(
+        "DedentLines",
+        "Dedent lines from the cursor by one level",
+        ActionCategory::Edit,
+        || DedentLines(1).into(),
+    ),
+    info(
+        "OperatorMotion",
+        "Apply an operator to the text covered by a motion",
+        ActionCategory::Edit,
+        || OperatorMotion::new(Operator::Delete, MoveWordForward(1)).into(),
+    ),
+    info(
+        "NextField",
+        "Move to the next field",
+        ActionCategory::Motion,
+        || NextField.into(),
+    ),
+    info(
+        "PreviousField",
+        "Move to the previous field",
+        ActionCategory::Motion,
+        || PreviousField.into(),
+    ),
+    info(
+        "ToggleBookmark",
+        "Toggle a bookmark on the current line",
+        ActionCategory::Other,
+        || ToggleBookmark.into(),
+    ),
+    info(
+        "NextBookmark",
+        "Jump to the next bookmark",
+        ActionCategory::Motion,
+        || NextBookmark.into(),
+    ),
+    info(
+        "PrevBookmark",
+        "Jump to the previous bookmark",
+        ActionCategory::Motion,
+        || PrevBookmark.into(),
+    ),
+    info(
+        "NextLocation",
+        "Jump to the next entry of the location list",
+        ActionCategory::Motion,
+        || NextLocation.into(),
+    ),
+    info(
+        "PrevLocation",
+        "Jump to the previous entry of the location list",
+        ActionCategory::Motion,
+        || PrevLocation.into(),
+    ),
+    info(
+        "OpenFileUnderCursor",
+        "Open the file under the cursor",
+        ActionCategory::Other,
+        || OpenFileUnderCursor.into(),
+    ),
+    info("Undo", "Undo the last change", ActionCategory::Edit, || {
+        Undo.into()
+    }),
+    info(
+        "Redo",
+        "Redo the last undone change",
+        ActionCategory::Edit,
+        || Redo.into(),
+    ),
+    info(
+        "UndoN",
+        "Undo a number of changes",
+        ActionCategory::Edit,
+        || UndoN(1).into(),
+    ),
+    info(
+        "RedoN",
+        "Redo a number of undone changes",
+        ActionCategory::Edit,
+        || RedoN(1).into(),
+    ),
+    info(
+        "RepeatLastChange",
+        "Repeat the last change",
+        ActionCategory::Edit,
+        || RepeatLastChange(1).into(),
+    ),
+    info(
+        "StartRecordingMacro",
+        "Start recording a macro",
+        ActionCategory::Other,
+        || StartRecordingMacro('q').into(),
+    ),
+    info(
+        "StopRecordingMacro",
+        "Stop recording a macro",
+        ActionCategory::Other,
+        || StopRecordingMacro.into(),
+    ),
+    info("PlayMacro", "Play a macro", ActionCategory::Other, || {
+        PlayMacro('q').into()
+    }),
+    info(
+        "ShowCursorInfo",
+        "Show the position of the cursor",
+        ActionCategory::Other,
+        || ShowCursorInfo.into(),
+    ),
+    info(
+        "RequestExit",
+        "Ask the app to close the editor",
+        ActionCategory::Other,
+        || RequestExit(ExitRequest::SaveAndClose).into(),
+    ),
+    info(
+        "PushIntent",
+        "Ask the app to carry out an intent",
+        ActionCategory::Other,
+        || PushIntent(EditorIntent::Save).into(),
+    ),
+    info(
+        "Paste",
+        "Paste from the clipboard",
+        ActionCategory::Edit,
+        || Paste.into(),
+    ),
+    info(
+        "PasteCycleNext",
+        "Replace the last paste with the previous yanked text",
+        ActionCategory::Edit,
+        || PasteCycleNext.into(),
+    ),
+    info(
+        "SelectRegister",
+        "Use a register for the next yank, deletion or paste",
+        ActionCategory::Edit,
+        || SelectRegister('"').into(),
+    ),
+    info(
+        "PasteOverSelection",
+        "Replace the selection with the clipboard",
+        ActionCategory::Edit,
+        || PasteOverSelection.into(),
+    ),
+    info(
+        "CopySelection",
+        "Copy the selection",
+        ActionCategory::Edit,
+        || CopySelection.into(),
+    ),
+    info(
+        "CopyLine",
+        "Copy the current line",
+        ActionCategory::Edit,
+        || CopyLine.into(),
+    ),
+    info(
+        "StartSearch",
+        "Start a search",
+        ActionCategory::Search,
+        || StartSearch.into(),
+    ),
+    info(
+        "StopSearch",
+        "Stop the search",
+        ActionCategory::Search,
+        || StopSearch.into(),
+    ),
+    info(
+        "ReplaceAll",
+        "Replace all occurrences of a pattern",
+        ActionCategory::Search,
+        || ReplaceAll::new("", "").into(),
+    ),
+    info(
+        "ReplaceNext",
+        "Replace the next occurrence of a pattern",
+        ActionCategory::Search,
+        || ReplaceNext::new("", "").into(),
+    ),
+    info(
+        "FindFirst",
+        "Jump to the first search match",
+        ActionCategory::Search,
+        || FindFirst.into(),
+    ),
+    info(
+        "FindNext",
+        "Jump to the next search match",
+        ActionCategory::Search,
+        || FindNext.into(),
+    ),
+    info(
+        "FindPrevious",
+        "Jump to the previous search match",
+        ActionCategory::Search,
+        || FindPrevious.into(),
+    ),
+    info(
+        "SelectCurrentSearch",
+        "Move to the current search match",
+        ActionCategory::Search,
+        || SelectCurrentSearch.into(),
+    ),
+    info(
+        "AppendCharToSearch",
+        "Append a character to the search pattern",
+        ActionCategory::Search,
+        || AppendCharToSearch(' ').into(),
+    ),
+    info(
+        "RemoveCharFromSearch",
+        "Remove the last character of the search pattern",
+        ActionCategory::Search,
+        || RemoveCharFromSearch.into(),
+    ),
+    info(
+        "StartSearchOffset",
+        "Start typing an offset after the search pattern",
+        ActionCategory::Search,
+        || StartSearchOffset.into(),
+    ),
+    info(
+        "AppendCharToCommand",
+        "Append a character to the command line",
+        ActionCategory::Other,
+        || AppendCharToCommand(' ').into(),
+    ),
+    info(
+        "RemoveCharFromCommand",
+        "Remove the last character from the command line",
+        ActionCategory::Other,
+        || RemoveCharFromCommand.into(),
+    ),
+    info(
+        "RunCommand",
+        "Run the command typed into the command line",
+        ActionCategory::Other,
+        || RunCommand.into(),
+    ),
+    #[cfg(feature = "system-editor")]
+    info(
+        "OpenSystemEditor",
+        "Open the text in the system editor",
+        ActionCategory::Other,
+        || OpenSystemEditor.into(),
+    ),
+];
+
+impl Action {
+    /// Returns the name of the action, e.g. `"MoveForward"`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SwitchMode(_) => "SwitchMode",
+            Self::MoveForward(_) => "MoveForward",
+            Self::MoveBackward(_) => "MoveBackward",
+            Self::MoveUp(_) => "MoveUp",
+            Self::MoveDown(_) => "MoveDown",
+            Self::MoveWordForward(_) => "MoveWordForward",
+            Self::MoveWordForwardToEndOfWord(_) => "MoveWordForwardToEndOfWord",
+            Self::MoveWordBackward(_) => "MoveWordBackward",
+            Self::MoveWordBackwardToEndOfWord(_) => "MoveWordBackwardToEndOfWord",
+            Self::MoveBigWordBackwardToEndOfWord(_) => "MoveBigWordBackwardToEndOfWord",
+            Self::MoveSubwordForward(_) => "MoveSubwordForward",
+            Self::MoveSubwordForwardToEnd(_) => "MoveSubwordForwardToEnd",
+            Self::MoveSubwordBackward(_) => "MoveSubwordBackward",
+            Self::MoveToStartOfLine(_) => "MoveToStartOfLine",
+            Self::MoveToFirst(_) => "MoveToFirst",
+            Self::SmartMoveToStart(_) => "SmartMoveToStart",
+            Self::MoveToEndOfLine(_) => "MoveToEndOfLine",
+            Self::MoveToFirstRow(_) => "MoveToFirstRow",
+            Self::MoveToLastRow(_) => "MoveToLastRow",
+            Self::MoveToMatchingBracket(_) => "MoveToMatchingBracket",
+            Self::MoveToPercent(_) => "MoveToPercent",
+            Self::NextError(_) => "NextError",
+            Self::MoveHalfPageDown(_) => "MoveHalfPageDown",
+            Self::MoveHalfPageUp(_) => "MoveHalfPageUp",
+            Self::MovePageDown(_) => "MovePageDown",
+            Self::MovePageUp(_) => "MovePageUp",
+            Self::InsertChar(_) => "InsertChar",
+            Self::InsertLastInserted(_) => "InsertLastInserted",
+            Self::LineBreak(_) => "LineBreak",
+            Self::AppendNewline(_) => "AppendNewline",
+            Self::InsertNewline(_) => "InsertNewline",
+            Self::ReplaceChar(_) => "ReplaceChar",
+            Self::RemoveChar(_) => "RemoveChar",
+            Self::DeleteChar(_) => "DeleteChar",
+            Self::DeleteCharForward(_) => "DeleteCharForward",
+            Self::DeleteLine(_) => "DeleteLine",
+            Self::DeleteMatchingLines(_) => "DeleteMatchingLines",
+            Self::KeepMatchingLines(_) => "KeepMatchingLines",
+            Self::DeleteToFirstCharOfLine(_) => "DeleteToFirstCharOfLine",
+            Self::DeleteToEndOfLine(_) => "DeleteToEndOfLine",
+            Self::DeleteSelection(_) => "DeleteSelection",
+            Self::JoinLineWithLineBelow(_) => "JoinLineWithLineBelow",
+            Self::SelectInnerBetween(_) => "SelectInnerBetween",
+            Self::SelectInnerWord(_) => "SelectInnerWord",
+            Self::SelectInnerSubword(_) => "SelectInnerSubword",
+            Self::ChangeInnerBetween(_) => "ChangeInnerBetween",
+            Self::ChangeInnerWord(_) => "ChangeInnerWord",
+            Self::ChangeSelection(_) => "ChangeSelection",
+            Self::SelectLine(_) => "SelectLine",
+            Self::SelectBlock(_) => "SelectBlock",
+            Self::InsertBlock(_) => "InsertBlock",
+            Self::AppendBlock(_) => "AppendBlock",
+            Self::AppendCharToCommand(_) => "AppendCharToCommand",
+            Self::RemoveCharFromCommand(_) => "RemoveCharFromCommand",
+            Self::RunCommand(_) => "RunCommand",
+            Self::SelectEntireBuffer(_) => "SelectEntireBuffer",
+            Self::SelectTextObject(_) => "SelectTextObject",
+            Self::SelectInnerIndent(_) => "SelectInnerIndent",
+            Self::SelectAroundIndent(_) => "SelectAroundIndent",
+            Self::SwapSelectionEnds(_) => "SwapSelectionEnds",
+            Self::ReselectLastVisual(_) => "ReselectLastVisual",
+            Self::ExpandSelection(_) => "ExpandSelection",
+            Self::ShrinkSelection(_) => "ShrinkSelection",
+            Self::UppercaseSelection(_) => "UppercaseSelection",
+            Self::LowercaseSelection(_) => "LowercaseSelection",
+            Self::ReplaceSelection(_) => "ReplaceSelection",
+            Self::AlignSelection(_) => "AlignSelection",
+            Self::IndentSelection(_) => "IndentSelection",
+            Self::DedentSelection(_) => "DedentSelection",
+            Self::IndentLines(_) => "IndentLines",
+            Self::DedentLines(_) => "DedentLines",
+            Self::NextField(_) => "NextField",
+            Self::PreviousField(_) => "PreviousField",
+            Self::ToggleBookmark(_) => "ToggleBookmark",
+            Self::NextBookmark(_) => "NextBookmark",
+            Self::PrevBookmark(_) => "PrevBookmark",
+            Self::NextLocation(_) => "NextLocation",
+            Self::OpenFileUnderCursor(_) => "OpenFileUnderCursor",
+            Self::PrevLocation(_) => "PrevLocation",
+            Self::Undo(_) => "Undo",
+            Self::Redo(_) => "Redo",
+            Self::UndoN(_) => "UndoN",
+            Self::RedoN(_) => "RedoN",
+            Self::RepeatLastChange(_) => "RepeatLastChange",
+            Self::StartRecordingMacro(_) => "StartRecordingMacro",
+            Self::StopRecordingMacro(_) => "StopRecordingMacro",
+            Self::PlayMacro(_) => "PlayMacro",
+            Self::ShowCursorInfo(_) => "ShowCursorInfo",
+            Self::SetContents(_) => "SetContents",
+            Self::RequestExit(_) => "RequestExit",
+            Self::PushIntent(_) => "PushIntent",
+            Self::Paste(_) => "Paste",
+            Self::PasteCycleNext(_) => "PasteCycleNext",
+            Self::SelectRegister(_) => "SelectRegister",
+            Self::PasteOverSelection(_) => "PasteOverSelection",
+            Self::CopySelection(_) => "CopySelection",
+            Self::CopyLine(_) => "CopyLine",
+            Self::StartSearch(_) => "StartSearch",
+            Self::StopSearch(_) => "StopSearch",
+            Self::ReplaceAll(_) => "ReplaceAll",
+            Self::ReplaceNext(_) => "ReplaceNext",
+            Self::FindFirst(_) => "FindFirst",
+            Self::FindNext(_) => "FindNext",
+            Self::FindPrevious(_) => "FindPrevious",
+            Self::SelectCurrentSearch(_) => "SelectCurrentSearch",
+            Self::AppendCharToSearch(_) => "AppendCharToSearch",
+            Self::RemoveCharFromSearch(_) => "RemoveCharFromSearch",
+            Self::StartSearchOffset(_) => "StartSearchOffset",
+            Self::Composed(_) => "Composed",
+            Self::OperatorMotion(_) => "OperatorMotion",
+            Self::Conditional(_) => "Conditional",
+            #[cfg(feature = "system-editor")]
+            Self::OpenSystemEditor(_) => "OpenSystemEditor",
+        }
+    }
+
+    /// Returns the parameters of the action, formatted for display, e.g.
+    /// `"1"` for `MoveForward(1)`. Empty for actions without parameters.
+    #[must_use]
+    pub fn params(&self) -> String {
+        let debug = format!("{self:?}");
+        // The debug output is `Variant(Inner)`, where `Inner` is `Name`,
+        // `Name(params)` or `Name { params }`
+        let inner = &debug[self.name().len() + 1..debug.len() - 1];
+        let Some(start) = inner.find(['(', '{']) else {
+            return String::new();
+        };
+        inner[start + 1..inner.len() - 1].trim().to_string()
+    }
+
+    /// Returns the built-in actions with a description, e.g. to list them in
+    /// a help screen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::Action;
+    ///
+    /// let info = Action::builtins().find(|info| info.name == "MoveForward").unwrap();
+    /// assert_eq!((info.create)().to_string(), "MoveForward(1)");
+    /// ```
+    pub fn builtins() -> impl Iterator<Item = &'static ActionInfo> {
+        BUILTIN_ACTIONS.iter()
+    }
+
+    /// Returns the description of a built-in action, if any. Composed actions
+    /// are described by their first step.
+    #[must_use]
+    pub fn description(&self) -> Option<&'static str> {
+        self.info().map(|info| info.description)
+    }
+
+    /// Returns the category of the action. Composed actions belong to the
+    /// category of their first step.
+    #[must_use]
+    pub fn category(&self) -> ActionCategory {
+        self.info()
+            .map_or(ActionCategory::Other, |info| info.category)
+    }
+
+    fn info(&self) -> Option<&'static ActionInfo> {
+        match self {
+            Self::Composed(composed) => composed.0.first().and_then(Self::info),
+            _ => {
+                let name = self.name();
+                Self::builtins().find(|info| info.name == name)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = self.params();
+        if params.is_empty() {
+            write!(f, "{}", self.name())
+        } else {
+            write!(f, "{}({params})", self.name())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_and_params() {
+        let action: Action = MoveForward(3).into();
+        assert_eq!(action.name(), "MoveForward");
+        assert_eq!(action.params(), "3");
+        assert_eq!(action.to_string(), "MoveForward(3)");
+
+        let action: Action = SelectInnerBetween::new('(', ')').into();
+        assert_eq!(action.params(), "opening: '(', closing: ')'");
+
+        let action: Action = MoveToStartOfLine().into();
+        assert_eq!(action.to_string(), "MoveToStartOfLine");
+        assert_eq!(action.description(), Some("Move to the start of the line"));
+        assert_eq!(action.category(), ActionCategory::Motion);
+    }
+
+    #[test]
+    fn test_builtins_match_their_names() {
+        for info in Action::builtins() {
+            assert_eq!((info.create)().name(), info.name);
+        }
+    }
+}
@@ -2,8 +2,8 @@ use jagged::index::RowIndex;
 
 use super::Execute;
 use crate::{
-    helper::{insert_char, line_break},
-    EditorState,
+    helper::{chars_width, insert_char, line_break},
+    EditorMode, EditorState, Index2, Lines, VirtualEdit,
 };
 
 /// Inserts a single character at the current cursor position
@@ -12,21 +12,135 @@ pub struct InsertChar(pub char);
 
 impl Execute for InsertChar {
     fn execute(&mut self, state: &mut EditorState) {
+        state.touch_row(state.cursor.row);
+        // Pad the line if the cursor is placed beyond its end.
+        if state.virtual_edit(state.mode) == VirtualEdit::All {
+            if let Some(line) = state.lines.get_mut(RowIndex::new(state.cursor.row)) {
+                if state.cursor.col > line.len() {
+                    line.resize(state.cursor.col, ' ');
+                }
+            }
+        }
         insert_char(&mut state.lines, &mut state.cursor, self.0, false);
+        if state.mode == EditorMode::Insert {
+            state.inserted.push(self.0);
+            if !self.0.is_whitespace() {
+                wrap_at_text_width(state);
+            }
+        }
     }
 }
 
-/// Inserts a newline at the current cursor position
+/// Breaks the cursor line at the last whitespace that keeps it within the
+/// text width, if the cursor moved beyond the text width.
+fn wrap_at_text_width(state: &mut EditorState) {
+    let Some(text_width) = state.text_width else {
+        return;
+    };
+    let Some(line) = state.lines.get_mut(RowIndex::new(state.cursor.row)) else {
+        return;
+    };
+    if state.cursor.col <= text_width {
+        return;
+    }
+
+    // The whitespace must follow some text, otherwise the line can't be broken.
+    let Some(start) = line[..=text_width.min(line.len() - 1)]
+        .iter()
+        .rposition(|ch| ch.is_whitespace())
+        .filter(|&i| line[..i].iter().any(|ch| !ch.is_whitespace()))
+    else {
+        return;
+    };
+    let start = line[..start]
+        .iter()
+        .rposition(|ch| !ch.is_whitespace())
+        .map_or(0, |i| i + 1);
+    let end = line[start..]
+        .iter()
+        .position(|ch| !ch.is_whitespace())
+        .map_or(line.len(), |i| start + i);
+
+    let rest = line.split_off(end);
+    line.truncate(start);
+    state.cursor = Index2::new(state.cursor.row + 1, state.cursor.col - end);
+    state.lines.insert(RowIndex::new(state.cursor.row), rest);
+}
+
+/// Inserts a newline at the current cursor position. With
+/// [`EditorConfig::auto_indent`], the new line is indented like the
+/// current line.
+///
+/// [`EditorConfig::auto_indent`]: crate::EditorConfig::auto_indent
 #[derive(Clone, Debug, Copy)]
 pub struct LineBreak(pub usize);
 
 impl Execute for LineBreak {
     fn execute(&mut self, state: &mut EditorState) {
         if state.lines.is_empty() {
+            state.touch_rows(0..0);
             state.lines.push(Vec::new());
         }
         for _ in 0..self.0 {
+            let indent = if state.auto_indent {
+                leading_whitespace(&state.lines, state.cursor)
+            } else {
+                Vec::new()
+            };
+            state.touch_row(state.cursor.row);
             line_break(&mut state.lines, &mut state.cursor);
+            if state.mode == EditorMode::Insert {
+                state.inserted.push('\n');
+            }
+            if let Some(line) = state.lines.get_mut(RowIndex::new(state.cursor.row)) {
+                state.cursor.col = indent.len();
+                line.splice(0..0, indent);
+            }
+        }
+    }
+}
+
+/// Returns the whitespace at the start of the cursor line, up to the cursor.
+fn leading_whitespace(lines: &Lines, cursor: Index2) -> Vec<char> {
+    let Some(line) = lines.get(RowIndex::new(cursor.row)) else {
+        return Vec::new();
+    };
+    line.iter()
+        .take(cursor.col)
+        .take_while(|ch| ch.is_whitespace())
+        .copied()
+        .collect()
+}
+
+/// Returns the characters that a tab inserts at the cursor: a tab or, with
+/// [`EditorConfig::expand_tab`], spaces up to the next multiple of the tab
+/// width.
+///
+/// [`EditorConfig::expand_tab`]: crate::EditorConfig::expand_tab
+pub(crate) fn tab_chars(state: &EditorState) -> Vec<char> {
+    if !state.expand_tab {
+        return vec!['\t'];
+    }
+    let tab_width = state.view.tab_width.max(1);
+    let width = state
+        .lines
+        .get(RowIndex::new(state.cursor.row))
+        .map_or(0, |line| {
+            chars_width(&line[..state.cursor.col.min(line.len())], tab_width)
+        });
+    vec![' '; tab_width - width % tab_width]
+}
+
+/// Inserts the text that was inserted during the last insert session, see
+/// [`EditorState::last_inserted`]. Intended to be called in insert mode.
+#[derive(Clone, Debug, Copy)]
+pub struct InsertLastInserted;
+
+impl Execute for InsertLastInserted {
+    fn execute(&mut self, state: &mut EditorState) {
+        let text = state.last_inserted.clone();
+        for ch in text.chars() {
+            InsertChar(ch).execute(state);
         }
     }
 }
@@ -43,6 +157,7 @@ impl Execute for AppendNewline {
             if !state.lines.is_empty() {
                 state.cursor.row += 1;
             }
+            state.touch_rows(state.cursor.row..state.cursor.row);
             if state.cursor.row < state.lines.len() {
                 state.lines.insert(RowIndex::new(state.cursor.row), vec![]);
             } else {
@@ -59,6 +174,7 @@ pub struct InsertNewline(pub usize);
 impl Execute for InsertNewline {
     fn execute(&mut self, state: &mut EditorState) {
         state.cursor.col = 0;
+        state.touch_rows(state.cursor.row..state.cursor.row);
         for _ in 0..self.0 {
             state.lines.insert(RowIndex::new(state.cursor.row), vec![]);
         }
@@ -73,12 +189,14 @@ pub struct PushLine<'a>(pub &'a str);
 impl Execute for PushLine<'_> {
     fn execute(&mut self, state: &mut EditorState) {
         let chars: Vec<char> = self.0.chars().collect();
+        state.touch_rows(state.lines.len()..state.lines.len());
         state.lines.push(chars);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::actions::{MoveForward, SwitchMode};
     use crate::{Index2, Lines};
 
     use super::*;
@@ -100,6 +218,22 @@ mod tests {
         assert_eq!(state.lines, Lines::from("!Hello World!!\n\n123."));
     }
 
+    #[test]
+    fn test_insert_char_wraps_at_text_width() {
+        let mut state = EditorState::new(Lines::from("Hello  Worl"));
+        state.set_text_width(Some(8));
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(0, 11);
+
+        InsertChar('d').execute(&mut state);
+        assert_eq!(state.lines, Lines::from("Hello\nWorld"));
+        assert_eq!(state.cursor, Index2::new(1, 5));
+
+        InsertChar(' ').execute(&mut state);
+        InsertChar('!').execute(&mut state);
+        assert_eq!(state.lines, Lines::from("Hello\nWorld !"));
+    }
+
     #[test]
     fn test_insert_char_into_empty_buffer() {
         let mut state = EditorState::new(Lines::from("\n"));
@@ -120,6 +254,20 @@ mod tests {
         assert_eq!(state.lines, Lines::from("a\nb"));
     }
 
+    #[test]
+    fn test_insert_char_with_virtual_edit() {
+        let mut state = EditorState::new(Lines::from("ab"));
+        state.set_virtual_edit(EditorMode::Normal, VirtualEdit::All);
+        state.set_virtual_edit(EditorMode::Insert, VirtualEdit::All);
+
+        MoveForward(4).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 4));
+
+        SwitchMode(EditorMode::Insert).execute(&mut state);
+        InsertChar('!').execute(&mut state);
+        assert_eq!(state.lines, Lines::from("ab  !"));
+    }
+
     #[test]
     fn test_line_break() {
         let mut state = test_state();
@@ -185,4 +333,20 @@ mod tests {
         PushLine("456.").execute(&mut state);
         assert_eq!(state.lines, Lines::from("Hello World!\n\n123.\n456."));
     }
+
+    #[test]
+    fn test_insert_last_inserted() {
+        let mut state = EditorState::new(Lines::from(""));
+
+        SwitchMode(EditorMode::Insert).execute(&mut state);
+        InsertChar('a').execute(&mut state);
+        LineBreak(1).execute(&mut state);
+        InsertChar('b').execute(&mut state);
+        SwitchMode(EditorMode::Normal).execute(&mut state);
+        assert_eq!(state.last_inserted(), "a\nb");
+
+        SwitchMode(EditorMode::Insert).execute(&mut state);
+        InsertLastInserted.execute(&mut state);
+        assert_eq!(state.lines, Lines::from("a\na\nbb"));
+    }
 }
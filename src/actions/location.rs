@@ -0,0 +1,65 @@
+use super::Execute;
+use crate::{EditorIntent, EditorState, Notification};
+
+/// Jumps to the next entry of the location list, wrapping around to the first
+/// entry. See [`EditorState::set_locations`].
+#[derive(Clone, Debug, Copy)]
+pub struct NextLocation;
+
+impl Execute for NextLocation {
+    fn execute(&mut self, state: &mut EditorState) {
+        let len = state.locations.entries.len();
+        let next = match state.locations.current {
+            Some(i) => (i + 1) % len.max(1),
+            None => 0,
+        };
+        jump_to_location(state, next);
+    }
+}
+
+/// Jumps to the previous entry of the location list, wrapping around to the
+/// last entry. See [`EditorState::set_locations`].
+#[derive(Clone, Debug, Copy)]
+pub struct PrevLocation;
+
+impl Execute for PrevLocation {
+    fn execute(&mut self, state: &mut EditorState) {
+        let len = state.locations.entries.len();
+        let previous = match state.locations.current {
+            Some(i) => (i + len - 1) % len.max(1),
+            None => len.saturating_sub(1),
+        };
+        jump_to_location(state, previous);
+    }
+}
+
+fn jump_to_location(state: &mut EditorState, index: usize) {
+    if let Some(position) = state.locations.select(index) {
+        state.push_jump(state.cursor);
+        state.cursor = state.clamp_index(position);
+        state.clamp_column();
+    }
+}
+
+/// Passes the file path under the cursor, together with the line and the
+/// column that may follow it, to the callback registered with
+/// [`EditorState::on_open_file`]. Without a callback, an
+/// [`EditorIntent::OpenFile`] is pushed to the intent queue.
+#[derive(Clone, Debug, Copy)]
+pub struct OpenFileUnderCursor;
+
+impl Execute for OpenFileUnderCursor {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(file) = state.file_reference_under_cursor() else {
+            state.notify(Notification::warning("No file name under cursor"));
+            return;
+        };
+        match state.open_file_hook.clone() {
+            Some(hook) => {
+                state.touch_all_rows();
+                hook(state, &file);
+            }
+            None => state.push_intent(EditorIntent::OpenFile(file)),
+        }
+    }
+}
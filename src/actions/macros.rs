@@ -0,0 +1,35 @@
+use super::Execute;
+use crate::EditorState;
+
+/// Starts recording the executed actions into the macro register `0`, like
+/// `q` in vim. An uppercase register appends to the macro of its lowercase
+/// register. A recording in progress is stopped first.
+#[derive(Clone, Debug, Copy)]
+pub struct StartRecordingMacro(pub char);
+
+impl Execute for StartRecordingMacro {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.start_recording_macro(self.0);
+    }
+}
+
+/// Stops recording a macro and stores it in its register.
+#[derive(Clone, Debug, Copy)]
+pub struct StopRecordingMacro;
+
+impl Execute for StopRecordingMacro {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.stop_recording_macro();
+    }
+}
+
+/// Plays the macro of register `0`, like `@` in vim. The register `@`
+/// plays the last played macro again.
+#[derive(Clone, Debug, Copy)]
+pub struct PlayMacro(pub char);
+
+impl Execute for PlayMacro {
+    fn execute(&mut self, state: &mut EditorState) {
+        state.play_macro(self.0);
+    }
+}
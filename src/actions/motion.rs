@@ -4,7 +4,7 @@ use crate::{
     helper::{find_matching_bracket, skip_empty_lines},
     state::selection::set_selection_with_lines,
 };
-use jagged::Index2;
+use jagged::{index::RowIndex, Index2};
 
 use super::Execute;
 use crate::{
@@ -18,7 +18,7 @@ pub struct MoveForward(pub usize);
 impl Execute for MoveForward {
     fn execute(&mut self, state: &mut EditorState) {
         for _ in 0..self.0 {
-            if state.cursor.col >= max_col(&state.lines, &state.cursor, state.mode) {
+            if state.cursor.col >= state.max_cursor_col() {
                 break;
             }
             state.cursor.col += 1;
@@ -38,7 +38,7 @@ impl Execute for MoveBackward {
             if state.cursor.col == 0 {
                 break;
             }
-            let max_col = max_col(&state.lines, &state.cursor, state.mode);
+            let max_col = state.max_cursor_col();
             if state.cursor.col > max_col {
                 state.cursor.col = max_col;
             }
@@ -55,12 +55,8 @@ pub struct MoveUp(pub usize);
 
 impl Execute for MoveUp {
     fn execute(&mut self, state: &mut EditorState) {
-        for _ in 0..self.0 {
-            if state.cursor.row == 0 {
-                break;
-            }
-            state.cursor.row = state.cursor.row.saturating_sub(1);
-        }
+        let row = state.cursor.row.saturating_sub(self.0);
+        move_to_row(state, row);
         if state.mode == EditorMode::Visual {
             set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
         }
@@ -72,18 +68,27 @@ pub struct MoveDown(pub usize);
 
 impl Execute for MoveDown {
     fn execute(&mut self, state: &mut EditorState) {
-        for _ in 0..self.0 {
-            if state.cursor.row >= state.lines.len().saturating_sub(1) {
-                break;
-            }
-            state.cursor.row += 1;
-        }
+        let row = min(
+            state.cursor.row.saturating_add(self.0),
+            state.lines.last_row_index(),
+        );
+        move_to_row(state, row);
         if state.mode == EditorMode::Visual {
             set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
         }
     }
 }
 
+/// Moves the cursor vertically to `row`. The column is clamped to the line
+/// length, but the original column is remembered as goal column and restored
+/// when moving onto a longer line again.
+fn move_to_row(state: &mut EditorState, row: usize) {
+    let goal = state.goal_column();
+    state.cursor.row = row;
+    state.cursor.col = goal.min(state.max_cursor_col());
+    state.goal = Some((goal, state.cursor));
+}
+
 /// Move one word forward. Breaks on the first character that is not of
 /// the same class as the initial character or breaks on line ending.
 /// Furthermore, after the first break, whitespaces are skipped.
@@ -109,7 +114,8 @@ impl Execute for MoveWordForward {
 }
 
 fn move_word_forward(state: &mut EditorState) {
-    let start_char_class = CharacterClass::from(state.lines.get(state.cursor));
+    let start_char_class =
+        CharacterClass::classify(state.lines.get(state.cursor), &state.word_chars);
 
     let start_index = match (
         state.lines.is_last_col(state.cursor),
@@ -124,7 +130,7 @@ fn move_word_forward(state: &mut EditorState) {
     };
 
     for (next_char, index) in state.lines.iter().from(start_index) {
-        if CharacterClass::from(next_char) != start_char_class {
+        if CharacterClass::classify(next_char, &state.word_chars) != start_char_class {
             state.cursor = index;
             skip_whitespace(&state.lines, &mut state.cursor);
             return;
@@ -168,11 +174,12 @@ fn move_word_forward_to_end_of_word(state: &mut EditorState) {
     };
     skip_empty_lines(&state.lines, &mut start_index.row);
     skip_whitespace(&state.lines, &mut start_index);
-    let start_char_class = CharacterClass::from(state.lines.get(start_index));
+    let start_char_class =
+        CharacterClass::classify(state.lines.get(start_index), &state.word_chars);
 
     for (next_char, index) in state.lines.iter().from(start_index) {
         // Break loop if characters don't belong to the same class
-        if CharacterClass::from(next_char) != start_char_class {
+        if CharacterClass::classify(next_char, &state.word_chars) != start_char_class {
             break;
         }
         state.cursor = index;
@@ -225,7 +232,8 @@ fn move_word_backward(state: &mut EditorState) {
 
     start_index.col = start_index.col.saturating_sub(1);
     skip_whitespace_rev(&state.lines, &mut start_index);
-    let start_char_class = CharacterClass::from(state.lines.get(start_index));
+    let start_char_class =
+        CharacterClass::classify(state.lines.get(start_index), &state.word_chars);
 
     for (next_char, i) in state.lines.iter().from(start_index).rev() {
         // Break loop if it reaches the start of the line
@@ -234,7 +242,7 @@ fn move_word_backward(state: &mut EditorState) {
             break;
         }
         // Break loop if characters don't belong to the same class
-        if CharacterClass::from(next_char) != start_char_class {
+        if CharacterClass::classify(next_char, &state.word_chars) != start_char_class {
             break;
         }
         start_index = i;
@@ -243,6 +251,272 @@ fn move_word_backward(state: &mut EditorState) {
     state.cursor = start_index;
 }
 
+/// Move one word backward to the end of the previous word, like `ge` in vim.
+/// Stops on empty lines.
+#[derive(Clone, Debug, Copy)]
+pub struct MoveWordBackwardToEndOfWord(pub usize);
+
+impl Execute for MoveWordBackwardToEndOfWord {
+    fn execute(&mut self, state: &mut EditorState) {
+        move_backward_to_end(state, self.0, false);
+    }
+}
+
+/// Move one WORD backward to the end of the previous WORD, like `gE` in vim.
+/// WORDs are separated by whitespace only. Stops on empty lines.
+#[derive(Clone, Debug, Copy)]
+pub struct MoveBigWordBackwardToEndOfWord(pub usize);
+
+impl Execute for MoveBigWordBackwardToEndOfWord {
+    fn execute(&mut self, state: &mut EditorState) {
+        move_backward_to_end(state, self.0, true);
+    }
+}
+
+fn move_backward_to_end(state: &mut EditorState, count: usize, big_word: bool) {
+    if state.lines.is_empty() {
+        return;
+    }
+
+    state.clamp_column();
+
+    for _ in 0..count {
+        move_word_backward_to_end(state, big_word);
+    }
+
+    if state.mode == EditorMode::Visual {
+        set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+    }
+}
+
+fn move_word_backward_to_end(state: &mut EditorState, big_word: bool) {
+    let lines = &state.lines;
+    // Line ends are visited as whitespace, empty lines are stops
+    let char_at = |index: Index2| lines.get(index).copied();
+    let is_empty_line = |index: Index2| lines.len_col(index.row) == Some(0);
+    let same_word = |a: Option<char>, b: Option<char>| match (a, b) {
+        (Some(a), Some(b)) if !a.is_whitespace() && !b.is_whitespace() => {
+            big_word
+                || CharacterClass::classify(Some(&a), &state.word_chars)
+                    == CharacterClass::classify(Some(&b), &state.word_chars)
+        }
+        _ => false,
+    };
+    let prev = |index: Index2| match index {
+        Index2 { row: 0, col: 0 } => None,
+        Index2 { row, col: 0 } => Some(Index2::new(row - 1, lines.len_col(row - 1)?)),
+        Index2 { row, col } => Some(Index2::new(row, col - 1)),
+    };
+
+    // Leave the word under the cursor
+    let mut index = state.cursor;
+    let start = char_at(index);
+    loop {
+        let Some(prev) = prev(index) else {
+            return;
+        };
+        index = prev;
+        if is_empty_line(index) || !same_word(start, char_at(index)) {
+            break;
+        }
+    }
+
+    // Skip whitespace up to the end of the previous word
+    while !is_empty_line(index) && char_at(index).is_none_or(char::is_whitespace) {
+        let Some(prev) = prev(index) else {
+            break;
+        };
+        index = prev;
+    }
+
+    state.cursor = index;
+}
+
+/// Move one sub-word forward. In addition to the breaks of
+/// [`MoveWordForward`], sub-word motions break at case changes within
+/// identifiers, e.g. `camel|Case`, and skip underscores, e.g. `snake_|case`.
+///
+/// Sub-word motions are not bound by default. They can be bound alongside
+/// the regular word motions, for example:
+///
+/// ```
+/// use edtui::actions::{MoveSubwordBackward, MoveSubwordForward};
+/// use edtui::events::{KeyEvent, KeyEventHandler, KeyEventRegister};
+///
+/// let mut key_handler = KeyEventHandler::vim_mode();
+/// key_handler.insert(
+///     KeyEventRegister::n(vec![KeyEvent::Char(','), KeyEvent::Char('w')]),
+///     MoveSubwordForward(1),
+/// );
+/// key_handler.insert(
+///     KeyEventRegister::n(vec![KeyEvent::Char(','), KeyEvent::Char('b')]),
+///     MoveSubwordBackward(1),
+/// );
+/// ```
+#[derive(Clone, Debug, Copy)]
+pub struct MoveSubwordForward(pub usize);
+
+impl Execute for MoveSubwordForward {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.lines.is_empty() {
+            return;
+        }
+
+        state.clamp_column();
+
+        for _ in 0..self.0 {
+            move_subword_forward(state);
+        }
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+fn move_subword_forward(state: &mut EditorState) {
+    let cursor = state.cursor;
+    for row in cursor.row..state.lines.len() {
+        let subwords = row_subwords(state, row);
+        // Like word motions, stop on empty lines
+        if row > cursor.row && subwords.is_empty() && state.lines.len_col(row) == Some(0) {
+            state.cursor = Index2::new(row, 0);
+            return;
+        }
+        if let Some(&(start, _)) = subwords
+            .iter()
+            .find(|(start, _)| row > cursor.row || *start > cursor.col)
+        {
+            state.cursor = Index2::new(row, start);
+            return;
+        }
+    }
+}
+
+/// Move one sub-word forward to the end of the sub-word. See
+/// [`MoveSubwordForward`].
+#[derive(Clone, Debug, Copy)]
+pub struct MoveSubwordForwardToEnd(pub usize);
+
+impl Execute for MoveSubwordForwardToEnd {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.lines.is_empty() {
+            return;
+        }
+
+        state.clamp_column();
+
+        for _ in 0..self.0 {
+            move_subword_forward_to_end(state);
+        }
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+fn move_subword_forward_to_end(state: &mut EditorState) {
+    let cursor = state.cursor;
+    for row in cursor.row..state.lines.len() {
+        if let Some(&(_, end)) = row_subwords(state, row)
+            .iter()
+            .find(|(_, end)| row > cursor.row || *end > cursor.col)
+        {
+            state.cursor = Index2::new(row, end);
+            return;
+        }
+    }
+}
+
+/// Move one sub-word backward. See [`MoveSubwordForward`].
+#[derive(Clone, Debug, Copy)]
+pub struct MoveSubwordBackward(pub usize);
+
+impl Execute for MoveSubwordBackward {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.lines.is_empty() {
+            return;
+        }
+
+        let max_col = max_col(&state.lines, &state.cursor, state.mode);
+        if state.cursor.col > max_col {
+            state.cursor.col = max_col;
+        }
+
+        for _ in 0..self.0 {
+            move_subword_backward(state);
+        }
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+fn move_subword_backward(state: &mut EditorState) {
+    let cursor = state.cursor;
+    for row in (0..=cursor.row).rev() {
+        let subwords = row_subwords(state, row);
+        if row < cursor.row && subwords.is_empty() && state.lines.len_col(row) == Some(0) {
+            state.cursor = Index2::new(row, 0);
+            return;
+        }
+        if let Some(&(start, _)) = subwords
+            .iter()
+            .rev()
+            .find(|(start, _)| row < cursor.row || *start < cursor.col)
+        {
+            state.cursor = Index2::new(row, start);
+            return;
+        }
+    }
+}
+
+/// Returns the sub-words of a row. See [`subwords`].
+pub(crate) fn row_subwords(state: &EditorState, row: usize) -> Vec<(usize, usize)> {
+    state
+        .lines
+        .get(RowIndex::new(row))
+        .map_or_else(Vec::new, |line| subwords(line, &state.word_chars))
+}
+
+/// Returns the sub-words of a line as inclusive column ranges. Sub-words are
+/// runs of characters of the same class, which additionally break at case
+/// changes (`camelCase`, `HTTPServer`). Whitespace and underscores separate
+/// sub-words and are not part of any.
+pub(crate) fn subwords(line: &[char], word_chars: &[char]) -> Vec<(usize, usize)> {
+    let mut subwords: Vec<(usize, usize)> = Vec::new();
+    for (col, ch) in line.iter().enumerate() {
+        if ch.is_whitespace() || *ch == '_' {
+            continue;
+        }
+        match subwords.last_mut() {
+            Some(subword) if subword.1 + 1 == col && continues_subword(line, col, word_chars) => {
+                subword.1 = col;
+            }
+            _ => subwords.push((col, col)),
+        }
+    }
+    subwords
+}
+
+/// Whether the character at `col` belongs to the same sub-word as the
+/// character before it.
+fn continues_subword(line: &[char], col: usize, word_chars: &[char]) -> bool {
+    let (prev, ch) = (line[col - 1], line[col]);
+    if CharacterClass::classify(Some(&prev), word_chars)
+        != CharacterClass::classify(Some(&ch), word_chars)
+    {
+        return false;
+    }
+    let lower_to_upper = (prev.is_lowercase() || prev.is_ascii_digit()) && ch.is_uppercase();
+    let acronym_end = prev.is_uppercase()
+        && ch.is_uppercase()
+        && line.get(col + 1).is_some_and(|next| next.is_lowercase());
+    !lower_to_upper && !acronym_end
+}
+
 // Move the cursor to the start of the line.
 #[derive(Clone, Debug, Copy)]
 pub struct MoveToStartOfLine();
@@ -271,6 +545,27 @@ impl Execute for MoveToFirst {
     }
 }
 
+// Move the cursor to the first non-whitespace character in the line, or to
+// the start of the line if it already is there.
+#[derive(Clone, Debug, Copy)]
+pub struct SmartMoveToStart();
+
+impl Execute for SmartMoveToStart {
+    fn execute(&mut self, state: &mut EditorState) {
+        let mut first = Index2::new(state.cursor.row, 0);
+        skip_whitespace(&state.lines, &mut first);
+        state.cursor.col = if state.cursor.col == first.col {
+            0
+        } else {
+            first.col
+        };
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
 // Move the cursor to the end of the line.
 #[derive(Clone, Debug, Copy)]
 pub struct MoveToEndOfLine();
@@ -278,6 +573,28 @@ pub struct MoveToEndOfLine();
 impl Execute for MoveToEndOfLine {
     fn execute(&mut self, state: &mut EditorState) {
         state.cursor.col = max_col(&state.lines, &state.cursor, state.mode);
+        // Stick to the end of the line on subsequent vertical motions.
+        state.goal = Some((usize::MAX, state.cursor));
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+/// Move the cursor to the line at the given percentage of the buffer, as
+/// with `{count}%` in vim.
+#[derive(Clone, Debug, Copy)]
+pub struct MoveToPercent(pub usize);
+
+impl Execute for MoveToPercent {
+    fn execute(&mut self, state: &mut EditorState) {
+        let percent = self.0.min(100);
+        let row = (percent * state.lines.len()).div_ceil(100);
+
+        state.push_jump(state.cursor);
+        state.cursor = Index2::new(row.saturating_sub(1), 0);
+        skip_whitespace(&state.lines, &mut state.cursor);
 
         if state.mode == EditorMode::Visual {
             set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
@@ -291,6 +608,7 @@ pub struct MoveToFirstRow();
 
 impl Execute for MoveToFirstRow {
     fn execute(&mut self, state: &mut EditorState) {
+        state.push_jump(state.cursor);
         state.cursor.row = 0;
 
         if state.mode == EditorMode::Visual {
@@ -305,6 +623,7 @@ pub struct MoveToLastRow();
 
 impl Execute for MoveToLastRow {
     fn execute(&mut self, state: &mut EditorState) {
+        state.push_jump(state.cursor);
         state.cursor.row = state.lines.len().saturating_sub(1);
 
         if state.mode == EditorMode::Visual {
@@ -322,6 +641,7 @@ impl Execute for MoveToMatchinBracket {
         let max_col = max_col_normal(&state.lines, &state.cursor);
         let index = Index2::new(state.cursor.row, state.cursor.col.min(max_col));
         if let Some(index) = find_matching_bracket(&state.lines, index) {
+            state.push_jump(state.cursor);
             state.cursor = index;
             if state.mode == EditorMode::Visual {
                 set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
@@ -336,7 +656,8 @@ pub struct MoveHalfPageDown();
 impl Execute for MoveHalfPageDown {
     fn execute(&mut self, state: &mut EditorState) {
         let jump_rows = state.view.num_rows / 2;
-        state.cursor.row = min(state.cursor.row + jump_rows, state.lines.last_row_index());
+        let row = min(state.cursor.row + jump_rows, state.lines.last_row_index());
+        move_to_row(state, row);
 
         if state.mode == EditorMode::Visual {
             set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
@@ -350,7 +671,51 @@ pub struct MoveHalfPageUp();
 impl Execute for MoveHalfPageUp {
     fn execute(&mut self, state: &mut EditorState) {
         let jump_rows = state.view.num_rows / 2;
-        state.cursor.row = state.cursor.row.saturating_sub(jump_rows);
+        let row = state.cursor.row.saturating_sub(jump_rows);
+        move_to_row(state, row);
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+/// Scrolls down by a full page. The cursor keeps its row on the screen.
+/// Consecutive pages overlap by the number of rows configured with
+/// [`EditorView::page_overlap`].
+///
+/// [`EditorView::page_overlap`]: crate::EditorView::page_overlap
+#[derive(Clone, Debug, Copy)]
+pub struct MovePageDown();
+
+impl Execute for MovePageDown {
+    fn execute(&mut self, state: &mut EditorState) {
+        let page = state.view.page_rows();
+        let last_row = state.lines.last_row_index();
+        state.view.viewport.y = min(state.view.viewport.y + page, last_row);
+        let row = min(state.cursor.row + page, last_row);
+        move_to_row(state, row);
+
+        if state.mode == EditorMode::Visual {
+            set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+        }
+    }
+}
+
+/// Scrolls up by a full page. The cursor keeps its row on the screen.
+/// Consecutive pages overlap by the number of rows configured with
+/// [`EditorView::page_overlap`].
+///
+/// [`EditorView::page_overlap`]: crate::EditorView::page_overlap
+#[derive(Clone, Debug, Copy)]
+pub struct MovePageUp();
+
+impl Execute for MovePageUp {
+    fn execute(&mut self, state: &mut EditorState) {
+        let page = state.view.page_rows();
+        state.view.viewport.y = state.view.viewport.y.saturating_sub(page);
+        let row = state.cursor.row.saturating_sub(page);
+        move_to_row(state, row);
 
         if state.mode == EditorMode::Visual {
             set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
@@ -366,6 +731,16 @@ pub(crate) enum CharacterClass {
     Whitespace,
 }
 
+impl CharacterClass {
+    /// Classifies a character, treating the `word_chars` as part of words.
+    pub(crate) fn classify(ch: Option<&char>, word_chars: &[char]) -> Self {
+        match ch {
+            Some(ch) if word_chars.contains(ch) => Self::Alphanumeric,
+            _ => Self::from(ch),
+        }
+    }
+}
+
 impl From<&char> for CharacterClass {
     fn from(value: &char) -> Self {
         if value.is_ascii_alphanumeric() {
@@ -396,6 +771,24 @@ impl PartialEq for CharacterClass {
     }
 }
 
+/// Moves the cursor to the start of the next validation error, wrapping
+/// around to the first error. See [`EditorState::set_validator`].
+#[derive(Clone, Debug, Copy)]
+pub struct NextError;
+
+impl Execute for NextError {
+    fn execute(&mut self, state: &mut EditorState) {
+        let errors = state.validation_errors();
+        let next = errors
+            .iter()
+            .find(|error| error.start > state.cursor)
+            .or_else(|| errors.first());
+        if let Some(error) = next {
+            state.cursor = error.start;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Index2, Lines};
@@ -440,13 +833,13 @@ mod tests {
         state.cursor = Index2::new(0, 6);
 
         MoveDown(1).execute(&mut state);
-        assert_eq!(state.cursor, Index2::new(1, 6));
+        assert_eq!(state.cursor, Index2::new(1, 0));
 
         MoveDown(1).execute(&mut state);
-        assert_eq!(state.cursor, Index2::new(2, 6));
+        assert_eq!(state.cursor, Index2::new(2, 3));
 
         MoveDown(1).execute(&mut state);
-        assert_eq!(state.cursor, Index2::new(2, 6));
+        assert_eq!(state.cursor, Index2::new(2, 3));
     }
 
     #[test]
@@ -455,7 +848,7 @@ mod tests {
         state.cursor = Index2::new(2, 2);
 
         MoveUp(1).execute(&mut state);
-        assert_eq!(state.cursor, Index2::new(1, 2));
+        assert_eq!(state.cursor, Index2::new(1, 0));
 
         MoveUp(1).execute(&mut state);
         assert_eq!(state.cursor, Index2::new(0, 2));
@@ -464,6 +857,43 @@ mod tests {
         assert_eq!(state.cursor, Index2::new(0, 2));
     }
 
+    #[test]
+    fn test_move_vertically_keeps_goal_column() {
+        let mut state = EditorState::new(Lines::from(
+            "Hello World!
+ab
+Hello",
+        ));
+        state.cursor = Index2::new(0, 6);
+
+        MoveDown(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 1));
+
+        MoveDown(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(2, 4));
+
+        MoveUp(2).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 6));
+
+        // a horizontal motion resets the goal column
+        MoveDown(1).execute(&mut state);
+        MoveBackward(1).execute(&mut state);
+        MoveUp(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+    }
+
+    #[test]
+    fn test_move_vertically_after_move_to_end_of_line() {
+        let mut state = EditorState::new(Lines::from(
+            "ab
+Hello World!",
+        ));
+
+        MoveToEndOfLine().execute(&mut state);
+        MoveDown(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 11));
+    }
+
     #[test]
     fn test_move_word_forward() {
         let mut state = test_state();
@@ -581,6 +1011,29 @@ mod tests {
         assert_eq!(state.cursor, Index2::new(2, 3));
     }
 
+    #[test]
+    fn test_move_word_backward_to_end_of_word() {
+        let mut state = EditorState::new(Lines::from("foo.bar baz\n\n  qux-quux"));
+        state.cursor = Index2::new(2, 6);
+
+        state.execute(MoveWordBackwardToEndOfWord(1));
+        assert_eq!(state.cursor, Index2::new(2, 5));
+        state.execute(MoveWordBackwardToEndOfWord(1));
+        assert_eq!(state.cursor, Index2::new(2, 4));
+        state.execute(MoveWordBackwardToEndOfWord(1));
+        assert_eq!(state.cursor, Index2::new(1, 0));
+        state.execute(MoveWordBackwardToEndOfWord(2));
+        assert_eq!(state.cursor, Index2::new(0, 6));
+
+        state.cursor = Index2::new(2, 8);
+        state.execute(MoveBigWordBackwardToEndOfWord(1));
+        assert_eq!(state.cursor, Index2::new(1, 0));
+        state.execute(MoveBigWordBackwardToEndOfWord(2));
+        assert_eq!(state.cursor, Index2::new(0, 6));
+        state.execute(MoveBigWordBackwardToEndOfWord(1));
+        assert_eq!(state.cursor, Index2::new(0, 6));
+    }
+
     #[test]
     fn test_move_word_backward() {
         let mut state = test_state();
@@ -605,6 +1058,32 @@ mod tests {
         assert_eq!(state.cursor, Index2::new(0, 0));
     }
 
+    #[test]
+    fn test_move_subword() {
+        let mut state = EditorState::new(Lines::from("let parseHTTPRequest = snake_case;\n\nx"));
+
+        MoveSubwordForward(2).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 9));
+
+        MoveSubwordForward(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 13));
+
+        MoveSubwordForwardToEnd(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 19));
+
+        MoveSubwordForward(3).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 29));
+
+        MoveSubwordForward(2).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        MoveSubwordBackward(2).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 29));
+
+        MoveSubwordBackward(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 23));
+    }
+
     #[test]
     fn test_move_to_start() {
         let mut state = test_state();
@@ -623,6 +1102,57 @@ mod tests {
         assert_eq!(state.cursor, Index2::new(0, 11));
     }
 
+    #[test]
+    fn test_move_page_down_and_up() {
+        let text: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let mut state = EditorState::new(Lines::from(text.join("\n").as_str()));
+        state.view.screen_area.height = 10;
+        state.view.page_overlap = 2;
+        state.cursor = Index2::new(3, 0);
+
+        MovePageDown().execute(&mut state);
+        assert_eq!(state.view.viewport.y, 8);
+        assert_eq!(state.cursor, Index2::new(11, 0));
+
+        MovePageDown().execute(&mut state);
+        assert_eq!(state.view.viewport.y, 16);
+        assert_eq!(state.cursor, Index2::new(19, 0));
+
+        MovePageUp().execute(&mut state);
+        assert_eq!(state.view.viewport.y, 8);
+        assert_eq!(state.cursor, Index2::new(11, 0));
+    }
+
+    #[test]
+    fn test_move_to_percent() {
+        let text: Vec<String> = (0..10).map(|i| format!(" {i}")).collect();
+        let mut state = EditorState::new(Lines::from(text.join("\n").as_str()));
+
+        MoveToPercent(50).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(4, 1));
+
+        MoveToPercent(1).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 1));
+
+        MoveToPercent(200).execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(9, 1));
+    }
+
+    #[test]
+    fn test_smart_move_to_start() {
+        let mut state = EditorState::new(Lines::from("  Hello"));
+        state.cursor = Index2::new(0, 4);
+
+        SmartMoveToStart().execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 2));
+
+        SmartMoveToStart().execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        SmartMoveToStart().execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 2));
+    }
+
     #[test]
     fn test_move_to_first() {
         let mut state = EditorState::new(Lines::from(" Hello"));
@@ -0,0 +1,255 @@
+//! Operators that act on the text covered by a motion, such as `dw` or `ce`.
+use super::motion::CharacterClass;
+use super::{
+    Action, ChangeSelection, CopySelection, DeleteSelection, Execute, MoveWordForwardToEndOfWord,
+    SwitchMode,
+};
+use crate::{state::selection::Selection, EditorMode, EditorState, Index2};
+
+/// An operation on a range of text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    /// Deletes the text, like `d` in vim.
+    Delete,
+    /// Deletes the text and switches to insert mode, like `c` in vim.
+    Change,
+    /// Copies the text, like `y` in vim.
+    Yank,
+}
+
+/// Applies an operator to the text between the cursor and the target of a
+/// motion, like `dw`, `ce` or `yb` in vim.
+///
+/// As in vim, some motions are adjusted depending on the operator:
+/// - Motions to the end of a word or line, such as `e` or `$`, include the
+///   character they move to, all other motions exclude it.
+/// - Vertical motions, such as `j` or `G`, act on entire lines.
+/// - `w` stops at the end of the line instead of moving to the next line.
+/// - `cw` on a word changes up to the end of the word, like `ce`, and keeps
+///   the whitespace after it.
+///
+/// # Example
+///
+/// ```
+/// use edtui::actions::{MoveWordForward, Operator, OperatorMotion};
+/// use edtui::{EditorState, Lines};
+///
+/// let mut state = EditorState::new(Lines::from("foo bar baz"));
+///
+/// state.execute(OperatorMotion::new(Operator::Delete, MoveWordForward(1)));
+/// assert_eq!(state.lines, Lines::from("bar baz"));
+///
+/// state.execute(OperatorMotion::new(Operator::Change, MoveWordForward(1)));
+/// assert_eq!(state.lines, Lines::from(" baz"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct OperatorMotion {
+    pub operator: Operator,
+    pub motion: Box<Action>,
+    /// How many times the motion is repeated.
+    pub count: usize,
+}
+
+impl OperatorMotion {
+    #[must_use]
+    pub fn new<A: Into<Action>>(operator: Operator, motion: A) -> Self {
+        Self {
+            operator,
+            motion: Box::new(motion.into()),
+            count: 1,
+        }
+    }
+
+    /// Sets how many times the motion is repeated.
+    #[must_use]
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+}
+
+impl Execute for OperatorMotion {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.lines.is_empty() {
+            return;
+        }
+        state.clamp_column();
+        let Some(selection) = self.motion_selection(state) else {
+            return;
+        };
+
+        state.selection = Some(selection);
+        match self.operator {
+            Operator::Delete => DeleteSelection.execute(state),
+            Operator::Change => {
+                SwitchMode(EditorMode::Insert).execute(state);
+                ChangeSelection.execute(state);
+            }
+            Operator::Yank => {
+                let start = state.selection.as_ref().map(Selection::start);
+                CopySelection.execute(state);
+                if let Some(start) = start {
+                    state.cursor = start;
+                }
+            }
+        }
+    }
+}
+
+impl OperatorMotion {
+    /// Runs the motion and returns the text it covers, or `None` if the
+    /// motion did not move.
+    fn motion_selection(&mut self, state: &mut EditorState) -> Option<Selection> {
+        let start = state.cursor;
+        let word_forward = matches!(*self.motion, Action::MoveWordForward(_));
+        let change_word = self.operator == Operator::Change
+            && word_forward
+            && state.lines.get(start).is_some_and(|ch| !ch.is_whitespace());
+
+        let inclusive = if change_word {
+            change_word_end(state, self.count);
+            true
+        } else {
+            for _ in 0..self.count {
+                self.motion.execute(state);
+            }
+            is_inclusive(&self.motion)
+        };
+        let target = std::mem::replace(&mut state.cursor, start);
+
+        let (first, mut last) = if start <= target {
+            (start, target)
+        } else {
+            (target, start)
+        };
+        if is_linewise(&self.motion) {
+            return Some(Selection::new(first, last).line_mode());
+        }
+
+        let inclusive = inclusive
+            || (word_forward
+                && state.lines.is_last_col(target)
+                && (target == start || !starts_word(state, target)));
+        if word_forward && last.row > first.row {
+            // `w` does not operate across the end of the line
+            let len = state.lines.len_col(first.row).unwrap_or_default();
+            last = Index2::new(first.row, len.saturating_sub(1));
+            if len == 0 {
+                return None;
+            }
+        } else if !inclusive {
+            if first == last {
+                return None;
+            }
+            last = if last.col > 0 {
+                Index2::new(last.row, last.col - 1)
+            } else {
+                let row = last.row - 1;
+                let len = state.lines.len_col(row).unwrap_or_default();
+                Index2::new(row, len.saturating_sub(1))
+            };
+        }
+        Some(Selection::new(first, last))
+    }
+}
+
+/// Moves the cursor to the end of the word under the cursor, and then to
+/// the end of `count - 1` further words. This is how `cw` differs from `dw`.
+fn change_word_end(state: &mut EditorState, count: usize) {
+    let next = Index2::new(state.cursor.row, state.cursor.col + 1);
+    let at_word_end = state.lines.is_last_col(state.cursor) || starts_word(state, next);
+    MoveWordForwardToEndOfWord(count - usize::from(at_word_end)).execute(state);
+}
+
+/// Whether a word starts at `index`, i.e. the character before it belongs
+/// to a different class.
+fn starts_word(state: &EditorState, index: Index2) -> bool {
+    let Some(col) = index.col.checked_sub(1) else {
+        return true;
+    };
+    let classify = |col| {
+        let ch = state.lines.get(Index2::new(index.row, col));
+        CharacterClass::classify(ch, &state.word_chars)
+    };
+    classify(col) != classify(index.col)
+}
+
+/// Whether the motion includes the character it moves to.
+fn is_inclusive(motion: &Action) -> bool {
+    matches!(
+        motion,
+        Action::MoveWordForwardToEndOfWord(_)
+            | Action::MoveWordBackwardToEndOfWord(_)
+            | Action::MoveBigWordBackwardToEndOfWord(_)
+            | Action::MoveSubwordForwardToEnd(_)
+            | Action::MoveToEndOfLine(_)
+            | Action::MoveToMatchingBracket(_)
+    )
+}
+
+/// Whether the motion acts on entire lines.
+fn is_linewise(motion: &Action) -> bool {
+    matches!(
+        motion,
+        Action::MoveUp(_)
+            | Action::MoveDown(_)
+            | Action::MoveToFirstRow(_)
+            | Action::MoveToLastRow(_)
+            | Action::MoveToPercent(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{MoveDown, MoveToEndOfLine, MoveWordBackward, MoveWordForward};
+    use crate::Lines;
+
+    fn apply(text: &str, col: usize, operator: Operator, motion: impl Into<Action>) -> Lines {
+        let mut state = EditorState::new(Lines::from(text));
+        state.cursor = Index2::new(0, col);
+        state.execute(OperatorMotion::new(operator, motion));
+        state.lines
+    }
+
+    #[test]
+    fn test_operator_motion() {
+        use Operator::{Change, Delete};
+
+        assert_eq!(
+            apply("foo bar", 0, Delete, MoveWordForward(1)),
+            "bar".into()
+        );
+        assert_eq!(
+            apply("foo bar", 0, Change, MoveWordForward(1)),
+            " bar".into()
+        );
+        assert_eq!(
+            apply("foo bar", 2, Change, MoveWordForward(1)),
+            "fo bar".into()
+        );
+        assert_eq!(
+            apply("foo bar", 3, Change, MoveWordForward(1)),
+            "foobar".into()
+        );
+        assert_eq!(
+            apply("foo bar", 4, Delete, MoveWordForward(1)),
+            "foo ".into()
+        );
+        assert_eq!(
+            apply("foo\nbar", 1, Delete, MoveWordForward(1)),
+            "f\nbar".into()
+        );
+        assert_eq!(
+            apply("foo bar", 4, Delete, MoveWordBackward(1)),
+            "bar".into()
+        );
+        assert_eq!(apply("foo bar", 1, Delete, MoveToEndOfLine()), "f".into());
+        assert_eq!(apply("foo\nbar\nbaz", 0, Delete, MoveDown(1)), "baz".into());
+
+        let mut state = EditorState::new(Lines::from("foo bar baz"));
+        state.execute(OperatorMotion::new(Change, MoveWordForward(1)).count(2));
+        assert_eq!(state.lines, Lines::from(" baz"));
+        assert_eq!(state.mode, EditorMode::Insert);
+    }
+}
@@ -1,4 +1,12 @@
-use crate::EditorState;
+use std::ops::Range;
+
+use jagged::index::RowIndex;
+
+use crate::{
+    helper::map_lines,
+    state::{search::is_within, selection::Selection},
+    EditorState, Index2, Notification, SearchOffset, SearchScope,
+};
 
 use super::Execute;
 
@@ -10,6 +18,10 @@ impl Execute for AppendCharToSearch {
     /// Executes the command, appending the specified character to the search buffer
     /// and triggering a search based on the updated buffer.
     fn execute(&mut self, state: &mut EditorState) {
+        if let Some(offset) = &mut state.search.offset_query {
+            offset.push(self.0);
+            return;
+        }
         state.search.push_char(self.0);
         state.search.trigger_search(&state.lines);
         if let Some(index) = state.search.first() {
@@ -26,12 +38,40 @@ impl Execute for RemoveCharFromSearch {
     /// Executes the command, removing the last character from the search buffer
     /// and triggering a search based on the updated buffer.
     fn execute(&mut self, state: &mut EditorState) {
+        if let Some(offset) = &mut state.search.offset_query {
+            if offset.pop().is_none() {
+                state.search.offset_query = None;
+            }
+            return;
+        }
         state.search.remove_char();
         state.search.trigger_search(&state.lines);
     }
 }
 
+/// Command to start typing a [`SearchOffset`] after the search pattern, like
+/// the `/` in `/foo/e` in vim. The offset is applied by [`FindFirst`]. A `/`
+/// that follows a backslash is added to the pattern instead.
+#[derive(Clone, Debug, Copy)]
+pub struct StartSearchOffset;
+
+impl Execute for StartSearchOffset {
+    fn execute(&mut self, state: &mut EditorState) {
+        if let Some(offset) = &mut state.search.offset_query {
+            offset.push('/');
+        } else if state.search.pattern.ends_with('\\') {
+            state.search.remove_char();
+            AppendCharToSearch('/').execute(state);
+        } else {
+            state.search.offset_query = Some(String::new());
+        }
+    }
+}
+
 /// Command to find the first match of the search pattern behind the last cursor position.
+///
+/// An offset typed after the pattern, e.g. `e` in `foo/e`, applies to this
+/// and to the following jumps to matches, see [`SearchOffset`].
 #[derive(Clone, Debug)]
 pub struct FindFirst;
 
@@ -40,8 +80,17 @@ impl Execute for FindFirst {
     /// the last cursor position and setting the cursor to the found match.
     /// Switches to normal mode.
     fn execute(&mut self, state: &mut EditorState) {
-        if let Some(index) = state.search.first() {
-            state.cursor = *index;
+        if let Some(offset) = state.search.offset_query.take() {
+            match SearchOffset::parse(&offset) {
+                Some(offset) => state.search.typed_offset = Some(offset),
+                None => state.notify(Notification::error(format!(
+                    "Invalid search offset: {offset}"
+                ))),
+            }
+        }
+        if let Some(&index) = state.search.first() {
+            state.push_jump(state.search.start_cursor);
+            state.cursor = match_cursor(state, index);
         }
     }
 }
@@ -54,9 +103,11 @@ impl Execute for FindNext {
     /// Executes the command, finding the next search match and updating the cursor position.
     /// Switches to normal mode.
     fn execute(&mut self, state: &mut EditorState) {
-        if let Some(index) = state.search.next() {
-            state.cursor = *index;
+        if let Some(&index) = state.search.next() {
+            state.push_jump(state.cursor);
+            state.cursor = match_cursor(state, index);
         }
+        notify_wrap(state, "BOTTOM", "TOP");
     }
 }
 
@@ -68,9 +119,39 @@ impl Execute for FindPrevious {
     /// Executes the command, finding the previous search match and updating the cursor position.
     /// Switches to normal mode.
     fn execute(&mut self, state: &mut EditorState) {
-        if let Some(index) = state.search.previous() {
-            state.cursor = *index;
+        if let Some(&index) = state.search.previous() {
+            state.push_jump(state.cursor);
+            state.cursor = match_cursor(state, index);
         }
+        notify_wrap(state, "TOP", "BOTTOM");
+    }
+}
+
+/// Returns the cursor position for a jump to the match at `start`, taking
+/// the search offset into account.
+fn match_cursor(state: &EditorState, start: Index2) -> Index2 {
+    let len = state.search.selected_len();
+    state
+        .search
+        .effective_offset()
+        .apply(&state.lines, start, len)
+}
+
+/// Notifies the user if the last jump to a match wrapped around, or would
+/// have wrapped around if searches wrapped.
+fn notify_wrap(state: &mut EditorState, end: &str, start: &str) {
+    if !state.search.wrapped {
+        return;
+    }
+    if state.search.wrap_scan {
+        state.notify(Notification::warning(format!(
+            "Search hit {end}, continuing at {start}"
+        )));
+    } else {
+        let pattern = &state.search.pattern;
+        state.notify(Notification::error(format!(
+            "Search hit {end} without match for: {pattern}"
+        )));
     }
 }
 
@@ -96,6 +177,7 @@ impl Execute for StartSearch {
     /// Executes the command, starting the search state.
     fn execute(&mut self, state: &mut EditorState) {
         state.search.start(state.cursor);
+        state.search.bounds = state.search_bounds(&state.search.scope);
     }
 }
 /// Command to clear the search state.
@@ -109,3 +191,617 @@ impl Execute for StopSearch {
         state.cursor = state.search.start_cursor;
     }
 }
+
+/// Command to replace all occurrences of a pattern.
+///
+/// Patterns are matched literally within a line, in the [`scope`] of the
+/// command. With the `regex` feature, [`regex`] matches a regular expression
+/// instead, and the replacement may refer to capture groups as `$1` or
+/// `${name}`. With [`preserve_case`], the pattern is matched
+/// case-insensitively and each replacement takes the casing of the text it
+/// replaces, e.g. replacing `foo` with `bar` turns `Foo` into `Bar` and
+/// `FOO` into `BAR`. All replacements are a single undo step.
+///
+/// ## Example
+///
+/// ```
+/// use edtui::actions::ReplaceAll;
+/// use edtui::{EditorState, Lines};
+///
+/// let mut state = EditorState::new(Lines::from("foo Foo FOO"));
+/// state.execute(ReplaceAll::new("foo", "bar").preserve_case(true));
+///
+/// assert_eq!(state.lines, Lines::from("bar Bar BAR"));
+/// ```
+///
+/// [`scope`]: ReplaceAll::scope
+/// [`preserve_case`]: ReplaceAll::preserve_case
+/// [`regex`]: ReplaceAll::regex
+#[derive(Clone, Debug)]
+pub struct ReplaceAll {
+    options: ReplaceOptions,
+    /// Whether only the first match of each line is replaced.
+    first_per_line: bool,
+}
+
+impl ReplaceAll {
+    /// Creates a command that replaces `pattern` with `replacement`.
+    #[must_use]
+    pub fn new<P: Into<String>, R: Into<String>>(pattern: P, replacement: R) -> Self {
+        Self {
+            options: ReplaceOptions::new(Some(pattern.into()), Some(replacement.into())),
+            first_per_line: false,
+        }
+    }
+
+    /// Creates a command that replaces the search pattern with the
+    /// replacement set with [`EditorState::set_search_replacement`].
+    #[must_use]
+    pub fn from_search() -> Self {
+        Self {
+            options: ReplaceOptions::new(None, None),
+            first_per_line: false,
+        }
+    }
+
+    /// Restricts the replacements to a part of the text, e.g. to the visual
+    /// selection. Replaces in the entire text by default.
+    #[must_use]
+    pub fn scope(mut self, scope: SearchScope) -> Self {
+        self.options.scope = scope;
+        self
+    }
+
+    /// Matches case-insensitively and preserves the casing of the replaced
+    /// text. Disabled by default.
+    #[must_use]
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.options.preserve_case = preserve_case;
+        self
+    }
+
+    /// Matches the pattern as a regular expression, whose capture groups
+    /// can be referred to in the replacement. Disabled by default.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn regex(mut self, regex: bool) -> Self {
+        self.options.regex = regex;
+        self
+    }
+
+    /// Replaces only the first match of each line, like `:s` without the
+    /// `g` flag.
+    #[must_use]
+    pub(crate) fn first_per_line(mut self) -> Self {
+        self.first_per_line = true;
+        self
+    }
+
+    /// Returns the replaced line, or `None` if the pattern does not occur.
+    fn replace_line(
+        &self,
+        replacer: &Replacer,
+        line: &[char],
+        row: usize,
+        bounds: Option<&Selection>,
+    ) -> Option<Vec<char>> {
+        let mut replaced = Vec::with_capacity(line.len());
+        let mut found = false;
+        let mut col = 0;
+        for (range, replacement) in replacer.matches(line) {
+            if !is_within(bounds, Index2::new(row, range.start), range.len()) {
+                continue;
+            }
+            replaced.extend_from_slice(&line[col..range.start]);
+            replaced.extend(replacement.chars());
+            col = range.end;
+            found = true;
+            if self.first_per_line {
+                break;
+            }
+        }
+        replaced.extend_from_slice(&line[col..]);
+        found.then_some(replaced)
+    }
+}
+
+impl Execute for ReplaceAll {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(replacer) = self.options.replacer(state) else {
+            return;
+        };
+
+        let bounds = state.search_bounds(&self.options.scope);
+        let this = &*self;
+        let replaced = map_lines(&state.lines, |row, line| {
+            this.replace_line(&replacer, line, row, bounds.as_ref())
+        });
+        let mut captured = false;
+        for (row, replaced) in replaced.into_iter().enumerate() {
+            let Some(replaced) = replaced else {
+                continue;
+            };
+            if !captured {
+                state.capture();
+                captured = true;
+            }
+            state.touch_row(row);
+            if let Some(line) = state.lines.get_mut(RowIndex::new(row)) {
+                *line = replaced;
+            }
+        }
+
+        if captured {
+            state.clamp_column();
+        }
+    }
+}
+
+/// Command to replace the next occurrence of a pattern at or after the
+/// cursor. The cursor moves to the occurrence that follows, so that the
+/// occurrences can be replaced one by one. Like searches, the command wraps
+/// around the end of the text unless wrap scan is disabled.
+///
+/// The pattern is matched like by [`ReplaceAll`].
+///
+/// ## Example
+///
+/// ```
+/// use edtui::actions::ReplaceNext;
+/// use edtui::{EditorState, Lines};
+///
+/// let mut state = EditorState::new(Lines::from("foo foo foo"));
+/// state.set_search_pattern("foo");
+/// state.set_search_replacement("bar");
+///
+/// state.execute(ReplaceNext::from_search());
+/// state.execute(ReplaceNext::from_search());
+/// assert_eq!(state.lines, Lines::from("bar bar foo"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplaceNext {
+    options: ReplaceOptions,
+}
+
+impl ReplaceNext {
+    /// Creates a command that replaces the next `pattern` with
+    /// `replacement`.
+    #[must_use]
+    pub fn new<P: Into<String>, R: Into<String>>(pattern: P, replacement: R) -> Self {
+        Self {
+            options: ReplaceOptions::new(Some(pattern.into()), Some(replacement.into())),
+        }
+    }
+
+    /// Creates a command that replaces the next match of the search pattern
+    /// with the replacement set with [`EditorState::set_search_replacement`].
+    #[must_use]
+    pub fn from_search() -> Self {
+        Self {
+            options: ReplaceOptions::new(None, None),
+        }
+    }
+
+    /// Restricts the replacement to a part of the text, e.g. to the visual
+    /// selection. Replaces in the entire text by default.
+    #[must_use]
+    pub fn scope(mut self, scope: SearchScope) -> Self {
+        self.options.scope = scope;
+        self
+    }
+
+    /// Matches case-insensitively and preserves the casing of the replaced
+    /// text. Disabled by default.
+    #[must_use]
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.options.preserve_case = preserve_case;
+        self
+    }
+
+    /// Matches the pattern as a regular expression, whose capture groups
+    /// can be referred to in the replacement. Disabled by default.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn regex(mut self, regex: bool) -> Self {
+        self.options.regex = regex;
+        self
+    }
+}
+
+impl Execute for ReplaceNext {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(replacer) = self.options.replacer(state) else {
+            return;
+        };
+        let bounds = state.search_bounds(&self.options.scope);
+        let Some((row, range, replacement)) =
+            next_match(state, &replacer, bounds.as_ref(), state.cursor)
+        else {
+            let message = format!("Pattern not found: {}", replacer.pattern);
+            state.notify(Notification::error(message));
+            return;
+        };
+
+        state.capture();
+        state.touch_row(row);
+        if let Some(line) = state.lines.get_mut(RowIndex::new(row)) {
+            line.splice(range.clone(), replacement.chars());
+        }
+        let after = Index2::new(row, range.start + replacement.chars().count());
+        state.cursor = match next_match(state, &replacer, bounds.as_ref(), after) {
+            Some((row, range, _)) => Index2::new(row, range.start),
+            None => Index2::new(row, range.start),
+        };
+        state.clamp_column();
+        if self.options.pattern.is_none() {
+            state.search.trigger_search(&state.lines);
+        }
+    }
+}
+
+/// Returns the first match at or after `from` together with its row and its
+/// replacement. Wraps around the end of the text if wrap scan is enabled.
+fn next_match(
+    state: &EditorState,
+    replacer: &Replacer,
+    bounds: Option<&Selection>,
+    from: Index2,
+) -> Option<(usize, Range<usize>, String)> {
+    let wrapped = if state.search.wrap_scan {
+        0..from.row + 1
+    } else {
+        0..0
+    };
+    for (i, row) in (from.row..state.lines.len()).chain(wrapped).enumerate() {
+        let Some(line) = state.lines.get(RowIndex::new(row)) else {
+            continue;
+        };
+        let found = replacer.matches(line).into_iter().find(|(range, _)| {
+            !(i == 0 && range.start < from.col)
+                && is_within(bounds, Index2::new(row, range.start), range.len())
+        });
+        if let Some((range, replacement)) = found {
+            return Some((row, range, replacement));
+        }
+    }
+    None
+}
+
+/// The pattern and the replacement of [`ReplaceAll`] and [`ReplaceNext`].
+#[derive(Clone, Debug)]
+struct ReplaceOptions {
+    /// The pattern, or `None` to use the search pattern.
+    pattern: Option<String>,
+    /// The replacement, or `None` to use the search replacement.
+    replacement: Option<String>,
+    preserve_case: bool,
+    scope: SearchScope,
+    #[cfg(feature = "regex")]
+    regex: bool,
+}
+
+impl ReplaceOptions {
+    fn new(pattern: Option<String>, replacement: Option<String>) -> Self {
+        Self {
+            pattern,
+            replacement,
+            preserve_case: false,
+            scope: SearchScope::Buffer,
+            #[cfg(feature = "regex")]
+            regex: false,
+        }
+    }
+
+    /// Resolves the pattern and the replacement in the current state.
+    /// Returns `None` if the pattern is empty, or shows an error if it is
+    /// not a valid regular expression. The search pattern is a regular
+    /// expression if it is matched as one by the search.
+    fn replacer(&self, state: &mut EditorState) -> Option<Replacer> {
+        let pattern = self
+            .pattern
+            .clone()
+            .unwrap_or_else(|| state.search.pattern_without_magic().to_string());
+        if pattern.is_empty() {
+            return None;
+        }
+        let replacement = self
+            .replacement
+            .clone()
+            .unwrap_or_else(|| state.search.replacement.clone());
+
+        #[cfg(feature = "regex")]
+        if self.regex || (self.pattern.is_none() && state.search.is_regex()) {
+            let regex = regex::RegexBuilder::new(&pattern)
+                .case_insensitive(self.preserve_case)
+                .build();
+            let Ok(regex) = regex else {
+                state.notify(Notification::error(format!("Invalid pattern: {pattern}")));
+                return None;
+            };
+            return Some(Replacer {
+                matcher: Matcher::Regex(regex),
+                pattern,
+                replacement,
+                preserve_case: self.preserve_case,
+            });
+        }
+
+        Some(Replacer {
+            matcher: Matcher::Literal(pattern.chars().collect()),
+            pattern,
+            replacement,
+            preserve_case: self.preserve_case,
+        })
+    }
+}
+
+/// A pattern together with its replacement, resolved when a replacement is
+/// executed.
+struct Replacer {
+    matcher: Matcher,
+    pattern: String,
+    replacement: String,
+    preserve_case: bool,
+}
+
+enum Matcher {
+    Literal(Vec<char>),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Replacer {
+    /// Returns the columns of the matches in `line` that do not overlap,
+    /// each together with the text that replaces it.
+    fn matches(&self, line: &[char]) -> Vec<(Range<usize>, String)> {
+        match &self.matcher {
+            Matcher::Literal(pattern) => {
+                let len = pattern.len();
+                let mut matches = Vec::new();
+                let mut col = 0;
+                while col + len <= line.len() {
+                    let text = &line[col..col + len];
+                    let is_match = if self.preserve_case {
+                        text.iter()
+                            .zip(pattern)
+                            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                    } else {
+                        text == pattern.as_slice()
+                    };
+                    if is_match {
+                        matches.push((col..col + len, self.with_case(&self.replacement, text)));
+                        col += len;
+                    } else {
+                        col += 1;
+                    }
+                }
+                matches
+            }
+            #[cfg(feature = "regex")]
+            Matcher::Regex(regex) => {
+                let text: String = line.iter().collect();
+                let col = |byte: usize| text[..byte].chars().count();
+                regex
+                    .captures_iter(&text)
+                    .filter_map(|captures| {
+                        let matched = captures.get(0)?;
+                        let range = col(matched.start())..col(matched.end());
+                        let mut replacement = String::new();
+                        captures.expand(&self.replacement, &mut replacement);
+                        let replacement = self.with_case(&replacement, &line[range.clone()]);
+                        Some((range, replacement))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn with_case(&self, replacement: &str, matched: &[char]) -> String {
+        if self.preserve_case {
+            match_case(replacement, matched)
+        } else {
+            replacement.to_string()
+        }
+    }
+}
+
+/// Applies the casing of `matched` to `replacement`: all uppercase, or an
+/// uppercase first letter. Other casings leave the replacement unchanged.
+fn match_case(replacement: &str, matched: &[char]) -> String {
+    let mut letters = matched.iter().filter(|ch| ch.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return replacement.to_string();
+    };
+    let rest_upper = letters.clone().all(|ch| ch.is_uppercase());
+    let rest_lower = letters.all(|ch| ch.is_lowercase());
+
+    if first.is_uppercase() && rest_upper && matched.len() > 1 {
+        return replacement.to_uppercase();
+    }
+    if first.is_uppercase() && rest_lower {
+        let mut chars = replacement.chars();
+        return chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        });
+    }
+    replacement.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index2, Lines};
+
+    #[test]
+    fn test_replace_all() {
+        let mut state = EditorState::new(Lines::from("foo Foo\nfoofoo"));
+        state.cursor = Index2::new(1, 5);
+
+        state.execute(ReplaceAll::new("foo", "x"));
+
+        assert_eq!(state.lines, Lines::from("x Foo\nxx"));
+        assert_eq!(state.cursor, Index2::new(1, 1));
+    }
+
+    #[test]
+    fn test_replace_all_preserve_case() {
+        let mut state = EditorState::new(Lines::from("foo Foo FOO fOO"));
+
+        state.execute(ReplaceAll::new("foo", "bar").preserve_case(true));
+
+        assert_eq!(state.lines, Lines::from("bar Bar BAR bar"));
+    }
+
+    #[test]
+    fn test_replace_all_in_scope() {
+        let mut state = EditorState::new(Lines::from("a a\na a\na a"));
+
+        state.execute(ReplaceAll::new("a", "b").scope(SearchScope::Rows(1..3)));
+        assert_eq!(state.lines, Lines::from("a a\nb b\nb b"));
+
+        state.last_visual = Some((
+            Selection::new(Index2::new(0, 2), Index2::new(1, 0)),
+            state.cursor,
+        ));
+        state.execute(ReplaceAll::new("a", "c").scope(SearchScope::Selection));
+        assert_eq!(state.lines, Lines::from("a c\nb b\nb b"));
+    }
+
+    #[test]
+    fn test_replace_next() {
+        let mut state = EditorState::new(Lines::from("ab ab\nab"));
+        state.cursor = Index2::new(0, 1);
+
+        state.execute(ReplaceNext::new("ab", "x"));
+        assert_eq!(state.lines, Lines::from("ab x\nab"));
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        state.execute(ReplaceNext::new("ab", "x"));
+        state.execute(ReplaceNext::new("ab", "x"));
+        assert_eq!(state.lines, Lines::from("x x\nx"));
+
+        state.execute(ReplaceNext::new("ab", "x"));
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error("Pattern not found: ab"))
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_replace_regex() {
+        let mut state = EditorState::new(Lines::from("fn ä(a: u8)\nfn b(b: u16)"));
+
+        state.execute(ReplaceAll::new(r"(\w+): (\w+)", "$2 ${1}").regex(true));
+        assert_eq!(state.lines, Lines::from("fn ä(u8 a)\nfn b(u16 b)"));
+
+        state.last_visual = Some((
+            Selection::new(Index2::new(1, 0), Index2::new(1, 11)),
+            state.cursor,
+        ));
+        let replace = ReplaceAll::new("^fn", "pub fn").regex(true);
+        state.execute(replace.scope(SearchScope::Selection));
+        assert_eq!(state.lines, Lines::from("fn ä(u8 a)\npub fn b(u16 b)"));
+
+        state.execute(ReplaceNext::new("(", "").regex(true));
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error("Invalid pattern: ("))
+        );
+    }
+
+    #[test]
+    fn test_search_in_scope() {
+        let mut state = EditorState::new(Lines::from("ab\nab\nab"));
+        state.set_search_scope(SearchScope::Rows(1..2));
+
+        state.execute(StartSearch);
+        state.execute(AppendCharToSearch('b'));
+
+        assert_eq!(state.search.matches, vec![Index2::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("bar", &['F']), "Bar");
+        assert_eq!(match_case("bar", &['F', 'O']), "BAR");
+        assert_eq!(match_case("bar", &['1']), "bar");
+    }
+
+    #[test]
+    fn test_find_wraps() {
+        let mut state = EditorState::new(Lines::from("foo\nfoo"));
+        state.set_search_pattern("foo");
+
+        state.execute(FindPrevious);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+        assert!(state.search_wrapped());
+        assert_eq!(
+            state.notification().map(|n| n.message.as_str()),
+            Some("Search hit TOP, continuing at BOTTOM")
+        );
+
+        state.clear_notification();
+        state.execute(FindPrevious);
+        assert!(!state.search_wrapped());
+        assert_eq!(state.notification(), None);
+
+        state.set_wrap_scan(false);
+        state.execute(FindPrevious);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error(
+                "Search hit TOP without match for: foo"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_search_offset() {
+        let mut state = EditorState::new(Lines::from("a foo\nb\nfoo/bar"));
+        let mut handler = crate::EditorEventHandler::default();
+
+        handler.feed_keys("/foo/e<CR>", &mut state).unwrap();
+        assert_eq!(state.cursor, Index2::new(0, 4));
+        assert_eq!(state.search_offset(), SearchOffset::End(0));
+
+        handler.feed_keys("n", &mut state).unwrap();
+        assert_eq!(state.cursor, Index2::new(2, 2));
+
+        handler.feed_keys("gg/foo/+1<CR>", &mut state).unwrap();
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        handler
+            .feed_keys("/foo\\/b/x<BS>s+1<CR>", &mut state)
+            .unwrap();
+        assert_eq!(state.search_pattern(), "foo/b");
+        assert_eq!(state.cursor, Index2::new(2, 1));
+
+        handler.feed_keys("/foo/x<CR>", &mut state).unwrap();
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error("Invalid search offset: x"))
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_search_magic() {
+        let mut state = EditorState::new(Lines::from("a1 b22 c333"));
+        let mut handler = crate::EditorEventHandler::default();
+
+        handler.feed_keys("/\\vb\\d+/e<CR>", &mut state).unwrap();
+        assert!(state.search_is_regex());
+        assert_eq!(state.cursor, Index2::new(0, 5));
+
+        state.set_search_replacement("x");
+        state.execute(ReplaceAll::from_search());
+        assert_eq!(state.lines, Lines::from("a1 x c333"));
+
+        handler.feed_keys(":s/\\V\\d/y/<CR>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a1 x c333"));
+        handler.feed_keys(":s/\\v\\d+$/y/<CR>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a1 x cy"));
+    }
+}
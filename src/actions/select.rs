@@ -1,8 +1,19 @@
+use std::cmp::Reverse;
+
 use jagged::index::RowIndex;
 
-use super::{delete::delete_selection, motion::CharacterClass, Execute};
+use super::{
+    delete::delete_selection,
+    motion::{row_subwords, CharacterClass},
+    Execute, SwitchMode,
+};
 use crate::{
-    clipboard::ClipboardTrait, state::selection::Selection, EditorMode, EditorState, Index2, Lines,
+    helper::chars_width,
+    state::{
+        occurrences::word_range,
+        selection::{set_selection_with_lines, Selection},
+    },
+    EditorMode, EditorState, Index2, Lines,
 };
 
 /// Selects text between specified delimiter characters.
@@ -25,18 +36,215 @@ impl SelectInnerBetween {
 
 impl Execute for SelectInnerBetween {
     fn execute(&mut self, state: &mut EditorState) {
-        if let Some(selection) = select_between(
-            &state.lines,
-            state.cursor,
-            |(ch, _)| *ch == self.opening,
-            |(ch, _)| *ch == self.closing,
-            |(_, _)| false,
-            |(_, _)| false,
-        ) {
-            state.selection = Some(selection);
+        let (start, end) = visual_range(state);
+        let find = |start, end| {
+            find_enclosing(
+                &state.lines,
+                start,
+                end,
+                self.opening,
+                self.closing,
+                start == end,
+            )
+        };
+
+        let Some((mut opening, mut closing)) = find(start, end) else {
+            return;
+        };
+        let mut inner = inner_range(&state.lines, opening, closing);
+        // If the inner text is selected already, expand to the next enclosing pair.
+        if start != end && inner == Some((start, end)) {
+            let Some(pair) = find(opening, closing) else {
+                return;
+            };
+            (opening, closing) = pair;
+            inner = inner_range(&state.lines, opening, closing);
+        }
+
+        if let Some((start, end)) = inner {
+            state.selection = Some(Selection::new(start, end));
             state.mode = EditorMode::Visual;
+            state.cursor = end;
+        }
+    }
+}
+
+/// Returns the range that is selected in visual mode, or the cursor position
+/// in any other mode.
+fn visual_range(state: &EditorState) -> (Index2, Index2) {
+    match &state.selection {
+        Some(selection) if state.mode == EditorMode::Visual => (selection.start(), selection.end()),
+        _ => (state.cursor, state.cursor),
+    }
+}
+
+/// Finds the innermost pair of delimiters that encloses the range from
+/// `start` to `end`. If `inclusive` is set, the delimiters may lie on the
+/// boundaries of the range. Quotes (`opening == closing`) are only matched
+/// within a single line. If the cursor is on a quote, the quotes of the line
+/// are paired from its start to tell whether it opens or closes a string.
+fn find_enclosing(
+    lines: &Lines,
+    start: Index2,
+    end: Index2,
+    opening: char,
+    closing: char,
+    inclusive: bool,
+) -> Option<(Index2, Index2)> {
+    if lines.is_empty() {
+        return None;
+    }
+
+    if opening == closing {
+        if start.row != end.row {
+            return None;
+        }
+        let line = lines.get(RowIndex::new(start.row))?;
+        if inclusive && line.get(start.col) == Some(&opening) {
+            let before = line[..start.col].iter().filter(|&&ch| ch == opening);
+            let (open, close) = if before.count() % 2 == 1 {
+                (
+                    line[..start.col].iter().rposition(|&ch| ch == opening)?,
+                    start.col,
+                )
+            } else {
+                let after = line[start.col + 1..].iter().position(|&ch| ch == closing)?;
+                (start.col, start.col + 1 + after)
+            };
+            return Some((Index2::new(start.row, open), Index2::new(start.row, close)));
+        }
+        let first = if inclusive { start.col + 1 } else { start.col };
+        let open = line.get(..first)?.iter().rposition(|&ch| ch == opening)?;
+        let last = if inclusive { end.col } else { end.col + 1 };
+        let from = last.max(open + 1);
+        let close = from + line.get(from..)?.iter().position(|&ch| ch == closing)?;
+        return Some((Index2::new(start.row, open), Index2::new(end.row, close)));
+    }
+
+    let mut iter = lines.iter().from(start).rev().peekable();
+    let starts_on_closing = iter.peek().is_some_and(|(ch, _)| *ch == Some(&closing));
+    if !inclusive || starts_on_closing {
+        iter.next();
+    }
+
+    let mut depth = 0;
+    for (ch, index) in iter {
+        match ch {
+            Some(&ch) if ch == closing => depth += 1,
+            Some(&ch) if ch == opening && depth > 0 => depth -= 1,
+            Some(&ch) if ch == opening => {
+                let close = find_closing(lines, index, opening, closing)?;
+                if close > end || inclusive && close == end {
+                    return Some((index, close));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Finds the delimiter that closes the `opening` delimiter at `index`.
+fn find_closing(lines: &Lines, index: Index2, opening: char, closing: char) -> Option<Index2> {
+    let mut depth = 0;
+    for (ch, index) in lines.iter().from(index).skip(1) {
+        match ch {
+            Some(&ch) if ch == opening => depth += 1,
+            Some(&ch) if ch == closing && depth == 0 => return Some(index),
+            Some(&ch) if ch == closing => depth -= 1,
+            _ => {}
         }
     }
+    None
+}
+
+/// Returns the range between a pair of delimiters, or `None` if there is
+/// nothing in between.
+fn inner_range(lines: &Lines, opening: Index2, closing: Index2) -> Option<(Index2, Index2)> {
+    let (_, start) = lines
+        .iter()
+        .from(opening)
+        .skip(1)
+        .find(|(ch, _)| ch.is_some())?;
+    let (_, end) = lines
+        .iter()
+        .from(closing)
+        .rev()
+        .skip(1)
+        .find(|(ch, _)| ch.is_some())?;
+    (start < closing && end > opening && start <= end).then_some((start, end))
+}
+
+/// Expands the selection to the next enclosing scope: the word under the
+/// cursor, the text within the enclosing brackets or quotes, or the brackets
+/// or quotes themselves.
+#[derive(Clone, Debug, Copy)]
+pub struct ExpandSelection;
+
+impl Execute for ExpandSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let (start, end) = visual_range(state);
+        let inclusive = start == end;
+
+        let mut candidates = Vec::new();
+        if inclusive {
+            if let Some(line) = state.lines.get(RowIndex::new(start.row)) {
+                if let Some(range) = word_range(line, start.col) {
+                    let word = (
+                        Index2::new(start.row, range.start),
+                        Index2::new(start.row, range.end - 1),
+                    );
+                    candidates.push(word);
+                }
+            }
+        }
+        for (opening, closing) in [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')] {
+            let Some(pair) = find_enclosing(&state.lines, start, end, opening, closing, inclusive)
+            else {
+                continue;
+            };
+            candidates.extend(inner_range(&state.lines, pair.0, pair.1));
+            candidates.push(pair);
+        }
+
+        // The smallest range that is larger than the current one.
+        let Some((new_start, new_end)) = candidates
+            .into_iter()
+            .filter(|&(s, e)| s <= start && e >= end && (s, e) != (start, end))
+            .max_by_key(|&(s, e)| ((s.row, s.col), Reverse((e.row, e.col))))
+        else {
+            return;
+        };
+
+        let previous = match &state.selection {
+            Some(selection) if state.mode == EditorMode::Visual => selection.clone(),
+            _ => Selection::new(state.cursor, state.cursor),
+        };
+        state.expansions.push(previous);
+        state.selection = Some(Selection::new(new_start, new_end));
+        state.mode = EditorMode::Visual;
+        state.cursor = new_end;
+    }
+}
+
+/// Restores the selection from before the last [`ExpandSelection`].
+#[derive(Clone, Debug, Copy)]
+pub struct ShrinkSelection;
+
+impl Execute for ShrinkSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let (start, end) = visual_range(state);
+        let Some(previous) = state.expansions.pop() else {
+            return;
+        };
+        if state.mode != EditorMode::Visual || previous.start() < start || previous.end() > end {
+            state.expansions.clear();
+            return;
+        }
+        state.cursor = previous.end;
+        state.selection = Some(previous);
+    }
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -56,12 +264,15 @@ impl Execute for SelectInnerWord {
         let max_col_index = len_col.saturating_sub(1);
 
         let start_col = state.cursor.col;
-        let start_char_class = CharacterClass::from(line.get(start_col));
+        let word_chars = &state.word_chars;
+        let start_char_class = CharacterClass::classify(line.get(start_col), word_chars);
 
-        let opening_predicate =
-            |(ch, _): (&char, usize)| CharacterClass::from(ch) != start_char_class.clone();
-        let closing_predicate =
-            |(ch, _): (&char, usize)| CharacterClass::from(ch) != start_char_class.clone();
+        let opening_predicate = |(ch, _): (&char, usize)| {
+            CharacterClass::classify(Some(ch), word_chars) != start_char_class.clone()
+        };
+        let closing_predicate = |(ch, _): (&char, usize)| {
+            CharacterClass::classify(Some(ch), word_chars) != start_char_class.clone()
+        };
 
         if let Some(selection) = select_between(
             &state.lines,
@@ -76,6 +287,28 @@ impl Execute for SelectInnerWord {
     }
 }
 
+/// Selects the sub-word under the cursor, e.g. `Case` in `camelCase` or
+/// `snake` in `snake_case`. See [`MoveSubwordForward`].
+///
+/// [`MoveSubwordForward`]: crate::actions::MoveSubwordForward
+#[derive(Clone, Debug, Copy)]
+pub struct SelectInnerSubword;
+
+impl Execute for SelectInnerSubword {
+    fn execute(&mut self, state: &mut EditorState) {
+        let cursor = state.cursor;
+        if let Some(&(start, end)) = row_subwords(state, cursor.row)
+            .iter()
+            .find(|(start, end)| (*start..=*end).contains(&cursor.col))
+        {
+            state.selection = Some(Selection::new(
+                Index2::new(cursor.row, start),
+                Index2::new(cursor.row, end),
+            ));
+        }
+    }
+}
+
 fn select_between(
     lines: &Lines,
     cursor: Index2,
@@ -146,7 +379,7 @@ impl Execute for ChangeInnerWord {
         if let Some(selection) = state.selection.take() {
             state.capture();
             let deleted = delete_selection(state, &selection);
-            state.clip.set_text(deleted.into());
+            state.yank_deleted(deleted.into());
         }
     }
 }
@@ -170,16 +403,33 @@ impl Execute for ChangeInnerBetween {
         if let Some(selection) = state.selection.take() {
             state.capture();
             let deleted = delete_selection(state, &selection);
-            state.clip.set_text(deleted.into());
+            state.yank_deleted(deleted.into());
         }
     }
 }
 
+/// Selects the current line. The selection is extended by whole lines as
+/// the cursor moves. In visual mode, a charwise selection is turned into a
+/// linewise selection, and a linewise selection is ended.
 #[derive(Clone, Debug, Copy)]
 pub struct SelectLine;
 
 impl Execute for SelectLine {
     fn execute(&mut self, state: &mut EditorState) {
+        if state.mode == EditorMode::Visual {
+            if let Some(selection) = state.selection.as_mut() {
+                if selection.line_mode {
+                    SwitchMode(EditorMode::Normal).execute(state);
+                } else {
+                    selection.anchor = Some(selection.anchor_position());
+                    selection.line_mode = true;
+                    selection.block_mode = false;
+                    set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+                }
+                return;
+            }
+        }
+
         let row = state.cursor.row;
         if let Some(len_col) = state.lines.len_col(row) {
             let start = Index2::new(row, 0);
@@ -190,20 +440,562 @@ impl Execute for SelectLine {
     }
 }
 
+/// Selects a block of columns, like `Ctrl+v` in vim. The block spans the
+/// rows and the columns between the start of the selection and the cursor.
+/// In visual mode, a charwise or linewise selection is turned into a block
+/// selection, and a block selection is ended.
+#[derive(Clone, Debug, Copy)]
+pub struct SelectBlock;
+
+impl Execute for SelectBlock {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.mode == EditorMode::Visual {
+            if let Some(selection) = state.selection.as_mut() {
+                if selection.block_mode {
+                    SwitchMode(EditorMode::Normal).execute(state);
+                } else {
+                    let anchor = selection.anchor_position();
+                    *selection = Selection::new(anchor, state.cursor).block_mode();
+                }
+                return;
+            }
+        }
+
+        state.selection = Some(Selection::new(state.cursor, state.cursor).block_mode());
+        state.mode = EditorMode::Visual;
+    }
+}
+
+/// Inserts text in front of the selection on every selected row, like `I`
+/// in the visual block mode of vim. The text is typed into the first row
+/// and repeated on the other rows when insert mode is left. Rows that end
+/// before the block are left unchanged. A linewise selection inserts at the
+/// start of each line.
+#[derive(Clone, Debug, Copy)]
+pub struct InsertBlock;
+
+impl Execute for InsertBlock {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(selection) = state.selection.take() else {
+            return;
+        };
+        let (start, end) = (selection.start(), selection.end());
+        let (rows, col) = if selection.block_mode {
+            (start.row..=end.row, start.col)
+        } else if selection.line_mode {
+            (start.row..=end.row, 0)
+        } else {
+            (start.row..=start.row, start.col)
+        };
+        state.start_block_insert(rows, Some(col), false);
+    }
+}
+
+/// Appends text after the selection on every selected row, like `A` in the
+/// visual block mode of vim. The text is typed into the first row and
+/// repeated on the other rows when insert mode is left. Rows that end before
+/// the block are padded with spaces. A linewise selection appends to the end
+/// of each line.
+#[derive(Clone, Debug, Copy)]
+pub struct AppendBlock;
+
+impl Execute for AppendBlock {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(selection) = state.selection.take() else {
+            return;
+        };
+        let (start, end) = (selection.start(), selection.end());
+        if selection.block_mode {
+            state.start_block_insert(start.row..=end.row, Some(end.col + 1), true);
+        } else if selection.line_mode {
+            state.start_block_insert(start.row..=end.row, None, false);
+        } else {
+            state.start_block_insert(end.row..=end.row, Some(end.col + 1), false);
+        }
+    }
+}
+
+/// Selects the entire text linewise and switches to visual mode, like the
+/// `ae` text object. See also [`EditorState::select_entire_buffer`].
+#[derive(Clone, Debug, Copy)]
+pub struct SelectEntireBuffer;
+
+impl Execute for SelectEntireBuffer {
+    fn execute(&mut self, state: &mut EditorState) {
+        if state.lines.is_empty() {
+            return;
+        }
+        let last = state.lines.last_row_index();
+        let end = Index2::new(last, state.lines.last_col_index(last));
+        state.selection = Some(Selection::new(Index2::new(0, 0), end).line_mode());
+        state.cursor = Index2::new(last, 0);
+        state.mode = EditorMode::Visual;
+    }
+}
+
+/// Selects the text object with the given key in the line of the cursor,
+/// such as the number object `n`. See [`EditorState::set_text_object`].
+#[derive(Clone, Debug, Copy)]
+pub struct SelectTextObject(pub char);
+
+impl Execute for SelectTextObject {
+    fn execute(&mut self, state: &mut EditorState) {
+        let row = state.cursor.row;
+        let Some(line) = state.lines.get(RowIndex::new(row)) else {
+            return;
+        };
+        let Some(range) = state.text_objects.find(self.0, line, state.cursor.col) else {
+            return;
+        };
+        let (start, end) = (
+            Index2::new(row, range.start),
+            Index2::new(row, range.end - 1),
+        );
+        state.selection = Some(Selection::new(start, end));
+        state.mode = EditorMode::Visual;
+        state.cursor = end;
+    }
+}
+
+/// Selects the lines around the cursor that are indented at least as deep
+/// as the cursor line, like the `ii` text object. Blank lines within the
+/// block are included. Useful for indentation based languages like Python
+/// or YAML.
+#[derive(Clone, Debug, Copy)]
+pub struct SelectInnerIndent;
+
+impl Execute for SelectInnerIndent {
+    fn execute(&mut self, state: &mut EditorState) {
+        select_indent(state, false);
+    }
+}
+
+/// Selects the lines of [`SelectInnerIndent`] together with the line above
+/// them, e.g. the header of a Python block, like the `ai` text object.
+#[derive(Clone, Debug, Copy)]
+pub struct SelectAroundIndent;
+
+impl Execute for SelectAroundIndent {
+    fn execute(&mut self, state: &mut EditorState) {
+        select_indent(state, true);
+    }
+}
+
+fn select_indent(state: &mut EditorState, around: bool) {
+    let tab_width = state.view.tab_width;
+    let indent = |row: usize| {
+        let line = state.lines.get(RowIndex::new(row))?;
+        let leading = line.iter().take_while(|ch| ch.is_whitespace()).count();
+        (leading < line.len()).then(|| chars_width(&line[..leading], tab_width))
+    };
+    // Blank lines belong to the block of the next non-blank line
+    let Some((row, level)) =
+        (state.cursor.row..state.lines.len()).find_map(|row| indent(row).map(|level| (row, level)))
+    else {
+        return;
+    };
+    let in_block = |row: usize| indent(row).is_none_or(|indent| indent >= level);
+
+    let mut first = row;
+    while first > 0 && in_block(first - 1) {
+        first -= 1;
+    }
+    let mut last = row;
+    while last + 1 < state.lines.len() && in_block(last + 1) {
+        last += 1;
+    }
+    // Leave out blank lines at the edges of the block
+    while first < row && indent(first).is_none() {
+        first += 1;
+    }
+    while last > row && indent(last).is_none() {
+        last -= 1;
+    }
+    if around && first > 0 {
+        first -= 1;
+        while first > 0 && indent(first).is_none() {
+            first -= 1;
+        }
+    }
+
+    let end = Index2::new(
+        last,
+        state
+            .lines
+            .len_col(last)
+            .unwrap_or_default()
+            .saturating_sub(1),
+    );
+    state.selection = Some(Selection::new(Index2::new(first, 0), end).line_mode());
+    state.mode = EditorMode::Visual;
+    state.cursor = Index2::new(last, 0);
+}
+
+/// Moves the cursor to the other end of the selection, so that the
+/// selection can be extended in the opposite direction.
+#[derive(Clone, Debug, Copy)]
+pub struct SwapSelectionEnds;
+
+impl Execute for SwapSelectionEnds {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(selection) = state.selection.as_mut() else {
+            return;
+        };
+        state.cursor = selection.swap_anchor(state.cursor);
+        set_selection_with_lines(&mut state.selection, state.cursor, &state.lines);
+    }
+}
+
+/// Re-enters visual mode with the last visual selection, similar to `gv`
+/// in vim. The selection is moved along with the edits made since.
+#[derive(Clone, Debug, Copy)]
+pub struct ReselectLastVisual;
+
+impl Execute for ReselectLastVisual {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some((mut selection, cursor)) = state.last_visual.clone() else {
+            return;
+        };
+        selection.start = state.clamp_index(selection.start);
+        selection.end = state.clamp_index(selection.end);
+        selection.anchor = selection.anchor.map(|anchor| state.clamp_index(anchor));
+
+        state.selection = Some(selection);
+        state.mode = EditorMode::Visual;
+        state.cursor = state.clamp_index(cursor);
+        state.clamp_column();
+    }
+}
+
+/// Uppercases the selected text and returns to normal mode.
+#[derive(Clone, Debug, Copy)]
+pub struct UppercaseSelection;
+
+impl Execute for UppercaseSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        map_selection(state, |ch| convert_case(ch, ch.to_uppercase()));
+    }
+}
+
+/// Lowercases the selected text and returns to normal mode.
+#[derive(Clone, Debug, Copy)]
+pub struct LowercaseSelection;
+
+impl Execute for LowercaseSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        map_selection(state, |ch| convert_case(ch, ch.to_lowercase()));
+    }
+}
+
+/// Replaces every selected character with the given character and returns
+/// to normal mode. Line breaks are kept.
+#[derive(Clone, Debug, Copy)]
+pub struct ReplaceSelection(pub char);
+
+impl Execute for ReplaceSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let replacement = self.0;
+        map_selection(state, |_| replacement);
+    }
+}
+
+/// Keeps characters whose case conversion would change the text length,
+/// such as `ß`, unchanged.
+fn convert_case(ch: char, mut converted: impl Iterator<Item = char>) -> char {
+    match (converted.next(), converted.next()) {
+        (Some(converted), None) => converted,
+        _ => ch,
+    }
+}
+
+/// Applies `f` to every selected character as a single undo step. Leaves
+/// visual mode and moves the cursor to the start of the selection.
+fn map_selection(state: &mut EditorState, f: impl Fn(char) -> char) {
+    let Some(selection) = state.selection.take() else {
+        return;
+    };
+    state.capture();
+
+    let (start, end) = (selection.start(), selection.end());
+    state.touch_rows(start.row..end.row + 1);
+    for row in start.row..=end.row.min(state.lines.last_row_index()) {
+        let Some(line) = state.lines.get_mut(RowIndex::new(row)) else {
+            continue;
+        };
+        let first = if selection.block_mode {
+            start.col
+        } else if selection.line_mode || row > start.row {
+            0
+        } else {
+            start.col
+        };
+        let last = if selection.block_mode {
+            (end.col + 1).min(line.len())
+        } else if selection.line_mode || row < end.row {
+            line.len()
+        } else {
+            (end.col + 1).min(line.len())
+        };
+        for ch in line.iter_mut().take(last).skip(first) {
+            *ch = f(*ch);
+        }
+    }
+
+    state.mode = EditorMode::Normal;
+    state.cursor = if selection.line_mode {
+        Index2::new(start.row, 0)
+    } else {
+        start
+    };
+    state.clamp_column();
+}
+
+/// Aligns the selected lines on a delimiter, such as `|`, `,` or `=`, by
+/// padding the text in front of each delimiter with spaces. Lines without
+/// the delimiter are kept unchanged. Returns to normal mode.
+#[derive(Clone, Debug, Copy)]
+pub struct AlignSelection(pub char);
+
+impl Execute for AlignSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let Some(selection) = state.selection.take() else {
+            return;
+        };
+        state.capture();
+
+        let delimiter = self.0;
+        let (start, end) = (selection.start(), selection.end());
+        let rows = start.row..=end.row.min(state.lines.last_row_index());
+
+        // The cells of each line, without trailing whitespace, and whether
+        // any cell of a column was followed by whitespace.
+        let mut cells: Vec<(usize, Vec<Vec<char>>)> = Vec::new();
+        let mut columns: Vec<(usize, bool)> = Vec::new();
+        for row in rows {
+            let Some(line) = state.lines.get(RowIndex::new(row)) else {
+                continue;
+            };
+            if !line.contains(&delimiter) {
+                continue;
+            }
+            let line_cells: Vec<Vec<char>> = line
+                .split(|&ch| ch == delimiter)
+                .map(<[char]>::to_vec)
+                .collect();
+            for (i, cell) in line_cells.iter().enumerate().take(line_cells.len() - 1) {
+                let len = trimmed_len(cell);
+                if columns.len() <= i {
+                    columns.push((0, false));
+                }
+                columns[i].0 = columns[i].0.max(len);
+                columns[i].1 |= len < cell.len();
+            }
+            cells.push((row, line_cells));
+        }
+
+        for (row, line_cells) in cells {
+            let mut aligned = Vec::new();
+            let last = line_cells.len() - 1;
+            for (i, mut cell) in line_cells.into_iter().enumerate() {
+                if i < last {
+                    let (width, gap) = columns[i];
+                    let gap = usize::from(gap && width > 0);
+                    cell.truncate(trimmed_len(&cell));
+                    cell.resize(width + gap, ' ');
+                    cell.push(delimiter);
+                }
+                aligned.extend(cell);
+            }
+            state.touch_row(row);
+            if let Some(line) = state.lines.get_mut(RowIndex::new(row)) {
+                *line = aligned;
+            }
+        }
+
+        state.mode = EditorMode::Normal;
+        state.cursor = Index2::new(start.row, 0);
+    }
+}
+
+/// Indents the selected lines, or the current line, by the given number of
+/// levels. A level is as wide as the tab width, see
+/// [`EditorView::tab_width`]. Blank lines are kept unchanged.
+///
+/// The selection is kept, so that the same lines can be shifted again,
+/// unless disabled with [`EditorState::set_keep_visual_selection`].
+///
+/// [`EditorView::tab_width`]: crate::EditorView::tab_width
+#[derive(Clone, Debug, Copy)]
+pub struct IndentSelection(pub usize);
+
+impl Execute for IndentSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let width = state.view.tab_width * self.0;
+        shift_lines(state, 1, |line| indent(line, width));
+    }
+}
+
+/// Removes up to the given number of indentation levels from the selected
+/// lines, or the current line. A level is a tab or as many spaces as the
+/// tab width.
+///
+/// The selection is kept, so that the same lines can be shifted again,
+/// unless disabled with [`EditorState::set_keep_visual_selection`].
+#[derive(Clone, Debug, Copy)]
+pub struct DedentSelection(pub usize);
+
+impl Execute for DedentSelection {
+    fn execute(&mut self, state: &mut EditorState) {
+        let (levels, width) = (self.0, state.view.tab_width.max(1));
+        shift_lines(state, 1, |line| dedent(line, levels, width));
+    }
+}
+
+/// Indents the given number of lines, starting at the current line, by one
+/// level, like `>>` in vim. With a selection, the selected lines are
+/// indented instead, see [`IndentSelection`].
+#[derive(Clone, Debug, Copy)]
+pub struct IndentLines(pub usize);
+
+impl Execute for IndentLines {
+    fn execute(&mut self, state: &mut EditorState) {
+        let width = state.view.tab_width;
+        shift_lines(state, self.0, |line| indent(line, width));
+    }
+}
+
+/// Removes one indentation level from the given number of lines, starting
+/// at the current line, like `<<` in vim. With a selection, the selected
+/// lines are dedented instead, see [`DedentSelection`].
+#[derive(Clone, Debug, Copy)]
+pub struct DedentLines(pub usize);
+
+impl Execute for DedentLines {
+    fn execute(&mut self, state: &mut EditorState) {
+        let width = state.view.tab_width.max(1);
+        shift_lines(state, self.0, |line| dedent(line, 1, width));
+    }
+}
+
+/// Inserts `width` spaces at the start of a non-blank line. Returns by how
+/// many characters the start of the line moved.
+fn indent(line: &mut Vec<char>, width: usize) -> isize {
+    if line.iter().all(|ch| ch.is_whitespace()) {
+        return 0;
+    }
+    line.splice(0..0, std::iter::repeat_n(' ', width));
+    width as isize
+}
+
+/// Removes up to `levels` tabs or runs of `width` spaces from the start of
+/// a line. Returns by how many characters the start of the line moved.
+fn dedent(line: &mut Vec<char>, levels: usize, width: usize) -> isize {
+    let mut removed = 0;
+    for _ in 0..levels {
+        let level = match line.get(removed) {
+            Some('\t') => 1,
+            _ => line[removed..]
+                .iter()
+                .take(width)
+                .take_while(|&&ch| ch == ' ')
+                .count(),
+        };
+        if level == 0 {
+            break;
+        }
+        removed += level;
+    }
+    line.drain(..removed);
+    -(removed as isize)
+}
+
+/// Applies `f` to the selected lines, or to `count` lines starting at the
+/// current line, as a single undo step. `f` returns by how many characters
+/// the start of the line moved. Keeps the selection if configured,
+/// otherwise returns to normal mode.
+fn shift_lines(state: &mut EditorState, count: usize, f: impl Fn(&mut Vec<char>) -> isize) {
+    let selection = state.selection.take();
+    let (first, last) = match &selection {
+        Some(selection) => (selection.start().row, selection.end().row),
+        None => (
+            state.cursor.row,
+            state.cursor.row.saturating_add(count.saturating_sub(1)),
+        ),
+    };
+    state.capture();
+    state.touch_rows(first..last.saturating_add(1));
+
+    let mut shifts = Vec::new();
+    for row in first..=last.min(state.lines.last_row_index()) {
+        if let Some(line) = state.lines.get_mut(RowIndex::new(row)) {
+            shifts.push((row, f(line)));
+        }
+    }
+    let shift = |index: Index2| {
+        let delta = shifts
+            .iter()
+            .find(|(row, _)| *row == index.row)
+            .map_or(0, |(_, delta)| *delta);
+        Index2::new(index.row, index.col.saturating_add_signed(delta))
+    };
+
+    match selection {
+        Some(mut selection) if state.keep_visual_selection => {
+            selection.start = shift(selection.start);
+            selection.end = shift(selection.end);
+            state.cursor = shift(state.cursor);
+            state.selection = Some(selection);
+        }
+        Some(selection) => {
+            state.mode = EditorMode::Normal;
+            state.cursor = Index2::new(selection.start().row, 0);
+        }
+        None => state.cursor = shift(state.cursor),
+    }
+    state.clamp_column();
+}
+
+/// Returns the length of `text` without trailing whitespace.
+fn trimmed_len(text: &[char]) -> usize {
+    text.iter()
+        .rposition(|ch| !ch.is_whitespace())
+        .map_or(0, |i| i + 1)
+}
+
+/// Deletes the selection, so that it can be replaced with new text. In line
+/// mode, the selected rows are replaced with a single empty row. In block
+/// mode, the text typed afterwards is inserted into every selected row, see
+/// [`InsertBlock`].
 #[derive(Clone, Debug, Copy)]
 pub struct ChangeSelection;
 impl Execute for ChangeSelection {
     fn execute(&mut self, state: &mut EditorState) {
         if let Some(selection) = state.selection.take() {
             state.capture();
+            let is_whole_buffer =
+                selection.start().row == 0 && selection.end().row >= state.lines.last_row_index();
             let deleted = delete_selection(state, &selection);
-            state.clip.set_text(deleted.into());
+            state.yank_deleted(deleted.into());
+            if selection.block_mode {
+                let (start, end) = (selection.start(), selection.end());
+                state.start_block_insert(start.row..=end.row, Some(start.col), false);
+            }
+            if selection.line_mode && !is_whole_buffer {
+                let row = selection.start().row;
+                state.touch_rows(row..row);
+                state.lines.insert(RowIndex::new(row), vec![]);
+                state.cursor = Index2::new(row, 0);
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::actions::{
+        DeleteLine, DeleteSelection, MoveBackward, MoveDown, MoveForward, MoveUp, SwitchMode,
+    };
+    use crate::clipboard::ClipboardTrait;
     use crate::state::selection::Selection;
     use crate::Index2;
     use crate::Lines;
@@ -455,6 +1247,71 @@ mod tests {
         assert_eq!(state.selection.unwrap(), want);
     }
 
+    #[test]
+    fn test_select_inner_between_on_quote() {
+        let mut state = EditorState::new(Lines::from("\"ab\" \"cd\""));
+        state.cursor = Index2::new(0, 3);
+        state.execute(SelectInnerBetween::new('"', '"'));
+        let want = Selection::new(Index2::new(0, 1), Index2::new(0, 2));
+        assert_eq!(state.selection, Some(want));
+
+        state.mode = EditorMode::Normal;
+        state.cursor = Index2::new(0, 5);
+        state.execute(SelectInnerBetween::new('"', '"'));
+        let want = Selection::new(Index2::new(0, 6), Index2::new(0, 7));
+        assert_eq!(state.selection, Some(want));
+    }
+
+    #[test]
+    fn test_select_between_in_empty_buffer() {
+        let mut state = EditorState::new(Lines::default());
+
+        state.execute(SelectInnerBetween::new('(', ')'));
+        state.execute(SelectInnerBetween::new('"', '"'));
+        state.execute(ExpandSelection);
+        assert_eq!(state.selection, None);
+        assert_eq!(state.mode, EditorMode::Normal);
+    }
+
+    #[test]
+    fn test_select_inner_between_expands() {
+        let mut state = EditorState::new(Lines::from("f(a, (bc), d)"));
+        state.cursor = Index2::new(0, 6);
+
+        state.execute(SelectInnerBetween::new('(', ')'));
+        let want = Selection::new(Index2::new(0, 6), Index2::new(0, 7));
+        assert_eq!(state.selection, Some(want));
+        assert_eq!(state.mode, EditorMode::Visual);
+
+        state.execute(SelectInnerBetween::new('(', ')'));
+        let want = Selection::new(Index2::new(0, 2), Index2::new(0, 11));
+        assert_eq!(state.selection, Some(want));
+        assert_eq!(state.cursor, Index2::new(0, 11));
+    }
+
+    #[test]
+    fn test_expand_and_shrink_selection() {
+        let mut state = EditorState::new(Lines::from("f(ab, [cd])"));
+        state.cursor = Index2::new(0, 8);
+
+        state.execute(ExpandSelection);
+        let word = Selection::new(Index2::new(0, 7), Index2::new(0, 8));
+        assert_eq!(state.selection, Some(word.clone()));
+
+        state.execute(ExpandSelection);
+        let brackets = Selection::new(Index2::new(0, 6), Index2::new(0, 9));
+        assert_eq!(state.selection, Some(brackets));
+
+        state.execute(ExpandSelection);
+        let want = Selection::new(Index2::new(0, 2), Index2::new(0, 9));
+        assert_eq!(state.selection, Some(want));
+
+        state.execute(ShrinkSelection);
+        state.execute(ShrinkSelection);
+        assert_eq!(state.selection, Some(word));
+        assert_eq!(state.cursor, Index2::new(0, 8));
+    }
+
     #[test]
     fn test_select_inner_word() {
         let lines = Lines::from("Hello World");
@@ -466,4 +1323,288 @@ mod tests {
         let want = Selection::new(Index2::new(0, 0), Index2::new(0, 4));
         assert_eq!(state.selection.unwrap(), want);
     }
+
+    #[test]
+    fn test_select_inner_word_with_word_chars() {
+        let lines = Lines::from("a: font-size");
+        let mut state = EditorState::new(lines);
+        state.set_word_chars("-");
+        state.cursor = Index2::new(0, 4);
+
+        SelectInnerWord.execute(&mut state);
+
+        let want = Selection::new(Index2::new(0, 3), Index2::new(0, 11));
+        assert_eq!(state.selection.unwrap(), want);
+    }
+
+    #[test]
+    fn test_select_inner_subword() {
+        let lines = Lines::from("parseHTTPRequest_v2");
+        let mut state = EditorState::new(lines);
+        state.cursor = Index2::new(0, 6);
+
+        SelectInnerSubword.execute(&mut state);
+
+        let want = Selection::new(Index2::new(0, 5), Index2::new(0, 8));
+        assert_eq!(state.selection.unwrap(), want);
+    }
+
+    #[test]
+    fn test_swap_selection_ends() {
+        let mut state = test_state();
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(0, 2), Index2::new(0, 4)));
+        state.cursor = Index2::new(0, 4);
+
+        SwapSelectionEnds.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 2));
+
+        MoveBackward(1).execute(&mut state);
+        let selection = state.selection.clone().unwrap();
+        assert_eq!(selection.start(), Index2::new(0, 1));
+        assert_eq!(selection.end(), Index2::new(0, 4));
+    }
+
+    #[test]
+    fn test_swap_selection_ends_in_line_mode() {
+        let mut state = test_state();
+        SelectLine.execute(&mut state);
+        state.cursor = Index2::new(2, 0);
+        MoveUp(0).execute(&mut state);
+
+        SwapSelectionEnds.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        let selection = state.selection.clone().unwrap();
+        assert_eq!(selection.start(), Index2::new(0, 0));
+        assert_eq!(selection.end(), Index2::new(2, 3));
+    }
+
+    #[test]
+    fn test_reselect_last_visual() {
+        let mut state = test_state();
+        state.execute(SelectLine);
+        state.execute(SwitchMode(EditorMode::Normal));
+        assert_eq!(state.selection, None);
+
+        state.execute(ReselectLastVisual);
+
+        assert_eq!(state.mode, EditorMode::Visual);
+        let want = Selection::new(Index2::new(0, 0), Index2::new(0, 11)).line_mode();
+        assert_eq!(state.selection, Some(want));
+    }
+
+    #[test]
+    fn test_reselect_last_visual_after_edit() {
+        let mut state = test_state();
+        state.cursor = Index2::new(2, 0);
+        state.execute(SelectLine);
+        state.execute(SwitchMode(EditorMode::Normal));
+        state.cursor = Index2::new(0, 0);
+        state.execute(DeleteLine(1));
+
+        state.execute(ReselectLastVisual);
+
+        let selection = state.selection.unwrap();
+        assert_eq!(selection.start, Index2::new(1, 0));
+        assert_eq!(selection.end, Index2::new(1, 3));
+        assert_eq!(state.cursor, Index2::new(1, 0));
+    }
+
+    #[test]
+    fn test_change_case_of_selection() {
+        let mut state = test_state();
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(0, 6), Index2::new(2, 1)));
+
+        state.execute(UppercaseSelection);
+
+        assert_eq!(state.lines, Lines::from("Hello WORLD!\n\n123."));
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(state.cursor, Index2::new(0, 6));
+
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(0, 0)).line_mode());
+        state.execute(LowercaseSelection);
+        assert_eq!(state.lines, Lines::from("hello world!\n\n123."));
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from("Hello WORLD!\n\n123."));
+    }
+
+    #[test]
+    fn test_align_selection() {
+        let mut state = EditorState::new(Lines::from("| a | bb |\n| ccc | d |\nx\nlong = 1"));
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(2, 0)));
+
+        state.execute(AlignSelection('|'));
+
+        assert_eq!(
+            state.lines,
+            Lines::from("| a   | bb |\n| ccc | d  |\nx\nlong = 1")
+        );
+        assert_eq!(state.mode, EditorMode::Normal);
+
+        state.undo();
+        assert_eq!(
+            state.lines.get(RowIndex::new(0)),
+            Some(&"| a | bb |".chars().collect())
+        );
+    }
+
+    #[test]
+    fn test_indent_selection() {
+        let mut state = EditorState::new(Lines::from("foo\n\n\tbar\nbaz"));
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(0, 1), Index2::new(2, 2)));
+        state.cursor = Index2::new(2, 2);
+
+        state.execute(IndentSelection(1));
+        state.execute(IndentSelection(1));
+        assert_eq!(state.lines, Lines::from("    foo\n\n    \tbar\nbaz"));
+        assert_eq!(state.mode, EditorMode::Visual);
+        assert_eq!(state.selection.as_ref().unwrap().start, Index2::new(0, 5));
+        assert_eq!(state.cursor, Index2::new(2, 6));
+
+        state.execute(DedentSelection(3));
+        assert_eq!(state.lines, Lines::from("foo\n\nbar\nbaz"));
+        assert_eq!(state.cursor, Index2::new(2, 1));
+
+        state.set_keep_visual_selection(false);
+        state.execute(IndentSelection(1));
+        assert_eq!(state.lines, Lines::from("  foo\n\n  bar\nbaz"));
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(state.selection, None);
+    }
+
+    #[test]
+    fn test_indent_lines() {
+        let mut state = EditorState::new(Lines::from("foo\n\tbar\nbaz"));
+        state.cursor = Index2::new(0, 1);
+
+        state.execute(IndentLines(2));
+        assert_eq!(state.lines, Lines::from("  foo\n  \tbar\nbaz"));
+        assert_eq!(state.cursor, Index2::new(0, 3));
+
+        state.cursor = Index2::new(1, 0);
+        state.execute(DedentLines(5));
+        assert_eq!(state.lines, Lines::from("  foo\n\tbar\nbaz"));
+    }
+
+    #[test]
+    fn test_select_entire_buffer() {
+        let mut state = test_state();
+        state.select_entire_buffer();
+        assert_eq!(state.mode, EditorMode::Visual);
+
+        state.execute(DeleteSelection);
+        assert_eq!(state.lines.to_string(), "");
+        assert_eq!(state.clip.get_text(), "\nHello World!\n\n123.");
+    }
+
+    #[test]
+    fn test_select_indent() {
+        let text = "def f():\n    if x:\n        a\n\n        b\n    c\n\nd";
+        let rows = |state: &EditorState| {
+            let selection = state.selection.as_ref().unwrap();
+            (selection.start().row, selection.end().row)
+        };
+
+        let mut state = EditorState::new(Lines::from(text));
+        state.cursor = Index2::new(2, 0);
+        state.execute(SelectInnerIndent);
+        assert_eq!(rows(&state), (2, 4));
+        assert_eq!(state.mode, EditorMode::Visual);
+
+        state.execute(SelectAroundIndent);
+        assert_eq!(rows(&state), (1, 4));
+
+        state.cursor = Index2::new(5, 0);
+        state.execute(SelectAroundIndent);
+        assert_eq!(rows(&state), (0, 5));
+    }
+
+    #[test]
+    fn test_replace_selection() {
+        let mut state = test_state();
+        state.mode = EditorMode::Visual;
+        state.selection = Some(Selection::new(Index2::new(2, 2), Index2::new(0, 10)));
+
+        state.execute(ReplaceSelection('x'));
+
+        assert_eq!(state.lines, Lines::from("Hello Worlxx\n\nxxx."));
+        assert_eq!(state.cursor, Index2::new(0, 10));
+    }
+
+    #[test]
+    fn test_select_line_from_charwise_selection() {
+        let mut state = test_state();
+        state.execute(SwitchMode(EditorMode::Visual));
+        state.execute(MoveForward(2));
+        state.execute(MoveDown(2));
+
+        state.execute(SelectLine);
+        let selection = state.selection.clone().unwrap();
+        assert!(selection.line_mode);
+        assert_eq!(selection.start, Index2::new(0, 0));
+        assert_eq!(selection.end, Index2::new(2, 3));
+
+        state.execute(MoveUp(1));
+        let selection = state.selection.clone().unwrap();
+        assert_eq!(selection.end, Index2::new(1, 0));
+
+        state.execute(SelectLine);
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(state.selection, None);
+    }
+
+    #[test]
+    fn test_change_selection_in_line_mode() {
+        let mut state = test_state();
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(1, 0)).line_mode());
+
+        state.execute(ChangeSelection);
+
+        assert_eq!(state.lines, Lines::from("\n123."));
+        assert_eq!(state.cursor, Index2::new(0, 0));
+    }
+
+    #[test]
+    fn test_select_block() {
+        let mut state = EditorState::new(Lines::from("abcd\nef\nghij"));
+        let mut handler = crate::EditorEventHandler::default();
+
+        handler.feed_keys("l<C-v>jjl", &mut state).unwrap();
+        let selection = state.selection.clone().unwrap();
+        assert!(selection.block_mode);
+        assert_eq!(selection.start(), Index2::new(0, 1));
+        assert_eq!(selection.end(), Index2::new(2, 2));
+
+        handler.feed_keys("d", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ad\ne\ngj"));
+        assert_eq!(state.cursor, Index2::new(0, 1));
+        assert_eq!(state.clip.get_text(), "bc\nf\nhi");
+    }
+
+    #[test]
+    fn test_insert_block() {
+        let mut state = EditorState::new(Lines::from("abc\nd\nefg"));
+        let mut handler = crate::EditorEventHandler::default();
+
+        handler.feed_keys("l<C-v>jjI--<Esc>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a--bc\nd--\ne--fg"));
+        assert_eq!(state.mode, EditorMode::Normal);
+
+        handler.feed_keys("u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("abc\nd\nefg"));
+
+        handler.feed_keys("gg0<C-v>jjlA|<Esc>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ab|c\nd |\nef|g"));
+
+        handler
+            .feed_keys("ugg0<C-v>jjlc*<Esc>", &mut state)
+            .unwrap();
+        assert_eq!(state.lines, Lines::from("*c\n*\n*g"));
+    }
 }
@@ -60,7 +60,7 @@ pub fn open<B: Backend>(state: &mut EditorState, terminal: &mut Terminal<B>) ->
 
     let edited = result.map_err(std::io::Error::other)?;
 
-    state.lines = Lines::from(edited.trim_end_matches('\n'));
+    state.replace_lines(Lines::from(edited.trim_end_matches('\n')));
     state.cursor = Index2::new(0, 0);
     state.selection = None;
 
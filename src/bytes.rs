@@ -0,0 +1,127 @@
+//! Loading text that is not valid UTF-8.
+//!
+//! Log files or files of unknown origin are not always valid UTF-8. With
+//! [`LossyBytes::from_bytes_lossy`], such content can still be edited: every
+//! byte that is not part of a valid UTF-8 sequence is kept as a placeholder
+//! character, which is displayed as its hex value, e.g. `<FF>`. When the
+//! text is saved with [`LossyBytes::to_bytes`], the placeholders are turned
+//! back into the original bytes.
+use crate::Lines;
+
+/// The first of the characters that represent invalid bytes. The invalid
+/// bytes `0x80..=0xFF` are mapped to private use characters at the end of
+/// the unicode range.
+const INVALID_BYTE_BASE: u32 = 0x10_FF00;
+
+/// Binary-safe conversion between bytes and [`Lines`].
+///
+/// # Example
+///
+/// ```
+/// use edtui::{Lines, LossyBytes};
+///
+/// let bytes = b"caf\xe9\nok\n";
+/// let lines = Lines::from_bytes_lossy(bytes);
+///
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines.to_bytes(), bytes);
+/// ```
+pub trait LossyBytes {
+    /// Creates lines from bytes that may not be valid UTF-8. Invalid bytes
+    /// are preserved, see the [module documentation](crate::bytes).
+    fn from_bytes_lossy(bytes: &[u8]) -> Self;
+
+    /// Converts the lines into bytes, restoring invalid bytes that were
+    /// loaded with [`LossyBytes::from_bytes_lossy`]. Rows are joined with
+    /// `\n`.
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl LossyBytes for Lines {
+    fn from_bytes_lossy(bytes: &[u8]) -> Self {
+        let mut lines = Lines::default();
+        let mut row = Vec::new();
+        for ch in decode_lossy(bytes) {
+            if ch == '\n' {
+                lines.push(std::mem::take(&mut row));
+            } else {
+                row.push(ch);
+            }
+        }
+        lines.push(row);
+        lines
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buf = [0; 4];
+        for (i, row) in self.iter_row().enumerate() {
+            if i > 0 {
+                bytes.push(b'\n');
+            }
+            for &ch in row {
+                match invalid_byte(ch) {
+                    Some(byte) => bytes.push(byte),
+                    None => bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes()),
+                }
+            }
+        }
+        bytes
+    }
+}
+
+/// Decodes UTF-8, mapping every invalid byte to a placeholder character.
+fn decode_lossy(mut bytes: &[u8]) -> Vec<char> {
+    let mut chars = Vec::with_capacity(bytes.len());
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                chars.extend(valid.chars());
+                return chars;
+            }
+            Err(err) => {
+                let (valid, rest) = bytes.split_at(err.valid_up_to());
+                chars.extend(std::str::from_utf8(valid).unwrap_or_default().chars());
+                let invalid_len = err.error_len().unwrap_or(rest.len());
+                chars.extend(
+                    rest[..invalid_len]
+                        .iter()
+                        .filter_map(|&byte| char::from_u32(INVALID_BYTE_BASE + u32::from(byte))),
+                );
+                bytes = &rest[invalid_len..];
+            }
+        }
+    }
+}
+
+/// Returns the original byte if `ch` is the placeholder of an invalid byte.
+pub(crate) fn invalid_byte(ch: char) -> Option<u8> {
+    let offset = u32::from(ch).checked_sub(INVALID_BYTE_BASE)?;
+    u8::try_from(offset).ok().filter(|byte| *byte >= 0x80)
+}
+
+#[cfg(test)]
+mod tests {
+    use jagged::index::RowIndex;
+
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_lossy_roundtrip() {
+        let bytes = b"a\xff\xc3b\r\n\xe2\x82\n";
+
+        let lines = Lines::from_bytes_lossy(bytes);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines.get(RowIndex::new(0)).map(Vec::len), Some(5));
+        assert_eq!(lines.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_from_bytes_lossy_valid_utf8() {
+        let lines = Lines::from_bytes_lossy("héllo\nwörld".as_bytes());
+
+        assert_eq!(lines, Lines::from("héllo\nwörld"));
+        assert_eq!(invalid_byte('é'), None);
+    }
+}
@@ -92,6 +92,63 @@ impl Clipboard {
     pub fn new(clipboard: impl ClipboardTrait + 'static) -> Self {
         Clipboard(Rc::new(RefCell::new(clipboard)))
     }
+
+    /// Creates a new `Clipboard` from a pair of closures, e.g. to forward
+    /// yank and paste text to a clipboard abstraction of the host without
+    /// defining a type for it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// use edtui::actions::{CopyLine, Paste};
+    /// use edtui::clipboard::Clipboard;
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let register = Rc::new(RefCell::new(String::from("pasted")));
+    /// let (get, set) = (Rc::clone(&register), Rc::clone(&register));
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// state.set_clipboard(Clipboard::from_fn(
+    ///     move || get.borrow().clone(),
+    ///     move |text| *set.borrow_mut() = text,
+    /// ));
+    ///
+    /// state.execute(Paste);
+    /// assert_eq!(state.lines, Lines::from("Hpastedello"));
+    ///
+    /// state.execute(CopyLine);
+    /// assert_eq!(*register.borrow(), "\nHpastedello");
+    /// ```
+    #[must_use]
+    pub fn from_fn<G, S>(get: G, set: S) -> Self
+    where
+        G: FnMut() -> String + 'static,
+        S: FnMut(String) + 'static,
+    {
+        Self::new(FnClipboard { get, set })
+    }
+}
+
+/// A clipboard that forwards to a pair of closures.
+struct FnClipboard<G, S> {
+    get: G,
+    set: S,
+}
+
+impl<G, S> ClipboardTrait for FnClipboard<G, S>
+where
+    G: FnMut() -> String,
+    S: FnMut(String),
+{
+    fn set_text(&mut self, text: String) {
+        (self.set)(text);
+    }
+
+    fn get_text(&mut self) -> String {
+        (self.get)()
+    }
 }
 
 impl ClipboardTrait for Clipboard {
@@ -125,3 +182,34 @@ impl Default for Clipboard {
         Self::new(InternalClipboard::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::actions::{CopySelection, Paste, SwitchMode};
+    use crate::state::selection::Selection;
+    use crate::{EditorMode, EditorState, Index2, Lines};
+
+    #[test]
+    fn test_clipboard_from_fn() {
+        let register = Rc::new(RefCell::new(String::from("!")));
+        let (get, set) = (Rc::clone(&register), Rc::clone(&register));
+        let mut state = EditorState::new(Lines::from("Hello"));
+        state.set_clipboard(Clipboard::from_fn(
+            move || get.borrow().clone(),
+            move |text| *set.borrow_mut() = text,
+        ));
+
+        state.execute(SwitchMode(EditorMode::Visual));
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(0, 1)));
+        state.execute(CopySelection);
+        assert_eq!(*register.borrow(), "He");
+
+        *register.borrow_mut() = String::from("!");
+        state.execute(Paste);
+        assert_eq!(state.lines, Lines::from("H!ello"));
+    }
+}
@@ -0,0 +1,117 @@
+//! A preset for editing git commit messages.
+//!
+//! Configures the editor the way most editors treat a `COMMIT_EDITMSG`:
+//! lines starting with the comment character are dimmed, color columns mark
+//! the recommended length of the subject (50) and of the body (72), and the
+//! body is wrapped automatically when typing beyond 72 characters. The
+//! comment lines are excluded from the final message.
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::commit::CommitMessage;
+//! use edtui::{EditorState, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("\n# Please enter the commit message."));
+//! let preset = CommitMessage::default();
+//! preset.apply(&mut state);
+//!
+//! state.lines = Lines::from("Fix typo\n\n# Please enter the commit message.");
+//! assert_eq!(preset.message(&state.lines), "Fix typo\n");
+//! ```
+use crate::{EditorState, Lines};
+
+/// The configuration for editing a git commit message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitMessage {
+    /// Lines starting with this character are comments.
+    pub comment_char: char,
+    /// The recommended maximum length of the subject line.
+    pub subject_width: usize,
+    /// The width at which the body is wrapped.
+    pub body_width: usize,
+}
+
+impl Default for CommitMessage {
+    fn default() -> Self {
+        Self {
+            comment_char: '#',
+            subject_width: 50,
+            body_width: 72,
+        }
+    }
+}
+
+impl CommitMessage {
+    /// Sets the comment character, `#` by default. Corresponds to
+    /// `core.commentChar` in git.
+    #[must_use]
+    pub fn comment_char(mut self, comment_char: char) -> Self {
+        self.comment_char = comment_char;
+        self
+    }
+
+    /// Sets the recommended maximum length of the subject line, 50 by
+    /// default.
+    #[must_use]
+    pub fn subject_width(mut self, subject_width: usize) -> Self {
+        self.subject_width = subject_width;
+        self
+    }
+
+    /// Sets the width at which the body is wrapped, 72 by default.
+    #[must_use]
+    pub fn body_width(mut self, body_width: usize) -> Self {
+        self.body_width = body_width;
+        self
+    }
+
+    /// Configures the editor for editing a commit message: sets the text
+    /// width, the color columns and the comment character.
+    pub fn apply(&self, state: &mut EditorState) {
+        state.set_text_width(Some(self.body_width));
+        state.view.color_columns = vec![self.subject_width, self.body_width];
+        state.view.comment_char = Some(self.comment_char);
+    }
+
+    /// Returns the commit message without comment lines, similar to
+    /// `git commit --cleanup=strip`: trailing whitespace is removed,
+    /// consecutive empty lines are collapsed and leading and trailing empty
+    /// lines are removed.
+    #[must_use]
+    pub fn message(&self, lines: &Lines) -> String {
+        let mut message = String::new();
+        let mut empty_lines = 0;
+        for line in lines.iter_row() {
+            if line.first() == Some(&self.comment_char) {
+                continue;
+            }
+            let line: String = line.iter().collect();
+            let line = line.trim_end();
+            if line.is_empty() {
+                empty_lines += 1;
+                continue;
+            }
+            if !message.is_empty() && empty_lines > 0 {
+                message.push('\n');
+            }
+            empty_lines = 0;
+            message.push_str(line);
+            message.push('\n');
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let preset = CommitMessage::default().comment_char(';');
+        let lines = Lines::from("\nSubject  \n\n\n; comment\nBody\n# heading\n\n; comment\n");
+
+        assert_eq!(preset.message(&lines), "Subject\n\nBody\n# heading\n");
+    }
+}
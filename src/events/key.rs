@@ -1,21 +1,37 @@
+use super::KeyLog;
 use crate::actions::cpaste::PasteOverSelection;
 use crate::actions::delete::{DeleteCharForward, DeleteToEndOfLine, DeleteToFirstCharOfLine};
-use crate::actions::motion::{MoveHalfPageDown, MoveToFirstRow, MoveToLastRow};
+use crate::actions::insert::tab_chars;
+use crate::actions::motion::{
+    MoveHalfPageDown, MovePageDown, MovePageUp, MoveSubwordBackward, MoveSubwordForward,
+    MoveSubwordForwardToEnd, MoveToFirstRow, MoveToLastRow, MoveToPercent,
+};
 use crate::actions::search::StartSearch;
 #[cfg(feature = "system-editor")]
 use crate::actions::OpenSystemEditor;
 use crate::actions::{
-    Action, AppendCharToSearch, AppendNewline, ChangeInnerBetween, ChangeInnerWord,
-    ChangeSelection, Composed, CopyLine, CopySelection, DeleteChar, DeleteLine, DeleteSelection,
-    Execute, FindFirst, FindNext, FindPrevious, InsertChar, InsertNewline, JoinLineWithLineBelow,
-    LineBreak, MoveBackward, MoveDown, MoveForward, MoveHalfPageUp, MoveToEndOfLine, MoveToFirst,
-    MoveToMatchinBracket, MoveToStartOfLine, MoveUp, MoveWordBackward, MoveWordForward,
-    MoveWordForwardToEndOfWord, Paste, Redo, RemoveChar, RemoveCharFromSearch, SelectCurrentSearch,
-    SelectInnerBetween, SelectInnerWord, SelectLine, StopSearch, SwitchMode, Undo,
+    Action, AlignSelection, AppendBlock, AppendCharToCommand, AppendCharToSearch, AppendNewline,
+    ChangeInnerBetween, ChangeInnerWord, ChangeSelection, Composed, Condition, CopyLine,
+    CopySelection, DedentLines, DedentSelection, DeleteChar, DeleteLine, DeleteSelection,
+    ExpandSelection, FindFirst, FindNext, FindPrevious, IndentLines, IndentSelection, InsertBlock,
+    InsertChar, InsertLastInserted, InsertNewline, JoinLineWithLineBelow, LineBreak,
+    LowercaseSelection, MoveBackward, MoveBigWordBackwardToEndOfWord, MoveDown, MoveForward,
+    MoveHalfPageUp, MoveToEndOfLine, MoveToFirst, MoveToMatchinBracket, MoveToStartOfLine, MoveUp,
+    MoveWordBackward, MoveWordBackwardToEndOfWord, MoveWordForward, MoveWordForwardToEndOfWord,
+    NextBookmark, NextError, NextField, NextLocation, OpenFileUnderCursor, Operator,
+    OperatorMotion, Paste, PasteCycleNext, PlayMacro, PrevBookmark, PrevLocation, PreviousField,
+    Redo, RedoN, RemoveChar, RemoveCharFromCommand, RemoveCharFromSearch, RepeatLastChange,
+    ReplaceSelection, RequestExit, ReselectLastVisual, RunCommand, SelectAroundIndent, SelectBlock,
+    SelectCurrentSearch, SelectEntireBuffer, SelectInnerBetween, SelectInnerIndent,
+    SelectInnerWord, SelectLine, SelectRegister, SelectTextObject, ShowCursorInfo, ShrinkSelection,
+    SmartMoveToStart, StartRecordingMacro, StartSearchOffset, StopRecordingMacro, StopSearch,
+    SwapSelectionEnds, SwitchMode, ToggleBookmark, Undo, UndoN, UppercaseSelection,
 };
-use crate::{EditorMode, EditorState};
-use crossterm::event::{KeyCode, KeyEvent as CTKeyEvent, KeyModifiers};
+use crate::{EditorMode, EditorState, ExitRequest};
+use crossterm::event::{KeyCode, KeyEvent as CTKeyEvent, KeyEventKind, KeyModifiers};
 use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -25,20 +41,39 @@ pub enum KeyEvent {
     Up,
     Right,
     Left,
+    ShiftDown,
+    ShiftUp,
+    ShiftRight,
+    ShiftLeft,
     Enter,
     Esc,
     Backspace,
     Delete,
     Tab,
+    BackTab,
     Ctrl(char),
     Alt(char),
+    /// A lower case letter with `Shift`, as reported by some terminals
+    /// instead of the upper case letter. Handled as the letter as it was
+    /// reported, or as the upper case letter if keys are normalized, see
+    /// [`KeyEventHandler::set_normalize_keys`].
+    Shift(char),
     Home,
     End,
+    PageUp,
+    PageDown,
     None,
 }
 
 impl From<CTKeyEvent> for KeyEvent {
+    /// Converts a crossterm key event. Key releases, which terminals only
+    /// report with the kitty keyboard protocol, are converted to
+    /// [`KeyEvent::None`].
     fn from(key: CTKeyEvent) -> Self {
+        if key.kind == KeyEventKind::Release {
+            return KeyEvent::None;
+        }
+
         if key.modifiers.contains(KeyModifiers::CONTROL) {
             return match key.code {
                 KeyCode::Char(c) => KeyEvent::Ctrl(c),
@@ -55,8 +90,17 @@ impl From<CTKeyEvent> for KeyEvent {
         }
 
         match key.code {
+            KeyCode::Char(c)
+                if key.modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_lowercase() =>
+            {
+                KeyEvent::Shift(c)
+            }
             KeyCode::Char(c) => KeyEvent::Char(c),
             KeyCode::Enter => KeyEvent::Enter,
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::ShiftDown,
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::ShiftUp,
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::ShiftRight,
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::ShiftLeft,
             KeyCode::Down => KeyEvent::Down,
             KeyCode::Up => KeyEvent::Up,
             KeyCode::Right => KeyEvent::Right,
@@ -64,19 +108,162 @@ impl From<CTKeyEvent> for KeyEvent {
             KeyCode::Esc => KeyEvent::Esc,
             KeyCode::Backspace => KeyEvent::Backspace,
             KeyCode::Delete => KeyEvent::Delete,
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::BackTab,
             KeyCode::Tab => KeyEvent::Tab,
+            KeyCode::BackTab => KeyEvent::BackTab,
             KeyCode::Home => KeyEvent::Home,
             KeyCode::End => KeyEvent::End,
+            KeyCode::PageUp => KeyEvent::PageUp,
+            KeyCode::PageDown => KeyEvent::PageDown,
             _ => KeyEvent::None,
         }
     }
 }
 
+/// Returns the `Alt` variant of a key, as sent by terminals that prefix the
+/// key with `Esc`.
+fn with_meta(key: KeyEvent) -> Option<KeyEvent> {
+    match key {
+        KeyEvent::Char(c) => Some(KeyEvent::Alt(c)),
+        KeyEvent::Backspace => Some(KeyEvent::Alt('\x08')),
+        _ => None,
+    }
+}
+
+impl KeyEvent {
+    /// Returns the key in a form that is reported the same way by all
+    /// terminals, see [`KeyEventHandler::set_normalize_keys`].
+    ///
+    /// `Ctrl` with a letter ignores the case of the letter, as most terminals
+    /// can't distinguish `Ctrl+a` from `Ctrl+Shift+a`. `Ctrl` with any other
+    /// character than a digit or space is a character typed with `AltGr`,
+    /// which Windows reports as `Ctrl+Alt`. `Shift` with a lower case letter
+    /// is the upper case letter, as some terminals report `Shift+a` as `a`
+    /// with the shift modifier instead of `A`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::events::KeyEvent;
+    ///
+    /// assert_eq!(KeyEvent::Ctrl('R').normalized(), KeyEvent::Ctrl('r'));
+    /// assert_eq!(KeyEvent::Ctrl('@').normalized(), KeyEvent::Char('@'));
+    /// assert_eq!(KeyEvent::Shift('a').normalized(), KeyEvent::Char('A'));
+    /// ```
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        match self {
+            Self::Shift(c) => Self::Char(c.to_ascii_uppercase()),
+            Self::Ctrl(c) if c.is_ascii_alphabetic() => Self::Ctrl(c.to_ascii_lowercase()),
+            Self::Ctrl(c) if !c.is_ascii_digit() && c != ' ' => Self::Char(c),
+            key => key,
+        }
+    }
+}
+
+impl fmt::Display for KeyEvent {
+    /// Formats the key in vim notation, e.g. `a`, `<Esc>` or `<C-r>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(' ') => write!(f, "<Space>"),
+            Self::Char(c) => write!(f, "{c}"),
+            Self::Ctrl(c) => write!(f, "<C-{c}>"),
+            Self::Alt('\x08') => write!(f, "<A-BS>"),
+            Self::Alt(c) => write!(f, "<A-{c}>"),
+            Self::Shift(c) => write!(f, "<S-{c}>"),
+            Self::Down => write!(f, "<Down>"),
+            Self::Up => write!(f, "<Up>"),
+            Self::Right => write!(f, "<Right>"),
+            Self::Left => write!(f, "<Left>"),
+            Self::ShiftDown => write!(f, "<S-Down>"),
+            Self::ShiftUp => write!(f, "<S-Up>"),
+            Self::ShiftRight => write!(f, "<S-Right>"),
+            Self::ShiftLeft => write!(f, "<S-Left>"),
+            Self::Enter => write!(f, "<CR>"),
+            Self::Esc => write!(f, "<Esc>"),
+            Self::Backspace => write!(f, "<BS>"),
+            Self::Delete => write!(f, "<Del>"),
+            Self::Tab => write!(f, "<Tab>"),
+            Self::BackTab => write!(f, "<S-Tab>"),
+            Self::Home => write!(f, "<Home>"),
+            Self::End => write!(f, "<End>"),
+            Self::PageUp => write!(f, "<PageUp>"),
+            Self::PageDown => write!(f, "<PageDown>"),
+            Self::None => Ok(()),
+        }
+    }
+}
+
+/// A problem in a keymap, see [`KeyEventHandler::conflicts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyConflict {
+    /// A binding is an exact prefix of a longer binding in the same mode.
+    /// The handler waits for the longer sequence, so the shorter binding
+    /// never fires.
+    Shadowed {
+        prefix: KeyEventRegister,
+        longer: KeyEventRegister,
+    },
+    /// The same keys are bound to an action and to a character action.
+    Duplicate(KeyEventRegister),
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shadowed { prefix, longer } => write!(f, "{prefix} is shadowed by {longer}"),
+            Self::Duplicate(key) => write!(f, "{key} is bound twice"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct KeyEventHandler {
     lookup: Vec<KeyEvent>,
+    count: Option<usize>,
+    /// Whether a single normal mode command is executed from insert mode
+    /// (`Ctrl+o`), after which the handler returns to insert mode.
+    one_shot_normal: bool,
+    /// An action that waits for the next character, such as `r{char}`.
+    pending: Option<CharAction>,
     register: HashMap<KeyEventRegister, Action>,
-    capture_on_insert: bool,
+    char_register: HashMap<KeyEventRegister, CharAction>,
+    undo_grouping: UndoGrouping,
+    /// When the last character was typed in insert mode.
+    last_insert: Option<Instant>,
+    /// How long to wait for the next key of a sequence, if at all.
+    timeout: Option<Duration>,
+    /// When the last key of a pending sequence was pressed.
+    last_key: Option<Instant>,
+    /// Whether keys and bindings are compared in their normalized form.
+    normalize_keys: bool,
+    /// Whether an unbound `Esc` was typed, which some terminals send before
+    /// a key instead of the `Alt` modifier.
+    meta_prefix: bool,
+    /// The received keys, if they are recorded.
+    key_log: Option<KeyLog>,
+}
+
+/// Creates an action from a character that is typed after the key sequence,
+/// such as `x` in `rx`.
+pub type CharAction = fn(char) -> Action;
+
+/// When text typed in insert mode starts a new undo step, see
+/// [`KeyEventHandler::set_undo_grouping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoGrouping {
+    /// Every typed character is undone on its own (default of the emacs
+    /// keybindings).
+    Keystroke,
+    /// Every typed word is undone on its own, together with the whitespace
+    /// that follows it.
+    Word,
+    /// Everything typed until insert mode is left is undone at once (default
+    /// of the vim keybindings).
+    InsertSession,
+    /// Characters typed without a pause longer than the duration are undone
+    /// at once.
+    Time(Duration),
 }
 
 impl Default for KeyEventHandler {
@@ -91,8 +278,22 @@ impl KeyEventHandler {
     pub fn new(register: HashMap<KeyEventRegister, Action>, capture_on_insert: bool) -> Self {
         Self {
             lookup: Vec::new(),
+            count: None,
+            one_shot_normal: false,
+            pending: None,
             register,
-            capture_on_insert,
+            char_register: HashMap::new(),
+            undo_grouping: if capture_on_insert {
+                UndoGrouping::Keystroke
+            } else {
+                UndoGrouping::InsertSession
+            },
+            last_insert: None,
+            timeout: None,
+            last_key: None,
+            normalize_keys: false,
+            meta_prefix: false,
+            key_log: None,
         }
     }
 
@@ -102,8 +303,18 @@ impl KeyEventHandler {
         let register: HashMap<KeyEventRegister, Action> = vim_keybindings();
         Self {
             lookup: Vec::new(),
+            count: None,
+            one_shot_normal: false,
+            pending: None,
             register,
-            capture_on_insert: false,
+            char_register: vim_char_keybindings(),
+            undo_grouping: UndoGrouping::InsertSession,
+            last_insert: None,
+            timeout: None,
+            last_key: None,
+            normalize_keys: false,
+            meta_prefix: false,
+            key_log: None,
         }
     }
 
@@ -113,61 +324,364 @@ impl KeyEventHandler {
         let register: HashMap<KeyEventRegister, Action> = emacs_keybindings();
         Self {
             lookup: Vec::new(),
+            count: None,
+            one_shot_normal: false,
+            pending: None,
             register,
-            capture_on_insert: true,
+            char_register: HashMap::new(),
+            undo_grouping: UndoGrouping::Keystroke,
+            last_insert: None,
+            timeout: None,
+            last_key: None,
+            normalize_keys: false,
+            meta_prefix: false,
+            key_log: None,
         }
     }
 
-    /// Insert a new callback to the registry
+    /// Sets how long the handler waits for the next key of a sequence, similar
+    /// to `timeoutlen` in vim. When the time passes, the keys typed so far run
+    /// their action if they are bound on their own, or are discarded otherwise.
+    /// Pass `None` to wait indefinitely (default).
+    ///
+    /// The timeout is checked in [`EditorEventHandler::on_tick`].
+    ///
+    /// [`EditorEventHandler::on_tick`]: crate::EditorEventHandler::on_tick
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Sets when text typed in insert mode starts a new undo step. The vim
+    /// keybindings undo everything typed in one insert session at once, the
+    /// emacs keybindings every character on its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::events::UndoGrouping;
+    /// use edtui::{EditorEventHandler, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from(""));
+    /// let mut event_handler = EditorEventHandler::default();
+    /// event_handler.key_handler.set_undo_grouping(UndoGrouping::Word);
+    ///
+    /// event_handler.feed_keys("ione two<Esc>u", &mut state).unwrap();
+    /// assert_eq!(state.lines, Lines::from("one "));
+    /// ```
+    pub fn set_undo_grouping(&mut self, grouping: UndoGrouping) {
+        self.undo_grouping = grouping;
+    }
+
+    /// Starts recording the received keys into a new [`KeyLog`], e.g. to
+    /// attach the exact input to a bug report. See
+    /// [`EditorEventHandler::replay`] to replay it.
+    ///
+    /// [`EditorEventHandler::replay`]: crate::EditorEventHandler::replay
+    pub fn start_key_log(&mut self) {
+        self.key_log = Some(KeyLog::recording());
+    }
+
+    /// Stops recording the received keys and returns the log, if keys were
+    /// recorded.
+    pub fn stop_key_log(&mut self) -> Option<KeyLog> {
+        self.key_log.take()
+    }
+
+    /// Returns the keys recorded so far, if keys are recorded.
+    #[must_use]
+    pub fn key_log(&self) -> Option<&KeyLog> {
+        self.key_log.as_ref()
+    }
+
+    /// Sets whether keys are normalized before they are looked up, so that
+    /// bindings work the same across terminals and keyboard layouts, see
+    /// [`KeyEvent::normalized`]. Existing and future bindings are normalized
+    /// too, e.g. a binding of `Ctrl('R')` fires on `Ctrl('r')`. Disabled by
+    /// default.
+    pub fn set_normalize_keys(&mut self, normalize: bool) {
+        self.normalize_keys = normalize;
+        if normalize {
+            self.register = std::mem::take(&mut self.register)
+                .into_iter()
+                .map(|(key, action)| (self.binding(key), action))
+                .collect();
+            self.char_register = std::mem::take(&mut self.char_register)
+                .into_iter()
+                .map(|(key, action)| (self.binding(key), action))
+                .collect();
+        }
+    }
+
+    /// Returns the key sequence as it is stored in the registry.
+    fn binding(&self, key: KeyEventRegister) -> KeyEventRegister {
+        if self.normalize_keys {
+            let keys = key.keys.iter().map(|key| key.normalized()).collect();
+            KeyEventRegister::new::<Vec<_>>(keys, key.mode)
+        } else {
+            key
+        }
+    }
+
+    /// Insert a new callback to the registry. It replaces an action that
+    /// takes a character and is bound to the same keys.
     pub fn insert<T>(&mut self, key: KeyEventRegister, action: T)
     where
         T: Into<Action>,
     {
+        let key = self.binding(key);
+        self.char_register.remove(&key);
         self.register.insert(key, action.into());
     }
 
+    /// Binds the text object registered under `key` with
+    /// [`EditorState::set_text_object`]: `i` followed by `key` selects the
+    /// object in visual mode, and `di`, `ci` and `yi` followed by `key`
+    /// delete, change and yank it in normal mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::events::KeyEventHandler;
+    /// use edtui::EditorState;
+    ///
+    /// let mut state = EditorState::default();
+    /// state.set_text_object('u', |line, col| {
+    ///     let start = line[..col].iter().rposition(|ch| ch.is_whitespace()).map_or(0, |i| i + 1);
+    ///     let len = line[start..].iter().take_while(|ch| !ch.is_whitespace()).count();
+    ///     line[start..start + len].starts_with(&['h', 't', 't', 'p']).then_some(start..start + len)
+    /// });
+    ///
+    /// let mut key_handler = KeyEventHandler::vim_mode();
+    /// key_handler.bind_text_object('u');
+    /// ```
+    pub fn bind_text_object(&mut self, key: char) {
+        for (key, action) in text_object_bindings('i', key, SelectTextObject(key)) {
+            self.register.insert(self.binding(key), action);
+        }
+    }
+
     /// Extents the register with the contents of an iterator
     pub fn extend<T, U>(&mut self, iter: T)
     where
         U: Into<Action>,
         T: IntoIterator<Item = (KeyEventRegister, U)>,
     {
-        self.register
-            .extend(iter.into_iter().map(|(k, v)| (k, v.into())));
+        for (key, action) in iter {
+            self.register.insert(self.binding(key), action.into());
+        }
+    }
+
+    /// Insert an action that takes the character typed after the key
+    /// sequence as argument, such as `r{char}`. It replaces a callback
+    /// bound to the same keys.
+    pub fn insert_char_action(&mut self, key: KeyEventRegister, action: CharAction) {
+        let key = self.binding(key);
+        self.register.remove(&key);
+        self.char_register.insert(key, action);
     }
 
     /// Remove a callback from the registry
     pub fn remove(&mut self, key: &KeyEventRegister) {
-        self.register.remove(key);
+        let key = self.binding(key.clone());
+        self.register.remove(&key);
+        self.char_register.remove(&key);
+    }
+
+    /// Removes all bindings of an action type, including composed actions
+    /// with a step of that type, e.g. all deletions for a read-only viewer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{DeleteLine, DeleteSelection};
+    /// use edtui::events::{KeyEvent, KeyEventHandler, KeyEventRegister};
+    ///
+    /// let mut handler = KeyEventHandler::vim_mode();
+    /// handler.remove_action::<DeleteLine>();
+    /// handler.remove_action::<DeleteSelection>();
+    ///
+    /// let dd = KeyEventRegister::n(vec![KeyEvent::Char('d'), KeyEvent::Char('d')]);
+    /// assert!(handler.bindings(edtui::EditorMode::Normal).iter().all(|(key, _)| **key != dd));
+    /// ```
+    pub fn remove_action<T>(&mut self)
+    where
+        Action: TryInto<T>,
+    {
+        self.register.retain(|_, action| !action.contains::<T>());
+        self.char_register
+            .retain(|_, action| !action(' ').contains::<T>());
     }
 
-    /// Returns an action for a specific register key, if present.
-    /// Returns an action only if there is an exact match. If there
-    /// are multiple matches or an inexact match, the specified key
-    /// is appended to the lookup vector.
-    /// If there is an exact match or if none of the keys in the registry
-    /// starts with the current sequence, the lookup sequence is reset.
+    /// Retains only the bindings for which `f` returns `true`. Bindings that
+    /// take a character, such as `r{char}`, are not affected.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&KeyEventRegister, &Action) -> bool,
+    {
+        self.register.retain(|key, action| f(key, action));
+    }
+
+    /// Returns the bindings of a mode, ordered by their keys.
     #[must_use]
-    fn get(&mut self, c: KeyEvent, mode: EditorMode) -> Option<Action> {
-        self.lookup.push(c);
-        let key = KeyEventRegister::new(self.lookup.clone(), mode);
+    pub fn bindings(&self, mode: EditorMode) -> Vec<(&KeyEventRegister, &Action)> {
+        let mut bindings: Vec<_> = self
+            .register
+            .iter()
+            .filter(|(key, _)| key.mode == mode)
+            .collect();
+        bindings.sort_by_key(|(key, _)| key.to_string());
+        bindings
+    }
+
+    /// Returns the bindings of a mode that take the character typed after
+    /// the keys, such as `r{char}`, ordered by their keys.
+    #[must_use]
+    pub fn char_bindings(&self, mode: EditorMode) -> Vec<(&KeyEventRegister, CharAction)> {
+        let mut bindings: Vec<_> = self
+            .char_register
+            .iter()
+            .filter(|(key, _)| key.mode == mode)
+            .map(|(key, action)| (key, *action))
+            .collect();
+        bindings.sort_by_key(|(key, _)| key.to_string());
+        bindings
+    }
 
-        match self
+    /// Returns the problems of the keymap, e.g. to validate a user keymap at
+    /// startup.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{DeleteLine, MoveDown};
+    /// use edtui::events::{KeyConflict, KeyEvent, KeyEventHandler, KeyEventRegister};
+    ///
+    /// let mut handler = KeyEventHandler::vim_mode();
+    /// handler.insert(KeyEventRegister::n(vec![KeyEvent::Char('d')]), MoveDown(1));
+    ///
+    /// assert_eq!(
+    ///     handler.conflicts()[0].to_string(),
+    ///     "Normal d is shadowed by Normal d$"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn conflicts(&self) -> Vec<KeyConflict> {
+        let keys: Vec<&KeyEventRegister> = self
+            .register
+            .keys()
+            .chain(self.char_register.keys())
+            .collect();
+        let mut conflicts: Vec<KeyConflict> = self
             .register
             .keys()
-            .filter(|k| k.mode == key.mode && k.keys.starts_with(&key.keys))
-            .count()
+            .filter(|key| self.char_register.contains_key(key))
+            .map(|key| KeyConflict::Duplicate(key.clone()))
+            .collect();
+        for prefix in &keys {
+            for longer in &keys {
+                if prefix.mode == longer.mode
+                    && prefix.keys.len() < longer.keys.len()
+                    && longer.keys.starts_with(&prefix.keys)
+                {
+                    conflicts.push(KeyConflict::Shadowed {
+                        prefix: (*prefix).clone(),
+                        longer: (*longer).clone(),
+                    });
+                }
+            }
+        }
+        conflicts.sort_by_key(ToString::to_string);
+        conflicts
+    }
+
+    /// Accumulates a count prefix such as `3` in `3dd`. Returns whether the
+    /// key was consumed as part of a count.
+    ///
+    /// Digits only start a count if no sequence is pending and the digit is
+    /// not bound itself, so that `0` can still move to the start of a line.
+    fn push_count(&mut self, key: KeyEvent, mode: EditorMode) -> bool {
+        let KeyEvent::Char(ch) = key else {
+            return false;
+        };
+        let Some(digit) = ch.to_digit(10) else {
+            return false;
+        };
+        if !self.lookup.is_empty() {
+            return false;
+        }
+        if self.count.is_none()
+            && self
+                .register
+                .contains_key(&KeyEventRegister::new(vec![key], mode))
         {
-            0 => {
-                self.lookup.clear();
-                None
+            return false;
+        }
+
+        let count = self.count.unwrap_or_default();
+        let count = count.saturating_mul(10).saturating_add(digit as usize);
+        self.count = Some(count.min(MAX_COUNT));
+        true
+    }
+
+    /// Appends a key to the pending key sequence and returns the actions
+    /// of the bindings it completes.
+    ///
+    /// The sequence is kept while it is a strict prefix of a binding, so
+    /// that sequences of any length can be bound. A binding is dispatched
+    /// once it is complete and no other binding starts with it. If the
+    /// sequence stops matching any binding, the longest bound prefix is
+    /// dispatched and the remaining keys are looked up again, e.g. `<Space>j`
+    /// runs `j` if only `<Space>ff` is bound.
+    #[must_use]
+    fn get(&mut self, c: KeyEvent, mode: EditorMode) -> Vec<Action> {
+        self.lookup.push(c);
+        let mut actions = Vec::new();
+        while !self.lookup.is_empty() {
+            let key = KeyEventRegister::new(self.lookup.clone(), mode);
+            match self
+                .register
+                .keys()
+                .chain(self.char_register.keys())
+                .filter(|k| k.mode == key.mode && k.keys.starts_with(&key.keys))
+                .count()
+            {
+                0 => {
+                    if self.lookup.len() == 1 {
+                        self.lookup.clear();
+                        self.count = None;
+                    } else {
+                        actions.extend(self.take_bound_prefix(mode));
+                    }
+                }
+                1 if self.char_register.contains_key(&key) => {
+                    self.lookup.clear();
+                    self.pending = self.char_register.get(&key).copied();
+                    break;
+                }
+                1 if self.register.contains_key(&key) => {
+                    self.lookup.clear();
+                    actions.extend(self.register.get(&key).cloned());
+                }
+                _ => break,
             }
-            1 => self.register.get(&key).map(|action| {
-                self.lookup.clear();
-                action.clone()
-            }),
-            _ => None,
         }
+        actions
+    }
+
+    /// Removes the longest prefix of the pending sequence that is bound and
+    /// returns its action. Without a bound prefix, only the first key is
+    /// removed.
+    fn take_bound_prefix(&mut self, mode: EditorMode) -> Option<Action> {
+        let bound = (1..=self.lookup.len()).rev().find_map(|len| {
+            let key = KeyEventRegister::new(self.lookup[..len].to_vec(), mode);
+            self.register.get(&key).map(|action| (len, action.clone()))
+        });
+        let Some((len, action)) = bound else {
+            self.lookup.remove(0);
+            return None;
+        };
+        self.lookup.drain(..len);
+        Some(action)
     }
 }
 
@@ -230,6 +744,32 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::s(vec![KeyEvent::Backspace]),
             RemoveCharFromSearch.into(),
         ),
+        // Type an offset after the pattern, e.g. `/foo/e`
+        (
+            KeyEventRegister::s(vec![KeyEvent::Char('/')]),
+            StartSearchOffset.into(),
+        ),
+        // Go into command mode and run the command with enter
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char(':')]),
+            SwitchMode(EditorMode::Command).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char(':')]),
+            SwitchMode(EditorMode::Command).into(),
+        ),
+        (
+            KeyEventRegister::c(vec![KeyEvent::Enter]),
+            RunCommand.into(),
+        ),
+        (
+            KeyEventRegister::c(vec![KeyEvent::Esc]),
+            SwitchMode(EditorMode::Normal).into(),
+        ),
+        (
+            KeyEventRegister::c(vec![KeyEvent::Backspace]),
+            RemoveCharFromCommand.into(),
+        ),
         // Go into insert mode and move one char forward
         (
             KeyEventRegister::n(vec![KeyEvent::Char('a')]),
@@ -312,6 +852,47 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::i(vec![KeyEvent::Down]),
             MoveDown(1).into(),
         ),
+        // Start or extend a selection with shift and the arrow keys
+        (
+            KeyEventRegister::n(vec![KeyEvent::ShiftRight]),
+            Composed::new(SwitchMode(EditorMode::Visual))
+                .chain(MoveForward(1))
+                .into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::ShiftLeft]),
+            Composed::new(SwitchMode(EditorMode::Visual))
+                .chain(MoveBackward(1))
+                .into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::ShiftUp]),
+            Composed::new(SwitchMode(EditorMode::Visual))
+                .chain(MoveUp(1))
+                .into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::ShiftDown]),
+            Composed::new(SwitchMode(EditorMode::Visual))
+                .chain(MoveDown(1))
+                .into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::ShiftRight]),
+            MoveForward(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::ShiftLeft]),
+            MoveBackward(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::ShiftUp]),
+            MoveUp(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::ShiftDown]),
+            MoveDown(1).into(),
+        ),
         // Move one word forward/backward
         (
             KeyEventRegister::n(vec![KeyEvent::Char('w')]),
@@ -337,6 +918,22 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::v(vec![KeyEvent::Char('b')]),
             MoveWordBackward(1).into(),
         ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('e')]),
+            MoveWordBackwardToEndOfWord(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('g'), KeyEvent::Char('e')]),
+            MoveWordBackwardToEndOfWord(1).into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('E')]),
+            MoveBigWordBackwardToEndOfWord(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('g'), KeyEvent::Char('E')]),
+            MoveBigWordBackwardToEndOfWord(1).into(),
+        ),
         // Move cursor to start/first/last position
         (
             KeyEventRegister::n(vec![KeyEvent::Char('0')]),
@@ -378,18 +975,19 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::v(vec![KeyEvent::Ctrl('u')]),
             MoveHalfPageUp().into(),
         ),
-        // `Home` and `End` go to first/last position in a line
+        // `Home` toggles between the first non-whitespace character and the
+        // start of a line, `End` goes to the last position in a line
         (
             KeyEventRegister::i(vec![KeyEvent::Home]),
-            MoveToStartOfLine().into(),
+            SmartMoveToStart().into(),
         ),
         (
             KeyEventRegister::n(vec![KeyEvent::Home]),
-            MoveToStartOfLine().into(),
+            SmartMoveToStart().into(),
         ),
         (
             KeyEventRegister::v(vec![KeyEvent::Home]),
-            MoveToStartOfLine().into(),
+            SmartMoveToStart().into(),
         ),
         (
             KeyEventRegister::i(vec![KeyEvent::End]),
@@ -403,6 +1001,92 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::v(vec![KeyEvent::End]),
             MoveToEndOfLine().into(),
         ),
+        // Jump to the next validation error
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char(']'), KeyEvent::Char('d')]),
+            NextError.into(),
+        ),
+        // Jump between the entries of the location list
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char(']'), KeyEvent::Char('q')]),
+            NextLocation.into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('['), KeyEvent::Char('q')]),
+            PrevLocation.into(),
+        ),
+        // Open the file under the cursor
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('f')]),
+            OpenFileUnderCursor.into(),
+        ),
+        // Show the position of the cursor
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Ctrl('g')]),
+            ShowCursorInfo.into(),
+        ),
+        // Ask the app to close the editor
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('Z'), KeyEvent::Char('Z')]),
+            RequestExit(ExitRequest::SaveAndClose).into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('Z'), KeyEvent::Char('Q')]),
+            RequestExit(ExitRequest::DiscardAndClose).into(),
+        ),
+        // Bookmarks
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('m'), KeyEvent::Char('m')]),
+            ToggleBookmark.into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('m'), KeyEvent::Char('n')]),
+            NextBookmark.into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('m'), KeyEvent::Char('p')]),
+            PrevBookmark.into(),
+        ),
+        // `Tab` and `Shift+Tab` navigate between fields, `Tab` inserts a tab in insert mode
+        (KeyEventRegister::n(vec![KeyEvent::Tab]), NextField.into()),
+        (
+            KeyEventRegister::n(vec![KeyEvent::BackTab]),
+            PreviousField.into(),
+        ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::BackTab]),
+            PreviousField.into(),
+        ),
+        // `PageUp` and `PageDown` scroll by a full page
+        (
+            KeyEventRegister::n(vec![KeyEvent::PageUp]),
+            MovePageUp().into(),
+        ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::PageUp]),
+            MovePageUp().into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::PageUp]),
+            MovePageUp().into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::PageDown]),
+            MovePageDown().into(),
+        ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::PageDown]),
+            MovePageDown().into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::PageDown]),
+            MovePageDown().into(),
+        ),
+        // `Ctrl+a` inserts the text of the last insert session
+        (
+            KeyEventRegister::i(vec![KeyEvent::Ctrl('a')]),
+            InsertLastInserted.into(),
+        ),
         // `Ctrl+u` deltes from cursor to first non-whitespace character in insert mode
         (
             KeyEventRegister::i(vec![KeyEvent::Ctrl('u')]),
@@ -427,6 +1111,55 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('g')]),
             MoveToFirstRow().into(),
         ),
+        // Move to the other end of the selection
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('o')]),
+            SwapSelectionEnds.into(),
+        ),
+        // Reselect the last visual selection
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('v')]),
+            ReselectLastVisual.into(),
+        ),
+        // Expand or shrink the selection to the enclosing scope
+        (
+            KeyEventRegister::n(vec![KeyEvent::Alt('o')]),
+            ExpandSelection.into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Alt('o')]),
+            ExpandSelection.into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Alt('i')]),
+            ShrinkSelection.into(),
+        ),
+        // Change the case of the selection
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('U')]),
+            UppercaseSelection.into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('u')]),
+            LowercaseSelection.into(),
+        ),
+        // Shift lines, keeping the selection for repeated shifts
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('>')]),
+            IndentSelection(1).into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('<')]),
+            DedentSelection(1).into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('>'), KeyEvent::Char('>')]),
+            IndentLines(1).into(),
+        ),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('<'), KeyEvent::Char('<')]),
+            DedentLines(1).into(),
+        ),
         (
             KeyEventRegister::v(vec![KeyEvent::Char('g'), KeyEvent::Char('g')]),
             MoveToFirstRow().into(),
@@ -644,7 +1377,7 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
         ),
         (
             KeyEventRegister::v(vec![KeyEvent::Char('x')]),
-            Composed::new(ChangeSelection)
+            Composed::new(DeleteSelection)
                 .chain(SwitchMode(EditorMode::Normal))
                 .into(),
         ),
@@ -653,10 +1386,37 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::n(vec![KeyEvent::Char('V')]),
             SelectLine.into(),
         ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('V')]),
+            SelectLine.into(),
+        ),
+        // Select a block of columns
+        (
+            KeyEventRegister::n(vec![KeyEvent::Ctrl('v')]),
+            SelectBlock.into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Ctrl('v')]),
+            SelectBlock.into(),
+        ),
+        // Insert in front of or after the selection on every row
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('I')]),
+            InsertBlock.into(),
+        ),
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('A')]),
+            AppendBlock.into(),
+        ),
         // Undo
         (KeyEventRegister::n(vec![KeyEvent::Char('u')]), Undo.into()),
         // Redo
         (KeyEventRegister::n(vec![KeyEvent::Ctrl('r')]), Redo.into()),
+        // Repeat the last change
+        (
+            KeyEventRegister::n(vec![KeyEvent::Char('.')]),
+            RepeatLastChange(1).into(),
+        ),
         // Copy
         (
             KeyEventRegister::v(vec![KeyEvent::Char('y')]),
@@ -670,6 +1430,10 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
         ),
         // Paste
         (KeyEventRegister::n(vec![KeyEvent::Char('p')]), Paste.into()),
+        (
+            KeyEventRegister::n(vec![KeyEvent::Ctrl('n')]),
+            PasteCycleNext.into(),
+        ),
         (
             KeyEventRegister::v(vec![KeyEvent::Char('p')]),
             Composed::new(PasteOverSelection)
@@ -678,6 +1442,78 @@ fn vim_keybindings() -> HashMap<KeyEventRegister, Action> {
         ),
     ]);
 
+    // Operators followed by a motion, e.g. `dw`, `ce` or `yb`
+    let motions: [(&[char], Action); 7] = [
+        (&['w'], MoveWordForward(1).into()),
+        (&['e'], MoveWordForwardToEndOfWord(1).into()),
+        (&['b'], MoveWordBackward(1).into()),
+        (&['g', 'e'], MoveWordBackwardToEndOfWord(1).into()),
+        (&['g', 'E'], MoveBigWordBackwardToEndOfWord(1).into()),
+        (&['$'], MoveToEndOfLine().into()),
+        (&['0'], MoveToStartOfLine().into()),
+    ];
+    for (key, operator) in [
+        ('d', Operator::Delete),
+        ('c', Operator::Change),
+        ('y', Operator::Yank),
+    ] {
+        for (keys, motion) in &motions {
+            let keys = std::iter::once(key).chain(keys.iter().copied());
+            map.insert(
+                KeyEventRegister::n(keys.map(KeyEvent::Char).collect::<Vec<_>>()),
+                OperatorMotion::new(operator, motion.clone()).into(),
+            );
+        }
+    }
+
+    // The entire text as a text object, e.g. `yae` or `dag`
+    for object in ['e', 'g'] {
+        map.insert(
+            KeyEventRegister::v(vec![KeyEvent::Char('a'), KeyEvent::Char(object)]),
+            SelectEntireBuffer.into(),
+        );
+        map.insert(
+            KeyEventRegister::n(vec![
+                KeyEvent::Char('d'),
+                KeyEvent::Char('a'),
+                KeyEvent::Char(object),
+            ]),
+            Composed::new(SelectEntireBuffer)
+                .chain(DeleteSelection)
+                .chain(SwitchMode(EditorMode::Normal))
+                .into(),
+        );
+        map.insert(
+            KeyEventRegister::n(vec![
+                KeyEvent::Char('c'),
+                KeyEvent::Char('a'),
+                KeyEvent::Char(object),
+            ]),
+            Composed::new(SelectEntireBuffer)
+                .chain(SwitchMode(EditorMode::Insert))
+                .chain(ChangeSelection)
+                .into(),
+        );
+        map.insert(
+            KeyEventRegister::n(vec![
+                KeyEvent::Char('y'),
+                KeyEvent::Char('a'),
+                KeyEvent::Char(object),
+            ]),
+            Composed::new(SelectEntireBuffer)
+                .chain(CopySelection)
+                .chain(SwitchMode(EditorMode::Normal))
+                .chain(MoveToFirstRow())
+                .into(),
+        );
+    }
+
+    // The number under the cursor as a text object, e.g. `cin`
+    map.extend(text_object_bindings('i', 'n', SelectTextObject('n')));
+    // The lines at the indentation level of the cursor, e.g. `dii` or `yai`
+    map.extend(text_object_bindings('i', 'i', SelectInnerIndent));
+    map.extend(text_object_bindings('a', 'i', SelectAroundIndent));
+
     // Open system editor (Ctrl+e in normal mode)
     #[cfg(feature = "system-editor")]
     map.insert(
@@ -766,6 +1602,14 @@ fn emacs_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::i(vec![KeyEvent::Alt('v')]),
             MoveHalfPageUp().into(),
         ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::PageUp]),
+            MovePageUp().into(),
+        ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::PageDown]),
+            MovePageDown().into(),
+        ),
         (
             KeyEventRegister::i(vec![KeyEvent::Alt('<')]),
             MoveToFirstRow().into(),
@@ -780,7 +1624,7 @@ fn emacs_keybindings() -> HashMap<KeyEventRegister, Action> {
         ),
         (
             KeyEventRegister::i(vec![KeyEvent::Home]),
-            MoveToStartOfLine().into(),
+            SmartMoveToStart().into(),
         ),
         (
             KeyEventRegister::i(vec![KeyEvent::End]),
@@ -790,6 +1634,10 @@ fn emacs_keybindings() -> HashMap<KeyEventRegister, Action> {
             KeyEventRegister::i(vec![KeyEvent::Ctrl('e')]),
             MoveToEndOfLine().into(),
         ),
+        (
+            KeyEventRegister::i(vec![KeyEvent::BackTab]),
+            PreviousField.into(),
+        ),
         (
             KeyEventRegister::i(vec![KeyEvent::Alt('u')]),
             DeleteToFirstCharOfLine.into(),
@@ -848,6 +1696,10 @@ fn emacs_keybindings() -> HashMap<KeyEventRegister, Action> {
         (KeyEventRegister::i(vec![KeyEvent::Ctrl('u')]), Undo.into()),
         (KeyEventRegister::i(vec![KeyEvent::Ctrl('r')]), Redo.into()),
         (KeyEventRegister::i(vec![KeyEvent::Ctrl('y')]), Paste.into()),
+        (
+            KeyEventRegister::i(vec![KeyEvent::Alt('y')]),
+            PasteCycleNext.into(),
+        ),
         #[cfg(feature = "system-editor")]
         (
             KeyEventRegister::i(vec![KeyEvent::Alt('e')]),
@@ -856,6 +1708,145 @@ fn emacs_keybindings() -> HashMap<KeyEventRegister, Action> {
     ])
 }
 
+fn vim_char_keybindings() -> HashMap<KeyEventRegister, CharAction> {
+    HashMap::from([
+        // Replace every selected character
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('r')]),
+            (|ch| ReplaceSelection(ch).into()) as CharAction,
+        ),
+        // Align the selected lines on a delimiter
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char('g'), KeyEvent::Char('a')]),
+            |ch| AlignSelection(ch).into(),
+        ),
+        // Use a register for the next yank, deletion or paste
+        (KeyEventRegister::n(vec![KeyEvent::Char('"')]), |ch| {
+            SelectRegister(ch).into()
+        }),
+        (KeyEventRegister::v(vec![KeyEvent::Char('"')]), |ch| {
+            SelectRegister(ch).into()
+        }),
+        // Record and play macros
+        (KeyEventRegister::n(vec![KeyEvent::Char('q')]), |ch| {
+            StartRecordingMacro(ch).into()
+        }),
+        (KeyEventRegister::n(vec![KeyEvent::Char('@')]), |ch| {
+            PlayMacro(ch).into()
+        }),
+    ])
+}
+
+/// Returns the bindings of a text object that is selected by `select`:
+/// `prefix` and `key` select it in visual mode, and `d`, `c` and `y`
+/// followed by `prefix` and `key` delete, change and yank it.
+fn text_object_bindings<A>(prefix: char, key: char, select: A) -> [(KeyEventRegister, Action); 4]
+where
+    A: Into<Action> + Clone,
+{
+    let keys = |operator: char| {
+        KeyEventRegister::n(vec![
+            KeyEvent::Char(operator),
+            KeyEvent::Char(prefix),
+            KeyEvent::Char(key),
+        ])
+    };
+    [
+        (
+            KeyEventRegister::v(vec![KeyEvent::Char(prefix), KeyEvent::Char(key)]),
+            select.clone().into(),
+        ),
+        (
+            keys('d'),
+            Composed::new(select.clone())
+                .chain_if(Condition::HasSelection, DeleteSelection)
+                .chain(SwitchMode(EditorMode::Normal))
+                .into(),
+        ),
+        (
+            keys('c'),
+            Composed::new(select.clone())
+                .chain_if(Condition::HasSelection, SwitchMode(EditorMode::Insert))
+                .chain_if(Condition::HasSelection, ChangeSelection)
+                .into(),
+        ),
+        (
+            keys('y'),
+            Composed::new(select)
+                .chain(CopySelection)
+                .chain(SwitchMode(EditorMode::Normal))
+                .into(),
+        ),
+    ]
+}
+
+/// The largest count prefix. Larger counts are clamped, so that typing a
+/// long run of digits does not repeat an action for minutes.
+const MAX_COUNT: usize = 10_000;
+
+/// Runs an action with a count prefix, see [`with_count`]. The repetitions
+/// are undone at once.
+fn run_with_count(state: &mut EditorState, action: Action, count: usize) {
+    let mut state = state.pause_history();
+    state.run_action(with_count(action, count));
+}
+
+/// Applies a count prefix to an action. `{count}%` jumps to the line at
+/// `count` percent of the buffer, motions move `count` times as far, edits
+/// with a count of their own apply to `count` times as much text and any
+/// other action is repeated `count` times.
+fn with_count(action: Action, count: usize) -> Action {
+    let count = count.min(MAX_COUNT);
+    match action {
+        Action::MoveToMatchingBracket(_) => MoveToPercent(count).into(),
+        Action::Undo(_) | Action::UndoN(_) => UndoN(count).into(),
+        Action::IndentSelection(_) => IndentSelection(count).into(),
+        Action::DedentSelection(_) => DedentSelection(count).into(),
+        Action::IndentLines(_) => IndentLines(count).into(),
+        Action::DedentLines(_) => DedentLines(count).into(),
+        Action::Redo(_) | Action::RedoN(_) => RedoN(count).into(),
+        Action::RepeatLastChange(_) => RepeatLastChange(count).into(),
+        Action::OperatorMotion(operator) => operator.count(count).into(),
+        Action::MoveForward(MoveForward(n)) => MoveForward(n.saturating_mul(count)).into(),
+        Action::MoveBackward(MoveBackward(n)) => MoveBackward(n.saturating_mul(count)).into(),
+        Action::MoveUp(MoveUp(n)) => MoveUp(n.saturating_mul(count)).into(),
+        Action::MoveDown(MoveDown(n)) => MoveDown(n.saturating_mul(count)).into(),
+        Action::MoveWordForward(MoveWordForward(n)) => {
+            MoveWordForward(n.saturating_mul(count)).into()
+        }
+        Action::MoveWordForwardToEndOfWord(MoveWordForwardToEndOfWord(n)) => {
+            MoveWordForwardToEndOfWord(n.saturating_mul(count)).into()
+        }
+        Action::MoveWordBackward(MoveWordBackward(n)) => {
+            MoveWordBackward(n.saturating_mul(count)).into()
+        }
+        Action::MoveWordBackwardToEndOfWord(MoveWordBackwardToEndOfWord(n)) => {
+            MoveWordBackwardToEndOfWord(n.saturating_mul(count)).into()
+        }
+        Action::MoveBigWordBackwardToEndOfWord(MoveBigWordBackwardToEndOfWord(n)) => {
+            MoveBigWordBackwardToEndOfWord(n.saturating_mul(count)).into()
+        }
+        Action::MoveSubwordForward(MoveSubwordForward(n)) => {
+            MoveSubwordForward(n.saturating_mul(count)).into()
+        }
+        Action::MoveSubwordForwardToEnd(MoveSubwordForwardToEnd(n)) => {
+            MoveSubwordForwardToEnd(n.saturating_mul(count)).into()
+        }
+        Action::MoveSubwordBackward(MoveSubwordBackward(n)) => {
+            MoveSubwordBackward(n.saturating_mul(count)).into()
+        }
+        Action::DeleteLine(DeleteLine(n)) => DeleteLine(n.saturating_mul(count)).into(),
+        Action::RemoveChar(RemoveChar(n)) => RemoveChar(n.saturating_mul(count)).into(),
+        Action::DeleteChar(DeleteChar(n)) => DeleteChar(n.saturating_mul(count)).into(),
+        Action::DeleteCharForward(DeleteCharForward(n)) => {
+            DeleteCharForward(n.saturating_mul(count)).into()
+        }
+        Action::AppendNewline(AppendNewline(n)) => AppendNewline(n.saturating_mul(count)).into(),
+        Action::InsertNewline(InsertNewline(n)) => InsertNewline(n.saturating_mul(count)).into(),
+        action => Composed(vec![action; count]).into(),
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct KeyEventRegister {
     keys: Vec<KeyEvent>,
@@ -867,7 +1858,49 @@ type RegisterCB = fn(&mut EditorState);
 #[derive(Clone, Debug)]
 struct RegisterVal(pub fn(&mut EditorState));
 
+impl fmt::Display for KeyEventRegister {
+    /// Formats the mode and the keys, e.g. `Normal dd`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.mode.name())?;
+        self.keys.iter().try_for_each(|key| write!(f, "{key}"))
+    }
+}
+
+impl fmt::Display for KeyEventHandler {
+    /// Lists the bindings of all modes, one per line, e.g. for a help screen.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modes = [
+            EditorMode::Normal,
+            EditorMode::Visual,
+            EditorMode::Insert,
+            EditorMode::Search,
+            EditorMode::Command,
+        ];
+        for mode in modes {
+            for (key, action) in self.bindings(mode) {
+                writeln!(f, "{:<16} {action}", key.to_string())?;
+            }
+            for (key, action) in self.char_bindings(mode) {
+                writeln!(f, "{:<16} {}", format!("{key}{{char}}"), action(' ').name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl KeyEventRegister {
+    /// Returns the key sequence.
+    #[must_use]
+    pub fn keys(&self) -> &[KeyEvent] {
+        &self.keys
+    }
+
+    /// Returns the mode in which the keys are bound.
+    #[must_use]
+    pub fn mode(&self) -> EditorMode {
+        self.mode
+    }
+
     pub fn new<T>(key: T, mode: EditorMode) -> Self
     where
         T: Into<Vec<KeyEvent>>,
@@ -905,6 +1938,13 @@ impl KeyEventRegister {
     {
         Self::new(key.into(), EditorMode::Search)
     }
+
+    pub fn c<T>(key: T) -> Self
+    where
+        T: Into<Vec<KeyEvent>>,
+    {
+        Self::new(key.into(), EditorMode::Command)
+    }
 }
 
 impl KeyEventHandler {
@@ -912,30 +1952,753 @@ impl KeyEventHandler {
     where
         T: Into<KeyEvent> + Copy + std::fmt::Debug,
     {
+        state.record_macro_key(key.into(), |state| self.handle_key(key, state));
+    }
+
+    fn handle_key<T>(&mut self, key: T, state: &mut EditorState)
+    where
+        T: Into<KeyEvent> + Copy + std::fmt::Debug,
+    {
+        if let Some(log) = self
+            .key_log
+            .as_mut()
+            .filter(|_| key.into() != KeyEvent::None)
+        {
+            log.record(key.into());
+        }
         let mode = state.mode;
+        let key = match key.into() {
+            key if self.normalize_keys => key.normalized(),
+            KeyEvent::Shift(c) => KeyEvent::Char(c),
+            key => key,
+        };
+        // Key releases and unsupported keys must not interrupt a sequence
+        if key == KeyEvent::None {
+            return;
+        }
+        // Some terminals send `Alt+x` as `Esc` followed by `x`
+        let key = match key {
+            KeyEvent::Esc if self.pending.is_none() && self.is_meta_prefix(mode) => {
+                self.meta_prefix = true;
+                self.last_key = Some(Instant::now());
+                return;
+            }
+            key if std::mem::take(&mut self.meta_prefix) => match with_meta(key) {
+                Some(alt) if self.next_keys(mode).any(|next| next == alt) => alt,
+                _ => {
+                    let actions = self.get(KeyEvent::Esc, mode);
+                    self.execute_with_count(actions, state);
+                    key
+                }
+            },
+            key => key,
+        };
 
-        match key.into() {
+        match key {
+            // Complete an action that waits for a character
+            key if self.pending.is_some() => {
+                let pending = self.pending.take();
+                let count = self.count.take();
+                if let (Some(pending), KeyEvent::Char(c)) = (pending, key) {
+                    match count {
+                        Some(count) => run_with_count(state, pending(c), count),
+                        None => state.run_action(pending(c)),
+                    }
+                }
+                if self.one_shot_normal {
+                    self.finish_one_shot_normal(state);
+                }
+            }
+            // `Ctrl+o` executes a single normal mode command, unless it is bound
+            KeyEvent::Ctrl('o')
+                if mode == EditorMode::Insert
+                    && !self
+                        .register
+                        .contains_key(&KeyEventRegister::i(vec![KeyEvent::Ctrl('o')])) =>
+            {
+                self.one_shot_normal = true;
+                state.mode = EditorMode::Normal;
+            }
             // Always insert characters in insert mode
             KeyEvent::Char(c) if mode == EditorMode::Insert => {
-                if self.capture_on_insert {
-                    state.capture();
-                }
-                InsertChar(c).execute(state)
+                self.capture_on_insert(c, state);
+                state.run_action(InsertChar(c).into());
             }
-            KeyEvent::Tab if mode == EditorMode::Insert => {
-                if self.capture_on_insert {
-                    state.capture();
-                }
-                InsertChar('\t').execute(state)
+            // `Tab` inserts a tab in insert mode, unless it is bound
+            KeyEvent::Tab
+                if mode == EditorMode::Insert
+                    && !self
+                        .register
+                        .contains_key(&KeyEventRegister::i(vec![KeyEvent::Tab])) =>
+            {
+                self.capture_on_insert('\t', state);
+                insert_tab(state);
+            }
+            // Add characters to search in search mode, unless they are bound
+            KeyEvent::Char(c)
+                if mode == EditorMode::Search
+                    && !self
+                        .register
+                        .contains_key(&KeyEventRegister::s(vec![KeyEvent::Char(c)])) =>
+            {
+                state.run_action(AppendCharToSearch(c).into());
             }
-            // Always add characters to search in search mode
-            KeyEvent::Char(c) if mode == EditorMode::Search => AppendCharToSearch(c).execute(state),
+            // Always add characters to the command line in command mode
+            KeyEvent::Char(c) if mode == EditorMode::Command => {
+                state.run_action(AppendCharToCommand(c).into());
+            }
+            // `q` stops the recording of a macro instead of waiting for a register
+            KeyEvent::Char('q')
+                if mode == EditorMode::Normal
+                    && self.lookup.is_empty()
+                    && state.recording_macro().is_some() =>
+            {
+                self.count = None;
+                state.run_action(StopRecordingMacro.into());
+            }
+            // Digits in normal and visual mode start a count prefix
+            key if [EditorMode::Normal, EditorMode::Visual].contains(&mode)
+                && self.push_count(key, mode) => {}
             // Else lookup an action from the register
             _ => {
-                if let Some(mut action) = self.get(key.into(), mode) {
-                    action.execute(state);
+                let actions = self.get(key, mode);
+                self.execute_with_count(actions, state);
+                if self.one_shot_normal
+                    && self.lookup.is_empty()
+                    && self.count.is_none()
+                    && self.pending.is_none()
+                {
+                    self.finish_one_shot_normal(state);
                 }
             }
         }
+
+        self.last_key = (!self.lookup.is_empty()).then(Instant::now);
+    }
+
+    /// Resolves the pending key sequence if the timeout passed. Returns
+    /// whether the sequence was resolved.
+    pub(crate) fn on_tick(&mut self, state: &mut EditorState) -> bool {
+        let elapsed = self.last_key.map(|last_key| last_key.elapsed());
+        if !self.timed_out(elapsed) {
+            return false;
+        }
+        self.resolve_pending(state);
+        true
+    }
+
+    /// Whether a pending key sequence times out after a pause of `pause`.
+    pub(crate) fn timed_out(&self, pause: Option<Duration>) -> bool {
+        match (self.timeout, pause) {
+            (Some(timeout), Some(pause)) => self.last_key.is_some() && pause >= timeout,
+            _ => false,
+        }
+    }
+
+    /// Resolves the pending key sequence: the keys typed so far run their
+    /// action if they are bound on their own, or are discarded otherwise.
+    pub(crate) fn resolve_pending(&mut self, state: &mut EditorState) {
+        self.last_key = None;
+        if std::mem::take(&mut self.meta_prefix) {
+            self.lookup.push(KeyEvent::Esc);
+        }
+
+        let mut actions = Vec::new();
+        while !self.lookup.is_empty() {
+            actions.extend(self.take_bound_prefix(state.mode));
+        }
+        self.execute_with_count(actions, state);
+        self.count = None;
+        if self.one_shot_normal && self.pending.is_none() {
+            self.finish_one_shot_normal(state);
+        }
+    }
+
+    /// Returns the keys that continue the pending sequence to a binding.
+    fn next_keys(&self, mode: EditorMode) -> impl Iterator<Item = KeyEvent> + '_ {
+        self.register
+            .keys()
+            .chain(self.char_register.keys())
+            .filter(move |key| key.mode == mode && key.keys.starts_with(&self.lookup))
+            .filter_map(|key| key.keys.get(self.lookup.len()).copied())
+    }
+
+    /// Returns whether `Esc` stands for the `Alt` modifier of the next key,
+    /// i.e. it continues no binding, but an `Alt` key does.
+    fn is_meta_prefix(&self, mode: EditorMode) -> bool {
+        let next_keys: Vec<KeyEvent> = self.next_keys(mode).collect();
+        !next_keys.contains(&KeyEvent::Esc)
+            && next_keys.iter().any(|key| matches!(key, KeyEvent::Alt(_)))
+    }
+
+    /// Executes actions, the first one with the pending count, if any.
+    fn execute_with_count(&mut self, actions: Vec<Action>, state: &mut EditorState) {
+        for (i, action) in actions.into_iter().enumerate() {
+            match self.count.take() {
+                Some(count) if i == 0 => run_with_count(state, action, count),
+                _ => state.run_action(action),
+            }
+        }
+    }
+
+    /// Starts a new undo step before `c` is typed in insert mode, depending
+    /// on the [`UndoGrouping`].
+    fn capture_on_insert(&mut self, c: char, state: &mut EditorState) {
+        let new_step = match self.undo_grouping {
+            UndoGrouping::Keystroke => true,
+            UndoGrouping::Word => {
+                !c.is_whitespace() && state.inserted.ends_with(char::is_whitespace)
+            }
+            UndoGrouping::InsertSession => false,
+            UndoGrouping::Time(pause) => self
+                .last_insert
+                .is_some_and(|last_insert| last_insert.elapsed() > pause),
+        };
+        if new_step {
+            state.capture();
+        }
+        self.last_insert = Some(Instant::now());
+    }
+
+    /// Returns to insert mode after the normal mode command started with
+    /// `Ctrl+o` completed. If the command entered visual, search or command
+    /// mode, the return is deferred until that mode is left again.
+    fn finish_one_shot_normal(&mut self, state: &mut EditorState) {
+        match state.mode {
+            EditorMode::Normal => {
+                self.one_shot_normal = false;
+                state.capture();
+                state.mode = EditorMode::Insert;
+            }
+            EditorMode::Insert => self.one_shot_normal = false,
+            EditorMode::Visual
+            | EditorMode::Search
+            | EditorMode::Command
+            | EditorMode::Custom(_) => {}
+        }
+    }
+}
+
+/// Inserts a tab, see [`tab_chars`].
+fn insert_tab(state: &mut EditorState) {
+    for ch in tab_chars(state) {
+        state.run_action(InsertChar(ch).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ReplaceChar;
+    use crate::{EditorEventHandler, Index2, Lines};
+    use jagged::index::RowIndex;
+
+    fn test_state() -> EditorState {
+        let text: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        EditorState::new(Lines::from(text.join("\n").as_str()))
+    }
+
+    #[test]
+    fn test_default_keymaps_have_no_conflicts() {
+        let conflicts: Vec<String> = KeyEventHandler::vim_mode()
+            .conflicts()
+            .iter()
+            .chain(&KeyEventHandler::emacs_mode().conflicts())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(conflicts, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_list_bindings() {
+        let mut handler = KeyEventHandler::new(HashMap::new(), false);
+        handler.insert(
+            KeyEventRegister::v(vec![KeyEvent::Ctrl('x')]),
+            DeleteLine(1),
+        );
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Char('g')]),
+            MoveToFirst(),
+        );
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('g')]),
+            MoveToFirstRow(),
+        );
+        handler.insert_char_action(KeyEventRegister::n(vec![KeyEvent::Char('r')]), |c| {
+            ReplaceChar(c).into()
+        });
+
+        assert_eq!(handler.bindings(EditorMode::Normal).len(), 2);
+        assert_eq!(handler.char_bindings(EditorMode::Normal).len(), 1);
+        assert_eq!(
+            handler.to_string(),
+            "Normal g         MoveToFirst\nNormal gg        MoveToFirstRow\n\
+             Normal r{char}   ReplaceChar\nVisual <C-x>     DeleteLine(1)\n"
+        );
+        assert_eq!(
+            handler.conflicts(),
+            vec![KeyConflict::Shadowed {
+                prefix: KeyEventRegister::n(vec![KeyEvent::Char('g')]),
+                longer: KeyEventRegister::n(vec![KeyEvent::Char('g'), KeyEvent::Char('g')]),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_remove_bindings_by_action() {
+        let mut handler = KeyEventHandler::vim_mode();
+        handler.remove_action::<ReplaceSelection>();
+        handler.retain(|key, _| key.mode() != EditorMode::Insert);
+
+        assert_eq!(handler.char_bindings(EditorMode::Visual).len(), 2);
+        assert!(handler.bindings(EditorMode::Insert).is_empty());
+        assert!(!handler.bindings(EditorMode::Normal).is_empty());
+    }
+
+    #[test]
+    fn test_count_prefix_repeats_action() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in ['3', 'j'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.cursor, Index2::new(3, 0));
+
+        for key in ['2', 'd', 'd'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.lines.len(), 8);
+
+        handler.on_event(KeyEvent::Char('j'), &mut state);
+        assert_eq!(state.cursor, Index2::new(4, 0));
+    }
+
+    #[test]
+    fn test_count_prefix_is_one_undo_step() {
+        for keys in ["5dd", "3x", "yy3p", "3J", "3rx"] {
+            let mut state = test_state();
+            let mut handler = EditorEventHandler::default();
+            handler.feed_keys(keys, &mut state).unwrap();
+            assert_ne!(state.lines, test_state().lines, "{keys}");
+
+            handler.feed_keys("u", &mut state).unwrap();
+            assert_eq!(state.lines, test_state().lines, "{keys}");
+        }
+    }
+
+    #[test]
+    fn test_count_prefix_shifts_lines() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("3>>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("  a\n  b\n  c\nd"));
+
+        handler.feed_keys("2<<", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a\nb\n  c\nd"));
+    }
+
+    #[test]
+    fn test_count_prefix_is_clamped() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in "99999999999999999999999j".chars() {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.cursor, Index2::new(9, 0));
+
+        for key in "99999999999999999999999x".chars() {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.lines.len(), 10);
+    }
+
+    #[test]
+    fn test_one_shot_normal_command() {
+        let mut state = test_state();
+        state.mode = EditorMode::Insert;
+        let mut handler = KeyEventHandler::vim_mode();
+
+        handler.on_event(KeyEvent::Ctrl('o'), &mut state);
+        assert_eq!(state.mode, EditorMode::Normal);
+
+        for key in ['d', 'd'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.mode, EditorMode::Insert);
+        assert_eq!(state.lines.len(), 9);
+
+        handler.on_event(KeyEvent::Char('x'), &mut state);
+        assert_eq!(state.lines.get(RowIndex::new(0)), Some(&vec!['x', '1']));
+    }
+
+    #[test]
+    fn test_count_prefix_percent() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in ['5', '0', '%'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.cursor, Index2::new(4, 0));
+
+        state.cursor = Index2::new(4, 0);
+        handler.on_event(KeyEvent::Char('0'), &mut state);
+        assert_eq!(state.cursor, Index2::new(4, 0));
+    }
+
+    #[test]
+    fn test_pending_char_action() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in ['v', 'j', 'r', 'x'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.lines.get(RowIndex::new(0)), Some(&vec!['x']));
+        assert_eq!(state.lines.get(RowIndex::new(1)), Some(&vec!['x']));
+        assert_eq!(state.mode, EditorMode::Normal);
+
+        handler.on_event(KeyEvent::Char('j'), &mut state);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+    }
+
+    #[test]
+    fn test_tab_navigates_fields() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+        state.set_fields(vec![crate::Field::new(
+            "field",
+            Index2::new(2, 0),
+            Index2::new(2, 1),
+        )]);
+
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(0, 0);
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.lines.get(RowIndex::new(0)), Some(&vec!['\t', '0']));
+
+        handler.insert(KeyEventRegister::i(vec![KeyEvent::Tab]), NextField);
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.cursor, Index2::new(2, 1));
+        assert_eq!(state.lines.get(RowIndex::new(0)), Some(&vec!['\t', '0']));
+
+        state.set_fields(Vec::new());
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.lines.get(RowIndex::new(2)), Some(&vec!['2', '\t']));
+    }
+
+    #[test]
+    fn test_delete_to_end_of_previous_word() {
+        let mut state = EditorState::new(Lines::from("foo bar"));
+        state.cursor = Index2::new(0, 4);
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in ['d', 'g', 'e'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.lines, Lines::from("foar"));
+        assert_eq!(state.cursor, Index2::new(0, 2));
+        assert_eq!(state.mode, EditorMode::Normal);
+    }
+
+    #[test]
+    fn test_change_number_object() {
+        let mut state = EditorState::new(Lines::from("width: 120px"));
+        let mut handler = KeyEventHandler::vim_mode();
+
+        for key in ['c', 'i', 'n', '8'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.lines, Lines::from("width: 8px"));
+        assert_eq!(state.mode, EditorMode::Insert);
+    }
+
+    #[test]
+    fn test_expand_tab() {
+        let mut state = EditorState::new(Lines::from("a"));
+        state.set_config(crate::EditorConfig::default().tab_width(4).expand_tab(true));
+        let mut handler = KeyEventHandler::vim_mode();
+
+        handler.on_event(KeyEvent::Char('A'), &mut state);
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.lines, Lines::from("a   "));
+        handler.on_event(KeyEvent::Tab, &mut state);
+        assert_eq!(state.lines, Lines::from("a       "));
+    }
+
+    #[test]
+    fn test_custom_mode() {
+        const PICK: EditorMode = EditorMode::Custom("Pick");
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Char('P')]),
+            SwitchMode(PICK),
+        );
+        handler.insert(
+            KeyEventRegister::new(vec![KeyEvent::Char('j')], PICK),
+            MoveDown(2),
+        );
+        handler.insert(
+            KeyEventRegister::new(vec![KeyEvent::Esc], PICK),
+            SwitchMode(EditorMode::Normal),
+        );
+
+        handler.on_event(KeyEvent::Char('P'), &mut state);
+        assert_eq!(state.mode, PICK);
+        assert_eq!(state.mode.name(), "Pick");
+
+        for key in ['j', 'x', 'd', 'd'] {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(state.cursor, Index2::new(2, 0));
+        assert_eq!(state.lines.len(), 10);
+
+        handler.on_event(KeyEvent::Esc, &mut state);
+        assert_eq!(state.mode, EditorMode::Normal);
+    }
+
+    #[test]
+    fn test_undo_grouping() {
+        let mut state = EditorState::new(Lines::from(""));
+        let mut handler = EditorEventHandler::default();
+        handler.feed_keys("iab cd<Esc>u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from(""));
+
+        handler
+            .key_handler
+            .set_undo_grouping(UndoGrouping::Keystroke);
+        handler.feed_keys("iab<Esc>u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a"));
+
+        let mut state = EditorState::new(Lines::from(""));
+        handler.key_handler.set_undo_grouping(UndoGrouping::Word);
+        handler.feed_keys("iab  cd ef<Esc>u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ab  cd "));
+        handler.feed_keys("u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ab  "));
+
+        let mut state = EditorState::new(Lines::from(""));
+        let pause = Duration::from_secs(60);
+        handler
+            .key_handler
+            .set_undo_grouping(UndoGrouping::Time(pause));
+        handler.feed_keys("iab<Esc>u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from(""));
+    }
+
+    #[test]
+    fn test_sequence_timeout() {
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+        handler.insert(KeyEventRegister::n(vec![KeyEvent::Char('g')]), MoveDown(1));
+        handler.on_event(KeyEvent::Char('g'), &mut state);
+        assert!(!handler.on_tick(&mut state));
+
+        handler.set_timeout(Some(Duration::ZERO));
+        assert!(handler.on_tick(&mut state));
+        assert_eq!(state.cursor, Index2::new(1, 0));
+        assert!(!handler.on_tick(&mut state));
+
+        handler.on_event(KeyEvent::Char('d'), &mut state);
+        assert!(handler.on_tick(&mut state));
+        handler.on_event(KeyEvent::Char('d'), &mut state);
+        assert_eq!(state.lines.len(), 10);
+    }
+
+    #[test]
+    fn test_multi_key_sequences() {
+        let mut state = EditorState::new(Lines::from("a b\nc d\n\ne f"));
+        let mut handler = KeyEventHandler::vim_mode();
+        let keys = |handler: &mut KeyEventHandler, state: &mut EditorState, keys: &str| {
+            for ch in keys.chars() {
+                handler.on_event(KeyEvent::Char(ch), state);
+            }
+        };
+        handler.insert(
+            KeyEventRegister::n(vec![
+                KeyEvent::Char('c'),
+                KeyEvent::Char('i'),
+                KeyEvent::Char('p'),
+            ]),
+            DeleteLine(2),
+        );
+        handler.insert(
+            KeyEventRegister::n(vec![
+                KeyEvent::Char(' '),
+                KeyEvent::Char('f'),
+                KeyEvent::Char('f'),
+            ]),
+            MoveToEndOfLine(),
+        );
+
+        keys(&mut handler, &mut state, "Gg");
+        assert_eq!(state.cursor, Index2::new(3, 0));
+        keys(&mut handler, &mut state, "g");
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        keys(&mut handler, &mut state, "ci");
+        assert_eq!(state.lines.len(), 4);
+        keys(&mut handler, &mut state, "p");
+        assert_eq!(state.lines, Lines::from("\ne f"));
+
+        keys(&mut handler, &mut state, "j f");
+        assert_eq!(state.cursor, Index2::new(1, 0));
+        keys(&mut handler, &mut state, "f");
+        assert_eq!(state.cursor, Index2::new(1, 2));
+
+        // A key that breaks the sequence is looked up on its own
+        keys(&mut handler, &mut state, " fk");
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        assert!(handler.lookup.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_keys() {
+        let shift_a = CTKeyEvent::new(KeyCode::Char('a'), KeyModifiers::SHIFT);
+        let altgr = CTKeyEvent::new(
+            KeyCode::Char('@'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        assert_eq!(KeyEvent::from(shift_a), KeyEvent::Shift('a'));
+
+        let mut state = EditorState::new(Lines::from("Hello"));
+        let mut handler = KeyEventHandler::vim_mode();
+        handler.on_event(KeyEvent::from(shift_a), &mut state);
+        assert_eq!(state.cursor, Index2::new(0, 1));
+        handler.on_event(KeyEvent::Esc, &mut state);
+        state.cursor = Index2::new(0, 0);
+
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Ctrl('T')]),
+            MoveToEndOfLine(),
+        );
+        handler.set_normalize_keys(true);
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Char('@')]),
+            MoveToStartOfLine(),
+        );
+
+        handler.on_event(KeyEvent::Ctrl('t'), &mut state);
+        assert_eq!(state.cursor, Index2::new(0, 4));
+        handler.on_event(KeyEvent::from(altgr), &mut state);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        handler.on_event(KeyEvent::from(shift_a), &mut state);
+        assert_eq!(state.mode, EditorMode::Insert);
+        assert_eq!(state.cursor, Index2::new(0, 5));
+    }
+
+    #[test]
+    fn test_enhanced_keyboard_keys() {
+        let press = |code, modifiers| CTKeyEvent::new(code, modifiers);
+        let release =
+            |code| CTKeyEvent::new_with_kind(code, KeyModifiers::NONE, KeyEventKind::Release);
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Char('i'), KeyModifiers::CONTROL)),
+            KeyEvent::Ctrl('i')
+        );
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Tab, KeyModifiers::NONE)),
+            KeyEvent::Tab
+        );
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Char('m'), KeyModifiers::CONTROL)),
+            KeyEvent::Ctrl('m')
+        );
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Tab, KeyModifiers::SHIFT)),
+            KeyEvent::BackTab
+        );
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Right, KeyModifiers::SHIFT)),
+            KeyEvent::ShiftRight
+        );
+        assert_eq!(
+            KeyEvent::from(press(KeyCode::Up, KeyModifiers::SHIFT)),
+            KeyEvent::ShiftUp
+        );
+
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+        state.cursor = Index2::new(5, 0);
+        for key in [
+            press(KeyCode::Char('g'), KeyModifiers::NONE),
+            release(KeyCode::Char('g')),
+            press(KeyCode::Char('g'), KeyModifiers::NONE),
+            release(KeyCode::Char('g')),
+        ] {
+            handler.on_event(key, &mut state);
+        }
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        for key in [
+            press(KeyCode::Char('v'), KeyModifiers::NONE),
+            press(KeyCode::Char('r'), KeyModifiers::NONE),
+            release(KeyCode::Char('r')),
+            press(KeyCode::Char('x'), KeyModifiers::NONE),
+        ] {
+            handler.on_event(key, &mut state);
+        }
+        assert_eq!(state.lines, Lines::from("x\n1\n2\n3\n4\n5\n6\n7\n8\n9"));
+    }
+
+    #[test]
+    fn test_esc_prefixed_alt_keys() {
+        let mut state = EditorState::new(Lines::from("Hello World"));
+        let mut handler = KeyEventHandler::emacs_mode();
+        state.mode = EditorMode::Insert;
+
+        handler.on_event(KeyEvent::Esc, &mut state);
+        handler.on_event(KeyEvent::Char('f'), &mut state);
+        assert_eq!(state.cursor, Index2::new(0, 6));
+        assert_eq!(state.lines, Lines::from("Hello World"));
+
+        handler.on_event(KeyEvent::Esc, &mut state);
+        handler.on_event(KeyEvent::Char('!'), &mut state);
+        assert_eq!(state.lines, Lines::from("Hello !World"));
+
+        let mut state = test_state();
+        let mut handler = KeyEventHandler::vim_mode();
+        for key in "ix".chars() {
+            handler.on_event(KeyEvent::Char(key), &mut state);
+        }
+        handler.on_event(KeyEvent::Esc, &mut state);
+        handler.on_event(KeyEvent::Char('x'), &mut state);
+        assert_eq!(state.lines.get(RowIndex::new(0)), Some(&vec!['x']));
+    }
+
+    #[test]
+    fn test_shift_arrows_select() {
+        let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+        let mut handler = KeyEventHandler::vim_mode();
+
+        handler.on_event(KeyEvent::ShiftRight, &mut state);
+        assert_eq!(state.mode, EditorMode::Visual);
+        handler.on_event(KeyEvent::ShiftRight, &mut state);
+        handler.on_event(KeyEvent::ShiftDown, &mut state);
+        let selection = state.selection.clone().unwrap();
+        assert_eq!(selection.start, Index2::new(0, 0));
+        assert_eq!(selection.end, Index2::new(1, 2));
+
+        handler.on_event(KeyEvent::ShiftLeft, &mut state);
+        handler.on_event(KeyEvent::ShiftUp, &mut state);
+        let selection = state.selection.clone().unwrap();
+        assert_eq!(selection.end, Index2::new(0, 1));
+    }
+
+    #[test]
+    fn test_linewise_edits_in_empty_buffer() {
+        for keys in ["vVd", "vVp", "vVx", "vVy", "<C-v>Vp"] {
+            let mut state = EditorState::new(Lines::default());
+            let mut handler = EditorEventHandler::default();
+            handler.feed_keys(keys, &mut state).unwrap();
+            assert!(state.lines.len() <= 1, "{keys}");
+        }
     }
 }
@@ -0,0 +1,169 @@
+//! A log of the keys an editor received, to replay them later, e.g. to
+//! reproduce a bug or in integration tests.
+//!
+//! The log is written as text with one key per line: the milliseconds since
+//! the recording started, followed by the key in vim notation.
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::events::KeyLog;
+//! use edtui::{EditorEventHandler, EditorState, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("Hello"));
+//! let mut event_handler = EditorEventHandler::default();
+//! event_handler.key_handler.start_key_log();
+//! event_handler.feed_keys("A!<Esc>", &mut state).unwrap();
+//!
+//! let log = event_handler.key_handler.stop_key_log().unwrap().to_string();
+//! assert_eq!(log.lines().count(), 3);
+//!
+//! let mut replayed = EditorState::new(Lines::from("Hello"));
+//! let log: KeyLog = log.parse().unwrap();
+//! EditorEventHandler::default().replay(&log, &mut replayed);
+//! assert_eq!(replayed.lines, Lines::from("Hello!"));
+//! ```
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use super::{EditorEventHandler, KeyEvent, KeyNotationError};
+use crate::EditorState;
+
+/// A key together with the time it was received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyLogEntry {
+    /// The time since the recording started.
+    pub time: Duration,
+    /// The key, as received from the terminal.
+    pub key: KeyEvent,
+}
+
+/// The keys an editor received, see [`KeyEventHandler::start_key_log`].
+///
+/// [`KeyEventHandler::start_key_log`]: crate::events::KeyEventHandler::start_key_log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyLog {
+    entries: Vec<KeyLogEntry>,
+    /// When the recording started, if the log is being recorded.
+    started: Option<Instant>,
+}
+
+impl KeyLog {
+    /// Creates an empty log, e.g. to write a key sequence for a test.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty log that records the time of its keys from now on.
+    pub(crate) fn recording() -> Self {
+        Self {
+            entries: Vec::new(),
+            started: Some(Instant::now()),
+        }
+    }
+
+    /// Appends a key received at `time`.
+    pub fn push(&mut self, time: Duration, key: KeyEvent) {
+        self.entries.push(KeyLogEntry { time, key });
+    }
+
+    /// Appends a key received now.
+    pub(crate) fn record(&mut self, key: KeyEvent) {
+        let time = self
+            .started
+            .map(|started| started.elapsed())
+            .unwrap_or_default();
+        self.push(time, key);
+    }
+
+    /// Returns the keys in the order they were received.
+    #[must_use]
+    pub fn entries(&self) -> &[KeyLogEntry] {
+        &self.entries
+    }
+}
+
+impl fmt::Display for KeyLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let key = KeyEvent::format_sequence(&[entry.key]);
+            writeln!(f, "{} {key}", entry.time.as_millis())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyLog {
+    type Err = KeyNotationError;
+
+    /// Parses a log as written by its [`fmt::Display`] implementation.
+    /// Empty lines are skipped.
+    fn from_str(log: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+        for line in log.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid = || KeyNotationError::InvalidLogEntry(line.to_string());
+            let (time, key) = line.split_once(' ').ok_or_else(invalid)?;
+            let time = time.parse().map_err(|_| invalid())?;
+            entries.push(KeyLogEntry {
+                time: Duration::from_millis(time),
+                key: key.parse()?,
+            });
+        }
+        Ok(Self {
+            entries,
+            started: None,
+        })
+    }
+}
+
+impl EditorEventHandler {
+    /// Handles the keys of a log as if they were pressed again. Pending key
+    /// sequences are resolved where the pauses between the logged keys
+    /// exceed the timeout of the key handler, so that a replay does not
+    /// depend on how fast it runs.
+    pub fn replay(&mut self, log: &KeyLog, state: &mut EditorState) {
+        let mut last = None;
+        for entry in log.entries() {
+            let paused = last.map(|last| entry.time.saturating_sub(last));
+            if self.key_handler.timed_out(paused) {
+                state.track_edits(|state| self.key_handler.resolve_pending(state));
+            }
+            self.on_key_event(entry.key, state);
+            last = Some(entry.time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index2, Lines};
+
+    #[test]
+    fn test_key_log() {
+        let log: KeyLog = "0 j\n10 <Space>\n\n20 <lt>\n".parse().unwrap();
+        assert_eq!(log.entries().len(), 3);
+        assert_eq!(log.entries()[2].key, KeyEvent::Char('<'));
+        assert_eq!(log.to_string(), "0 j\n10 <Space>\n20 <lt>\n");
+        assert_eq!(
+            "j".parse::<KeyLog>(),
+            Err(KeyNotationError::InvalidLogEntry("j".to_string()))
+        );
+
+        // The pause after `g` exceeds the timeout, so `gg` is not a sequence
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+        let mut handler = EditorEventHandler::default();
+        handler
+            .key_handler
+            .set_timeout(Some(Duration::from_millis(500)));
+        let log: KeyLog = "0 G\n10 g\n1000 g".parse().unwrap();
+        handler.replay(&log, &mut state);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+
+        let log: KeyLog = "0 G\n10 g\n20 g".parse().unwrap();
+        handler.replay(&log, &mut state);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+    }
+}
@@ -1,10 +1,14 @@
 pub(crate) mod deprecated_input;
 mod key;
+mod key_log;
 #[cfg(feature = "mouse-support")]
 pub(crate) mod mouse;
+mod notation;
 pub(crate) mod paste;
 
-pub use key::{KeyEvent, KeyEventHandler, KeyEventRegister};
+pub use key::{CharAction, KeyConflict, KeyEvent, KeyEventHandler, KeyEventRegister, UndoGrouping};
+pub use key_log::{KeyLog, KeyLogEntry};
+pub use notation::KeyNotationError;
 
 #[cfg(feature = "mouse-support")]
 pub use mouse::{MouseEvent, MouseEventHandler};
@@ -66,7 +70,8 @@ impl EditorEventHandler {
     where
         T: Into<KeyEvent>,
     {
-        self.key_handler.on_event(event.into(), state);
+        let event = event.into();
+        state.track_edits(|state| self.key_handler.on_event(event, state));
     }
 
     #[cfg(feature = "mouse-support")]
@@ -80,7 +85,50 @@ impl EditorEventHandler {
 
     /// Handles paste events.
     pub fn on_paste_event(&self, text: String, state: &mut EditorState) {
-        PasteEventHandler::on_event(text, state);
+        state.close_gap();
+        state.track_edits(|state| PasteEventHandler::on_event(text, state));
+    }
+
+    /// Drives time-based behavior: resolves key sequences after the timeout
+    /// of the key handler, removes expired highlights and notifications,
+    /// runs debounced validation and keeps smooth scrolling going.
+    ///
+    /// Apps should call this periodically, e.g. whenever polling for events
+    /// times out, and redraw the editor if it returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use crossterm::event;
+    /// use edtui::{EditorEventHandler, EditorState};
+    ///
+    /// let mut state = EditorState::default();
+    /// let mut event_handler = EditorEventHandler::default();
+    /// loop {
+    ///     let redraw = if event::poll(Duration::from_millis(50)).unwrap() {
+    ///         event_handler.on_event(event::read().unwrap(), &mut state);
+    ///         true
+    ///     } else {
+    ///         event_handler.on_tick(&mut state)
+    ///     };
+    ///     if redraw {
+    ///         // terminal.draw(..)
+    ///     }
+    /// }
+    /// ```
+    pub fn on_tick(&mut self, state: &mut EditorState) -> bool {
+        let mut redraw = false;
+        state.close_gap();
+        state.track_edits(|state| {
+            redraw = self.key_handler.on_tick(state);
+            redraw |= state.yank_flash.tick();
+            redraw |= state.locations.tick();
+            redraw |= state.validation.tick(&state.lines);
+            redraw |= state.notifications.tick();
+        });
+        redraw |= state.view.is_scrolling();
+        redraw
     }
 }
 
@@ -5,7 +5,7 @@ use crate::{
     actions::{Execute, SwitchMode},
     helper::char_width,
     state::selection::set_selection,
-    view::line_wrapper::LineWrapper,
+    view::line_wrapper::{LineLayout, LineWrapper},
     EditorMode, EditorState,
 };
 
@@ -25,6 +25,7 @@ impl MouseEventHandler {
         if event == MouseEvent::None {
             return;
         }
+        state.close_gap();
 
         // Handle scroll events
         match event {
@@ -167,41 +168,47 @@ fn mouse_position_to_cursor_position(
 
     let mut row_screen_index = 0;
     for line in state.lines.iter_row().skip(row_index) {
-        let wrapped_line = LineWrapper::wrap_line(
+        let layout = LineWrapper::layout(
             line,
             state.view.screen_area.width.into(),
             state.view.tab_width,
+            &state.view.wrap_options,
         );
-        let wrapped_line_len = wrapped_line.len().max(1);
-        if row_screen_index + wrapped_line_len > mouse.row {
+        if row_screen_index + layout.num_rows() > mouse.row {
             mouse.row = mouse.row.saturating_sub(row_screen_index);
-            col_index = find_cursor_column_in_wrapped_line(&wrapped_line, &mouse, tab_width);
+            col_index = find_cursor_column_in_layout(line, &layout, &mouse, tab_width);
             break;
         }
-        row_screen_index += wrapped_line_len;
+        row_screen_index += layout.num_rows();
         row_index += 1;
     }
 
     Index2::new(row_index, col_index)
 }
 
-fn find_cursor_column_in_wrapped_line(
-    line: &[Vec<char>],
+fn find_cursor_column_in_layout(
+    line: &[char],
+    layout: &LineLayout,
     mouse: &Index2,
     tab_width: usize,
 ) -> usize {
-    let Some(l) = line.get(mouse.row) else {
+    let Some(row) = layout.rows.get(mouse.row) else {
         return 0;
     };
 
-    let col_offset: usize = line.iter().take(mouse.row).map(Vec::len).sum();
+    // Continuation rows start after the wrap prefix
+    let mouse_col = if mouse.row > 0 {
+        mouse.col.saturating_sub(layout.prefix_width)
+    } else {
+        mouse.col
+    };
     let mut current_width = 0;
-    let mut col_index = 0;
+    let mut col_index = row.start;
 
-    for &ch in l {
+    for &ch in &line[row.clone()] {
         let char_width = char_width(ch, tab_width);
 
-        if current_width + char_width > mouse.col {
+        if current_width + char_width > mouse_col {
             break;
         }
 
@@ -209,7 +216,7 @@ fn find_cursor_column_in_wrapped_line(
         col_index += 1;
     }
 
-    col_offset + col_index
+    col_index
 }
 
 /// Represents a mouse event.
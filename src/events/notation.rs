@@ -0,0 +1,264 @@
+//! Key sequences in vim notation, such as `dd`, `<C-w>q` or `<Esc>:w<CR>`.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{EditorEventHandler, KeyEvent};
+use crate::EditorState;
+
+/// An error of a key sequence in vim notation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyNotationError {
+    /// The name between `<` and `>` is not a known key, e.g. `<Foo>`.
+    UnknownKey(String),
+    /// A single key was expected, but the notation contains none or several.
+    NotASingleKey(String),
+    /// A line of a [`KeyLog`] is not a time followed by a key.
+    ///
+    /// [`KeyLog`]: crate::events::KeyLog
+    InvalidLogEntry(String),
+}
+
+impl fmt::Display for KeyNotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKey(name) => write!(f, "Unknown key: <{name}>"),
+            Self::NotASingleKey(notation) => write!(f, "Not a single key: {notation}"),
+            Self::InvalidLogEntry(line) => write!(f, "Invalid key log entry: {line}"),
+        }
+    }
+}
+
+impl Error for KeyNotationError {}
+
+impl KeyEvent {
+    /// Parses a key sequence in vim notation, e.g. to load keybindings or
+    /// macros from a text file.
+    ///
+    /// Characters stand for themselves, special keys are written between
+    /// `<` and `>`, such as `<CR>`, `<Esc>`, `<Space>`, `<C-w>` or `<A-x>`.
+    /// Key names are case-insensitive. A literal `<` is written as `<lt>`,
+    /// or as `<` if it does not start a key name. This is the inverse of
+    /// [`KeyEvent::format_sequence`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name between `<` and `>` is not a known key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::events::KeyEvent;
+    ///
+    /// let keys = KeyEvent::parse_sequence("<C-w>q").unwrap();
+    /// assert_eq!(keys, vec![KeyEvent::Ctrl('w'), KeyEvent::Char('q')]);
+    /// ```
+    pub fn parse_sequence(notation: &str) -> Result<Vec<KeyEvent>, KeyNotationError> {
+        let mut keys = Vec::new();
+        let mut rest = notation;
+        while let Some(ch) = rest.chars().next() {
+            let name = rest
+                .strip_prefix('<')
+                .and_then(|name| name.split_once('>'))
+                .map(|(name, _)| name)
+                .filter(|name| !name.is_empty() && !name.contains(['<', ' ']));
+            match name {
+                Some(name) => {
+                    let key = parse_key_name(name)
+                        .ok_or_else(|| KeyNotationError::UnknownKey(name.to_string()))?;
+                    keys.push(key);
+                    rest = &rest[name.len() + 2..];
+                }
+                None => {
+                    keys.push(KeyEvent::Char(ch));
+                    rest = &rest[ch.len_utf8()..];
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Formats a key sequence in vim notation, e.g. to store keybindings or
+    /// macros in a text file. This is the inverse of
+    /// [`KeyEvent::parse_sequence`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::events::KeyEvent;
+    ///
+    /// let keys = [KeyEvent::Char('<'), KeyEvent::Char('<'), KeyEvent::Esc];
+    /// assert_eq!(KeyEvent::format_sequence(&keys), "<lt><lt><Esc>");
+    /// ```
+    #[must_use]
+    pub fn format_sequence(keys: &[KeyEvent]) -> String {
+        keys.iter()
+            .map(|key| match key {
+                KeyEvent::Char('<') => "<lt>".to_string(),
+                key => key.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for KeyEvent {
+    type Err = KeyNotationError;
+
+    /// Parses a single key in vim notation, e.g. `x` or `<C-r>`.
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        match KeyEvent::parse_sequence(notation)?.as_slice() {
+            [key] => Ok(*key),
+            _ => Err(KeyNotationError::NotASingleKey(notation.to_string())),
+        }
+    }
+}
+
+/// Parses the name of a key between `<` and `>`.
+fn parse_key_name(name: &str) -> Option<KeyEvent> {
+    let modified = |prefix: &str| {
+        let name = name
+            .get(..2)?
+            .eq_ignore_ascii_case(prefix)
+            .then(|| &name[2..])?;
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        }
+    };
+    if let Some(ch) = modified("C-") {
+        return Some(KeyEvent::Ctrl(ch.to_ascii_lowercase()));
+    }
+    if let Some(ch) = modified("A-").or_else(|| modified("M-")) {
+        return Some(KeyEvent::Alt(ch));
+    }
+    if let Some(ch) = modified("S-").filter(char::is_ascii_lowercase) {
+        return Some(KeyEvent::Shift(ch));
+    }
+
+    let key = match name.to_ascii_lowercase().as_str() {
+        "lt" => KeyEvent::Char('<'),
+        "gt" => KeyEvent::Char('>'),
+        "bar" => KeyEvent::Char('|'),
+        "bslash" => KeyEvent::Char('\\'),
+        "space" => KeyEvent::Char(' '),
+        "cr" | "enter" | "return" => KeyEvent::Enter,
+        "esc" => KeyEvent::Esc,
+        "bs" => KeyEvent::Backspace,
+        "a-bs" | "m-bs" => KeyEvent::Alt('\x08'),
+        "del" => KeyEvent::Delete,
+        "tab" => KeyEvent::Tab,
+        "s-tab" => KeyEvent::BackTab,
+        "up" => KeyEvent::Up,
+        "down" => KeyEvent::Down,
+        "left" => KeyEvent::Left,
+        "right" => KeyEvent::Right,
+        "s-up" => KeyEvent::ShiftUp,
+        "s-down" => KeyEvent::ShiftDown,
+        "s-left" => KeyEvent::ShiftLeft,
+        "s-right" => KeyEvent::ShiftRight,
+        "home" => KeyEvent::Home,
+        "end" => KeyEvent::End,
+        "pageup" => KeyEvent::PageUp,
+        "pagedown" => KeyEvent::PageDown,
+        _ => return None,
+    };
+    Some(key)
+}
+
+impl EditorEventHandler {
+    /// Handles a key sequence in vim notation as if the keys were pressed,
+    /// e.g. to replay a stored macro. See [`KeyEvent::parse_sequence`] for
+    /// the notation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence can't be parsed. No key is handled
+    /// then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorEventHandler, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// let mut event_handler = EditorEventHandler::default();
+    ///
+    /// event_handler.feed_keys("A World<Esc>", &mut state).unwrap();
+    /// assert_eq!(state.lines, Lines::from("Hello World"));
+    /// ```
+    pub fn feed_keys(
+        &mut self,
+        notation: &str,
+        state: &mut EditorState,
+    ) -> Result<(), KeyNotationError> {
+        for key in KeyEvent::parse_sequence(notation)? {
+            self.on_key_event(key, state);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sequence() {
+        let parse = |notation| KeyEvent::parse_sequence(notation);
+
+        assert_eq!(
+            parse("dd"),
+            Ok(vec![KeyEvent::Char('d'), KeyEvent::Char('d')])
+        );
+        assert_eq!(
+            parse("<c-W>q<cr>"),
+            Ok(vec![
+                KeyEvent::Ctrl('w'),
+                KeyEvent::Char('q'),
+                KeyEvent::Enter
+            ])
+        );
+        assert_eq!(
+            parse("<<<lt>>"),
+            Ok(vec![
+                KeyEvent::Char('<'),
+                KeyEvent::Char('<'),
+                KeyEvent::Char('<'),
+                KeyEvent::Char('>')
+            ])
+        );
+        assert_eq!(
+            parse("<A-BS><S-Tab><Space>"),
+            Ok(vec![
+                KeyEvent::Alt('\x08'),
+                KeyEvent::BackTab,
+                KeyEvent::Char(' ')
+            ])
+        );
+        assert_eq!(
+            parse("<Foo>"),
+            Err(KeyNotationError::UnknownKey("Foo".into()))
+        );
+        assert_eq!("<C-r>".parse(), Ok(KeyEvent::Ctrl('r')));
+        assert!("ab".parse::<KeyEvent>().is_err());
+    }
+
+    #[test]
+    fn test_format_sequence_round_trip() {
+        let keys = vec![
+            KeyEvent::Char('<'),
+            KeyEvent::Char('a'),
+            KeyEvent::Char('>'),
+            KeyEvent::Ctrl('o'),
+            KeyEvent::Alt('x'),
+            KeyEvent::PageDown,
+            KeyEvent::ShiftUp,
+            KeyEvent::Shift('a'),
+            KeyEvent::Char(' '),
+        ];
+        let notation = KeyEvent::format_sequence(&keys);
+        assert_eq!(notation, "<lt>a><C-o><A-x><PageDown><S-Up><S-a><Space>");
+        assert_eq!(KeyEvent::parse_sequence(&notation), Ok(keys));
+    }
+}
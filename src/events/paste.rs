@@ -14,6 +14,8 @@ impl PasteEventHandler {
             crate::EditorMode::Normal | crate::EditorMode::Insert => Paste.execute(state),
             crate::EditorMode::Visual => PasteOverSelection.execute(state),
             crate::EditorMode::Search => {} // TODO: Insert into search
+            crate::EditorMode::Command => {}
+            crate::EditorMode::Custom(_) => {}
         }
     }
 }
@@ -1,7 +1,39 @@
 use jagged::index::RowIndex;
-use ratatui_core::{layout::Rect, text::Span};
-
-use crate::{EditorMode, EditorState, Index2, Lines};
+use ratatui_core::{layout::Rect, style::Style, text::Span};
+
+use crate::{bytes::invalid_byte, EditorMode, EditorState, Index2, Lines};
+
+/// The minimum number of lines searched by each task with the `rayon`
+/// feature.
+#[cfg(feature = "rayon")]
+const MIN_LINES_PER_TASK: usize = 1024;
+
+/// Maps each line together with its row to a value. With the `rayon`
+/// feature, the lines of large texts are mapped in parallel. The values are
+/// always in the order of the lines.
+pub(crate) fn map_lines<T, F>(lines: &Lines, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize, &[char]) -> T + Sync,
+{
+    #[cfg(feature = "rayon")]
+    if lines.len() > MIN_LINES_PER_TASK {
+        use rayon::prelude::*;
+
+        let rows: Vec<&Vec<char>> = lines.iter_row().collect();
+        return rows
+            .par_iter()
+            .with_min_len(MIN_LINES_PER_TASK)
+            .enumerate()
+            .map(|(row, line)| f(row, line))
+            .collect();
+    }
+    lines
+        .iter_row()
+        .enumerate()
+        .map(|(row, line)| f(row, line))
+        .collect()
+}
 
 /// Inserts a character into the lines data at the given `index`.
 pub(crate) fn insert_char(lines: &mut Lines, index: &mut Index2, ch: char, skip_move: bool) {
@@ -202,9 +234,41 @@ pub(crate) fn char_width(ch: char, tab_width: usize) -> usize {
     if ch == '\t' {
         return tab_width;
     }
+    if let Some(placeholder) = control_char_placeholder(ch) {
+        return placeholder.len();
+    }
     ch.width().unwrap_or(0)
 }
 
+/// Returns the visible placeholder of a control character: caret notation
+/// such as `^[` for C0 control characters and `<XX>` for C1 control
+/// characters and bytes that are not valid UTF-8. Tabs are not considered
+/// control characters.
+pub(crate) fn control_char_placeholder(ch: char) -> Option<String> {
+    match ch {
+        '\t' => None,
+        '\0'..='\x1f' => Some(format!("^{}", char::from(ch as u8 + b'@'))),
+        '\x7f' => Some(String::from("^?")),
+        '\u{80}'..='\u{9f}' => Some(format!("<{:02X}>", ch as u32)),
+        _ => invalid_byte(ch).map(|byte| format!("<{byte:02X}>")),
+    }
+}
+
+/// Replaces tabs with spaces and control characters with their placeholder.
+fn display_str(s: &str, tab_width: usize) -> String {
+    let mut display = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\t' => display.push_str(&" ".repeat(tab_width)),
+            ch => match control_char_placeholder(ch) {
+                Some(placeholder) => display.push_str(&placeholder),
+                None => display.push(ch),
+            },
+        }
+    }
+    display
+}
+
 /// Determines the unicode width of chars.
 pub(crate) fn chars_width(chars: &[char], tab_width: usize) -> usize {
     chars
@@ -215,10 +279,7 @@ pub(crate) fn chars_width(chars: &[char], tab_width: usize) -> usize {
 /// Determines the unicode width of a span.
 pub(crate) fn span_width(s: &Span, tab_width: usize) -> usize {
     use unicode_width::UnicodeWidthStr;
-    s.content
-        .as_ref()
-        .replace('\t', &" ".repeat(tab_width))
-        .width()
+    display_str(&s.content, tab_width).width()
 }
 
 /// Splits span into two at an index. Other than [`str::split_at`], this method
@@ -231,8 +292,44 @@ pub(crate) fn split_str_at<T: AsRef<str>>(s: T, mid: usize) -> (String, String)
     (first_half, second_half)
 }
 
-pub(crate) fn replace_tabs_in_span(span: &mut Span, tab_width: usize) {
-    span.content = span.content.replace('\t', &" ".repeat(tab_width)).into();
+/// Prepares a span for display. Tabs are replaced with spaces, and control
+/// characters are replaced with their placeholder in `control_char_style`.
+pub(crate) fn display_spans<'a>(
+    span: &Span<'a>,
+    tab_width: usize,
+    control_char_style: Style,
+) -> Vec<Span<'a>> {
+    if !span
+        .content
+        .chars()
+        .any(|ch| control_char_placeholder(ch).is_some())
+    {
+        return vec![Span::styled(
+            display_str(&span.content, tab_width),
+            span.style,
+        )];
+    }
+
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    for ch in span.content.chars() {
+        if let Some(placeholder) = control_char_placeholder(ch) {
+            if !text.is_empty() {
+                spans.push(Span::styled(display_str(&text, tab_width), span.style));
+                text.clear();
+            }
+            spans.push(Span::styled(
+                placeholder,
+                span.style.patch(control_char_style),
+            ));
+        } else {
+            text.push(ch);
+        }
+    }
+    if !text.is_empty() {
+        spans.push(Span::styled(display_str(&text, tab_width), span.style));
+    }
+    spans
 }
 
 pub(crate) fn rect_indent_y(rect: Rect, offset: usize) -> Rect {
@@ -354,4 +451,41 @@ mod tests {
         let closing_bracket = find_matching_bracket(&lines, cursor);
         assert_eq!(closing_bracket, Some(Index2::new(0, 0)));
     }
+
+    #[test]
+    fn test_control_char_placeholder() {
+        assert_eq!(control_char_placeholder('\x1b'), Some(String::from("^[")));
+        assert_eq!(control_char_placeholder('\0'), Some(String::from("^@")));
+        assert_eq!(control_char_placeholder('\x7f'), Some(String::from("^?")));
+        assert_eq!(
+            control_char_placeholder('\u{85}'),
+            Some(String::from("<85>"))
+        );
+        assert_eq!(
+            control_char_placeholder('\u{10ffff}'),
+            Some(String::from("<FF>"))
+        );
+        assert_eq!(control_char_placeholder('\t'), None);
+        assert_eq!(control_char_placeholder('a'), None);
+
+        assert_eq!(char_width('\x1b', 4), 2);
+        assert_eq!(span_width(&Span::raw("a\x1b\u{85}"), 4), 7);
+    }
+
+    #[test]
+    fn test_display_spans() {
+        let style = Style::default().italic();
+        let span = Span::raw("a\x1b[0m\t");
+
+        let spans = display_spans(&span, 2, style);
+
+        assert_eq!(
+            spans,
+            vec![
+                Span::raw("a"),
+                Span::styled("^[", style),
+                Span::raw("[0m  "),
+            ]
+        );
+    }
 }
@@ -69,6 +69,18 @@
 //! - Syntax highlighting.
 //! - Line numbers (absolute and relative).
 //! - System editor support (optional, via `system-editor` feature).
+//! - Right-to-left and bidirectional text (optional, via `bidi` feature).
+//! - Preset for editing git commit messages (`commit::CommitMessage`).
+//! - Markdown rendering that styles and conceals markup (`EditorView::markdown`).
+//! - Validation hooks that underline errors in structured text (`validation::Validate`).
+//! - Sub-word motions for `camelCase` and `snake_case` identifiers (unbound by default, e.g. `actions::MoveSubwordForward`).
+//! - Help overlay listing the key bindings, generated from the keymap (`KeymapHelp`).
+//! - Search bar overlay with a match counter (`EditorTheme::search_bar`).
+//! - Search offsets such as `/foo/e` or `/foo/+1` (`SearchOffset`).
+//! - Regular expression search with a `\v` prefix, or literal search with `\V` (optional, via `regex` feature, `EditorState::set_search_regex`).
+//! - Focus mode that dims all lines but the current paragraph (`EditorView::focus`).
+//! - Rendering into plain text for snapshot tests, without a terminal (`EditorView::snapshot`).
+//! - Command line with ex commands such as `:%s/old/new/g`, extensible with app commands (`CommandHandler`).
 //!
 //! ## Theming
 //!
@@ -87,6 +99,9 @@
 //!     .hide_status_line(); // or use `.status_line(..)` for styling the status line
 //! ```
 //!
+//! `mode_cursor_style` styles the cursor differently per mode, and `selection_anchor_style` marks the
+//! end of the selection that stays in place, which makes the mode visible without a status line.
+//!
 //! ## Line Numbers
 //!
 //! Display absolute or relative line numbers:
@@ -136,6 +151,9 @@
 //!
 //! ![](resources/syntax_highlighting.gif)
 //!
+//! [`SyntaxHighlighter::breadcrumbs`] returns the definitions that enclose the cursor, e.g. to show
+//! a breadcrumb such as `impl Foo > function bar` above the editor.
+//!
 //! ## Paste Support
 //!
 //! If you want to enable paste (via ctrl+y or cmd+y) you must explicitly enable it at the start of your app:
@@ -189,14 +207,22 @@
 //! |---------------------------|----------------------------------------------------------|
 //! | `i`                       | Enter Insert mode                                        |
 //! | `v`                       | Enter Visual mode                                        |
+//! | `V`                       | Enter linewise Visual mode                               |
+//! | `ctrl+v`                  | Enter blockwise Visual mode                              |
+//! | `shift+arrows`            | Start or extend a selection                              |
+//! | `:`                       | Enter Command mode, e.g. `:12`, `:d`, `:%s/old/new/g`    |
 //! | `h`, `j`, `k`, `l`        | Navigate left, down, up, and right                       |
 //! | `w`                       | Move forward to the start of a word                      |
 //! | `e`                       | Move forward to the end of a word                        |
 //! | `b`                       | Move backward to the start of a word                     |
+//! | `ge` / `gE`               | Move backward to the end of a word/WORD                  |
 //! | `ctrl+d`                  | Jump a half page down                                    |
 //! | `ctrl+u`                  | Jump a half page up                                      |
 //! | `x`                       | Delete the character under the cursor                    |
 //! | `u`, `ctrl+r`             | Undo/Redo last action                                    |
+//! | `.`                       | Repeat the last change                                   |
+//! | `q{register}`, `q`        | Start/Stop recording a macro                             |
+//! | `@{register}`, `@@`       | Play a macro, or the last played macro                   |
 //! | `Esc`                     | Escape Visual mode                                       |
 //! | `0`                       | Move cursor to start of line                             |
 //! | `_`                       | Move cursor to first non-blank character                 |
@@ -204,25 +230,53 @@
 //! | `gg`                      | Move cursor to the first row                             |
 //! | `G `                      | Move cursor to the last row                              |
 //! | `%`                       | Move cursor to closing/opening bracket                   |
+//! | `{count}%`                | Move cursor to the line at `count` percent of the buffer |
+//! | `{count}` + motion/action | Repeat a motion or action `count` times, e.g. `3j`       |
 //! | `a`                       | Append after the cursor                                  |
 //! | `A`                       | Append at the end of the line                            |
 //! | `o`                       | Add a new line below and enter Insert mode               |
 //! | `O`                       | Add a new line above and enter Insert mode               |
 //! | `J`                       | Join current line with the line below                    |
 //! | `d`                       | Delete the selection (Visual mode)                       |
+//! | `o`                       | Move to the other end of the selection (Visual mode)     |
+//! | `gv`                      | Reselect the last visual selection                       |
+//! | `]d`                      | Jump to the next validation error                        |
+//! | `]q`                      | Jump to the next entry of the location list              |
+//! | `[q`                      | Jump to the previous entry of the location list          |
+//! | `gf`                      | Open the file under the cursor, e.g. `src/main.rs:12`    |
+//! | `ZZ` / `ZQ`               | Ask the app to save and close / close without saving     |
+//! | `g ctrl+g`                | Show the line, column and byte offset of the cursor      |
+//! | `mm`                      | Toggle a bookmark on the current line                    |
+//! | `mn`                      | Jump to the next bookmark                                |
+//! | `mp`                      | Jump to the previous bookmark                            |
+//! | `Tab` / `shift+Tab`       | Jump to the next/previous field                          |
+//! | `U` / `u`                 | Uppercase/Lowercase the selection (Visual mode)          |
+//! | `r` + char                | Replace each selected character (Visual mode)            |
+//! | `ga` + char               | Align the selected lines on a delimiter (Visual mode)    |
+//! | `I` / `A`                 | Insert/Append on every selected row (Visual mode)        |
+//! | `>` / `<`                 | Indent/Dedent the selection, keeping it (Visual mode)    |
+//! | `>>` / `<<`               | Indent/Dedent the current line, or `{count}` lines       |
 //! | `dd`                      | Delete the current line                                  |
 //! | `D`                       | Delete to the end of the line                            |
+//! | `d`/`c`/`y` + motion      | Delete/Change/Yank up to a motion, e.g. `dw`, `ce`       |
 //! | `viw`                     | Select between word.                                     |
 //! | `ciw`                     | Change between word.                                     |
-//! | `vi` + `", ', (, [ or {`  | Select between delimiter `", ', (, [ or {`               |
+//! | `yae` / `dae` / `cae`     | Yank/Delete/Change the entire text (also `ag`)           |
+//! | `yin` / `din` / `cin`     | Yank/Delete/Change the number under the cursor           |
+//! | `ii` / `ai`               | Select the indented block (`ai` adds the line above)     |
+//! | `vi` + `", ', (, [ or {`  | Select between delimiter, repeat to expand              |
+//! | `alt+o` / `alt+i`         | Expand/Shrink the selection to the enclosing scope       |
 //! | `ci` + `", ', (, [ or {`  | Change between delimiter `", ', (, [ or {`               |
 //! | `u`                       | Undo the last change                                     |
 //! | `r`                       | Redo the last undone action                              |
 //! | `y`                       | Copy the selected text in visual mode                    |
 //! | `yy`                      | Copy the current line in normal mode                     |
 //! | `p`                       | Paste the copied text                                    |
-//! | `Home`                    | Move cursor to start of line                             |
+//! | `Ctrl+n`                  | Replace the pasted text with the previous yanked text    |
+//! | `"{char}`                 | Use register for next yank/delete/paste (e.g. `"1p`)     |
+//! | `Home`                    | Move cursor to first non-whitespace / start of line      |
 //! | `End`                     | Move cursor to end of line                               |
+//! | `PageUp` / `PageDown`     | Scroll up/down by a full page                            |
 //! | `ctrl+e`                  | Open in system editor (requires `system-editor` feature) |
 //!
 //! #### Insert Mode:
@@ -234,9 +288,14 @@
 //! | `Delete`    | Delete the character after the cursor   |
 //! | `Enter`     | Insert line break                       |
 //! | `Arrows`    | Navigation                              |
-//! | `Home`      | Move cursor to start of line            |
+//! | `Home`      | Move to first non-blank / start of line |
 //! | `End`       | Move cursor to end of line              |
+//! | `PageUp`    | Scroll up by a full page                |
+//! | `PageDown`  | Scroll down by a full page              |
 //! | `ctrl+u`    | Delete until first character            |
+//! | `ctrl+o`    | Execute one Normal mode command         |
+//! | `ctrl+a`    | Insert the last inserted text           |
+//! | `shift+Tab` | Jump to the previous field              |
 //!
 //! ### Emacs Mode
 //!
@@ -254,6 +313,8 @@
 //! | `Ctrl+e`        | Move to end of line                                      |
 //! | `Ctrl+v`        | Page down                                                |
 //! | `Alt+v`         | Page up                                                  |
+//! | `PageDown`      | Page down                                                |
+//! | `PageUp`        | Page up                                                  |
 //! | `Alt+f`         | Forward word                                             |
 //! | `Alt+b`         | Backward word                                            |
 //! | `Alt+<`         | Beginning of buffer                                      |
@@ -267,15 +328,17 @@
 //! | `Ctrl+o`        | Open line (insert newline, stay)                         |
 //! | `Ctrl+j`        | Newline                                                  |
 //! | `Ctrl+y`        | Paste                                                    |
+//! | `Alt+y`         | Replace the pasted text with the previous yanked text    |
 //! | `Ctrl+u`        | Undo                                                     |
 //! | `Ctrl+r`        | Redo                                                     |
 //! | `Ctrl+g`        | Cancel search                                            |
 //! | `Enter`         | Insert line break                                        |
 //! | `Backspace`     | Delete previous character                                |
 //! | `Arrows`        | Navigation                                               |
-//! | `Home`          | Move to start of line                                    |
+//! | `Home`          | Move to first non-whitespace / start of line             |
 //! | `End`           | Move to end of line                                      |
 //! | `Alt+e`         | Open in system editor (requires `system-editor` feature) |
+//! | `Shift+Tab`     | Jump to the previous field                               |
 //! | `Ctrl+s`        | Start search                                             |
 //! | `Ctrl+s`        | Search mode: Go to next match                            |
 //! | `Ctrl+r`        | Search mode: Go to previous match                        |
@@ -292,24 +355,50 @@
     clippy::cast_possible_truncation
 )]
 pub mod actions;
+pub mod bytes;
 pub mod clipboard;
+pub mod commit;
 mod debug;
 pub mod events;
 #[cfg(feature = "system-editor")]
 pub use actions::system_editor;
 mod helper;
+pub mod merge;
 mod state;
+pub mod validation;
 mod view;
 
+pub use bytes::LossyBytes;
 #[allow(deprecated)]
 pub use events::deprecated_input::EditorInput;
 pub use events::EditorEventHandler;
-pub use state::{mode::EditorMode, EditorState};
-pub use view::{theme::EditorTheme, EditorStatusLine, EditorView, LineNumbers};
+pub use state::{
+    accessibility::ScreenDescription,
+    command::CommandHandler,
+    config::{ConfigError, EditorConfig},
+    cursor_info::CursorInfo,
+    exit::ExitRequest,
+    fields::{Field, Validator},
+    file_reference::FileReference,
+    highlight::Highlight,
+    intent::EditorIntent,
+    locations::Location,
+    mode::EditorMode,
+    notification::{Notification, NotificationLevel},
+    session::Session,
+    EditorState, HistoryPause, LineStorage, SearchOffset, SearchScope, SetContentsOptions,
+    VirtualEdit,
+};
+pub use view::{
+    snapshot::Snapshot,
+    theme::{EditorTheme, MarkdownTheme},
+    EditorSearchBar, EditorStatusLine, EditorView, Focus, KeymapHelp, LineLayout, LineNumbers,
+    SearchBarPosition, WrapOptions,
+};
 
 #[cfg(feature = "syntax-highlighting")]
 pub use view::syntax_higlighting::{
-    SyntaxHighlighter, SyntaxHighlighterError, SYNTAX_SET, THEME_SET,
+    Breadcrumb, SyntaxHighlighter, SyntaxHighlighterError, SYNTAX_SET, THEME_SET,
 };
 
 #[cfg(feature = "syntax-highlighting")]
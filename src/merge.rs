@@ -0,0 +1,332 @@
+//! Line based three-way merge.
+//!
+//! Useful when a file that is open in the editor was modified externally.
+//! Given the text the editor was loaded with (`base`), the edited text in the
+//! editor (`ours`) and the new text on disk (`theirs`), [`merge`] combines
+//! both sets of changes. Regions that were changed differently on both sides
+//! are kept as conflicts, surrounded by git-style conflict markers:
+//!
+//! ```text
+//! <<<<<<< ours
+//! line edited in the editor
+//! =======
+//! line edited on disk
+//! >>>>>>> theirs
+//! ```
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::{EditorState, Lines};
+//!
+//! let base = Lines::from("a\nb\nc\nd");
+//! let mut state = EditorState::new(base.clone());
+//! state.lines = Lines::from("a\nB\nc\nd");
+//!
+//! let conflicts = state.reload(&base, Lines::from("a\nb\nc\nD"));
+//!
+//! assert!(conflicts.is_empty());
+//! assert_eq!(state.lines, Lines::from("a\nB\nc\nD"));
+//! ```
+use crate::{EditorState, Lines, SetContentsOptions};
+
+/// The marker that opens a conflict.
+pub const CONFLICT_START: &str = "<<<<<<< ours";
+/// The marker that separates both sides of a conflict.
+pub const CONFLICT_SEPARATOR: &str = "=======";
+/// The marker that closes a conflict.
+pub const CONFLICT_END: &str = ">>>>>>> theirs";
+
+/// The result of a three-way [`merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// The merged text, including conflict markers.
+    pub lines: Lines,
+    /// The conflicting regions within `lines`.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A conflicting region in a [`MergeResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The row of the opening conflict marker.
+    pub start: usize,
+    /// The row of the separator between both sides.
+    pub separator: usize,
+    /// The row of the closing conflict marker.
+    pub end: usize,
+}
+
+/// Merges the changes from `base` to `ours` and from `base` to `theirs`.
+///
+/// The merge works on whole lines. Its runtime is quadratic in the number of
+/// lines, which is fine for files that are typically edited in a TUI.
+#[must_use]
+pub fn merge(base: &Lines, ours: &Lines, theirs: &Lines) -> MergeResult {
+    let base: Vec<&Vec<char>> = base.iter_row().collect();
+    let ours: Vec<&Vec<char>> = ours.iter_row().collect();
+    let theirs: Vec<&Vec<char>> = theirs.iter_row().collect();
+
+    let matches_ours = match_lines(&base, &ours);
+    let matches_theirs = match_lines(&base, &theirs);
+
+    let mut merged: Vec<Vec<char>> = Vec::new();
+    let mut conflicts = Vec::new();
+
+    let (mut i, mut a, mut b) = (0, 0, 0);
+    loop {
+        // Lines that are unchanged on both sides are taken as they are.
+        if i < base.len() && matches_ours[i] == Some(a) && matches_theirs[i] == Some(b) {
+            merged.push(base[i].clone());
+            i += 1;
+            a += 1;
+            b += 1;
+            continue;
+        }
+
+        // Find the end of the chunk that changed on at least one side.
+        let next_stable =
+            (i..base.len()).find(|&j| matches_ours[j].is_some() && matches_theirs[j].is_some());
+        let (end_base, end_ours, end_theirs) = match next_stable {
+            Some(j) => (
+                j,
+                matches_ours[j].unwrap_or(a),
+                matches_theirs[j].unwrap_or(b),
+            ),
+            None => (base.len(), ours.len(), theirs.len()),
+        };
+
+        let chunk_base = &base[i..end_base];
+        let chunk_ours = &ours[a..end_ours];
+        let chunk_theirs = &theirs[b..end_theirs];
+
+        if chunk_ours == chunk_base || chunk_ours == chunk_theirs {
+            merged.extend(chunk_theirs.iter().map(|&line| line.clone()));
+        } else if chunk_theirs == chunk_base {
+            merged.extend(chunk_ours.iter().map(|&line| line.clone()));
+        } else {
+            let start = merged.len();
+            merged.push(CONFLICT_START.chars().collect());
+            merged.extend(chunk_ours.iter().map(|&line| line.clone()));
+            let separator = merged.len();
+            merged.push(CONFLICT_SEPARATOR.chars().collect());
+            merged.extend(chunk_theirs.iter().map(|&line| line.clone()));
+            let end = merged.len();
+            merged.push(CONFLICT_END.chars().collect());
+            conflicts.push(MergeConflict {
+                start,
+                separator,
+                end,
+            });
+        }
+
+        if next_stable.is_none() {
+            break;
+        }
+        (i, a, b) = (end_base, end_ours, end_theirs);
+    }
+
+    let mut lines = Lines::default();
+    for line in merged {
+        lines.push(line);
+    }
+
+    MergeResult { lines, conflicts }
+}
+
+/// Computes the longest common subsequence of two sets of lines. Returns for
+/// each line in `base` the index of the matching line in `other`, if any.
+///
+/// Uses Hirschberg's algorithm, which needs memory linear in the number of
+/// lines, after the common prefix and suffix are matched directly.
+fn match_lines(base: &[&Vec<char>], other: &[&Vec<char>]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    match_range(base, other, (0, 0), &mut matches);
+    matches
+}
+
+/// Matches the lines of `base` and `other`, which start at the lines
+/// `offset` of the full texts.
+fn match_range(
+    base: &[&Vec<char>],
+    other: &[&Vec<char>],
+    offset: (usize, usize),
+    matches: &mut [Option<usize>],
+) {
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for i in 0..prefix {
+        matches[offset.0 + i] = Some(offset.1 + i);
+    }
+    for i in 1..=suffix {
+        matches[offset.0 + base.len() - i] = Some(offset.1 + other.len() - i);
+    }
+
+    let base = &base[prefix..base.len() - suffix];
+    let other = &other[prefix..other.len() - suffix];
+    let offset = (offset.0 + prefix, offset.1 + prefix);
+    if base.is_empty() || other.is_empty() {
+        return;
+    }
+    if let [line] = base {
+        if let Some(j) = other.iter().position(|other| other == line) {
+            matches[offset.0] = Some(offset.1 + j);
+        }
+        return;
+    }
+
+    // Split `other` where the halves of `base` have the longest common
+    // subsequences with both parts in total.
+    let mid = base.len() / 2;
+    let forward = lcs_lengths(base[..mid].iter(), other.iter());
+    let backward = lcs_lengths(base[mid..].iter().rev(), other.iter().rev());
+    let mut split = 0;
+    for j in 0..=other.len() {
+        let len = forward[j] + backward[other.len() - j];
+        if len > forward[split] + backward[other.len() - split] {
+            split = j;
+        }
+    }
+
+    match_range(&base[..mid], &other[..split], offset, matches);
+    let offset = (offset.0 + mid, offset.1 + split);
+    match_range(&base[mid..], &other[split..], offset, matches);
+}
+
+/// Returns the lengths of the longest common subsequences of `base` and
+/// the first `j` lines of `other`, for each `j`.
+fn lcs_lengths<'a, I, J>(base: I, other: J) -> Vec<usize>
+where
+    I: Iterator<Item = &'a &'a Vec<char>>,
+    J: Iterator<Item = &'a &'a Vec<char>> + Clone,
+{
+    let mut lengths = vec![0; other.clone().count() + 1];
+    for line in base {
+        let mut diagonal = 0;
+        for (j, other) in other.clone().enumerate() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if line == other {
+                diagonal + 1
+            } else {
+                above.max(lengths[j])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+impl EditorState {
+    /// Reloads the text after it was modified externally.
+    ///
+    /// `base` is the text the editor was originally loaded with, `theirs`
+    /// the new external text. The edits made in the editor are merged with
+    /// the external changes, see [`merge`]. The reload can be undone as a
+    /// single step, and the cursor is kept in place.
+    ///
+    /// Returns the conflicting regions, if any.
+    pub fn reload(&mut self, base: &Lines, theirs: Lines) -> Vec<MergeConflict> {
+        let result = merge(base, &self.lines, &theirs);
+        let options = SetContentsOptions::default()
+            .keep_history(true)
+            .preserve_view(true);
+        self.track_edits(|state| state.set_contents(result.lines, options));
+        result.conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_non_overlapping_changes() {
+        let base = Lines::from("a\nb\nc\nd");
+        let ours = Lines::from("a\nB\nc\nd");
+        let theirs = Lines::from("a\nb\nc\nd\ne");
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert_eq!(result.lines, Lines::from("a\nB\nc\nd\ne"));
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_identical_changes() {
+        let base = Lines::from("a\nb");
+        let ours = Lines::from("x\nb");
+
+        let result = merge(&base, &ours, &ours);
+
+        assert_eq!(result.lines, Lines::from("x\nb"));
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let base = Lines::from("a\nb\nc");
+        let ours = Lines::from("a\nours\nc");
+        let theirs = Lines::from("a\ntheirs\nc");
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert_eq!(
+            result.lines,
+            Lines::from("a\n<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\nc")
+        );
+        assert_eq!(
+            result.conflicts,
+            vec![MergeConflict {
+                start: 1,
+                separator: 3,
+                end: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_match_lines() {
+        let match_strs = |base: &str, other: &str| {
+            let base: Vec<Vec<char>> = base.chars().map(|ch| vec![ch]).collect();
+            let other: Vec<Vec<char>> = other.chars().map(|ch| vec![ch]).collect();
+            let base: Vec<&Vec<char>> = base.iter().collect();
+            let other: Vec<&Vec<char>> = other.iter().collect();
+            match_lines(&base, &other)
+        };
+
+        assert_eq!(
+            match_strs("abcd", "axcde"),
+            vec![Some(0), None, Some(2), Some(3)]
+        );
+        assert_eq!(match_strs("", "ab"), vec![]);
+        assert_eq!(match_strs("ab", ""), vec![None, None]);
+
+        // The longest common subsequence of both has a length of 4
+        let matches = match_strs("abcabba", "cbabac");
+        let matched: Vec<usize> = matches.iter().flatten().copied().collect();
+        assert_eq!(matched.len(), 4);
+        assert!(matched.windows(2).all(|pair| pair[0] < pair[1]));
+        for (i, j) in matches.iter().enumerate() {
+            if let Some(j) = j {
+                assert_eq!("abcabba".chars().nth(i), "cbabac".chars().nth(*j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reload_can_be_undone() {
+        let base = Lines::from("a\nb");
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+
+        let _ = state.reload(&base, Lines::from("z\nb"));
+        assert_eq!(state.lines, Lines::from("z\nb\nc"));
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from("a\nb\nc"));
+    }
+}
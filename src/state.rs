@@ -1,16 +1,75 @@
 //! The editors state
+pub mod accessibility;
+mod block_insert;
+mod bookmarks;
+mod cleanup;
+pub mod command;
+mod composition;
+pub mod config;
+pub mod cursor_info;
+pub(crate) mod edit;
+pub mod exit;
+pub mod fields;
+pub mod file_reference;
+mod flash;
+mod generation;
+pub mod highlight;
+mod hooks;
+pub mod intent;
+pub mod locations;
+mod macros;
+mod metadata;
 pub mod mode;
-mod search;
+pub mod notification;
+pub(crate) mod occurrences;
+mod registers;
+mod repeat;
+pub(crate) mod search;
 pub mod selection;
+#[cfg(feature = "serde")]
+mod serde_index;
+pub mod session;
+mod storage;
+mod text_objects;
 mod undo;
 mod view;
+mod yank_ring;
 
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+use jagged::index::RowIndex;
+
+use self::block_insert::BlockInsert;
+use self::cleanup::Cleanup;
+use self::command::CommandHandler;
+use self::edit::EditTracker;
+use self::exit::ExitHook;
+use self::fields::Fields;
+use self::file_reference::OpenFileHook;
+use self::flash::Flash;
+use self::highlight::Highlights;
+use self::hooks::{ActionHooks, ModeHooks};
+use self::intent::Intents;
+use self::locations::Locations;
+use self::macros::Macros;
+use self::metadata::LineMetadata;
+use self::notification::Notifications;
+use self::registers::Registers;
+use self::repeat::LastChange;
 use self::search::SearchState;
-use self::view::ViewState;
+pub use self::search::{SearchOffset, SearchScope};
+use self::storage::Gap;
+pub use self::storage::LineStorage;
+use self::text_objects::TextObjects;
+pub use self::undo::HistoryPause;
+use self::view::{Offset, ViewState};
+use self::yank_ring::YankRing;
 use self::{mode::EditorMode, selection::Selection, undo::Stack};
-use crate::actions::Execute;
+use crate::actions::{Action, Execute, SelectEntireBuffer, SetContents};
 use crate::clipboard::{Clipboard, ClipboardTrait};
-use crate::helper::max_col;
+use crate::helper::{max_col, max_col_insert};
+use crate::validation::ValidationState;
 use crate::{Index2, Lines};
 
 /// Represents the state of an editor.
@@ -40,9 +99,134 @@ pub struct EditorState {
     /// Stack for redo operations.
     pub(crate) redo: Stack,
 
+    /// Number of active [`HistoryPause`] guards.
+    pub(crate) history_paused: usize,
+
+    /// Whether visual mode indentation keeps the selection.
+    pub(crate) keep_visual_selection: bool,
+
+    /// Whether `Tab` inserts spaces instead of a tab.
+    pub(crate) expand_tab: bool,
+
+    /// Whether a line break copies the indentation of the current line.
+    pub(crate) auto_indent: bool,
+
+    /// Custom text objects by their key.
+    pub(crate) text_objects: TextObjects,
+
     /// Clipboard for yank and paste operations.
     pub(crate) clip: Clipboard,
 
+    /// Named marks.
+    pub(crate) marks: HashMap<char, Index2>,
+
+    /// Bookmarked rows.
+    pub(crate) bookmarks: BTreeSet<usize>,
+
+    /// Positions the cursor jumped away from.
+    pub(crate) jumps: Vec<Index2>,
+
+    /// The text inserted during the current insert session.
+    pub(crate) inserted: String,
+
+    /// The text inserted during the last insert session.
+    pub(crate) last_inserted: String,
+
+    /// The block insert in progress, if any.
+    pub(crate) block_insert: Option<BlockInsert>,
+
+    /// The last change made in normal mode, repeated with `.`.
+    pub(crate) last_change: Option<LastChange>,
+
+    /// The recorded macros.
+    pub(crate) macros: Macros,
+
+    /// The text typed into the command line.
+    pub(crate) command_line: String,
+
+    /// The commands that can be run from the command line.
+    pub(crate) commands: CommandHandler,
+
+    /// The last yanked and deleted texts.
+    pub(crate) yank_ring: YankRing,
+
+    /// The cleanups applied before saving or when leaving insert mode.
+    pub(crate) cleanup: Cleanup,
+
+    /// The numbered, small delete and named registers.
+    pub(crate) registers: Registers,
+
+    /// Where the cursor may be placed beyond the end of a line, per mode.
+    pub(crate) virtual_edit: HashMap<EditorMode, VirtualEdit>,
+
+    /// Characters that are part of words, in addition to alphanumeric
+    /// characters.
+    pub(crate) word_chars: Vec<char>,
+
+    /// The maximum width of a line before typed text is wrapped.
+    pub(crate) text_width: Option<usize>,
+
+    /// How the characters of the row being typed into are stored.
+    pub(crate) line_storage: LineStorage,
+
+    /// The row split at the cursor while typing, if any.
+    pub(crate) gap: Option<Gap>,
+
+    /// The column that vertical motions try to keep, together with the
+    /// cursor position it was last applied to.
+    pub(crate) goal: Option<(usize, Index2)>,
+
+    /// The last visual selection and the cursor position within it.
+    pub(crate) last_visual: Option<(Selection, Index2)>,
+
+    /// The selections that were replaced by expanding the selection.
+    pub(crate) expansions: Vec<Selection>,
+
+    /// The in-progress IME composition.
+    pub(crate) composition: String,
+
+    /// Host-defined highlight groups.
+    pub(crate) highlights: Highlights,
+
+    /// Host-defined metadata attached to lines.
+    pub(crate) metadata: LineMetadata,
+
+    /// The editable fields, if the text is a template.
+    pub(crate) fields: Fields,
+
+    /// The validator and the errors it reported.
+    pub(crate) validation: ValidationState,
+
+    /// Host-defined location list.
+    pub(crate) locations: Locations,
+
+    /// The highlight of the last yanked text.
+    pub(crate) yank_flash: Flash,
+
+    /// The notification shown in the status line.
+    pub(crate) notifications: Notifications,
+
+    /// Callbacks that run when the mode changes.
+    pub(crate) mode_hooks: ModeHooks,
+
+    /// Callbacks that run around the execution of an action.
+    pub(crate) action_hooks: ActionHooks,
+
+    /// Callback that opens the file under the cursor.
+    pub(crate) open_file_hook: Option<OpenFileHook>,
+
+    /// Callback that closes the editor.
+    pub(crate) exit_hook: Option<ExitHook>,
+
+    /// Intents pushed by actions, drained by the app.
+    pub(crate) intents: Intents,
+
+    /// Increases whenever an action or an event changed the state.
+    pub(crate) generation: u64,
+
+    /// The text as of the last action, to find the edits of an action.
+    pub(crate) edit_tracker: EditTracker,
+
     /// Flag indicating a system editor was requested.
     #[cfg(feature = "system-editor")]
     pub(crate) system_edit_requested: bool,
@@ -68,6 +252,7 @@ impl EditorState {
     #[must_use]
     pub fn new(lines: Lines) -> EditorState {
         EditorState {
+            edit_tracker: EditTracker::default(),
             lines,
             cursor: Index2::new(0, 0),
             mode: EditorMode::Normal,
@@ -76,7 +261,47 @@ impl EditorState {
             search: SearchState::default(),
             undo: Stack::new(),
             redo: Stack::new(),
+            history_paused: 0,
+            keep_visual_selection: true,
+            expand_tab: false,
+            auto_indent: false,
+            text_objects: TextObjects::default(),
             clip: Clipboard::default(),
+            marks: HashMap::new(),
+            bookmarks: BTreeSet::new(),
+            jumps: Vec::new(),
+            inserted: String::new(),
+            last_inserted: String::new(),
+            block_insert: None,
+            last_change: None,
+            macros: Macros::default(),
+            command_line: String::new(),
+            commands: CommandHandler::default(),
+            yank_ring: YankRing::default(),
+            cleanup: Cleanup::default(),
+            registers: Registers::default(),
+            virtual_edit: HashMap::new(),
+            word_chars: Vec::new(),
+            text_width: None,
+            line_storage: LineStorage::default(),
+            gap: None,
+            goal: None,
+            last_visual: None,
+            expansions: Vec::new(),
+            composition: String::new(),
+            highlights: Highlights::default(),
+            metadata: LineMetadata::default(),
+            fields: Fields::default(),
+            validation: ValidationState::default(),
+            locations: Locations::default(),
+            yank_flash: Flash::default(),
+            notifications: Notifications::default(),
+            mode_hooks: ModeHooks::default(),
+            action_hooks: ActionHooks::default(),
+            open_file_hook: None,
+            exit_hook: None,
+            intents: Intents::default(),
+            generation: 0,
             #[cfg(feature = "system-editor")]
             system_edit_requested: false,
         }
@@ -93,7 +318,45 @@ impl EditorState {
     /// state.execute(DeleteLine(1))
     /// ```
     pub fn execute(&mut self, mut action: impl Execute) {
-        action.execute(self);
+        self.track_edits(|state| {
+            state.touch_all_rows();
+            action.execute(state);
+        });
+    }
+
+    /// Executes a sequence of actions as a single change, e.g. to insert a
+    /// template or to wrap the selection in markup. The actions are undone
+    /// at once and increase the [`EditorState::generation`] at most once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{Action, InsertChar, MoveToEndOfLine, SwitchMode};
+    /// use edtui::{EditorMode, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// state.apply_actions([
+    ///     Action::from(SwitchMode(EditorMode::Insert)),
+    ///     MoveToEndOfLine().into(),
+    ///     InsertChar('!').into(),
+    ///     InsertChar('!').into(),
+    /// ]);
+    /// assert_eq!(state.lines, Lines::from("Hello!!"));
+    ///
+    /// state.undo();
+    /// assert_eq!(state.lines, Lines::from("Hello"));
+    /// ```
+    pub fn apply_actions<T, A>(&mut self, actions: T)
+    where
+        T: IntoIterator<Item = A>,
+        A: Into<Action>,
+    {
+        self.track_edits(|state| {
+            let mut state = state.pause_history();
+            for action in actions {
+                state.run_action(action.into());
+            }
+        });
     }
 
     /// Set a custom clipboard.
@@ -101,17 +364,618 @@ impl EditorState {
         self.clip = Clipboard::new(clipboard);
     }
 
+    /// Returns the text that was inserted during the last insert session,
+    /// similar to the `.` register in vim.
+    #[must_use]
+    pub fn last_inserted(&self) -> &str {
+        &self.last_inserted
+    }
+
     /// Returns the current search pattern.
     #[must_use]
     pub fn search_pattern(&self) -> String {
         self.search.pattern.clone()
     }
 
+    /// Sets the search pattern and searches the text for it, e.g. to drive
+    /// the search from a search field of the host. Matches are found within
+    /// the current [`SearchScope`].
+    ///
+    /// The first match at or after the cursor is selected, but the cursor
+    /// does not move. Use [`SelectCurrentSearch`], [`FindNext`] and
+    /// [`FindPrevious`] to jump between the matches. The selected match is
+    /// highlighted in search mode.
+    ///
+    /// [`SelectCurrentSearch`]: crate::actions::SelectCurrentSearch
+    /// [`FindNext`]: crate::actions::FindNext
+    /// [`FindPrevious`]: crate::actions::FindPrevious
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::FindNext;
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("foo bar\nbar foo"));
+    /// state.set_search_pattern("bar");
+    /// assert_eq!(state.search_matches().count(), 2);
+    ///
+    /// state.execute(FindNext);
+    /// assert_eq!(state.cursor, Index2::new(1, 0));
+    /// ```
+    pub fn set_search_pattern(&mut self, pattern: &str) {
+        self.search.start(self.cursor);
+        self.search.bounds = self.search_bounds(&self.search.scope);
+        self.search.pattern = pattern.to_string();
+        self.search.trigger_search(&self.lines);
+        self.search.selected_index = None;
+        self.search.first();
+    }
+
+    /// Returns the text that replaces the search pattern, see
+    /// [`EditorState::set_search_replacement`].
+    #[must_use]
+    pub fn search_replacement(&self) -> &str {
+        &self.search.replacement
+    }
+
+    /// Sets the text that replaces the search pattern when
+    /// [`ReplaceNext::from_search`] or [`ReplaceAll::from_search`] is
+    /// executed, e.g. from a replace field of the host.
+    ///
+    /// [`ReplaceNext::from_search`]: crate::actions::ReplaceNext::from_search
+    /// [`ReplaceAll::from_search`]: crate::actions::ReplaceAll::from_search
+    pub fn set_search_replacement<S: Into<String>>(&mut self, replacement: S) {
+        self.search.replacement = replacement.into();
+    }
+
+    /// Returns the matches of the current search pattern, in the order of
+    /// their positions.
+    pub fn search_matches(&self) -> impl Iterator<Item = Selection> + '_ {
+        let matches = self.search.matches.iter().zip(&self.search.lengths);
+        matches.map(|(&start, &len)| {
+            Selection::new(
+                start,
+                Index2::new(start.row, start.col + len.saturating_sub(1)),
+            )
+        })
+    }
+
+    /// Returns the selected match of the current search pattern, if any.
+    #[must_use]
+    pub fn current_search_match(&self) -> Option<Selection> {
+        (&self.search).into()
+    }
+
+    /// Returns whether the last jump to a search match, e.g. with
+    /// [`FindNext`], wrapped around the end or the beginning of the text.
+    /// Without [`EditorState::set_wrap_scan`], it returns whether the jump
+    /// stopped at the end or the beginning instead.
+    ///
+    /// [`FindNext`]: crate::actions::FindNext
+    #[must_use]
+    pub fn search_wrapped(&self) -> bool {
+        self.search.wrapped
+    }
+
+    /// Sets whether searches wrap around the end of the text, similar to
+    /// `wrapscan` in vim. Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::FindNext;
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("foo\nfoo"));
+    /// state.set_wrap_scan(false);
+    /// state.set_search_pattern("foo");
+    ///
+    /// state.execute(FindNext);
+    /// state.execute(FindNext);
+    /// assert_eq!(state.cursor, Index2::new(1, 0));
+    /// assert!(state.search_wrapped());
+    /// ```
+    pub fn set_wrap_scan(&mut self, wrap_scan: bool) {
+        self.search.wrap_scan = wrap_scan;
+    }
+
+    /// Sets where the cursor lands relative to a search match when jumping
+    /// to it, e.g. at the end of the match like `/foo/e` in vim. An offset
+    /// typed after the pattern in search mode takes precedence until the
+    /// next search is started.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::FindNext;
+    /// use edtui::{EditorState, Index2, Lines, SearchOffset};
+    ///
+    /// let mut state = EditorState::new(Lines::from("foo bar\nbar foo"));
+    /// state.set_search_offset(SearchOffset::End(0));
+    /// state.set_search_pattern("bar");
+    ///
+    /// state.execute(FindNext);
+    /// assert_eq!(state.cursor, Index2::new(1, 2));
+    /// ```
+    pub fn set_search_offset(&mut self, offset: SearchOffset) {
+        self.search.offset = offset;
+    }
+
+    /// Sets whether search patterns are matched as regular expressions,
+    /// like `magic` in vim. Disabled by default. A single search can switch
+    /// with a prefix: `\v` matches the pattern as a regular expression and
+    /// `\V` literally. Applies from the next search on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("a1 b22 c"));
+    /// state.set_search_pattern("\\v\\d+");
+    /// assert_eq!(state.search_matches().count(), 2);
+    ///
+    /// state.set_search_regex(true);
+    /// state.set_search_pattern("\\V\\d+");
+    /// assert_eq!(state.search_matches().count(), 0);
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn set_search_regex(&mut self, regex: bool) {
+        self.search.regex = regex;
+    }
+
+    /// Returns whether the current search pattern is matched as a regular
+    /// expression, see [`EditorState::set_search_regex`].
+    #[must_use]
+    pub fn search_is_regex(&self) -> bool {
+        self.search.is_regex()
+    }
+
+    /// Returns where the cursor lands relative to a search match, see
+    /// [`EditorState::set_search_offset`].
+    #[must_use]
+    pub fn search_offset(&self) -> SearchOffset {
+        self.search.effective_offset()
+    }
+
+    /// Selects the entire text in visual mode, e.g. for a "select all"
+    /// button. The selection can then be copied or deleted with the
+    /// selection actions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::DeleteSelection;
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+    /// state.select_entire_buffer();
+    /// state.execute(DeleteSelection);
+    /// assert_eq!(state.lines.to_string(), "");
+    /// ```
+    pub fn select_entire_buffer(&mut self) {
+        self.execute(SelectEntireBuffer);
+    }
+
+    /// Sets whether [`IndentSelection`] and [`DedentSelection`] keep the
+    /// visual selection, so that repeated `>` and `<` shift the same lines.
+    /// Enabled by default. When disabled, the editor returns to normal mode
+    /// and the selection can be restored with `gv`.
+    ///
+    /// [`IndentSelection`]: crate::actions::IndentSelection
+    /// [`DedentSelection`]: crate::actions::DedentSelection
+    pub fn set_keep_visual_selection(&mut self, keep: bool) {
+        self.keep_visual_selection = keep;
+    }
+
+    /// Restricts searches to a part of the text, e.g. to the visual
+    /// selection. Applies from the next search on.
+    pub fn set_search_scope(&mut self, scope: SearchScope) {
+        self.search.scope = scope;
+    }
+
+    /// Returns the bounds of a search scope in the current text, or `None`
+    /// if the scope is the entire text. An empty selection scope or range
+    /// yields empty bounds.
+    pub(crate) fn search_bounds(&self, scope: &SearchScope) -> Option<Selection> {
+        // Bounds on a row that does not exist contain no position
+        let nowhere = || Selection::new(Index2::new(usize::MAX, 0), Index2::new(usize::MAX, 0));
+        match scope {
+            SearchScope::Buffer => None,
+            SearchScope::Rows(rows) if rows.is_empty() => Some(nowhere()),
+            SearchScope::Rows(rows) => Some(
+                Selection::new(Index2::new(rows.start, 0), Index2::new(rows.end - 1, 0))
+                    .line_mode(),
+            ),
+            SearchScope::Selection => Some(
+                self.selection
+                    .clone()
+                    .filter(|_| self.mode == EditorMode::Visual)
+                    .or_else(|| self.last_visual.clone().map(|(selection, _)| selection))
+                    .unwrap_or_else(nowhere),
+            ),
+        }
+    }
+
+    /// Replaces the entire text of the editor.
+    ///
+    /// Intended for flows such as reloading a file from disk. The
+    /// [`SetContentsOptions`] control whether the undo history is kept,
+    /// whether the cursor and viewport are preserved and whether the
+    /// callbacks run as if a [`SetContents`] action was executed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines, SetContentsOptions};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+    /// state.cursor = Index2::new(1, 3);
+    ///
+    /// let options = SetContentsOptions::default()
+    ///     .keep_history(true)
+    ///     .preserve_view(true);
+    /// state.set_contents(Lines::from("Hello\nEarth!"), options);
+    ///
+    /// assert_eq!(state.cursor, Index2::new(1, 3));
+    /// state.undo();
+    /// assert_eq!(state.lines, Lines::from("Hello\nWorld"));
+    /// ```
+    pub fn set_contents(&mut self, lines: Lines, options: SetContentsOptions) {
+        if !options.run_hooks {
+            self.replace_contents(lines, options);
+            return;
+        }
+        let old = self.mode;
+        let action = SetContents(lines, options).into();
+        self.with_action_hooks(action, |state, action| action.execute(state));
+        self.run_mode_hooks(old);
+    }
+
+    /// Replaces the entire text of the editor without running any callbacks.
+    pub(crate) fn replace_contents(&mut self, lines: Lines, options: SetContentsOptions) {
+        if options.keep_history {
+            self.capture();
+        } else {
+            self.undo.clear();
+            self.redo.clear();
+        }
+
+        self.replace_lines(lines);
+        self.insert_final_newline_on_load();
+        self.selection = None;
+        if self.mode == EditorMode::Visual {
+            self.mode = EditorMode::Normal;
+        }
+
+        if options.preserve_view {
+            self.cursor = self.clamp_index(self.cursor);
+            self.clamp_column();
+            self.view.viewport.y = self.view.viewport.y.min(self.lines.last_row_index());
+        } else {
+            self.cursor = Index2::new(0, 0);
+            self.view.viewport = Offset::default();
+        }
+
+        if !self.search.pattern.is_empty() {
+            self.search.trigger_search(&self.lines);
+        }
+        self.generation += 1;
+    }
+
+    /// Inserts `text` at column `col` of each row in `rows`, as a single
+    /// undo step. Rows that are shorter than `col` are padded with spaces,
+    /// rows beyond the end of the buffer are ignored. `text` should not
+    /// contain line breaks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("ab\na\nabc"));
+    /// state.insert_at_column(0..2, 2, "> ");
+    ///
+    /// assert_eq!(state.lines, Lines::from("ab> \na > \nabc"));
+    /// ```
+    pub fn insert_at_column(&mut self, rows: Range<usize>, col: usize, text: &str) {
+        self.capture();
+        self.touch_rows(rows.clone());
+        for row in rows.start..rows.end.min(self.lines.len()) {
+            if let Some(line) = self.lines.get_mut(RowIndex::new(row)) {
+                if line.len() < col {
+                    line.resize(col, ' ');
+                }
+                line.splice(col..col, text.chars());
+            }
+        }
+    }
+
+    /// Appends `text` to the end of each row in `rows`, as a single undo
+    /// step. Rows beyond the end of the buffer are ignored.
+    pub fn append_to_lines(&mut self, rows: Range<usize>, text: &str) {
+        self.capture();
+        self.touch_rows(rows.clone());
+        for row in rows.start..rows.end.min(self.lines.len()) {
+            if let Some(line) = self.lines.get_mut(RowIndex::new(row)) {
+                line.extend(text.chars());
+            }
+        }
+    }
+
+    /// Clamps an index to the bounds of the buffer.
+    pub(crate) fn clamp_index(&self, index: Index2) -> Index2 {
+        let row = index.row.min(self.lines.last_row_index());
+        let col = index.col.min(self.lines.len_col(row).unwrap_or_default());
+        Index2::new(row, col)
+    }
+
+    /// Returns the column that vertical motions try to keep. The goal column
+    /// is discarded as soon as the cursor is moved by any other means.
+    pub(crate) fn goal_column(&self) -> usize {
+        match self.goal {
+            Some((col, cursor)) if cursor == self.cursor => col,
+            _ => self.cursor.col,
+        }
+    }
+
+    /// Sets where the cursor may be placed beyond the end of a line in the
+    /// given mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorMode, EditorState, VirtualEdit};
+    ///
+    /// let mut state = EditorState::default();
+    /// state.set_virtual_edit(EditorMode::Normal, VirtualEdit::OnePastEnd);
+    /// ```
+    pub fn set_virtual_edit(&mut self, mode: EditorMode, virtual_edit: VirtualEdit) {
+        self.virtual_edit.insert(mode, virtual_edit);
+    }
+
+    /// Returns where the cursor may be placed beyond the end of a line in
+    /// the given mode.
+    #[must_use]
+    pub fn virtual_edit(&self, mode: EditorMode) -> VirtualEdit {
+        self.virtual_edit.get(&mode).copied().unwrap_or_default()
+    }
+
+    /// Sets the characters that are part of words, in addition to
+    /// alphanumeric characters, similar to `iskeyword` in vim. Word motions
+    /// and text objects, such as `w`, `b` and `iw`, do not stop at these
+    /// characters. By default, no additional characters are part of words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::MoveWordForward;
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// // Treat CSS properties such as `font-size` as a single word
+    /// let mut state = EditorState::new(Lines::from("font-size: 12px"));
+    /// state.set_word_chars("-");
+    ///
+    /// state.execute(MoveWordForward(1));
+    /// assert_eq!(state.cursor, Index2::new(0, 9));
+    /// ```
+    pub fn set_word_chars(&mut self, word_chars: &str) {
+        self.word_chars = word_chars.chars().collect();
+    }
+
+    /// Sets the maximum width of a line, similar to `textwidth` in vim.
+    /// When typing beyond it in insert mode, the line is broken at the
+    /// last whitespace. Pass `None` to disable wrapping (default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::EditorState;
+    ///
+    /// let mut state = EditorState::default();
+    /// state.set_text_width(Some(72));
+    /// ```
+    pub fn set_text_width(&mut self, text_width: Option<usize>) {
+        self.text_width = text_width;
+    }
+
+    /// Returns the maximum width of a line, see [`EditorState::set_text_width`].
+    #[must_use]
+    pub fn text_width(&self) -> Option<usize> {
+        self.text_width
+    }
+
+    /// Returns the maximum column of the cursor in the current row, taking
+    /// the [`VirtualEdit`] setting of the current mode into account.
+    pub(crate) fn max_cursor_col(&self) -> usize {
+        let max_col = max_col(&self.lines, &self.cursor, self.mode);
+        match self.virtual_edit(self.mode) {
+            VirtualEdit::None => max_col,
+            VirtualEdit::OnePastEnd => max_col_insert(&self.lines, &self.cursor),
+            VirtualEdit::All => usize::MAX,
+        }
+    }
+
     /// Clamps the column of the cursor if the cursor is out of bounds.
     /// In normal or visual mode, clamps on `col = len() - 1`, in insert
-    /// mode on `col = len()`.
+    /// mode on `col = len()`, unless virtual editing is enabled.
     pub(crate) fn clamp_column(&mut self) {
-        let max_col = max_col(&self.lines, &self.cursor, self.mode);
-        self.cursor.col = self.cursor.col.min(max_col);
+        self.cursor.col = self.cursor.col.min(self.max_cursor_col());
+    }
+}
+
+/// Where the cursor may be placed beyond the end of a line, see
+/// [`EditorState::set_virtual_edit`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VirtualEdit {
+    /// The cursor stays within the text (default).
+    #[default]
+    None,
+    /// The cursor may be placed one past the end of the line.
+    OnePastEnd,
+    /// The cursor may be placed anywhere. Text inserted beyond the end of
+    /// a line is padded with spaces.
+    All,
+}
+
+/// Options for [`EditorState::set_contents`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SetContentsOptions {
+    /// Whether the undo history is kept. If enabled, the replacement can
+    /// be undone as a single step.
+    pub keep_history: bool,
+    /// Whether the cursor and the viewport are kept (clamped to the new
+    /// text). Otherwise, they are reset to the start of the buffer.
+    pub preserve_view: bool,
+    /// Whether the callbacks registered with [`EditorState::before_action`],
+    /// [`EditorState::after_action`] and [`EditorState::on_mode_change`]
+    /// run. The action callbacks receive a [`SetContents`] action and can
+    /// reject it.
+    pub run_hooks: bool,
+}
+
+impl SetContentsOptions {
+    /// Sets whether the undo history is kept.
+    #[must_use]
+    pub fn keep_history(mut self, keep_history: bool) -> Self {
+        self.keep_history = keep_history;
+        self
+    }
+
+    /// Sets whether the cursor and the viewport are kept.
+    #[must_use]
+    pub fn preserve_view(mut self, preserve_view: bool) -> Self {
+        self.preserve_view = preserve_view;
+        self
+    }
+
+    /// Sets whether the action and mode-change callbacks run.
+    #[must_use]
+    pub fn run_hooks(mut self, run_hooks: bool) -> Self {
+        self.run_hooks = run_hooks;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_contents_resets_view_and_history() {
+        let mut state = EditorState::new(Lines::from("Hello World!\n\n123."));
+        state.cursor = Index2::new(2, 2);
+        state.capture();
+
+        state.set_contents(Lines::from("Hi"), SetContentsOptions::default());
+
+        assert_eq!(state.lines, Lines::from("Hi"));
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        state.undo();
+        assert_eq!(state.lines, Lines::from("Hi"));
+    }
+
+    #[test]
+    fn test_set_contents_preserves_view() {
+        let mut state = EditorState::new(Lines::from("Hello World!\n\n123."));
+        state.cursor = Index2::new(2, 3);
+
+        let options = SetContentsOptions::default().preserve_view(true);
+        state.set_contents(Lines::from("Hello\nWorld"), options);
+
+        assert_eq!(state.cursor, Index2::new(1, 3));
+    }
+
+    #[test]
+    fn test_set_contents_runs_hooks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut state = EditorState::new(Lines::from("Hello"));
+        state.mode = EditorMode::Visual;
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (after, mode) = (Rc::clone(&log), Rc::clone(&log));
+        state.after_action(move |_, action| after.borrow_mut().push(action.name().to_string()));
+        state.on_mode_change(move |_, _, new| mode.borrow_mut().push(new.name()));
+
+        state.set_contents(Lines::from("Hi"), SetContentsOptions::default());
+        assert_eq!(state.lines, Lines::from("Hi"));
+        assert!(log.borrow().is_empty());
+
+        state.mode = EditorMode::Visual;
+        let options = SetContentsOptions::default().run_hooks(true);
+        state.set_contents(Lines::from("Hey"), options);
+        assert_eq!(state.lines, Lines::from("Hey"));
+        assert_eq!(*log.borrow(), vec!["SetContents", "Normal"]);
+
+        state.before_action(|_, action| !matches!(action, Action::SetContents(_)));
+        state.set_contents(Lines::from("Bye"), options);
+        assert_eq!(state.lines, Lines::from("Hey"));
+    }
+
+    #[test]
+    fn test_apply_actions() {
+        use crate::actions::{AppendNewline, InsertChar, SwitchMode};
+
+        let mut state = EditorState::new(Lines::from("Hello"));
+        state.apply_actions([
+            Action::from(AppendNewline(2)),
+            InsertChar('a').into(),
+            SwitchMode(EditorMode::Normal).into(),
+        ]);
+        assert_eq!(state.lines, Lines::from("Hello\n\na"));
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(state.generation(), 1);
+
+        assert!(state.undo());
+        assert_eq!(state.lines, Lines::from("Hello"));
+        assert!(!state.undo());
+    }
+
+    #[test]
+    fn test_insert_at_column() {
+        let mut state = EditorState::new(Lines::from("Hello World!\n\n123."));
+
+        state.insert_at_column(1..5, 1, "#");
+        assert_eq!(state.lines, Lines::from("Hello World!\n #\n1#23."));
+
+        state.append_to_lines(0..2, ";");
+        assert_eq!(state.lines, Lines::from("Hello World!;\n #;\n1#23."));
+
+        state.undo();
+        state.undo();
+        assert_eq!(state.lines, Lines::from("Hello World!\n\n123."));
+    }
+
+    #[test]
+    fn test_set_search_pattern() {
+        let mut state = EditorState::new(Lines::from("foo bar\nbar foo\nbar"));
+        state.cursor = Index2::new(0, 5);
+
+        state.set_search_pattern("bar");
+        assert_eq!(state.search_pattern(), "bar");
+        assert_eq!(state.cursor, Index2::new(0, 5));
+        let matches: Vec<_> = state.search_matches().collect();
+        assert_eq!(
+            matches,
+            vec![
+                Selection::new(Index2::new(0, 4), Index2::new(0, 6)),
+                Selection::new(Index2::new(1, 0), Index2::new(1, 2)),
+                Selection::new(Index2::new(2, 0), Index2::new(2, 2)),
+            ]
+        );
+        assert_eq!(
+            state.current_search_match(),
+            Some(Selection::new(Index2::new(1, 0), Index2::new(1, 2)))
+        );
+
+        state.set_search_scope(SearchScope::Rows(1..2));
+        state.set_search_pattern("bar");
+        assert_eq!(state.search_matches().count(), 1);
+
+        state.set_search_pattern("baz");
+        assert_eq!(state.search_matches().count(), 0);
+        assert_eq!(state.current_search_match(), None);
     }
 }
@@ -0,0 +1,137 @@
+//! A plain description of what the editor shows, for screen readers and logs.
+use std::fmt;
+use std::ops::Range;
+
+use jagged::index::RowIndex;
+
+use crate::{EditorMode, EditorState, Index2};
+
+/// The content of the editor as of the last render, see
+/// [`EditorState::describe_screen`]. Formatting the description prints it
+/// as text that a screen reader can read out, with 1-based positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenDescription {
+    /// The mode of the editor.
+    pub mode: EditorMode,
+    /// The rows of the text that are visible.
+    pub rows: Range<usize>,
+    /// The number of rows of the text.
+    pub total_rows: usize,
+    /// The text of the visible rows.
+    pub lines: Vec<String>,
+    /// The position of the cursor.
+    pub cursor: Index2,
+    /// The start and the end of the selection, if any.
+    pub selection: Option<(Index2, Index2)>,
+    /// Whether whole lines are selected.
+    pub line_selection: bool,
+}
+
+impl fmt::Display for ScreenDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} mode, line {}, column {}",
+            self.mode.name(),
+            self.cursor.row + 1,
+            self.cursor.col + 1
+        )?;
+        match self.selection {
+            Some((start, end)) if self.line_selection => {
+                writeln!(f, "Selected lines {} to {}", start.row + 1, end.row + 1)?;
+            }
+            Some((start, end)) => writeln!(
+                f,
+                "Selected from line {}, column {} to line {}, column {}",
+                start.row + 1,
+                start.col + 1,
+                end.row + 1,
+                end.col + 1
+            )?,
+            None => {}
+        }
+        write!(
+            f,
+            "Showing lines {} to {} of {}",
+            self.rows.start + 1,
+            self.rows.end,
+            self.total_rows
+        )?;
+        for (row, line) in self.rows.clone().zip(&self.lines) {
+            write!(f, "\n{}: {line}", row + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl EditorState {
+    /// Describes what the editor showed in the last render: the visible
+    /// lines, the cursor, the selection and the mode, e.g. to pass it to a
+    /// screen reader or to log it. The visible lines are updated whenever
+    /// the editor is rendered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, EditorTheme, EditorView, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("one\ntwo\nthree"));
+    /// state.cursor = Index2::new(1, 2);
+    /// let _ = EditorView::new(&mut state)
+    ///     .theme(EditorTheme::default().hide_status_line())
+    ///     .snapshot(10, 2);
+    ///
+    /// assert_eq!(
+    ///     state.describe_screen().to_string(),
+    ///     "Normal mode, line 2, column 3\nShowing lines 1 to 2 of 3\n1: one\n2: two",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn describe_screen(&self) -> ScreenDescription {
+        let start = self.view.rendered_y.min(self.lines.len());
+        let end = (start + self.view.num_rows).min(self.lines.len());
+        let lines = (start..end)
+            .filter_map(|row| self.lines.get(RowIndex::new(row)))
+            .map(|line| line.iter().collect())
+            .collect();
+        ScreenDescription {
+            mode: self.mode,
+            rows: start..end,
+            total_rows: self.lines.len(),
+            lines,
+            cursor: self.cursor,
+            selection: self
+                .selection
+                .as_ref()
+                .map(|selection| (selection.start(), selection.end())),
+            line_selection: self
+                .selection
+                .as_ref()
+                .is_some_and(|selection| selection.line_mode),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::{MoveDown, SelectLine};
+    use crate::{EditorState, EditorView, Lines};
+
+    #[test]
+    fn test_describe_screen() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd\ne"));
+        let _ = EditorView::new(&mut state).snapshot(10, 3);
+        assert_eq!(state.describe_screen().rows, 0..2);
+
+        state.execute(SelectLine);
+        state.execute(MoveDown(3));
+        let _ = EditorView::new(&mut state).snapshot(10, 3);
+        let description = state.describe_screen();
+        assert_eq!(description.rows, 2..4);
+        assert_eq!(description.lines, vec!["c", "d"]);
+        assert_eq!(
+            description.to_string(),
+            "Visual mode, line 4, column 1\nSelected lines 1 to 4\nShowing lines 3 to 4 of 5\n3: c\n4: d"
+        );
+    }
+}
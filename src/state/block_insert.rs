@@ -0,0 +1,101 @@
+//! Inserting the same text into several rows, like `I` and `A` in the
+//! visual block mode of vim.
+//!
+//! The text is typed into the first row as usual. When insert mode is left,
+//! the text that was added to the first row is inserted into the other rows.
+//! Nothing is repeated if a line break was typed.
+use std::ops::RangeInclusive;
+
+use jagged::index::RowIndex;
+
+use crate::actions::{Execute, SwitchMode};
+use crate::{EditorMode, EditorState, Index2};
+
+/// A block insert in progress, see [`EditorState::start_block_insert`].
+#[derive(Debug, Clone)]
+pub(crate) struct BlockInsert {
+    /// The rows the text is inserted into.
+    rows: RangeInclusive<usize>,
+    /// The column the text is inserted at, or `None` to append it to the
+    /// end of each row.
+    col: Option<usize>,
+    /// Whether rows that end before `col` are padded with spaces, or are
+    /// left unchanged.
+    pad: bool,
+    /// The column of the first row the text is typed at.
+    at: usize,
+    /// The length of the first row when the insert started.
+    len: usize,
+}
+
+impl EditorState {
+    /// Enters insert mode to insert text into each of `rows` at `col`, or at
+    /// the end of each row if `col` is `None`. The cursor is placed in the
+    /// first row.
+    pub(crate) fn start_block_insert(
+        &mut self,
+        rows: RangeInclusive<usize>,
+        col: Option<usize>,
+        pad: bool,
+    ) {
+        self.selection = None;
+        SwitchMode(EditorMode::Insert).execute(self);
+
+        let first = *rows.start();
+        self.touch_row(first);
+        let Some(line) = self.lines.get_mut(RowIndex::new(first)) else {
+            return;
+        };
+        let mut at = col.unwrap_or(line.len());
+        if pad && line.len() < at {
+            line.resize(at, ' ');
+        }
+        at = at.min(line.len());
+
+        self.cursor = Index2::new(first, at);
+        self.block_insert = Some(BlockInsert {
+            rows,
+            col,
+            pad,
+            at,
+            len: line.len(),
+        });
+    }
+
+    /// Inserts the text typed into the first row of a block insert into the
+    /// other rows. Called when insert mode is left.
+    pub(crate) fn finish_block_insert(&mut self) {
+        let Some(block) = self.block_insert.take() else {
+            return;
+        };
+        let first = *block.rows.start();
+        if self.cursor.row != first {
+            return;
+        }
+        let Some(line) = self.lines.get(RowIndex::new(first)) else {
+            return;
+        };
+        let added = line.len().saturating_sub(block.len);
+        let Some(text) = line.get(block.at..block.at + added).map(<[char]>::to_vec) else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        self.touch_rows(first..block.rows.end().saturating_add(1));
+        for row in block.rows.skip(1) {
+            let Some(line) = self.lines.get_mut(RowIndex::new(row)) else {
+                break;
+            };
+            let col = block.col.unwrap_or(line.len());
+            if line.len() < col {
+                if !block.pad {
+                    continue;
+                }
+                line.resize(col, ' ');
+            }
+            line.splice(col..col, text.iter().copied());
+        }
+    }
+}
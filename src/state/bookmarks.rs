@@ -0,0 +1,83 @@
+//! Bookmarked lines.
+//!
+//! Bookmarks mark whole lines, are shown in the sign column and move along
+//! with the text when lines are inserted or deleted above them. Unlike named
+//! marks, they are toggled and navigated in order of their rows.
+use std::collections::BTreeSet;
+
+use super::edit::Edit;
+use crate::{EditorState, Index2};
+
+/// Moves the bookmarked rows along with an edit. Bookmarks on deleted lines
+/// are removed.
+pub(crate) fn adjust(bookmarks: &mut BTreeSet<usize>, edit: &Edit) {
+    *bookmarks = std::mem::take(bookmarks)
+        .into_iter()
+        .filter(|&row| !edit.is_row_deleted(row))
+        .map(|row| edit.map(Index2::new(row, 0)).row)
+        .collect();
+}
+
+impl EditorState {
+    /// Adds a bookmark to a line, or removes it if the line is bookmarked.
+    pub fn toggle_bookmark(&mut self, row: usize) {
+        if !self.bookmarks.remove(&row) && row < self.lines.len() {
+            self.bookmarks.insert(row);
+        }
+    }
+
+    /// Returns the bookmarked rows in ascending order.
+    pub fn bookmarks(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bookmarks.iter().copied()
+    }
+
+    /// Removes all bookmarks.
+    pub fn clear_bookmarks(&mut self) {
+        self.bookmarks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, InsertNewline, NextBookmark, PrevBookmark, ToggleBookmark};
+    use crate::{EditorMode, Lines};
+
+    #[test]
+    fn test_navigate_bookmarks() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+        state.toggle_bookmark(1);
+        state.toggle_bookmark(3);
+
+        state.execute(NextBookmark);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        state.execute(NextBookmark);
+        assert_eq!(state.cursor, Index2::new(3, 0));
+
+        state.execute(NextBookmark);
+        assert_eq!(state.cursor, Index2::new(1, 0));
+
+        state.execute(PrevBookmark);
+        assert_eq!(state.cursor, Index2::new(3, 0));
+
+        state.execute(ToggleBookmark);
+        assert_eq!(state.bookmarks().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_bookmarks_move_with_edits() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+        state.toggle_bookmark(1);
+        state.toggle_bookmark(3);
+
+        state.mode = EditorMode::Insert;
+        state.execute(InsertNewline(1));
+        assert_eq!(state.bookmarks().collect::<Vec<_>>(), vec![2, 4]);
+
+        state.mode = EditorMode::Normal;
+        state.cursor = Index2::new(2, 0);
+        state.execute(DeleteLine(1));
+        assert_eq!(state.bookmarks().collect::<Vec<_>>(), vec![3]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Cleanup of the text before it is saved, such as removing trailing
+//! whitespace.
+use jagged::index::RowIndex;
+
+use crate::{EditorState, Lines};
+
+/// The cleanups applied to the text, see [`EditorState::prepare_for_save`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Cleanup {
+    pub(crate) trim_trailing_whitespace: bool,
+    pub(crate) insert_final_newline: bool,
+    pub(crate) trim_on_leave_insert: bool,
+}
+
+impl EditorState {
+    /// Prepares the text to be saved: removes trailing whitespace and adds
+    /// a final newline, if enabled with
+    /// [`EditorConfig::trim_trailing_whitespace`] and
+    /// [`EditorConfig::insert_final_newline`]. The cleanup is a single undo
+    /// step and increases the [`EditorState::generation`]. Returns whether
+    /// the text changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorConfig, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello  \nWorld"));
+    /// state.set_config(
+    ///     EditorConfig::default()
+    ///         .trim_trailing_whitespace(true)
+    ///         .insert_final_newline(true),
+    /// );
+    ///
+    /// assert!(state.prepare_for_save());
+    /// assert_eq!(state.lines, Lines::from("Hello\nWorld\n"));
+    /// assert!(!state.prepare_for_save());
+    /// ```
+    ///
+    /// [`EditorConfig::trim_trailing_whitespace`]: crate::EditorConfig::trim_trailing_whitespace
+    /// [`EditorConfig::insert_final_newline`]: crate::EditorConfig::insert_final_newline
+    pub fn prepare_for_save(&mut self) -> bool {
+        let trim = self.cleanup.trim_trailing_whitespace && has_trailing_whitespace(&self.lines);
+        let newline = self.cleanup.insert_final_newline && lacks_final_newline(&self.lines);
+        if !trim && !newline {
+            return false;
+        }
+        self.track_edits(|state| {
+            state.capture();
+            if trim {
+                state.trim_trailing_whitespace();
+            }
+            if newline {
+                state.touch_rows(state.lines.len()..state.lines.len());
+                state.lines.push(Vec::new());
+            }
+            state.clamp_column();
+        });
+        true
+    }
+
+    /// Returns whether the text ends with a newline. An empty text does not.
+    #[must_use]
+    pub fn has_final_newline(&self) -> bool {
+        self.lines.len() > 1
+            && self
+                .lines
+                .get(RowIndex::new(self.lines.last_row_index()))
+                .is_some_and(Vec::is_empty)
+    }
+
+    /// Returns the text, e.g. to write it to a file. If
+    /// [`EditorConfig::insert_final_newline`] is enabled, the text ends with
+    /// a newline unless it is empty, even if the buffer does not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorConfig, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// assert_eq!(state.contents(), "Hello");
+    ///
+    /// state.set_config(EditorConfig::default().insert_final_newline(true));
+    /// assert_eq!(state.contents(), "Hello\n");
+    /// assert!(!state.has_final_newline());
+    /// ```
+    ///
+    /// [`EditorConfig::insert_final_newline`]: crate::EditorConfig::insert_final_newline
+    #[must_use]
+    pub fn contents(&self) -> String {
+        let mut text = self.lines.to_string();
+        if self.cleanup.insert_final_newline && lacks_final_newline(&self.lines) {
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Adds a missing final newline to a text that was loaded, if enabled
+    /// with [`EditorConfig::insert_final_newline`].
+    ///
+    /// [`EditorConfig::insert_final_newline`]: crate::EditorConfig::insert_final_newline
+    pub(crate) fn insert_final_newline_on_load(&mut self) {
+        if self.cleanup.insert_final_newline && lacks_final_newline(&self.lines) {
+            self.touch_rows(self.lines.len()..self.lines.len());
+            self.lines.push(Vec::new());
+        }
+    }
+
+    /// Removes trailing whitespace after insert mode was left, if enabled
+    /// with [`EditorConfig::trim_on_leave_insert`].
+    ///
+    /// [`EditorConfig::trim_on_leave_insert`]: crate::EditorConfig::trim_on_leave_insert
+    pub(crate) fn trim_on_leave_insert(&mut self) {
+        if self.cleanup.trim_on_leave_insert && has_trailing_whitespace(&self.lines) {
+            self.capture();
+            self.trim_trailing_whitespace();
+            self.clamp_column();
+        }
+    }
+
+    /// Removes the whitespace at the end of each line.
+    fn trim_trailing_whitespace(&mut self) {
+        for row in 0..self.lines.len() {
+            let Some(line) = self.lines.get(RowIndex::new(row)) else {
+                continue;
+            };
+            let len = line
+                .iter()
+                .rposition(|ch| !ch.is_whitespace())
+                .map_or(0, |i| i + 1);
+            if len < line.len() {
+                self.touch_row(row);
+                if let Some(line) = self.lines.get_mut(RowIndex::new(row)) {
+                    line.truncate(len);
+                }
+            }
+        }
+    }
+}
+
+/// Whether the last line of a non-empty text is not empty.
+fn lacks_final_newline(lines: &Lines) -> bool {
+    lines
+        .get(RowIndex::new(lines.last_row_index()))
+        .is_some_and(|line| !line.is_empty())
+}
+
+fn has_trailing_whitespace(lines: &Lines) -> bool {
+    lines
+        .iter_row()
+        .any(|line| line.last().is_some_and(|ch| ch.is_whitespace()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{InsertChar, SwitchMode};
+    use crate::{EditorConfig, EditorMode, Index2, SetContentsOptions};
+
+    #[test]
+    fn test_trim_on_leave_insert() {
+        let mut state = EditorState::new(Lines::from("Hello\nWorld \t"));
+        state.set_config(EditorConfig::default().trim_on_leave_insert(true));
+        state.cursor = Index2::new(0, 4);
+
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.execute(InsertChar(' '));
+        assert_eq!(state.lines, Lines::from("Hell o\nWorld \t"));
+        state.execute(SwitchMode(EditorMode::Normal));
+        assert_eq!(state.lines, Lines::from("Hell o\nWorld"));
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from("Hell o\nWorld \t"));
+        assert!(!state.prepare_for_save());
+    }
+
+    #[test]
+    fn test_final_newline_policy() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        state.set_config(EditorConfig::default().insert_final_newline(true));
+        assert!(!state.has_final_newline());
+
+        state.set_contents(Lines::from("World"), SetContentsOptions::default());
+        assert_eq!(state.lines, Lines::from("World\n"));
+        assert!(state.has_final_newline());
+        assert_eq!(state.contents(), "World\n");
+
+        state.set_contents(Lines::default(), SetContentsOptions::default());
+        assert!(!state.has_final_newline());
+        assert_eq!(state.contents(), "");
+    }
+}
@@ -0,0 +1,370 @@
+//! The command line, entered with `:` like in vim.
+//!
+//! The command line runs a small set of ex commands:
+//!
+//! - `:{line}` jumps to a line, e.g. `:12`.
+//! - `:d` deletes the current line, or the lines of a range such as `:3,5d`.
+//! - `:s/pattern/replacement/` replaces the first match of each line, or
+//!   every match with the `g` flag. The current line is changed unless a
+//!   range is given, such as `:%s/…/…/g` for the entire text or
+//!   `:'<,'>s/…/…/g` for the last visual selection. An empty pattern
+//!   reuses the last search. Like in a search, a `\v` prefix matches the
+//!   pattern as a regular expression and `\V` literally.
+//! - `:noh` clears the search.
+//!
+//! Other commands, such as `:w` and `:q`, are looked up in the
+//! [`CommandHandler`] of the editor, which applications fill with their own
+//! commands. `:wq` runs the `w` and the `q` command unless it is registered
+//! itself.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::{cell::Cell, rc::Rc};
+//!
+//! use edtui::{EditorEventHandler, EditorState, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("foo\nfoo foo"));
+//! let saved = Rc::new(Cell::new(false));
+//! let sink = Rc::clone(&saved);
+//! state
+//!     .command_handler_mut()
+//!     .insert("w", move |_, _| sink.set(true));
+//!
+//! let mut event_handler = EditorEventHandler::default();
+//! event_handler
+//!     .feed_keys(":%s/foo/bar/g<CR>:w<CR>", &mut state)
+//!     .unwrap();
+//! assert_eq!(state.lines, Lines::from("bar\nbar bar"));
+//! assert!(saved.get());
+//! ```
+use std::collections::HashMap;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::actions::{DeleteLine, Execute, ReplaceAll};
+use crate::helper::skip_whitespace;
+use crate::state::search::split_magic;
+use crate::{EditorState, Index2, Notification, SearchScope};
+
+type Command = Rc<dyn Fn(&mut EditorState, &str)>;
+
+/// The commands that applications add to the command line, by name.
+///
+/// A command receives the state together with the text that follows its
+/// name, e.g. `notes.txt` for `:w notes.txt`. Commands added to the handler
+/// take precedence over the built-in ones.
+#[derive(Clone, Default)]
+pub struct CommandHandler {
+    commands: HashMap<String, Command>,
+}
+
+impl CommandHandler {
+    /// Creates a handler without commands.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a command. A command with the same name is replaced.
+    pub fn insert<S, F>(&mut self, name: S, command: F)
+    where
+        S: Into<String>,
+        F: Fn(&mut EditorState, &str) + 'static,
+    {
+        self.commands.insert(name.into(), Rc::new(command));
+    }
+
+    /// Removes the command with the given name.
+    pub fn remove(&mut self, name: &str) {
+        self.commands.remove(name);
+    }
+
+    /// Whether a command with the given name was added.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    fn get(&self, name: &str) -> Option<Command> {
+        self.commands.get(name).cloned()
+    }
+}
+
+/// The lines a command applies to, as written in front of its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineRange {
+    /// `%`, the entire text.
+    All,
+    /// `'<,'>`, the last visual selection.
+    Selection,
+    /// `3` or `3,5`, 1-based lines.
+    Lines(usize, usize),
+}
+
+/// Splits the range off the front of a command.
+fn split_range(command: &str) -> (Option<LineRange>, &str) {
+    if let Some(rest) = command.strip_prefix('%') {
+        return (Some(LineRange::All), rest);
+    }
+    if let Some(rest) = command.strip_prefix("'<,'>") {
+        return (Some(LineRange::Selection), rest);
+    }
+    let Some((first, rest)) = split_number(command) else {
+        return (None, command);
+    };
+    if let Some((last, rest)) = rest.strip_prefix(',').and_then(split_number) {
+        return (Some(LineRange::Lines(first, last)), rest);
+    }
+    (Some(LineRange::Lines(first, first)), rest)
+}
+
+fn split_number(text: &str) -> Option<(usize, &str)> {
+    let end = text
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(text.len());
+    Some((text[..end].parse().ok()?, &text[end..]))
+}
+
+/// Splits `/pattern/replacement/flags` at the delimiter that follows the
+/// command name. A delimiter is escaped with a backslash.
+fn split_substitution(args: &str) -> Option<(String, String, &str)> {
+    let mut chars = args.char_indices();
+    let (_, delimiter) = chars.next()?;
+    if delimiter.is_alphanumeric() || delimiter.is_whitespace() || delimiter == '\\' {
+        return None;
+    }
+    let mut parts = vec![String::new()];
+    let mut flags = "";
+    let mut escaped = false;
+    for (i, ch) in chars {
+        let part = parts.last_mut()?;
+        if escaped {
+            if ch != delimiter {
+                part.push('\\');
+            }
+            part.push(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == delimiter {
+            if parts.len() == 2 {
+                flags = &args[i + ch.len_utf8()..];
+                break;
+            }
+            parts.push(String::new());
+        } else {
+            part.push(ch);
+        }
+    }
+    let replacement = parts.get(1).cloned().unwrap_or_default();
+    Some((parts.swap_remove(0), replacement, flags))
+}
+
+impl EditorState {
+    /// Returns the text typed into the command line.
+    #[must_use]
+    pub fn command_line(&self) -> &str {
+        &self.command_line
+    }
+
+    /// Returns the commands that can be run from the command line, e.g. to
+    /// add a `w` command that saves the text.
+    pub fn command_handler_mut(&mut self) -> &mut CommandHandler {
+        &mut self.commands
+    }
+
+    /// Replaces the commands that can be run from the command line, e.g.
+    /// to share them between several editors.
+    pub fn set_command_handler(&mut self, handler: CommandHandler) {
+        self.commands = handler;
+    }
+
+    /// Runs a command as if it was typed into the command line, with or
+    /// without the leading `:`. Unknown commands show an error in the
+    /// status line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("one\ntwo\nthree\nfour"));
+    /// state.run_command(":2,3d");
+    /// assert_eq!(state.lines, Lines::from("one\nfour"));
+    ///
+    /// state.run_command("2");
+    /// assert_eq!(state.cursor, Index2::new(1, 0));
+    /// ```
+    pub fn run_command(&mut self, command: &str) {
+        let command = command.trim().trim_start_matches(':').trim_start();
+        let (range, rest) = split_range(command);
+        let name_len = rest
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (name, args) = rest.split_at(name_len);
+
+        if let Some(custom) = self.commands.get(name) {
+            self.touch_all_rows();
+            custom(self, args.trim());
+            return;
+        }
+        match name {
+            "" if args.trim().is_empty() => {
+                if let Some(range) = range {
+                    self.goto_line(range);
+                }
+            }
+            "d" | "delete" if args.trim().is_empty() => {
+                let rows = self.range_rows(range);
+                if !rows.is_empty() {
+                    self.cursor = Index2::new(rows.start, 0);
+                    DeleteLine(rows.len()).execute(self);
+                }
+            }
+            "s" | "substitute" => self.substitute(range, args),
+            "noh" | "nohlsearch" => self.search.clear(),
+            "wq" if self.commands.contains("w") && self.commands.contains("q") => {
+                self.run_command("w");
+                self.run_command("q");
+            }
+            _ => self.notify(Notification::error(format!(
+                "Not an editor command: {command}"
+            ))),
+        }
+    }
+
+    /// Returns the rows of a range, clamped to the text. Without a range,
+    /// the row of the cursor.
+    fn range_rows(&self, range: Option<LineRange>) -> Range<usize> {
+        let len = self.lines.len();
+        let rows = match range {
+            None => self.cursor.row..self.cursor.row + 1,
+            Some(LineRange::All) => 0..len,
+            Some(LineRange::Selection) => self
+                .search_bounds(&SearchScope::Selection)
+                .map_or(0..0, |bounds| {
+                    bounds.start().row..bounds.end().row.saturating_add(1)
+                }),
+            Some(LineRange::Lines(first, last)) => {
+                first.min(last).saturating_sub(1)..first.max(last)
+            }
+        };
+        rows.start.min(len)..rows.end.min(len)
+    }
+
+    fn goto_line(&mut self, range: LineRange) {
+        let row = match range {
+            LineRange::Lines(_, last) => last.saturating_sub(1),
+            _ => self.range_rows(Some(range)).end.saturating_sub(1),
+        };
+        self.push_jump(self.cursor);
+        self.cursor = Index2::new(row.min(self.lines.last_row_index()), 0);
+        skip_whitespace(&self.lines, &mut self.cursor);
+    }
+
+    fn substitute(&mut self, range: Option<LineRange>, args: &str) {
+        let Some((mut pattern, replacement, flags)) = split_substitution(args) else {
+            self.notify(Notification::error(
+                "Invalid substitution, e.g. :s/old/new/g",
+            ));
+            return;
+        };
+        if pattern.is_empty() {
+            pattern = self.search.pattern.clone();
+        }
+        let scope = match range {
+            Some(LineRange::All) => SearchScope::Buffer,
+            Some(LineRange::Selection) => SearchScope::Selection,
+            _ => SearchScope::Rows(self.range_rows(range)),
+        };
+        #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+        let (magic, body) = split_magic(&pattern);
+        let mut replace = ReplaceAll::new(body, replacement).scope(scope);
+        #[cfg(feature = "regex")]
+        if magic.unwrap_or(self.search.regex) {
+            replace = replace.regex(true);
+        }
+        if !flags.contains('g') {
+            replace = replace.first_per_line();
+        }
+
+        let old = self.lines.clone();
+        replace.execute(self);
+        if self.lines == old {
+            self.notify(Notification::error(format!("Pattern not found: {pattern}")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EditorEventHandler, EditorMode, Lines};
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range("%s/a/b/"), (Some(LineRange::All), "s/a/b/"));
+        assert_eq!(split_range("'<,'>d"), (Some(LineRange::Selection), "d"));
+        assert_eq!(split_range("3,5d"), (Some(LineRange::Lines(3, 5)), "d"));
+        assert_eq!(split_range("12"), (Some(LineRange::Lines(12, 12)), ""));
+        assert_eq!(split_range("noh"), (None, "noh"));
+    }
+
+    #[test]
+    fn test_split_substitution() {
+        assert_eq!(
+            split_substitution("/a\\/b/c/g"),
+            Some(("a/b".to_string(), "c".to_string(), "g"))
+        );
+        assert_eq!(
+            split_substitution("#a#"),
+            Some(("a".to_string(), String::new(), ""))
+        );
+        assert_eq!(split_substitution(" a b"), None);
+    }
+
+    #[test]
+    fn test_command_line() {
+        let mut state = EditorState::new(Lines::from("a a\na a\na a\na a"));
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys(":s/a/b/<CR>", &mut state).unwrap();
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(state.lines, Lines::from("b a\na a\na a\na a"));
+
+        handler.feed_keys("jVj:s/a/c/g<CR>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("b a\nc c\nc c\na a"));
+
+        handler.feed_keys(":x<BS><BS>", &mut state).unwrap();
+        assert_eq!(state.mode, EditorMode::Normal);
+
+        handler.feed_keys(":foo<CR>", &mut state).unwrap();
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error("Not an editor command: foo"))
+        );
+
+        handler.feed_keys(":4<CR>:d<CR>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("b a\nc c\nc c"));
+    }
+
+    #[test]
+    fn test_custom_command() {
+        let mut state = EditorState::new(Lines::from("a\nb"));
+        state
+            .command_handler_mut()
+            .insert("w", |state, args| state.notify(format!("written {args}")));
+
+        state.run_command("w notes.txt");
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::info("written notes.txt"))
+        );
+        state.run_command("wq");
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::error("Not an editor command: wq"))
+        );
+    }
+}
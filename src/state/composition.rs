@@ -0,0 +1,93 @@
+//! Input method editor (IME) composition.
+//!
+//! Input methods for e.g. CJK scripts compose text in several steps before
+//! it is committed. Hosts that receive composition events from their
+//! terminal layer pass the in-progress text to [`EditorState::set_composition`].
+//! It is displayed at the cursor, but not part of the text until it is
+//! committed with [`EditorState::commit_composition`].
+use crate::actions::InsertChar;
+use crate::EditorState;
+
+impl EditorState {
+    /// Sets the in-progress IME composition, which is displayed at the
+    /// cursor. An empty string clears the composition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorMode, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hi "));
+    /// state.mode = EditorMode::Insert;
+    /// state.cursor.col = 3;
+    ///
+    /// state.set_composition("にほ");
+    /// state.set_composition("日本");
+    /// state.commit_composition();
+    ///
+    /// assert_eq!(state.lines, Lines::from("Hi 日本"));
+    /// ```
+    pub fn set_composition<S: Into<String>>(&mut self, text: S) {
+        self.composition = text.into();
+    }
+
+    /// Returns the in-progress IME composition.
+    #[must_use]
+    pub fn composition(&self) -> &str {
+        &self.composition
+    }
+
+    /// Inserts the in-progress IME composition at the cursor as a single
+    /// undo step.
+    pub fn commit_composition(&mut self) {
+        let text = std::mem::take(&mut self.composition);
+        if text.is_empty() {
+            return;
+        }
+        self.capture();
+        for ch in text.chars() {
+            self.execute(InsertChar(ch));
+        }
+    }
+
+    /// Discards the in-progress IME composition.
+    pub fn cancel_composition(&mut self) {
+        self.composition.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EditorMode, Index2, Lines};
+
+    use super::*;
+
+    #[test]
+    fn test_commit_composition() {
+        let mut state = EditorState::new(Lines::from("ab"));
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(0, 1);
+
+        state.set_composition("漢字");
+        assert_eq!(state.lines, Lines::from("ab"));
+
+        state.commit_composition();
+        assert_eq!(state.lines, Lines::from("a漢字b"));
+        assert_eq!(state.cursor, Index2::new(0, 3));
+        assert_eq!(state.composition(), "");
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from("ab"));
+    }
+
+    #[test]
+    fn test_cancel_composition() {
+        let mut state = EditorState::new(Lines::from("ab"));
+
+        state.set_composition("漢字");
+        state.cancel_composition();
+        state.commit_composition();
+
+        assert_eq!(state.lines, Lines::from("ab"));
+    }
+}
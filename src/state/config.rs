@@ -0,0 +1,500 @@
+//! The options of an editor, consolidated in one configuration.
+//!
+//! An [`EditorConfig`] is built once, e.g. from a settings file, and applied
+//! with [`EditorState::set_config`]. Options can also be changed at runtime
+//! with [`EditorState::update_config`] or with vim-like `:set` commands,
+//! see [`EditorState::run_set_command`].
+use std::error::Error;
+use std::fmt;
+
+use crate::EditorState;
+
+/// The options of an editor, similar to the options set with `:set` in vim.
+///
+/// # Example
+///
+/// ```
+/// use edtui::{EditorConfig, EditorState, Lines};
+///
+/// let config = EditorConfig::default()
+///     .tab_width(4)
+///     .expand_tab(true)
+///     .auto_indent(true);
+///
+/// let mut state = EditorState::new(Lines::default());
+/// state.set_config(config.clone());
+/// assert_eq!(state.config(), config);
+///
+/// state.update_config(|config| config.ignore_case = true);
+/// assert!(state.config().ignore_case);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EditorConfig {
+    /// The number of spaces used to display a tab and to indent a line.
+    /// Defaults to 2.
+    pub tab_width: usize,
+    /// Whether lines longer than the viewport are wrapped. Enabled by
+    /// default.
+    pub wrap: bool,
+    /// Whether `Tab` inserts spaces up to the next multiple of the tab
+    /// width instead of a tab, similar to `expandtab` in vim.
+    pub expand_tab: bool,
+    /// Whether a line break copies the indentation of the current line to
+    /// the new line, similar to `autoindent` in vim.
+    pub auto_indent: bool,
+    /// The minimum number of rows kept above and below the cursor when
+    /// scrolling, similar to `scrolloff` in vim.
+    pub scroll_off: usize,
+    /// Whether searches wrap around the end of the text. Enabled by default.
+    pub wrap_scan: bool,
+    /// Whether searches ignore the case of letters.
+    pub ignore_case: bool,
+    /// Characters that are part of words, in addition to alphanumeric
+    /// characters.
+    pub word_chars: String,
+    /// The maximum width of a line before typed text is wrapped.
+    pub text_width: Option<usize>,
+    /// Whether visual mode indentation keeps the selection. Enabled by
+    /// default.
+    pub keep_visual_selection: bool,
+    /// The maximum number of changes kept in the undo history. Defaults to
+    /// 100.
+    pub undo_limit: usize,
+    /// The maximum estimated memory in bytes used by the undo history.
+    pub undo_memory_limit: Option<usize>,
+    /// Whether [`EditorState::prepare_for_save`] removes trailing
+    /// whitespace.
+    pub trim_trailing_whitespace: bool,
+    /// Whether the text must end with a newline, similar to `fixendofline`
+    /// in vim. A missing newline is added by [`EditorState::set_contents`]
+    /// and [`EditorState::prepare_for_save`], and [`EditorState::contents`]
+    /// always ends with one.
+    pub insert_final_newline: bool,
+    /// Whether trailing whitespace is removed when leaving insert mode.
+    pub trim_on_leave_insert: bool,
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self {
+            tab_width: 2,
+            wrap: true,
+            expand_tab: false,
+            auto_indent: false,
+            scroll_off: 0,
+            wrap_scan: true,
+            ignore_case: false,
+            word_chars: String::new(),
+            text_width: None,
+            keep_visual_selection: true,
+            undo_limit: 100,
+            undo_memory_limit: None,
+            trim_trailing_whitespace: false,
+            insert_final_newline: false,
+            trim_on_leave_insert: false,
+        }
+    }
+}
+
+impl EditorConfig {
+    /// Sets the number of spaces used to display a tab and to indent a line.
+    #[must_use]
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Sets whether lines longer than the viewport are wrapped.
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets whether `Tab` inserts spaces instead of a tab.
+    #[must_use]
+    pub fn expand_tab(mut self, expand_tab: bool) -> Self {
+        self.expand_tab = expand_tab;
+        self
+    }
+
+    /// Sets whether a line break copies the indentation of the current line.
+    #[must_use]
+    pub fn auto_indent(mut self, auto_indent: bool) -> Self {
+        self.auto_indent = auto_indent;
+        self
+    }
+
+    /// Sets the minimum number of rows kept above and below the cursor.
+    #[must_use]
+    pub fn scroll_off(mut self, scroll_off: usize) -> Self {
+        self.scroll_off = scroll_off;
+        self
+    }
+
+    /// Sets whether searches wrap around the end of the text.
+    #[must_use]
+    pub fn wrap_scan(mut self, wrap_scan: bool) -> Self {
+        self.wrap_scan = wrap_scan;
+        self
+    }
+
+    /// Sets whether searches ignore the case of letters.
+    #[must_use]
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Sets the characters that are part of words, in addition to
+    /// alphanumeric characters.
+    #[must_use]
+    pub fn word_chars(mut self, word_chars: &str) -> Self {
+        self.word_chars = word_chars.to_string();
+        self
+    }
+
+    /// Sets the maximum width of a line before typed text is wrapped.
+    #[must_use]
+    pub fn text_width(mut self, text_width: Option<usize>) -> Self {
+        self.text_width = text_width;
+        self
+    }
+
+    /// Sets whether visual mode indentation keeps the selection.
+    #[must_use]
+    pub fn keep_visual_selection(mut self, keep: bool) -> Self {
+        self.keep_visual_selection = keep;
+        self
+    }
+
+    /// Sets the maximum number of changes kept in the undo history.
+    #[must_use]
+    pub fn undo_limit(mut self, max_entries: usize) -> Self {
+        self.undo_limit = max_entries;
+        self
+    }
+
+    /// Sets the maximum estimated memory in bytes used by the undo history.
+    #[must_use]
+    pub fn undo_memory_limit(mut self, max_bytes: Option<usize>) -> Self {
+        self.undo_memory_limit = max_bytes;
+        self
+    }
+
+    /// Sets whether [`EditorState::prepare_for_save`] removes trailing
+    /// whitespace.
+    #[must_use]
+    pub fn trim_trailing_whitespace(mut self, trim: bool) -> Self {
+        self.trim_trailing_whitespace = trim;
+        self
+    }
+
+    /// Sets whether the text must end with a newline.
+    #[must_use]
+    pub fn insert_final_newline(mut self, insert: bool) -> Self {
+        self.insert_final_newline = insert;
+        self
+    }
+
+    /// Sets whether trailing whitespace is removed when leaving insert mode.
+    #[must_use]
+    pub fn trim_on_leave_insert(mut self, trim: bool) -> Self {
+        self.trim_on_leave_insert = trim;
+        self
+    }
+}
+
+impl EditorState {
+    /// Returns the current options of the editor.
+    #[must_use]
+    pub fn config(&self) -> EditorConfig {
+        EditorConfig {
+            tab_width: self.view.tab_width,
+            wrap: self.view.wrap,
+            expand_tab: self.expand_tab,
+            auto_indent: self.auto_indent,
+            scroll_off: self.view.scroll_off,
+            wrap_scan: self.search.wrap_scan,
+            ignore_case: self.search.ignore_case,
+            word_chars: self.word_chars.iter().collect(),
+            text_width: self.text_width,
+            keep_visual_selection: self.keep_visual_selection,
+            undo_limit: self.undo.max_size,
+            undo_memory_limit: self.undo.max_bytes,
+            trim_trailing_whitespace: self.cleanup.trim_trailing_whitespace,
+            insert_final_newline: self.cleanup.insert_final_newline,
+            trim_on_leave_insert: self.cleanup.trim_on_leave_insert,
+        }
+    }
+
+    /// Applies the options of an [`EditorConfig`]. The options apply from
+    /// the next action on, a running search is not repeated.
+    pub fn set_config(&mut self, config: EditorConfig) {
+        self.view.tab_width = config.tab_width;
+        self.view.wrap = config.wrap;
+        self.expand_tab = config.expand_tab;
+        self.auto_indent = config.auto_indent;
+        self.view.scroll_off = config.scroll_off;
+        self.search.wrap_scan = config.wrap_scan;
+        self.search.ignore_case = config.ignore_case;
+        self.word_chars = config.word_chars.chars().collect();
+        self.text_width = config.text_width;
+        self.keep_visual_selection = config.keep_visual_selection;
+        self.undo
+            .set_limits(config.undo_limit, config.undo_memory_limit);
+        self.redo
+            .set_limits(config.undo_limit, config.undo_memory_limit);
+        self.cleanup.trim_trailing_whitespace = config.trim_trailing_whitespace;
+        self.cleanup.insert_final_newline = config.insert_final_newline;
+        self.cleanup.trim_on_leave_insert = config.trim_on_leave_insert;
+    }
+
+    /// Changes some options of the editor at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::default());
+    /// state.update_config(|config| config.tab_width = 8);
+    /// assert_eq!(state.config().tab_width, 8);
+    /// ```
+    pub fn update_config<F: FnOnce(&mut EditorConfig)>(&mut self, update: F) {
+        let mut config = self.config();
+        update(&mut config);
+        self.set_config(config);
+    }
+}
+
+/// An error of a `:set` command, see [`EditorConfig::apply_set_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The command does not start with `set`.
+    NotASetCommand(String),
+    /// The option does not exist.
+    UnknownOption(String),
+    /// The value is not valid for the option, e.g. `tabstop=x`.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotASetCommand(command) => write!(f, "Not a set command: {command}"),
+            Self::UnknownOption(option) => write!(f, "Unknown option: {option}"),
+            Self::InvalidArgument(argument) => write!(f, "Invalid argument: {argument}"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl EditorConfig {
+    /// Applies a vim-like `:set` command, such as `:set nowrap`,
+    /// `:set tabstop=4` or `set ignorecase noexpandtab`. The leading colon is
+    /// optional.
+    ///
+    /// Boolean options are enabled with `name`, disabled with `noname` and
+    /// toggled with `invname` or `name!`. Number and string options are set
+    /// with `name=value`, a `textwidth` of 0 disables wrapping of typed
+    /// text. Either all options of the command are applied or, on an error,
+    /// none.
+    ///
+    /// The options are `tabstop` (`ts`, also `shiftwidth`/`sw`), `wrap`,
+    /// `expandtab` (`et`), `autoindent` (`ai`), `scrolloff` (`so`),
+    /// `wrapscan` (`ws`), `ignorecase` (`ic`), `wordchars`, `textwidth`
+    /// (`tw`), `undolevels` (`ul`) and `fixendofline` (`fixeol`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command is not a `:set` command, an option
+    /// is unknown or a value is invalid.
+    pub fn apply_set_command(&mut self, command: &str) -> Result<(), ConfigError> {
+        let command = command.trim();
+        let mut args = command.trim_start_matches(':').split_whitespace();
+        if !matches!(args.next(), Some("set" | "se")) {
+            return Err(ConfigError::NotASetCommand(command.to_string()));
+        }
+
+        let mut config = self.clone();
+        for arg in args {
+            config.apply_set_argument(arg)?;
+        }
+        *self = config;
+        Ok(())
+    }
+
+    fn apply_set_argument(&mut self, arg: &str) -> Result<(), ConfigError> {
+        let invalid = || ConfigError::InvalidArgument(arg.to_string());
+
+        if let Some((name, value)) = arg.split_once('=') {
+            let number = || value.parse::<usize>().map_err(|_| invalid());
+            match name {
+                "tabstop" | "ts" | "shiftwidth" | "sw" => self.tab_width = number()?,
+                "scrolloff" | "so" => self.scroll_off = number()?,
+                "textwidth" | "tw" => self.text_width = Some(number()?).filter(|&w| w > 0),
+                "undolevels" | "ul" => self.undo_limit = number()?,
+                "wordchars" => self.word_chars = value.to_string(),
+                _ if self.flag(name).is_some() => return Err(invalid()),
+                _ => return Err(ConfigError::UnknownOption(name.to_string())),
+            }
+            return Ok(());
+        }
+
+        let (name, value): (&str, fn(bool) -> bool) = if let Some(name) = arg.strip_suffix('!') {
+            (name, |value| !value)
+        } else if let Some(name) = arg.strip_prefix("inv") {
+            (name, |value| !value)
+        } else if let Some(name) = arg
+            .strip_prefix("no")
+            .filter(|name| self.flag(name).is_some())
+        {
+            (name, |_| false)
+        } else {
+            (arg, |_| true)
+        };
+        match self.flag(name) {
+            Some(flag) => *flag = value(*flag),
+            None if is_value_option(name) => return Err(invalid()),
+            None => return Err(ConfigError::UnknownOption(name.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Returns the boolean option with the given name.
+    fn flag(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "wrap" => Some(&mut self.wrap),
+            "expandtab" | "et" => Some(&mut self.expand_tab),
+            "autoindent" | "ai" => Some(&mut self.auto_indent),
+            "wrapscan" | "ws" => Some(&mut self.wrap_scan),
+            "ignorecase" | "ic" => Some(&mut self.ignore_case),
+            "fixendofline" | "fixeol" => Some(&mut self.insert_final_newline),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the option is set with `name=value`.
+fn is_value_option(name: &str) -> bool {
+    matches!(
+        name,
+        "tabstop"
+            | "ts"
+            | "shiftwidth"
+            | "sw"
+            | "scrolloff"
+            | "so"
+            | "textwidth"
+            | "tw"
+            | "undolevels"
+            | "ul"
+            | "wordchars"
+    )
+}
+
+impl EditorState {
+    /// Applies a vim-like `:set` command to the options of the editor, see
+    /// [`EditorConfig::apply_set_command`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command is not a `:set` command, an option
+    /// is unknown or a value is invalid. The options are not changed then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::default());
+    /// state.run_set_command(":set nowrap tabstop=4 ic").unwrap();
+    ///
+    /// let config = state.config();
+    /// assert!(!config.wrap);
+    /// assert_eq!(config.tab_width, 4);
+    /// assert!(config.ignore_case);
+    ///
+    /// assert!(state.run_set_command(":set foo").is_err());
+    /// ```
+    pub fn run_set_command(&mut self, command: &str) -> Result<(), ConfigError> {
+        let mut config = self.config();
+        config.apply_set_command(command)?;
+        self.set_config(config);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{FindNext, LineBreak, SwitchMode};
+    use crate::{EditorMode, Index2, Lines};
+
+    #[test]
+    fn test_config() {
+        let mut state = EditorState::new(Lines::from("  foo\nFOO"));
+        assert_eq!(state.config(), EditorConfig::default());
+
+        state.set_config(
+            EditorConfig::default()
+                .auto_indent(true)
+                .ignore_case(true)
+                .word_chars("-"),
+        );
+        assert_eq!(state.word_chars, vec!['-']);
+
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.cursor = Index2::new(0, 5);
+        state.execute(LineBreak(1));
+        assert_eq!(state.lines, Lines::from("  foo\n  \nFOO"));
+        assert_eq!(state.cursor, Index2::new(1, 2));
+
+        state.execute(SwitchMode(EditorMode::Normal));
+        state.cursor = Index2::new(0, 0);
+        state.set_search_pattern("foo");
+        state.execute(FindNext);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+    }
+
+    #[test]
+    fn test_set_command() {
+        let mut config = EditorConfig::default();
+        config
+            .apply_set_command(":set nowrap ts=4 et tw=72 wordchars=-_")
+            .unwrap();
+        assert!(!config.wrap);
+        assert_eq!(config.tab_width, 4);
+        assert!(config.expand_tab);
+        assert_eq!(config.text_width, Some(72));
+        assert_eq!(config.word_chars, "-_");
+
+        config.apply_set_command("set invwrap et! tw=0").unwrap();
+        assert!(config.wrap);
+        assert!(!config.expand_tab);
+        assert_eq!(config.text_width, None);
+
+        let unchanged = config.clone();
+        let err = |command| config.clone().apply_set_command(command).unwrap_err();
+        assert_eq!(err("set ts=x"), ConfigError::InvalidArgument("ts=x".into()));
+        assert_eq!(
+            err("set wrap=1"),
+            ConfigError::InvalidArgument("wrap=1".into())
+        );
+        assert_eq!(
+            err("set tabstop"),
+            ConfigError::InvalidArgument("tabstop".into())
+        );
+        assert_eq!(err("set nofoo"), ConfigError::UnknownOption("nofoo".into()));
+        assert_eq!(err("nowrap"), ConfigError::NotASetCommand("nowrap".into()));
+        assert_eq!(
+            config.apply_set_command("set nowrap foo"),
+            Err(ConfigError::UnknownOption("foo".into()))
+        );
+        assert_eq!(config, unchanged);
+    }
+}
@@ -0,0 +1,104 @@
+//! Information about the cursor position, similar to `g Ctrl+g` in vim.
+use std::fmt;
+
+use jagged::index::RowIndex;
+
+use crate::EditorState;
+
+/// The position of the cursor within the text, see
+/// [`EditorState::cursor_info`]. Lines, columns and bytes are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorInfo {
+    /// The line of the cursor.
+    pub line: usize,
+    /// The number of lines.
+    pub lines: usize,
+    /// The column of the cursor, counted in characters.
+    pub column: usize,
+    /// The number of characters in the line of the cursor.
+    pub columns: usize,
+    /// The byte offset of the cursor in the UTF-8 encoded text.
+    pub byte: usize,
+    /// The number of bytes of the text, including the line breaks.
+    pub bytes: usize,
+    /// How far the line of the cursor is through the text, in percent.
+    pub percent: usize,
+}
+
+impl fmt::Display for CursorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {} of {}; Col {} of {}; Byte {} of {}; {}%",
+            self.line, self.lines, self.column, self.columns, self.byte, self.bytes, self.percent
+        )
+    }
+}
+
+impl EditorState {
+    /// Returns the position of the cursor: its line, column and byte offset
+    /// together with the size of the text. [`ShowCursorInfo`] shows it in
+    /// the status line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello\nWörld\n!"));
+    /// state.cursor = Index2::new(1, 2);
+    ///
+    /// let info = state.cursor_info();
+    /// assert_eq!((info.line, info.lines), (2, 3));
+    /// assert_eq!((info.column, info.columns), (3, 5));
+    /// assert_eq!((info.byte, info.bytes), (10, 14));
+    /// assert_eq!(info.to_string(), "Line 2 of 3; Col 3 of 5; Byte 10 of 14; 66%");
+    /// ```
+    ///
+    /// [`ShowCursorInfo`]: crate::actions::ShowCursorInfo
+    #[must_use]
+    pub fn cursor_info(&self) -> CursorInfo {
+        let lines = self.lines.len().max(1);
+        let row = self.cursor.row.min(lines - 1);
+        let line_bytes = |line: &Vec<char>| line.iter().map(|ch| ch.len_utf8()).sum::<usize>();
+        let before: usize = (0..row)
+            .filter_map(|row| self.lines.get(RowIndex::new(row)))
+            .map(|line| line_bytes(line) + 1)
+            .sum();
+        let bytes = self.lines.iter_row().map(line_bytes).sum::<usize>() + lines - 1;
+        let line = self.lines.get(RowIndex::new(row));
+        let columns = line.map_or(0, Vec::len);
+        let column = self.cursor.col.min(columns.saturating_sub(1));
+        let in_line: usize = line.map_or(0, |line| {
+            line.iter().take(column).map(|ch| ch.len_utf8()).sum()
+        });
+        CursorInfo {
+            line: row + 1,
+            lines,
+            column: column + 1,
+            columns,
+            byte: before + in_line + 1,
+            bytes,
+            percent: (row + 1) * 100 / lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EditorEventHandler, EditorState, Lines, Notification};
+
+    #[test]
+    fn test_show_cursor_info() {
+        let mut state = EditorState::new(Lines::from("first\nsecond\nthird\nfourth"));
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("jlg<C-g>", &mut state).unwrap();
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::info(
+                "Line 2 of 4; Col 2 of 6; Byte 8 of 25; 50%"
+            ))
+        );
+    }
+}
@@ -0,0 +1,544 @@
+//! Keeping positions in place while the text is edited.
+//!
+//! Highlights, line metadata, fields, validation errors and the last visual
+//! selection refer to positions in the text. When the text changes, these
+//! positions are moved along with the edit.
+//!
+//! Code that edits the text touches the rows before it changes them, which
+//! keeps a copy of these rows as they were. After an action, only the
+//! touched rows are compared to find the edit, so that editing stays cheap
+//! in large buffers and actions that edit nothing compare nothing at all.
+//! Custom actions and callbacks of the app may edit any row, so the whole
+//! text is touched before they run.
+use std::ops::Range;
+
+use super::generation::Fingerprint;
+use super::{bookmarks, mode::EditorMode, notification::Notification};
+use crate::{EditorState, Index2, Lines};
+
+/// An edit that replaced a single range of characters of an old text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Edit {
+    /// The start of the replaced range, the same in the old and the new text.
+    pub(crate) start: Index2,
+    /// The end of the replaced range (exclusive) in the old text.
+    pub(crate) old_end: Index2,
+    /// The end of the replaced range (exclusive) in the new text.
+    pub(crate) new_end: Index2,
+    /// The lengths of the old rows from `start.row` to `old_end.row`.
+    old_lens: Vec<usize>,
+}
+
+impl Edit {
+    /// Finds the edit from the `old` to the `new` rows, both starting at row
+    /// `first`. Returns `None` if the rows are equal.
+    ///
+    /// Only the rows that differ are compared character by character, along
+    /// with an unchanged row on each side, so that the line breaks around
+    /// them are compared as well.
+    fn between(first: usize, old: &[&[char]], new: &[&[char]]) -> Option<Self> {
+        let (prefix, suffix) = common_rows(old, new);
+        if prefix == old.len() && prefix == new.len() {
+            return None;
+        }
+        let start = prefix.saturating_sub(1);
+        let old_end = (old.len() - suffix + 1).min(old.len());
+        let new_end = (new.len() - suffix + 1).min(new.len());
+        Some(Self::from_rows(
+            first + start,
+            &old[start..old_end],
+            &new[start..new_end],
+        ))
+    }
+
+    /// Returns the edit that inserted `len` characters at `start` into a
+    /// row that was `old_len` characters long.
+    pub(crate) fn insertion(start: Index2, len: usize, old_len: usize) -> Self {
+        Self {
+            start,
+            old_end: start,
+            new_end: Index2::new(start.row, start.col + len),
+            old_lens: vec![old_len],
+        }
+    }
+
+    /// Finds the edit from the `old` to the `new` rows, both starting at
+    /// row `first`.
+    ///
+    /// The edit is determined by comparing the common prefix and suffix of
+    /// the rows joined with newlines, i.e. it is the single range of
+    /// characters that was replaced.
+    fn from_rows(first: usize, old: &[&[char]], new: &[&[char]]) -> Self {
+        let (old_text, new_text) = (join(old), join(new));
+        let prefix = old_text
+            .iter()
+            .zip(&new_text)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let max_suffix = old_text.len().min(new_text.len()) - prefix;
+        let suffix = old_text
+            .iter()
+            .rev()
+            .zip(new_text.iter().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let start = position(first, old, prefix);
+        let old_end = position(first, old, old_text.len() - suffix);
+        let old_lens = old
+            .iter()
+            .skip(start.row - first)
+            .take(old_end.row - start.row + 1)
+            .map(|row| row.len())
+            .collect();
+        Self {
+            start,
+            old_end,
+            new_end: position(first, new, new_text.len() - suffix),
+            old_lens,
+        }
+    }
+
+    /// Whether no characters were replaced.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.start == self.old_end && self.start == self.new_end
+    }
+
+    /// Moves a position in the old text to the corresponding position in
+    /// the new text. Positions before the edit stay in place, positions after
+    /// the edit are shifted, and positions within the replaced range are moved
+    /// to its start.
+    pub(crate) fn map(&self, index: Index2) -> Index2 {
+        if index < self.start {
+            index
+        } else if index >= self.old_end {
+            self.shift(index)
+        } else {
+            self.start
+        }
+    }
+
+    /// Moves a position at or after the end of the replaced range by the
+    /// difference in length of the replaced range.
+    pub(crate) fn shift(&self, index: Index2) -> Index2 {
+        if index.row == self.old_end.row {
+            let col = self.new_end.col + index.col.saturating_sub(self.old_end.col);
+            Index2::new(self.new_end.row, col)
+        } else {
+            let row = index.row - self.old_end.row + self.new_end.row;
+            Index2::new(row, index.col)
+        }
+    }
+
+    /// Whether the range from `start` to `end` (inclusive) was deleted
+    /// entirely.
+    pub(crate) fn is_deleted(&self, start: Index2, end: Index2) -> bool {
+        start >= self.start && end < self.old_end
+    }
+
+    /// Whether a row of the old text was deleted entirely, along with its
+    /// line break.
+    pub(crate) fn is_row_deleted(&self, row: usize) -> bool {
+        let len = row
+            .checked_sub(self.start.row)
+            .and_then(|i| self.old_lens.get(i));
+        len.is_some_and(|&len| self.is_deleted(Index2::new(row, 0), Index2::new(row, len)))
+    }
+}
+
+/// Returns the number of equal rows at the start and at the end of both.
+fn common_rows<T: PartialEq>(old: &[T], new: &[T]) -> (usize, usize) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    (prefix, suffix)
+}
+
+/// Joins the rows with newlines into a single sequence of characters.
+fn join(rows: &[&[char]]) -> Vec<char> {
+    let mut text = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        text.extend(*row);
+    }
+    text
+}
+
+/// Converts an offset within the joined `rows`, which start at row `first`,
+/// into a position.
+fn position(first: usize, rows: &[&[char]], mut offset: usize) -> Index2 {
+    for (i, row) in rows.iter().enumerate() {
+        if offset <= row.len() {
+            return Index2::new(first + i, offset);
+        }
+        offset -= row.len() + 1;
+    }
+    Index2::new(first + rows.len().saturating_sub(1), offset)
+}
+
+/// The rows touched since the last sync, as they were before the edits.
+#[derive(Debug, Clone)]
+struct Touched {
+    /// The first touched row.
+    row: usize,
+    /// The touched rows of the old text.
+    rows: Vec<Vec<char>>,
+    /// The number of rows of the old text.
+    len: usize,
+}
+
+impl Touched {
+    /// Returns the touched rows in the current text. Rows are only inserted
+    /// or removed within the touched rows, so the rows after them are the
+    /// same as in the old text.
+    fn current(&self, lines: &Lines) -> Range<usize> {
+        let after = self.len - self.row - self.rows.len();
+        self.row..lines.len().saturating_sub(after).max(self.row)
+    }
+}
+
+/// The rows touched since the last tracked action, along with the cursor
+/// and the length of the undo history at that time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EditTracker {
+    touched: Option<Touched>,
+    cursor: Index2,
+    undo_len: usize,
+    /// Increases whenever an edit was tracked.
+    pub(super) edits: u64,
+    /// The text as of the last tracked action, to check in tests that all
+    /// edited rows were touched.
+    #[cfg(test)]
+    lines: Option<Lines>,
+}
+
+impl EditTracker {
+    /// Whether no rows were touched since the last sync.
+    pub(crate) fn is_clean(&self) -> bool {
+        self.touched.is_none()
+    }
+}
+
+/// Joins the lines with newlines into a single sequence of characters.
+pub(super) fn flatten(lines: &Lines) -> Vec<char> {
+    let rows: Vec<&[char]> = lines.iter_row().map(Vec::as_slice).collect();
+    join(&rows)
+}
+
+/// Converts a position into an offset within the flattened text.
+pub(super) fn to_offset(lines: &Lines, index: Index2) -> usize {
+    lines
+        .iter_row()
+        .take(index.row)
+        .map(|line| line.len() + 1)
+        .sum::<usize>()
+        + index.col
+}
+
+/// Converts an offset within the flattened text into a position.
+pub(super) fn from_offset(lines: &Lines, mut offset: usize) -> Index2 {
+    for (row, line) in lines.iter_row().enumerate() {
+        if offset <= line.len() {
+            return Index2::new(row, offset);
+        }
+        offset -= line.len() + 1;
+    }
+    Index2::new(lines.len().saturating_sub(1), offset)
+}
+
+/// Replaces the rows in `range` with `rows`.
+fn replace_rows(lines: &mut Lines, range: Range<usize>, rows: Vec<Vec<char>>) {
+    let mut rest = lines.split_off(Index2::new(range.start, 0));
+    let mut rest = rest.split_off(Index2::new(range.len(), 0));
+    lines.append(&mut Lines::new(rows));
+    lines.append(&mut rest);
+}
+
+impl EditorState {
+    /// Keeps a copy of the rows in `rows` as they are before they are
+    /// edited, so that their edits are tracked. Rows that are inserted or
+    /// removed must lie within touched rows, rows inserted at `row` are
+    /// touched with the empty range `row..row`.
+    pub(crate) fn touch_rows(&mut self, rows: Range<usize>) {
+        self.close_gap();
+        let len = self.lines.len();
+        let start = rows.start.min(len);
+        let end = rows.end.clamp(start, len);
+        let copy = |lines: &Lines, range: Range<usize>| -> Vec<Vec<char>> {
+            lines
+                .iter_row()
+                .skip(range.start)
+                .take(range.len())
+                .cloned()
+                .collect()
+        };
+
+        let Some(touched) = &mut self.edit_tracker.touched else {
+            self.edit_tracker.touched = Some(Touched {
+                row: start,
+                rows: copy(&self.lines, start..end),
+                len,
+            });
+            return;
+        };
+        let current = touched.current(&self.lines);
+        if end > current.end {
+            touched.rows.extend(copy(&self.lines, current.end..end));
+        }
+        if start < current.start {
+            let mut rows = copy(&self.lines, start..current.start);
+            rows.append(&mut touched.rows);
+            touched.rows = rows;
+            touched.row = start;
+        }
+    }
+
+    /// Touches the row at `row`, see [`EditorState::touch_rows`].
+    pub(crate) fn touch_row(&mut self, row: usize) {
+        self.touch_rows(row..row + 1);
+    }
+
+    /// Touches the row at `row` after it was edited, given the row as it was
+    /// before the edits. No other rows may be touched yet.
+    pub(super) fn touch_edited_row(&mut self, row: usize, old: Vec<char>) {
+        debug_assert!(self.edit_tracker.is_clean());
+        self.edit_tracker.touched = Some(Touched {
+            row,
+            rows: vec![old],
+            len: self.lines.len(),
+        });
+    }
+
+    /// Touches the whole text before code of the app runs, which may edit
+    /// any row.
+    pub(crate) fn touch_all_rows(&mut self) {
+        self.touch_rows(0..self.lines.len());
+    }
+
+    /// Replaces the text, touching only the rows that differ. Returns the
+    /// edit, or `None` if the text did not change.
+    pub(crate) fn replace_lines(&mut self, lines: Lines) -> Option<Edit> {
+        self.close_gap();
+        let old: Vec<&[char]> = self.lines.iter_row().map(Vec::as_slice).collect();
+        let new: Vec<&[char]> = lines.iter_row().map(Vec::as_slice).collect();
+        let (prefix, suffix) = common_rows(&old, &new);
+        let (edit, rows) = (Edit::between(0, &old, &new), prefix..old.len() - suffix);
+        self.touch_rows(rows);
+        self.lines = lines;
+        edit
+    }
+
+    /// Runs `f` and moves the highlights, the line metadata, the fields, the
+    /// validation errors, the location list, the bookmarks and the last
+    /// visual selection along with the edits made to the text.
+    ///
+    /// If fields are set, edits outside of a field are reverted. Increases
+    /// the generation if the state changed.
+    pub(crate) fn track_edits<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        // Changes made to the public fields in the meantime are not edits
+        self.edit_tracker.touched = None;
+        #[cfg(test)]
+        {
+            self.edit_tracker.lines = Some(self.whole_lines());
+        }
+        self.checkpoint_edits();
+
+        let (fingerprint, generation) = (Fingerprint::new(self), self.generation);
+        let mode = self.mode;
+        f(self);
+        self.run_mode_hooks(mode);
+        if mode == EditorMode::Insert && self.mode != EditorMode::Insert {
+            self.finish_block_insert();
+            self.trim_on_leave_insert();
+        }
+        if self.sync_edits() && self.mode == EditorMode::Visual {
+            if let Some(selection) = &self.selection {
+                self.last_visual = Some((selection.clone(), self.cursor));
+            }
+        }
+
+        // Undo, redo and replacing the text increase the generation themselves
+        if self.generation == generation && Fingerprint::new(self) != fingerprint {
+            self.generation += 1;
+        }
+    }
+
+    /// Moves the tracked positions along with the edits made to the touched
+    /// rows since the last call. Returns `false` if the edits were reverted
+    /// because they are outside of the fields.
+    pub(crate) fn sync_edits(&mut self) -> bool {
+        #[cfg(test)]
+        self.check_touched_rows();
+        // Characters typed into the gap are inserted at a known position,
+        // so their edit is known without comparing the row
+        if let Some(edit) = self.take_gap_edit() {
+            self.move_along(&edit);
+            return true;
+        }
+        let Some(touched) = self.edit_tracker.touched.take() else {
+            self.checkpoint_edits();
+            return true;
+        };
+
+        // The unchanged rows around the touched rows are passed along, so
+        // that the line breaks around them are compared as well.
+        let current = touched.current(&self.lines);
+        let first = current.start.saturating_sub(1);
+        let last = (current.end + 1).min(self.lines.len());
+        let new: Vec<&[char]> = self
+            .lines
+            .iter_row()
+            .skip(first)
+            .take(last - first)
+            .map(Vec::as_slice)
+            .collect();
+        let (before, after) = (&new[..current.start - first], &new[current.end - first..]);
+        let old: Vec<&[char]> = before
+            .iter()
+            .copied()
+            .chain(touched.rows.iter().map(Vec::as_slice))
+            .chain(after.iter().copied())
+            .collect();
+        let Some(edit) = Edit::between(first, &old, &new) else {
+            self.checkpoint_edits();
+            return true;
+        };
+
+        if !self.fields.allows(&edit) {
+            replace_rows(&mut self.lines, current, touched.rows);
+            self.cursor = self.edit_tracker.cursor;
+            self.undo.truncate(self.edit_tracker.undo_len);
+            self.notify(Notification::warning("Text outside of fields is read-only"));
+            return false;
+        }
+        self.move_along(&edit);
+        true
+    }
+
+    /// Moves the tracked positions along with an edit.
+    fn move_along(&mut self, edit: &Edit) {
+        self.fields.adjust(edit);
+        self.highlights.adjust(edit);
+        self.metadata.adjust(edit);
+        self.validation.adjust(edit);
+        self.locations.adjust(edit);
+        bookmarks::adjust(&mut self.bookmarks, edit);
+        self.yank_flash.clear();
+        if let Some((selection, cursor)) = &mut self.last_visual {
+            selection.start = edit.map(selection.start);
+            selection.end = edit.map(selection.end);
+            selection.anchor = selection.anchor.map(|anchor| edit.map(anchor));
+            *cursor = edit.map(*cursor);
+        }
+
+        self.edit_tracker.edits += 1;
+        self.checkpoint_edits();
+    }
+
+    /// Remembers the cursor and the undo history that reverted edits return to.
+    fn checkpoint_edits(&mut self) {
+        self.edit_tracker.cursor = self.cursor;
+        self.edit_tracker.undo_len = self.undo.len();
+    }
+
+    /// Checks that the rows that changed since the last sync were touched.
+    #[cfg(test)]
+    fn check_touched_rows(&mut self) {
+        let lines = self.whole_lines();
+        let Some(old) = self.edit_tracker.lines.replace(lines.clone()) else {
+            return;
+        };
+        let (old, new) = (old.to_vecs(), lines.to_vecs());
+        if old == new {
+            return;
+        }
+        if let Some(row) = self.gap_edit_row() {
+            assert!(
+                old.len() == new.len() && (0..old.len()).all(|i| i == row || old[i] == new[i]),
+                "edits outside the gap in row {row}",
+            );
+            return;
+        }
+        let touched = self
+            .edit_tracker
+            .touched
+            .as_ref()
+            .expect("edited rows were not touched");
+        let current = touched.current(&self.lines);
+        let old_end = touched.row + touched.rows.len();
+        assert!(
+            old[..touched.row] == new[..touched.row]
+                && old[old_end..] == new[current.end..]
+                && old[touched.row..old_end] == touched.rows[..],
+            "edits outside the touched rows {current:?}",
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::AppendNewline;
+    use crate::state::selection::Selection;
+
+    fn edit(old: &str, new: &str) -> Option<Edit> {
+        let (old, new) = (Lines::from(old), Lines::from(new));
+        let old: Vec<&[char]> = old.iter_row().map(Vec::as_slice).collect();
+        let new: Vec<&[char]> = new.iter_row().map(Vec::as_slice).collect();
+        Edit::between(0, &old, &new)
+    }
+
+    #[test]
+    fn test_edit_map() {
+        let edit = edit("Hello World!\n123.", "Hello\nWorld!\n123.").unwrap();
+
+        assert_eq!(edit.map(Index2::new(0, 2)), Index2::new(0, 2));
+        assert_eq!(edit.map(Index2::new(0, 6)), Index2::new(1, 0));
+        assert_eq!(edit.map(Index2::new(1, 1)), Index2::new(2, 1));
+        assert!(edit.is_deleted(Index2::new(0, 5), Index2::new(0, 5)));
+        assert!(!edit.is_deleted(Index2::new(0, 5), Index2::new(0, 6)));
+    }
+
+    #[test]
+    fn test_edit_of_changed_rows() {
+        let range = |old: &str, new: &str| {
+            let edit = edit(old, new).unwrap();
+            let pos = |index: Index2| (index.row, index.col);
+            (pos(edit.start), pos(edit.old_end), pos(edit.new_end))
+        };
+
+        assert!(edit("a\nb\nc", "a\nb\nc").is_none());
+        assert_eq!(range("a", "a\nb"), ((0, 1), (0, 1), (1, 1)));
+        assert_eq!(range("a\nb", "b"), ((0, 0), (1, 0), (0, 0)));
+        assert_eq!(
+            range("a\nb\nd\ne", "a\nb\nc\nd\ne"),
+            ((2, 0), (2, 0), (3, 0))
+        );
+        assert_eq!(range("a\nbc\nd", "a\nbxc\nd"), ((1, 1), (1, 1), (1, 2)));
+    }
+
+    #[test]
+    fn test_track_edits_in_touched_rows() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+        state.lines = Lines::from("x\nb\nc");
+        state.last_visual = Some((
+            Selection::new(Index2::new(2, 0), Index2::new(2, 0)),
+            Index2::new(2, 0),
+        ));
+
+        state.execute(AppendNewline(1));
+        assert_eq!(state.lines, Lines::from("x\n\nb\nc"));
+        let (selection, _) = state.last_visual.clone().unwrap();
+        assert_eq!(selection.start, Index2::new(3, 0));
+        assert!(state.edit_tracker.touched.is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Requests to close the editor, e.g. with `ZZ` and `ZQ` like in vim.
+//!
+//! The editor does not close itself. Apps register a callback with
+//! [`EditorState::on_exit_request`] that receives the [`ExitRequest`] when
+//! [`RequestExit`] is executed, and save the text or close the editor there.
+//! Without a callback, the request is pushed to the intent queue as
+//! [`EditorIntent`]s.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::{cell::Cell, rc::Rc};
+//!
+//! use edtui::{EditorEventHandler, EditorState, ExitRequest, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("Hello"));
+//! let request = Rc::new(Cell::new(None));
+//! let sink = Rc::clone(&request);
+//! state.on_exit_request(move |_, exit| sink.set(Some(exit)));
+//!
+//! let mut event_handler = EditorEventHandler::default();
+//! event_handler.feed_keys("ZZ", &mut state).unwrap();
+//! assert_eq!(request.get(), Some(ExitRequest::SaveAndClose));
+//!
+//! event_handler.feed_keys("ZQ", &mut state).unwrap();
+//! assert_eq!(request.get(), Some(ExitRequest::DiscardAndClose));
+//! ```
+//!
+//! [`RequestExit`]: crate::actions::RequestExit
+//! [`EditorIntent`]: crate::EditorIntent
+use std::rc::Rc;
+
+use crate::EditorState;
+
+pub(crate) type ExitHook = Rc<dyn Fn(&mut EditorState, ExitRequest)>;
+
+/// How the app is asked to close the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitRequest {
+    /// Save the text, then close the editor, like `ZZ` in vim.
+    SaveAndClose,
+    /// Close the editor without saving the text, like `ZQ` in vim.
+    DiscardAndClose,
+}
+
+impl EditorState {
+    /// Registers the callback that handles [`RequestExit`], e.g. to save
+    /// the text and leave the app. A previously registered callback is
+    /// replaced.
+    ///
+    /// [`RequestExit`]: crate::actions::RequestExit
+    pub fn on_exit_request<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut EditorState, ExitRequest) + 'static,
+    {
+        self.exit_hook = Some(Rc::new(hook));
+    }
+
+    /// Removes the callback registered with [`EditorState::on_exit_request`].
+    pub fn clear_exit_hook(&mut self) {
+        self.exit_hook = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::events::KeyEvent;
+    use crate::{EditorEventHandler, EditorIntent, Lines};
+
+    #[test]
+    fn test_exit_request_from_keys() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        let mut handler = EditorEventHandler::default();
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&requests);
+        state.on_exit_request(move |_, exit| sink.borrow_mut().push(exit));
+
+        for key in "ZZZQ".chars() {
+            handler.on_key_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(
+            *requests.borrow(),
+            vec![ExitRequest::SaveAndClose, ExitRequest::DiscardAndClose]
+        );
+        assert_eq!(state.lines, Lines::from("Hello"));
+        assert_eq!(state.drain_intents().count(), 0);
+
+        state.clear_exit_hook();
+        for key in "ZZZQ".chars() {
+            handler.on_key_event(KeyEvent::Char(key), &mut state);
+        }
+        assert_eq!(requests.borrow().len(), 2);
+        assert_eq!(
+            state.drain_intents().collect::<Vec<_>>(),
+            vec![
+                EditorIntent::Save,
+                EditorIntent::Quit { force: false },
+                EditorIntent::Quit { force: true },
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Editable fields within an otherwise read-only text.
+//!
+//! Fields turn the editor into a form: once fields are set, only the text
+//! within a field can be edited, e.g. the subject of a commit template or
+//! the values of a YAML front-matter. `Tab` and `Shift+Tab` navigate between
+//! the fields and each field can be validated by the app. In insert mode,
+//! `Tab` inserts a tab unless it is bound to [`NextField`].
+//!
+//! [`NextField`]: crate::actions::NextField
+use super::edit::{flatten, to_offset, Edit};
+use crate::{EditorState, Index2, Lines};
+
+/// Validates the text of a field, returning an error message if it is
+/// invalid.
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// An editable region of text.
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// The name of the field.
+    pub name: String,
+    /// The first position of the field.
+    pub start: Index2,
+    /// The position after the last character of the field (exclusive), so
+    /// that a field can be empty.
+    pub end: Index2,
+    validator: Option<Validator>,
+}
+
+impl Field {
+    /// Creates a new field from `start` to `end` (exclusive).
+    #[must_use]
+    pub fn new<S: Into<String>>(name: S, start: Index2, end: Index2) -> Self {
+        Self {
+            name: name.into(),
+            start,
+            end,
+            validator: None,
+        }
+    }
+
+    /// Sets a function that validates the text of the field.
+    #[must_use]
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Returns the text of the field.
+    #[must_use]
+    pub fn text(&self, lines: &Lines) -> String {
+        let start = to_offset(lines, self.start);
+        let end = to_offset(lines, self.end);
+        flatten(lines)
+            .get(start..end)
+            .map(|text| text.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the range from `start` to `end` (exclusive) lies within the
+    /// field.
+    fn contains(&self, start: Index2, end: Index2) -> bool {
+        self.start <= start && end <= self.end
+    }
+}
+
+/// The fields of an editor.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Fields {
+    fields: Vec<Field>,
+}
+
+impl Fields {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the index of the field that contains an edit.
+    fn containing(&self, edit: &Edit) -> Option<usize> {
+        self.fields
+            .iter()
+            .position(|field| field.contains(edit.start, edit.old_end))
+    }
+
+    /// Whether an edit is allowed, i.e. there are no fields or the edit lies
+    /// within a field.
+    pub(crate) fn allows(&self, edit: &Edit) -> bool {
+        self.is_empty() || self.containing(edit).is_some()
+    }
+
+    /// Moves the fields along with an edit. The field that contains the edit
+    /// grows or shrinks with it.
+    pub(crate) fn adjust(&mut self, edit: &Edit) {
+        let containing = self.containing(edit);
+        for (i, field) in self.fields.iter_mut().enumerate() {
+            if Some(i) == containing {
+                field.end = edit.shift(field.end);
+            } else {
+                field.start = edit.map(field.start);
+                field.end = edit.map(field.end);
+            }
+        }
+    }
+}
+
+impl EditorState {
+    /// Sets the editable fields. Once fields are set, edits outside of the
+    /// fields are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Field, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Subject: \nBody:"));
+    /// let subject = Field::new("subject", Index2::new(0, 9), Index2::new(0, 9))
+    ///     .validator(|text| match text.is_empty() {
+    ///         true => Err(String::from("The subject must not be empty")),
+    ///         false => Ok(()),
+    ///     });
+    /// state.set_fields(vec![subject]);
+    ///
+    /// assert_eq!(state.validate_fields().len(), 1);
+    /// ```
+    pub fn set_fields(&mut self, fields: Vec<Field>) {
+        self.fields.fields = fields;
+    }
+
+    /// Removes all fields, making the whole text editable again.
+    pub fn clear_fields(&mut self) {
+        self.fields.fields.clear();
+    }
+
+    /// Returns the fields.
+    #[must_use]
+    pub fn fields(&self) -> &[Field] {
+        &self.fields.fields
+    }
+
+    /// Returns the text of the field with the given name.
+    #[must_use]
+    pub fn field_text(&self, name: &str) -> Option<String> {
+        self.fields
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| field.text(&self.lines))
+    }
+
+    /// Validates all fields and returns the names of the invalid fields
+    /// together with their error messages.
+    #[must_use]
+    pub fn validate_fields(&self) -> Vec<(String, String)> {
+        self.fields
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let validator = field.validator?;
+                let error = validator(&field.text(&self.lines)).err()?;
+                Some((field.name.clone(), error))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, InsertChar, RemoveChar};
+    use crate::EditorMode;
+
+    fn test_state() -> EditorState {
+        let mut state = EditorState::new(Lines::from("Name: \nAge: 42"));
+        state.set_fields(vec![
+            Field::new("name", Index2::new(0, 6), Index2::new(0, 6)),
+            Field::new("age", Index2::new(1, 5), Index2::new(1, 7)).validator(|text| {
+                text.parse::<u8>()
+                    .map(|_| ())
+                    .map_err(|_| String::from("Not a number"))
+            }),
+        ]);
+        state
+    }
+
+    #[test]
+    fn test_edit_within_field() {
+        let mut state = test_state();
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(0, 6);
+
+        state.execute(InsertChar('B'));
+        state.execute(InsertChar('o'));
+
+        assert_eq!(state.lines, Lines::from("Name: Bo\nAge: 42"));
+        assert_eq!(state.field_text("name"), Some(String::from("Bo")));
+        assert_eq!(state.field_text("age"), Some(String::from("42")));
+    }
+
+    #[test]
+    fn test_edit_outside_field_is_rejected() {
+        let mut state = test_state();
+        state.cursor = Index2::new(0, 2);
+
+        state.execute(RemoveChar(1));
+        state.execute(DeleteLine(1));
+
+        assert_eq!(state.lines, Lines::from("Name: \nAge: 42"));
+        assert_eq!(state.cursor, Index2::new(0, 2));
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        let mut state = test_state();
+        assert!(state.validate_fields().is_empty());
+
+        state.cursor = Index2::new(1, 5);
+        state.execute(RemoveChar(1));
+        state.mode = EditorMode::Insert;
+        state.execute(InsertChar('x'));
+
+        assert_eq!(state.field_text("age"), Some(String::from("x2")));
+        assert_eq!(
+            state.validate_fields(),
+            vec![(String::from("age"), String::from("Not a number"))]
+        );
+    }
+}
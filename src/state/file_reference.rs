@@ -0,0 +1,181 @@
+//! The file path under the cursor, e.g. to open it with `gf` like in vim.
+//!
+//! Apps register a callback with [`EditorState::on_open_file`] that receives
+//! the path under the cursor when [`OpenFileUnderCursor`] is executed. A path
+//! may be followed by a line and a column, as in compiler output such as
+//! `src/main.rs:12:5`.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::{cell::RefCell, rc::Rc};
+//!
+//! use edtui::actions::OpenFileUnderCursor;
+//! use edtui::{EditorState, FileReference, Index2, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("error at src/main.rs:12:5"));
+//! state.cursor = Index2::new(0, 12);
+//!
+//! let opened = Rc::new(RefCell::new(None));
+//! let sink = Rc::clone(&opened);
+//! state.on_open_file(move |_, file| *sink.borrow_mut() = Some(file.clone()));
+//!
+//! state.execute(OpenFileUnderCursor);
+//! assert_eq!(
+//!     *opened.borrow(),
+//!     Some(FileReference::new("src/main.rs").line(12).column(5)),
+//! );
+//! ```
+//!
+//! [`OpenFileUnderCursor`]: crate::actions::OpenFileUnderCursor
+use std::rc::Rc;
+
+use jagged::index::RowIndex;
+
+use crate::EditorState;
+
+pub(crate) type OpenFileHook = Rc<dyn Fn(&mut EditorState, &FileReference)>;
+
+/// A file path found in the text, optionally with a line and a column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReference {
+    /// The path as written in the text.
+    pub path: String,
+    /// The 1-based line that follows the path, e.g. `12` in `main.rs:12`.
+    pub line: Option<usize>,
+    /// The 1-based column that follows the line, e.g. `5` in `main.rs:12:5`.
+    pub column: Option<usize>,
+}
+
+impl FileReference {
+    /// Creates a reference to `path` without a line or a column.
+    #[must_use]
+    pub fn new<S: Into<String>>(path: S) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Sets the 1-based line.
+    #[must_use]
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the 1-based column.
+    #[must_use]
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Parses a token such as `src/main.rs:12:5`. Trailing punctuation,
+    /// e.g. the period that ends a sentence, is not part of the path.
+    fn parse(token: &str) -> Option<Self> {
+        let token = token.trim_end_matches(['.', ',', ':']);
+        let mut parts = token.split(':');
+        let mut path = parts.next()?.to_string();
+        let mut rest: Vec<&str> = parts.collect();
+        // Keep the drive letter of a windows path, e.g. `C:\src\main.rs`
+        if path.len() == 1
+            && path.chars().all(|ch| ch.is_ascii_alphabetic())
+            && rest
+                .first()
+                .is_some_and(|part| part.starts_with(['\\', '/']))
+        {
+            path.push(':');
+            path.push_str(rest.remove(0));
+        }
+        if path.is_empty() {
+            return None;
+        }
+        let mut numbers = rest.iter().map_while(|part| part.parse::<usize>().ok());
+        Some(Self {
+            path,
+            line: numbers.next(),
+            column: numbers.next(),
+        })
+    }
+}
+
+/// Whether `ch` can be part of a file path or of its line and column suffix.
+fn is_path_char(ch: char) -> bool {
+    ch.is_alphanumeric() || "/\\._-~+@%#$=,:".contains(ch)
+}
+
+impl EditorState {
+    /// Returns the file path under the cursor together with the line and the
+    /// column that may follow it, or `None` if the cursor is not on a path.
+    #[must_use]
+    pub fn file_reference_under_cursor(&self) -> Option<FileReference> {
+        let line = self.lines.get(RowIndex::new(self.cursor.row))?;
+        let col = self.cursor.col.min(line.len().checked_sub(1)?);
+        if !is_path_char(line[col]) {
+            return None;
+        }
+        let start = line[..col]
+            .iter()
+            .rposition(|&ch| !is_path_char(ch))
+            .map_or(0, |i| i + 1);
+        let end = line[col..]
+            .iter()
+            .position(|&ch| !is_path_char(ch))
+            .map_or(line.len(), |i| col + i);
+        let token: String = line[start..end].iter().collect();
+        FileReference::parse(&token)
+    }
+
+    /// Registers the callback that opens a file for
+    /// [`OpenFileUnderCursor`], e.g. in a new buffer of the app. A
+    /// previously registered callback is replaced.
+    ///
+    /// [`OpenFileUnderCursor`]: crate::actions::OpenFileUnderCursor
+    pub fn on_open_file<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut EditorState, &FileReference) + 'static,
+    {
+        self.open_file_hook = Some(Rc::new(hook));
+    }
+
+    /// Removes the callback registered with [`EditorState::on_open_file`].
+    pub fn clear_open_file_hook(&mut self) {
+        self.open_file_hook = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index2, Lines};
+
+    fn reference_at(text: &str, col: usize) -> Option<FileReference> {
+        let mut state = EditorState::new(Lines::from(text));
+        state.cursor = Index2::new(0, col);
+        state.file_reference_under_cursor()
+    }
+
+    #[test]
+    fn test_file_reference_under_cursor() {
+        assert_eq!(
+            reference_at("see ~/notes/todo.md.", 8),
+            Some(FileReference::new("~/notes/todo.md"))
+        );
+        assert_eq!(
+            reference_at("--> src/lib.rs:42:7", 6),
+            Some(FileReference::new("src/lib.rs").line(42).column(7))
+        );
+        assert_eq!(
+            reference_at("main.rs:3: warning", 0),
+            Some(FileReference::new("main.rs").line(3))
+        );
+        assert_eq!(
+            reference_at(r"at C:\src\main.rs:10", 5),
+            Some(FileReference::new(r"C:\src\main.rs").line(10))
+        );
+        assert_eq!(reference_at("a b", 1), None);
+        assert_eq!(reference_at("", 0), None);
+    }
+}
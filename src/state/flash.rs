@@ -0,0 +1,105 @@
+//! Regions that are highlighted briefly, such as the yanked text.
+use std::time::{Duration, Instant};
+
+use super::selection::Selection;
+use crate::EditorState;
+
+/// A region that is highlighted for a while after it was flashed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Flash {
+    /// How long a region is highlighted, or `None` if flashing is disabled.
+    pub(crate) duration: Option<Duration>,
+    /// The flashed region and when it was flashed.
+    region: Option<(Selection, Instant)>,
+}
+
+impl Flash {
+    /// Highlights a region, if flashing is enabled.
+    pub(crate) fn flash(&mut self, selection: Selection) {
+        if self.duration.is_some() {
+            self.region = Some((selection, Instant::now()));
+        }
+    }
+
+    /// Returns the region that is currently highlighted, if any.
+    pub(crate) fn region(&self) -> Option<&Selection> {
+        let (selection, since) = self.region.as_ref()?;
+        (since.elapsed() < self.duration?).then_some(selection)
+    }
+
+    /// Removes the region once it expired. Returns whether a region was
+    /// highlighted.
+    pub(crate) fn tick(&mut self) -> bool {
+        let active = self.region.is_some();
+        if self.region().is_none() {
+            self.region = None;
+        }
+        active
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.region = None;
+    }
+}
+
+impl EditorState {
+    /// Highlights the yanked text for the given duration after copying,
+    /// similar to the yank highlight of neovim. Pass `None` to disable the
+    /// highlight (default).
+    ///
+    /// The highlight uses [`EditorTheme::yank_style`]. Apps should call
+    /// [`EditorEventHandler::on_tick`] periodically and redraw when it
+    /// returns `true`, so that the highlight disappears.
+    ///
+    /// [`EditorTheme::yank_style`]: crate::EditorTheme::yank_style
+    /// [`EditorEventHandler::on_tick`]: crate::EditorEventHandler::on_tick
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// state.set_yank_flash(Some(Duration::from_millis(150)));
+    /// ```
+    pub fn set_yank_flash(&mut self, duration: Option<Duration>) {
+        self.yank_flash = Flash {
+            duration,
+            region: None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{CopyLine, CopySelection, InsertChar};
+    use crate::clipboard::InternalClipboard;
+    use crate::{Index2, Lines};
+
+    #[test]
+    fn test_yank_flash() {
+        let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+        state.set_clipboard(InternalClipboard::default());
+        state.execute(CopyLine);
+        assert!(state.yank_flash.region().is_none());
+
+        state.set_yank_flash(Some(Duration::from_secs(60)));
+        state.execute(CopyLine);
+        let line = Selection::new(Index2::new(0, 0), Index2::new(0, 0)).line_mode();
+        assert_eq!(state.yank_flash.region(), Some(&line));
+
+        let selection = Selection::new(Index2::new(1, 1), Index2::new(1, 3));
+        state.selection = Some(selection.clone());
+        state.execute(CopySelection);
+        assert_eq!(state.yank_flash.region(), Some(&selection));
+
+        state.execute(InsertChar('!'));
+        assert!(state.yank_flash.region().is_none());
+
+        state.set_yank_flash(Some(Duration::ZERO));
+        state.execute(CopyLine);
+        assert!(state.yank_flash.region().is_none());
+    }
+}
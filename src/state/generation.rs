@@ -0,0 +1,117 @@
+//! Counters that change whenever the state or the view changed, so that
+//! hosts can cheaply detect changes since the last frame.
+use super::selection::Selection;
+use crate::{EditorMode, EditorState, Index2};
+
+/// The parts of the state that are compared to detect a change.
+#[derive(PartialEq, Eq)]
+pub(crate) struct Fingerprint {
+    edits: u64,
+    cursor: Index2,
+    mode: EditorMode,
+    selection: Option<Selection>,
+    search: (String, Option<usize>),
+    notification: Option<String>,
+}
+
+impl Fingerprint {
+    pub(crate) fn new(state: &EditorState) -> Self {
+        Self {
+            edits: state.edit_tracker.edits,
+            cursor: state.cursor,
+            mode: state.mode,
+            selection: state.selection.clone(),
+            search: (state.search.pattern.clone(), state.search.selected_index),
+            notification: state
+                .notifications
+                .current()
+                .map(|notification| notification.message.clone()),
+        }
+    }
+}
+
+impl EditorState {
+    /// Returns a number that increases whenever an action or an event
+    /// changed the text, the cursor, the mode, the selection, the search or
+    /// the notification. Hosts can compare it with the number of the last
+    /// frame to detect changes, e.g. for their own caching or to sync the
+    /// text over the network.
+    ///
+    /// Modifying the public fields directly does not increase it, call
+    /// [`EditorState::invalidate_render_cache`] afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{MoveForward, MoveBackward};
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// let generation = state.generation();
+    ///
+    /// state.execute(MoveBackward(1));
+    /// assert_eq!(state.generation(), generation);
+    ///
+    /// state.execute(MoveForward(1));
+    /// assert!(state.generation() > generation);
+    /// ```
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns a number that increases whenever the view changed as of the
+    /// last render: the viewport scrolled, the area of the editor changed,
+    /// or lines were wrapped, numbered or indented differently.
+    #[must_use]
+    pub fn view_generation(&self) -> u64 {
+        self.view.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::motion::MoveToLastRow;
+    use crate::actions::{DeleteChar, InsertChar, SwitchMode};
+    use crate::{EditorView, Lines, SetContentsOptions};
+    use ratatui_core::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    #[test]
+    fn test_generation() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        assert_eq!(state.generation(), 0);
+
+        state.execute(DeleteChar(1));
+        assert_eq!(state.generation(), 0);
+
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.execute(InsertChar('!'));
+        assert_eq!(state.generation(), 2);
+
+        state.invalidate_render_cache();
+        assert_eq!(state.generation(), 3);
+
+        state.undo();
+        assert_eq!(state.generation(), 4);
+
+        state.set_contents(Lines::from("World"), SetContentsOptions::default());
+        assert_eq!(state.generation(), 5);
+    }
+
+    #[test]
+    fn test_view_generation() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+        let area = Rect::new(0, 0, 10, 2);
+        let render = |state: &mut EditorState| {
+            EditorView::new(state).render(area, &mut Buffer::empty(area));
+            state.view_generation()
+        };
+
+        let generation = render(&mut state);
+        assert_eq!(render(&mut state), generation);
+
+        state.execute(MoveToLastRow());
+        assert_eq!(render(&mut state), generation + 1);
+    }
+}
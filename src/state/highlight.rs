@@ -0,0 +1,171 @@
+//! Highlight groups for arbitrary ranges of text.
+//!
+//! Apps can attach styled ranges to the editor, e.g. to flash a line that
+//! changed, to underline lint warnings or to mark all occurrences of the
+//! symbol under the cursor. Highlights are organized in named groups, so that
+//! each group can be replaced or cleared independently. The ranges move along
+//! with the text when it is edited.
+use std::collections::BTreeMap;
+
+use ratatui_core::style::Style;
+
+use super::{edit::Edit, selection::Selection};
+use crate::{EditorState, Index2};
+
+/// A styled range of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    /// The first highlighted position.
+    pub start: Index2,
+    /// The last highlighted position (inclusive).
+    pub end: Index2,
+    /// The style of the highlighted text.
+    pub style: Style,
+    /// Highlights with a higher priority are drawn on top of highlights
+    /// with a lower priority.
+    pub priority: i32,
+}
+
+impl Highlight {
+    /// Creates a new highlight from `start` to `end` (inclusive).
+    #[must_use]
+    pub fn new(start: Index2, end: Index2, style: Style) -> Self {
+        Self {
+            start,
+            end,
+            style,
+            priority: 0,
+        }
+    }
+
+    /// Sets the priority of the highlight.
+    #[must_use]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub(crate) fn as_selection(&self) -> Selection {
+        Selection::new(self.start, self.end)
+    }
+}
+
+/// The highlight groups of an editor.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Highlights {
+    groups: BTreeMap<String, Vec<Highlight>>,
+}
+
+impl Highlights {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.values().all(Vec::is_empty)
+    }
+
+    /// Returns all highlights, ordered from lowest to highest priority.
+    pub(crate) fn sorted(&self) -> Vec<&Highlight> {
+        let mut highlights: Vec<&Highlight> = self.groups.values().flatten().collect();
+        highlights.sort_by_key(|highlight| highlight.priority);
+        highlights
+    }
+
+    /// Moves the highlights along with an edit. Highlights that were
+    /// deleted entirely are removed.
+    pub(crate) fn adjust(&mut self, edit: &Edit) {
+        for highlights in self.groups.values_mut() {
+            highlights.retain_mut(|highlight| {
+                if edit.is_deleted(highlight.start, highlight.end) {
+                    return false;
+                }
+                highlight.start = edit.map(highlight.start);
+                highlight.end = edit.map(highlight.end);
+                true
+            });
+        }
+    }
+}
+
+impl EditorState {
+    /// Replaces the highlights of a group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Highlight, Index2, Lines};
+    /// use ratatui::style::{Style, Stylize};
+    ///
+    /// let mut state = EditorState::new(Lines::from("let x = 1;"));
+    /// let warning = Highlight::new(Index2::new(0, 4), Index2::new(0, 4), Style::default().red());
+    /// state.set_highlights("lint", vec![warning]);
+    /// ```
+    pub fn set_highlights<S: Into<String>>(&mut self, group: S, highlights: Vec<Highlight>) {
+        self.highlights.groups.insert(group.into(), highlights);
+    }
+
+    /// Adds a highlight to a group.
+    pub fn add_highlight<S: Into<String>>(&mut self, group: S, highlight: Highlight) {
+        self.highlights
+            .groups
+            .entry(group.into())
+            .or_default()
+            .push(highlight);
+    }
+
+    /// Removes all highlights of a group.
+    pub fn clear_highlights(&mut self, group: &str) {
+        self.highlights.groups.remove(group);
+    }
+
+    /// Returns the highlights of a group.
+    #[must_use]
+    pub fn highlights(&self, group: &str) -> &[Highlight] {
+        self.highlights.groups.get(group).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, InsertChar, LineBreak};
+    use crate::Lines;
+
+    fn test_state() -> EditorState {
+        let mut state = EditorState::new(Lines::from("Hello World!\n\n123."));
+        let highlight = Highlight::new(Index2::new(0, 6), Index2::new(0, 10), Style::default());
+        state.set_highlights("test", vec![highlight]);
+        state
+    }
+
+    #[test]
+    fn test_highlight_moves_with_inserted_text() {
+        let mut state = test_state();
+        state.mode = crate::EditorMode::Insert;
+
+        state.execute(InsertChar('!'));
+
+        let highlight = &state.highlights("test")[0];
+        assert_eq!(highlight.start, Index2::new(0, 7));
+        assert_eq!(highlight.end, Index2::new(0, 11));
+    }
+
+    #[test]
+    fn test_highlight_moves_with_line_break() {
+        let mut state = test_state();
+        state.mode = crate::EditorMode::Insert;
+        state.cursor = Index2::new(0, 6);
+
+        state.execute(LineBreak(1));
+
+        let highlight = &state.highlights("test")[0];
+        assert_eq!(highlight.start, Index2::new(1, 0));
+        assert_eq!(highlight.end, Index2::new(1, 4));
+    }
+
+    #[test]
+    fn test_highlight_is_removed_with_deleted_text() {
+        let mut state = test_state();
+
+        state.execute(DeleteLine(1));
+
+        assert!(state.highlights("test").is_empty());
+    }
+}
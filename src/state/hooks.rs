@@ -0,0 +1,244 @@
+//! Callbacks that run when the editor switches modes or executes actions.
+use std::rc::Rc;
+
+use super::mode::EditorMode;
+use crate::actions::{Action, Execute};
+use crate::EditorState;
+
+type ModeHook = Rc<dyn Fn(&mut EditorState, EditorMode, EditorMode)>;
+type BeforeActionHook = Rc<dyn Fn(&mut EditorState, &mut Action) -> bool>;
+type AfterActionHook = Rc<dyn Fn(&mut EditorState, &Action)>;
+
+/// The registered mode-change callbacks.
+#[derive(Clone, Default)]
+pub(crate) struct ModeHooks {
+    hooks: Vec<ModeHook>,
+    /// Set while the hooks run, so that mode changes made by a hook do not
+    /// trigger the hooks again.
+    running: bool,
+}
+
+impl ModeHooks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+/// The registered callbacks around the execution of actions.
+#[derive(Clone, Default)]
+pub(crate) struct ActionHooks {
+    before: Vec<BeforeActionHook>,
+    after: Vec<AfterActionHook>,
+    /// Set while the hooks run, so that actions executed by a hook do not
+    /// trigger the hooks again.
+    running: bool,
+}
+
+impl EditorState {
+    /// Registers a callback that runs whenever an action or event moves the
+    /// editor to another mode. The callback receives the state together with
+    /// the old and the new mode.
+    ///
+    /// Mode changes made by the callback itself do not trigger the callbacks
+    /// again. Assigning [`EditorState::mode`] directly does not run them
+    /// either.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorMode, EditorState, Lines, RowIndex};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello  "));
+    /// state.on_mode_change(|state, old, _| {
+    ///     if old == EditorMode::Insert {
+    ///         for row in 0..state.lines.len() {
+    ///             let line = state.lines.get_mut(RowIndex::new(row)).unwrap();
+    ///             while line.last().is_some_and(|ch| ch.is_whitespace()) {
+    ///                 line.pop();
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn on_mode_change<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut EditorState, EditorMode, EditorMode) + 'static,
+    {
+        self.mode_hooks.hooks.push(Rc::new(hook));
+    }
+
+    /// Removes all mode-change callbacks.
+    pub fn clear_mode_hooks(&mut self) {
+        self.mode_hooks.hooks.clear();
+    }
+
+    /// Registers a callback that runs before an action is executed by the
+    /// key handler or by [`EditorState::apply_actions`]. The callback can
+    /// inspect or replace the action, and returns whether the action is
+    /// executed, e.g. to make the editor read-only.
+    ///
+    /// Actions executed by the callback itself do not trigger the callbacks.
+    /// Actions passed to [`EditorState::execute`] do not run them either.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{Action, InsertChar};
+    /// use edtui::{EditorEventHandler, EditorMode, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// state.before_action(|_, action| !matches!(action, Action::InsertChar(_)));
+    ///
+    /// let mut event_handler = EditorEventHandler::default();
+    /// event_handler.feed_keys("ix", &mut state).unwrap();
+    /// assert_eq!(state.mode, EditorMode::Insert);
+    /// assert_eq!(state.lines, Lines::from("Hello"));
+    /// ```
+    pub fn before_action<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut EditorState, &mut Action) -> bool + 'static,
+    {
+        self.action_hooks.before.push(Rc::new(hook));
+    }
+
+    /// Registers a callback that runs after an action was executed by the
+    /// key handler or by [`EditorState::apply_actions`], e.g. to log the
+    /// action or to format the text after a paste.
+    ///
+    /// Actions executed by the callback itself do not trigger the callbacks.
+    pub fn after_action<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut EditorState, &Action) + 'static,
+    {
+        self.action_hooks.after.push(Rc::new(hook));
+    }
+
+    /// Removes all callbacks registered with [`EditorState::before_action`]
+    /// and [`EditorState::after_action`].
+    pub fn clear_action_hooks(&mut self) {
+        self.action_hooks.before.clear();
+        self.action_hooks.after.clear();
+    }
+
+    /// Executes an action surrounded by the action callbacks.
+    pub(crate) fn run_action(&mut self, action: Action) {
+        self.with_action_hooks(action, |state, action| {
+            state.execute_change(action);
+            state.record_macro_action(action);
+        });
+    }
+
+    /// Runs `f` with an action surrounded by the action callbacks. `f` is
+    /// not called if a callback rejects the action.
+    pub(crate) fn with_action_hooks(
+        &mut self,
+        mut action: Action,
+        f: impl FnOnce(&mut Self, &mut Action),
+    ) {
+        if self.action_hooks.running {
+            action.execute(self);
+            return;
+        }
+        let hooks = self.action_hooks.clone();
+        if !hooks.before.is_empty() {
+            self.touch_all_rows();
+        }
+        self.action_hooks.running = true;
+        let allowed = hooks.before.iter().all(|hook| hook(self, &mut action));
+        self.action_hooks.running = false;
+        if !allowed {
+            return;
+        }
+
+        f(self, &mut action);
+
+        if !hooks.after.is_empty() {
+            self.touch_all_rows();
+        }
+        self.action_hooks.running = true;
+        for hook in &hooks.after {
+            hook(self, &action);
+        }
+        self.action_hooks.running = false;
+    }
+
+    /// Runs the mode-change callbacks if the mode differs from `old`.
+    pub(crate) fn run_mode_hooks(&mut self, old: EditorMode) {
+        if self.mode == old || self.mode_hooks.running || self.mode_hooks.is_empty() {
+            return;
+        }
+        self.touch_all_rows();
+        self.mode_hooks.running = true;
+        let new = self.mode;
+        for hook in self.mode_hooks.hooks.clone() {
+            hook(self, old, new);
+        }
+        self.mode_hooks.running = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, InsertChar, SwitchMode};
+    use crate::Lines;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_mode_hooks() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&changes);
+        state.on_mode_change(move |state, old, new| {
+            recorded.borrow_mut().push((old, new));
+            if new == EditorMode::Visual {
+                state.execute(SwitchMode(EditorMode::Normal));
+            }
+        });
+
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.execute(InsertChar('!'));
+        state.execute(SwitchMode(EditorMode::Normal));
+        state.execute(SwitchMode(EditorMode::Visual));
+
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(
+            *changes.borrow(),
+            vec![
+                (EditorMode::Normal, EditorMode::Insert),
+                (EditorMode::Insert, EditorMode::Normal),
+                (EditorMode::Normal, EditorMode::Visual),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_action_hooks() {
+        let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+        let executed = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&executed);
+        state.before_action(|_, action| {
+            if let Action::InsertChar(InsertChar(ch)) = action {
+                *ch = ch.to_ascii_uppercase();
+            }
+            !matches!(action, Action::DeleteLine(_))
+        });
+        state.after_action(move |state, action| {
+            recorded.borrow_mut().push(action.name());
+            state.run_action(SwitchMode(EditorMode::Normal).into());
+        });
+
+        state.apply_actions([
+            Action::from(DeleteLine(1)),
+            SwitchMode(EditorMode::Insert).into(),
+            InsertChar('h').into(),
+        ]);
+        assert_eq!(state.lines, Lines::from("HHello\nWorld"));
+        assert_eq!(state.mode, EditorMode::Normal);
+        assert_eq!(*executed.borrow(), vec!["SwitchMode", "InsertChar"]);
+
+        state.clear_action_hooks();
+        state.apply_actions([DeleteLine(1)]);
+        assert_eq!(state.lines, Lines::from("World"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Intents for effects that live outside of the text, such as saving it or
+//! opening another file.
+//!
+//! Actions push an [`EditorIntent`] to a queue on the state with
+//! [`EditorState::push_intent`] instead of changing the text. Apps drain the
+//! queue after handling events, e.g. once per frame, and act on the intents.
+//! Bind [`PushIntent`] to keys to add app-specific bindings.
+//!
+//! [`RequestExit`] and [`OpenFileUnderCursor`] push their intents to the
+//! queue if the app did not register a callback for them.
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::actions::PushIntent;
+//! use edtui::events::{KeyEvent, KeyEventRegister};
+//! use edtui::{EditorEventHandler, EditorIntent, EditorState, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("Hello"));
+//! let mut event_handler = EditorEventHandler::default();
+//! event_handler.key_handler.insert(
+//!     KeyEventRegister::n(vec![KeyEvent::Char(' '), KeyEvent::Char('t')]),
+//!     PushIntent(EditorIntent::Custom("toggle-tree".to_string())),
+//! );
+//!
+//! event_handler.feed_keys("ZZ<Space>t", &mut state).unwrap();
+//! let intents: Vec<_> = state.drain_intents().collect();
+//! assert_eq!(
+//!     intents,
+//!     vec![
+//!         EditorIntent::Save,
+//!         EditorIntent::Quit { force: false },
+//!         EditorIntent::Custom("toggle-tree".to_string()),
+//!     ]
+//! );
+//! assert!(!state.has_intents());
+//! ```
+//!
+//! [`PushIntent`]: crate::actions::PushIntent
+//! [`RequestExit`]: crate::actions::RequestExit
+//! [`OpenFileUnderCursor`]: crate::actions::OpenFileUnderCursor
+use std::collections::VecDeque;
+
+use super::exit::ExitRequest;
+use crate::{EditorState, FileReference};
+
+/// An effect that an action requests from the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorIntent {
+    /// Save the text.
+    Save,
+    /// Close the editor. With `force`, unsaved changes are discarded, like
+    /// `:q!` in vim.
+    Quit {
+        /// Whether unsaved changes are discarded.
+        force: bool,
+    },
+    /// Open a file, e.g. the one under the cursor.
+    OpenFile(FileReference),
+    /// An app-defined intent, identified by its name.
+    Custom(String),
+}
+
+impl EditorIntent {
+    /// Returns the intents that carry out an exit request, e.g. `Save` and
+    /// `Quit` for [`ExitRequest::SaveAndClose`].
+    pub(crate) fn from_exit(request: ExitRequest) -> Vec<Self> {
+        match request {
+            ExitRequest::SaveAndClose => vec![Self::Save, Self::Quit { force: false }],
+            ExitRequest::DiscardAndClose => vec![Self::Quit { force: true }],
+        }
+    }
+}
+
+/// The intents that were pushed and not yet drained by the app.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Intents {
+    queue: VecDeque<EditorIntent>,
+}
+
+impl EditorState {
+    /// Adds an intent to the end of the queue.
+    pub fn push_intent(&mut self, intent: EditorIntent) {
+        self.intents.queue.push_back(intent);
+    }
+
+    /// Removes the pending intents and returns them, oldest first.
+    pub fn drain_intents(&mut self) -> impl Iterator<Item = EditorIntent> + '_ {
+        self.intents.queue.drain(..)
+    }
+
+    /// Whether intents were pushed that were not yet drained.
+    #[must_use]
+    pub fn has_intents(&self) -> bool {
+        !self.intents.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::OpenFileUnderCursor;
+    use crate::{Index2, Lines};
+
+    #[test]
+    fn test_open_file_without_hook_pushes_intent() {
+        let mut state = EditorState::new(Lines::from("see notes.md:3"));
+        state.cursor = Index2::new(0, 6);
+
+        state.execute(OpenFileUnderCursor);
+        assert_eq!(
+            state.drain_intents().collect::<Vec<_>>(),
+            vec![EditorIntent::OpenFile(
+                FileReference::new("notes.md").line(3)
+            )]
+        );
+
+        state.on_open_file(|_, _| {});
+        state.execute(OpenFileUnderCursor);
+        assert!(!state.has_intents());
+    }
+}
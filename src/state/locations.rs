@@ -0,0 +1,178 @@
+//! A location list, similar to the quickfix and location lists in vim.
+//!
+//! Apps fill the list with positions and messages, e.g. from grep results,
+//! compiler output or validation errors. [`NextLocation`] and [`PrevLocation`]
+//! jump between the entries and briefly flash the target line. The message of
+//! the current entry is shown in the status line while the cursor is on its
+//! line. The positions move along with the text when it is edited.
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::actions::NextLocation;
+//! use edtui::{EditorState, Index2, Lines, Location};
+//!
+//! let mut state = EditorState::new(Lines::from("fn main() {\n    let x = 1;\n}"));
+//! state.set_locations(vec![Location::new(Index2::new(1, 8), "unused variable: `x`")]);
+//!
+//! state.execute(NextLocation);
+//! assert_eq!(state.cursor, Index2::new(1, 8));
+//! ```
+//!
+//! [`NextLocation`]: crate::actions::NextLocation
+//! [`PrevLocation`]: crate::actions::PrevLocation
+use std::time::{Duration, Instant};
+
+use super::edit::Edit;
+use crate::{validation::ValidationError, EditorState, Index2};
+
+/// How long the target line of a jump is flashed.
+const FLASH_DURATION: Duration = Duration::from_millis(400);
+
+/// An entry of the location list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// The position of the entry.
+    pub position: Index2,
+    /// A description of the entry.
+    pub message: String,
+}
+
+impl Location {
+    /// Creates a new entry at `position`.
+    #[must_use]
+    pub fn new<S: Into<String>>(position: Index2, message: S) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<&ValidationError> for Location {
+    fn from(error: &ValidationError) -> Self {
+        Self::new(error.start, error.message.clone())
+    }
+}
+
+/// The location list of an editor.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Locations {
+    pub(crate) entries: Vec<Location>,
+    /// The index of the entry that was jumped to last.
+    pub(crate) current: Option<usize>,
+    /// The row that was jumped to and when.
+    flash: Option<(usize, Instant)>,
+}
+
+impl Locations {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Selects the entry at `index` and flashes its line.
+    pub(crate) fn select(&mut self, index: usize) -> Option<Index2> {
+        let position = self.entries.get(index)?.position;
+        self.current = Some(index);
+        self.flash = Some((position.row, Instant::now()));
+        Some(position)
+    }
+
+    /// Returns the row that is currently flashed, if any.
+    pub(crate) fn flashed_row(&self) -> Option<usize> {
+        self.flash
+            .filter(|(_, since)| since.elapsed() < FLASH_DURATION)
+            .map(|(row, _)| row)
+    }
+
+    /// Removes the flash once it expired. Returns whether a row was flashed.
+    pub(crate) fn tick(&mut self) -> bool {
+        let active = self.flash.is_some();
+        if self.flashed_row().is_none() {
+            self.flash = None;
+        }
+        active
+    }
+
+    /// Moves the entries along with an edit.
+    pub(crate) fn adjust(&mut self, edit: &Edit) {
+        for entry in &mut self.entries {
+            entry.position = edit.map(entry.position);
+        }
+        self.flash = None;
+    }
+}
+
+impl EditorState {
+    /// Replaces the entries of the location list.
+    pub fn set_locations(&mut self, locations: Vec<Location>) {
+        self.locations = Locations {
+            entries: locations,
+            ..Locations::default()
+        };
+    }
+
+    /// Removes all entries of the location list.
+    pub fn clear_locations(&mut self) {
+        self.locations = Locations::default();
+    }
+
+    /// Returns the entries of the location list.
+    #[must_use]
+    pub fn locations(&self) -> &[Location] {
+        &self.locations.entries
+    }
+
+    /// Returns the entry that was jumped to last, if any.
+    #[must_use]
+    pub fn current_location(&self) -> Option<&Location> {
+        self.locations
+            .current
+            .and_then(|index| self.locations.entries.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{InsertNewline, NextLocation, PrevLocation};
+    use crate::{EditorMode, Lines};
+
+    fn test_state() -> EditorState {
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+        state.set_locations(vec![
+            Location::new(Index2::new(0, 0), "first"),
+            Location::new(Index2::new(2, 0), "second"),
+        ]);
+        state
+    }
+
+    #[test]
+    fn test_navigate_locations() {
+        let mut state = test_state();
+
+        state.execute(NextLocation);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+        assert_eq!(state.locations.flashed_row(), Some(0));
+
+        state.execute(NextLocation);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+        assert_eq!(state.current_location().unwrap().message, "second");
+
+        state.execute(NextLocation);
+        assert_eq!(state.cursor, Index2::new(0, 0));
+
+        state.execute(PrevLocation);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+    }
+
+    #[test]
+    fn test_locations_move_with_edits() {
+        let mut state = test_state();
+        state.mode = EditorMode::Insert;
+
+        state.execute(InsertNewline(1));
+
+        assert_eq!(state.locations()[1].position, Index2::new(3, 0));
+    }
+}
@@ -0,0 +1,304 @@
+//! Macros, recorded with `q` and played with `@` like in vim.
+//!
+//! A macro records the actions executed by the key handler or by
+//! [`EditorState::apply_actions`] while it is recorded. The macros are kept
+//! apart from the registers used to yank and paste text. `@@` plays the last
+//! played macro again.
+//!
+//! The keys typed while recording are kept as well, so that apps can store
+//! macros in vim notation, e.g. in a config file, with
+//! [`EditorState::macro_keys`] and restore or edit them with
+//! [`EditorEventHandler::set_macro_keys`]. A menu item or a button can play
+//! a macro with [`PlayMacro`](crate::actions::PlayMacro).
+//!
+//! ## Example
+//!
+//! ```
+//! use edtui::actions::PlayMacro;
+//! use edtui::{EditorEventHandler, EditorState, Lines};
+//!
+//! let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+//! let mut event_handler = EditorEventHandler::default();
+//! event_handler.feed_keys("qaA;<Esc>jq2@a", &mut state).unwrap();
+//! assert_eq!(state.lines, Lines::from("a;\nb;\nc;\nd"));
+//! assert_eq!(state.macro_keys('a').as_deref(), Some("A;<Esc>j"));
+//!
+//! event_handler.set_macro_keys('b', "I-<Esc>", &mut state).unwrap();
+//! state.execute(PlayMacro('b'));
+//! assert_eq!(state.lines, Lines::from("a;\nb;\nc;\n-d"));
+//! ```
+use std::collections::HashMap;
+
+use crate::actions::Action;
+use crate::events::{KeyEvent, KeyNotationError};
+use crate::{EditorEventHandler, EditorMode, EditorState};
+
+/// The recorded macros and the recording in progress.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Macros {
+    /// The recorded actions by register.
+    registers: HashMap<char, Vec<Action>>,
+    /// The typed keys by register, for the macros that were typed.
+    keys: HashMap<char, Vec<KeyEvent>>,
+    /// The recording in progress.
+    recording: Option<Recording>,
+    /// The register of the last played macro, played again by `@@`.
+    last_played: Option<char>,
+    /// The registers of the macros that are playing, so that a macro
+    /// cannot play itself.
+    playing: Vec<char>,
+    /// Whether the key handler handles a key.
+    typing: bool,
+}
+
+/// A macro that is being recorded.
+#[derive(Debug, Clone)]
+struct Recording {
+    register: char,
+    actions: Vec<Action>,
+    /// The typed keys, or `None` if the macro appends to a macro that was
+    /// not typed.
+    keys: Option<Vec<KeyEvent>>,
+}
+
+impl EditorState {
+    /// Returns the register a macro is recorded into, if any, e.g. to show
+    /// `recording @q` in the status line.
+    #[must_use]
+    pub fn recording_macro(&self) -> Option<char> {
+        self.macros
+            .recording
+            .as_ref()
+            .map(|recording| recording.register)
+    }
+
+    /// Returns the actions of the macro in `register`, if any.
+    #[must_use]
+    pub fn macro_actions(&self, register: char) -> Option<&[Action]> {
+        self.macros.registers.get(&register).map(Vec::as_slice)
+    }
+
+    /// Returns the keys of the macro in `register` in vim notation, if it
+    /// was typed, e.g. to store it in a config file. See
+    /// [`EditorEventHandler::set_macro_keys`] to restore it.
+    #[must_use]
+    pub fn macro_keys(&self, register: char) -> Option<String> {
+        let keys = self.macros.keys.get(&register)?;
+        Some(KeyEvent::format_sequence(keys))
+    }
+
+    /// Returns the registers that hold a macro, in alphabetical order.
+    #[must_use]
+    pub fn macro_registers(&self) -> Vec<char> {
+        let mut registers: Vec<char> = self.macros.registers.keys().copied().collect();
+        registers.sort_unstable();
+        registers
+    }
+
+    /// Stores the actions of a macro in `register`, e.g. to restore macros
+    /// recorded in an earlier session.
+    pub fn set_macro(&mut self, register: char, actions: Vec<Action>) {
+        self.macros.registers.insert(register, actions);
+        self.macros.keys.remove(&register);
+    }
+
+    /// Removes the macro in `register`.
+    pub fn clear_macro(&mut self, register: char) {
+        self.macros.registers.remove(&register);
+        self.macros.keys.remove(&register);
+    }
+
+    /// Starts recording a macro into `register`. An uppercase register
+    /// appends to its lowercase register.
+    pub(crate) fn start_recording_macro(&mut self, register: char) {
+        self.stop_recording_macro();
+        let append = register.is_uppercase();
+        let register = register.to_ascii_lowercase();
+        let actions = self.macros.registers.get(&register).filter(|_| append);
+        let keys = match actions {
+            Some(_) => self.macros.keys.get(&register).cloned(),
+            None => Some(Vec::new()),
+        };
+        self.macros.recording = Some(Recording {
+            register,
+            actions: actions.cloned().unwrap_or_default(),
+            keys,
+        });
+    }
+
+    /// Stores the macro in progress in its register.
+    pub(crate) fn stop_recording_macro(&mut self) {
+        if let Some(recording) = self.macros.recording.take() {
+            let register = recording.register;
+            self.macros.registers.insert(register, recording.actions);
+            match recording.keys {
+                Some(keys) => self.macros.keys.insert(register, keys),
+                None => self.macros.keys.remove(&register),
+            };
+        }
+    }
+
+    /// Adds an executed action to the macro in progress.
+    pub(crate) fn record_macro_action(&mut self, action: &Action) {
+        if let Some(recording) = &mut self.macros.recording {
+            if !matches!(
+                action,
+                Action::StartRecordingMacro(_) | Action::StopRecordingMacro(_)
+            ) {
+                recording.actions.push(action.clone());
+                // Actions that were not typed can't be written as keys
+                if !self.macros.typing {
+                    recording.keys = None;
+                }
+            }
+        }
+    }
+
+    /// Handles a key received by the key handler with `f` and adds the key
+    /// to the macro in progress. The keys that start and stop the recording
+    /// are not added.
+    pub(crate) fn record_macro_key<F: FnOnce(&mut Self)>(&mut self, key: KeyEvent, f: F) {
+        let recording = self.recording_macro();
+        self.macros.typing = true;
+        f(self);
+        self.macros.typing = false;
+
+        if recording.is_none() || self.recording_macro() != recording || key == KeyEvent::None {
+            return;
+        }
+        if let Some(keys) = self
+            .macros
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.keys.as_mut())
+        {
+            keys.push(key);
+        }
+    }
+
+    /// Executes the actions of the macro in `register`, or of the last
+    /// played macro for `@`.
+    pub(crate) fn play_macro(&mut self, register: char) {
+        let register = match register {
+            '@' => self.macros.last_played,
+            register => Some(register.to_ascii_lowercase()),
+        };
+        let Some(register) = register.filter(|register| !self.macros.playing.contains(register))
+        else {
+            return;
+        };
+        let Some(actions) = self.macros.registers.get(&register).cloned() else {
+            return;
+        };
+        self.macros.last_played = Some(register);
+        self.macros.playing.push(register);
+        for mut action in actions {
+            self.execute_change(&mut action);
+        }
+        self.macros.playing.pop();
+    }
+}
+
+impl EditorEventHandler {
+    /// Stores a macro in `register` from keys in vim notation, e.g. a macro
+    /// read from a config file or edited by the user. The keys are handled
+    /// in normal mode on a copy of the text to find the actions of the
+    /// macro, the text itself is not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keys can't be parsed. No macro is stored then.
+    pub fn set_macro_keys(
+        &self,
+        register: char,
+        notation: &str,
+        state: &mut EditorState,
+    ) -> Result<(), KeyNotationError> {
+        let keys = KeyEvent::parse_sequence(notation)?;
+        let register = register.to_ascii_lowercase();
+
+        let mut scratch = EditorState::new(state.lines.clone());
+        scratch.cursor = state.cursor;
+        scratch.mode = EditorMode::Normal;
+        scratch.start_recording_macro(register);
+        let mut handler = self.clone();
+        for key in &keys {
+            handler.on_key_event(*key, &mut scratch);
+        }
+        let actions = match scratch.macros.recording.take() {
+            Some(recording) => recording.actions,
+            // The keys stopped the recording themselves
+            None => scratch
+                .macros
+                .registers
+                .remove(&register)
+                .unwrap_or_default(),
+        };
+
+        state.macros.registers.insert(register, actions);
+        state.macros.keys.insert(register, keys);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::{Action, MoveDown, PlayMacro, StartRecordingMacro, StopRecordingMacro};
+    use crate::{EditorEventHandler, EditorState, Lines};
+
+    #[test]
+    fn test_macros() {
+        let mut state = EditorState::new(Lines::from("1\n2\n3\n4\n5\n6"));
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("qaI-<Esc>jq", &mut state).unwrap();
+        assert_eq!(state.recording_macro(), None);
+        assert_eq!(state.macro_actions('a').map(<[Action]>::len), Some(4));
+
+        handler.feed_keys("@a2@@", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("-1\n-2\n-3\n-4\n5\n6"));
+
+        // An uppercase register appends to the macro
+        handler.feed_keys("qAxq", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("-1\n-2\n-3\n-4\n\n6"));
+        handler.feed_keys("u@a", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("-1\n-2\n-3\n-4\n-5\n"));
+
+        // A macro that plays itself stops instead of recursing
+        handler.feed_keys("ggqbx@bqj@b", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("1\n2\n-3\n-4\n-5\n"));
+
+        // The keys are kept in vim notation, but not for macros set as actions
+        assert_eq!(state.macro_keys('a').as_deref(), Some("I-<Esc>jx"));
+        assert_eq!(state.macro_registers(), vec!['a', 'b']);
+
+        state.execute(StartRecordingMacro('c'));
+        state.apply_actions([MoveDown(1)]);
+        assert_eq!(state.recording_macro(), Some('c'));
+        state.execute(StopRecordingMacro);
+        assert_eq!(state.macro_actions('c').map(<[Action]>::len), Some(1));
+        assert_eq!(state.macro_keys('c'), None);
+    }
+
+    #[test]
+    fn test_set_macro_keys() {
+        let mut state = EditorState::new(Lines::from("a\nb"));
+        let handler = EditorEventHandler::default();
+
+        handler.set_macro_keys('a', "A!<Esc>j", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("a\nb"));
+        assert_eq!(state.macro_keys('a').as_deref(), Some("A!<Esc>j"));
+
+        state.execute(PlayMacro('a'));
+        state.execute(PlayMacro('@'));
+        assert_eq!(state.lines, Lines::from("a!\nb!"));
+
+        assert!(handler.set_macro_keys('b', "<Nope>", &mut state).is_err());
+        assert!(state.macro_actions('b').is_none());
+
+        state.set_macro('a', Vec::new());
+        assert_eq!(state.macro_keys('a'), None);
+        state.clear_macro('a');
+        assert!(state.macro_registers().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! Typed metadata attached to lines.
+//!
+//! Apps can attach arbitrary values to lines, e.g. a message id or a git
+//! blame entry. The metadata moves with its line when lines are inserted or
+//! deleted above it, and it is dropped when its line is deleted or joined
+//! into the line above.
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use super::edit::Edit;
+use crate::{EditorState, Index2};
+
+/// The line metadata of an editor, grouped by type.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LineMetadata {
+    types: HashMap<TypeId, BTreeMap<usize, Rc<dyn Any>>>,
+}
+
+impl LineMetadata {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.types.values().all(BTreeMap::is_empty)
+    }
+
+    /// Moves the metadata along with an edit. Metadata of lines that were
+    /// deleted, or that no longer start a line, is removed.
+    pub(crate) fn adjust(&mut self, edit: &Edit) {
+        for rows in self.types.values_mut() {
+            *rows = std::mem::take(rows)
+                .into_iter()
+                .filter_map(|(row, value)| {
+                    if edit.is_row_deleted(row) {
+                        return None;
+                    }
+                    let index = edit.map(Index2::new(row, 0));
+                    (index.col == 0).then_some((index.row, value))
+                })
+                .collect();
+        }
+    }
+}
+
+impl EditorState {
+    /// Attaches a value to a line, replacing any previous value of the
+    /// same type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    /// use edtui::actions::InsertNewline;
+    ///
+    /// let mut state = EditorState::new(Lines::from("first\nsecond"));
+    /// state.set_line_metadata(1, 42_u32);
+    ///
+    /// state.execute(InsertNewline(1));
+    /// assert_eq!(state.line_metadata::<u32>(2), Some(&42));
+    /// ```
+    pub fn set_line_metadata<T: Any>(&mut self, row: usize, value: T) {
+        self.metadata
+            .types
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(row, Rc::new(value));
+    }
+
+    /// Returns the value of type `T` attached to a line.
+    #[must_use]
+    pub fn line_metadata<T: Any>(&self, row: usize) -> Option<&T> {
+        self.metadata
+            .types
+            .get(&TypeId::of::<T>())?
+            .get(&row)?
+            .downcast_ref()
+    }
+
+    /// Returns all lines with a value of type `T` attached, ordered by row.
+    pub fn iter_line_metadata<T: Any>(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.metadata
+            .types
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .filter_map(|(row, value)| Some((*row, value.downcast_ref()?)))
+    }
+
+    /// Removes the value of type `T` from a line.
+    pub fn remove_line_metadata<T: Any>(&mut self, row: usize) {
+        if let Some(rows) = self.metadata.types.get_mut(&TypeId::of::<T>()) {
+            rows.remove(&row);
+        }
+    }
+
+    /// Removes the values of type `T` from all lines.
+    pub fn clear_line_metadata<T: Any>(&mut self) {
+        self.metadata.types.remove(&TypeId::of::<T>());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, JoinLineWithLineBelow, LineBreak};
+    use crate::{EditorMode, Lines};
+
+    fn test_state() -> EditorState {
+        let mut state = EditorState::new(Lines::from("Hello World!\n\n123."));
+        state.set_line_metadata(0, "first");
+        state.set_line_metadata(2, "third");
+        state
+    }
+
+    #[test]
+    fn test_metadata_moves_with_lines() {
+        let mut state = test_state();
+        state.mode = EditorMode::Insert;
+        state.cursor = Index2::new(0, 0);
+
+        state.execute(LineBreak(1));
+
+        assert_eq!(state.line_metadata::<&str>(0), None);
+        assert_eq!(state.line_metadata::<&str>(1), Some(&"first"));
+        assert_eq!(state.line_metadata::<&str>(3), Some(&"third"));
+    }
+
+    #[test]
+    fn test_metadata_is_removed_with_deleted_lines() {
+        let mut state = test_state();
+
+        state.execute(DeleteLine(1));
+
+        assert_eq!(
+            state.iter_line_metadata::<&str>().collect::<Vec<_>>(),
+            vec![(1, &"third")]
+        );
+    }
+
+    #[test]
+    fn test_metadata_is_removed_with_joined_lines() {
+        let mut state = EditorState::new(Lines::from("Hello\nWorld!"));
+        state.set_line_metadata(1, "second");
+
+        state.execute(JoinLineWithLineBelow);
+
+        assert_eq!(state.line_metadata::<&str>(0), None);
+    }
+
+    #[test]
+    fn test_metadata_is_typed() {
+        let mut state = test_state();
+        state.set_line_metadata(0, 1_u8);
+
+        state.clear_line_metadata::<&str>();
+
+        assert_eq!(state.line_metadata::<&str>(0), None);
+        assert_eq!(state.line_metadata::<u8>(0), Some(&1));
+    }
+}
@@ -6,6 +6,43 @@ pub enum EditorMode {
     Insert,
     Visual,
     Search,
+    /// Typing a command into the command line, entered with `:`. See
+    /// [`command`](crate::state::command).
+    Command,
+    /// A host-defined mode, identified by its name.
+    ///
+    /// Custom modes have no built-in behavior: keys do nothing unless they
+    /// are bound for the mode, and the mode is entered and left with
+    /// [`SwitchMode`](crate::actions::SwitchMode) like any other mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{MoveDown, MoveUp, SwitchMode};
+    /// use edtui::events::{KeyEvent, KeyEventRegister};
+    /// use edtui::{EditorEventHandler, EditorMode};
+    ///
+    /// const PICK: EditorMode = EditorMode::Custom("Pick");
+    ///
+    /// let mut handler = EditorEventHandler::default();
+    /// handler.key_handler.insert(
+    ///     KeyEventRegister::n(vec![KeyEvent::Char('P')]),
+    ///     SwitchMode(PICK),
+    /// );
+    /// handler.key_handler.insert(
+    ///     KeyEventRegister::new(vec![KeyEvent::Char('j')], PICK),
+    ///     MoveDown(1),
+    /// );
+    /// handler.key_handler.insert(
+    ///     KeyEventRegister::new(vec![KeyEvent::Char('k')], PICK),
+    ///     MoveUp(1),
+    /// );
+    /// handler.key_handler.insert(
+    ///     KeyEventRegister::new(vec![KeyEvent::Esc], PICK),
+    ///     SwitchMode(EditorMode::Normal),
+    /// );
+    /// ```
+    Custom(&'static str),
 }
 
 impl EditorMode {
@@ -17,6 +54,8 @@ impl EditorMode {
             Self::Insert => "Insert".to_string(),
             Self::Visual => "Visual".to_string(),
             Self::Search => "Search".to_string(),
+            Self::Command => "Command".to_string(),
+            Self::Custom(name) => (*name).to_string(),
         }
     }
 }
@@ -0,0 +1,193 @@
+//! Short messages for the user, such as the number of yanked lines.
+//!
+//! Notifications are shown in the status line and disappear after a while.
+//! Built-in actions use them to report what they did, and apps can show their
+//! own with [`EditorState::notify`].
+use std::time::{Duration, Instant};
+
+use crate::EditorState;
+
+/// How long a notification is shown by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The severity of a [`Notification`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NotificationLevel {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// A message for the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+}
+
+impl Notification {
+    /// Creates a new notification.
+    pub fn new<S: Into<String>>(level: NotificationLevel, message: S) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a new informational notification.
+    pub fn info<S: Into<String>>(message: S) -> Self {
+        Self::new(NotificationLevel::Info, message)
+    }
+
+    /// Creates a new warning.
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        Self::new(NotificationLevel::Warning, message)
+    }
+
+    /// Creates a new error notification.
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Self::new(NotificationLevel::Error, message)
+    }
+}
+
+impl From<&str> for Notification {
+    fn from(message: &str) -> Self {
+        Self::info(message)
+    }
+}
+
+impl From<String> for Notification {
+    fn from(message: String) -> Self {
+        Self::info(message)
+    }
+}
+
+/// The current notification of an editor.
+#[derive(Debug, Clone)]
+pub(crate) struct Notifications {
+    /// How long a notification is shown.
+    timeout: Duration,
+    current: Option<(Notification, Instant)>,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            current: None,
+        }
+    }
+}
+
+impl Notifications {
+    /// Returns the current notification, unless it expired.
+    pub(crate) fn current(&self) -> Option<&Notification> {
+        self.current
+            .as_ref()
+            .filter(|(_, since)| since.elapsed() < self.timeout)
+            .map(|(notification, _)| notification)
+    }
+
+    /// Removes the notification once it expired. Returns whether a
+    /// notification was shown.
+    pub(crate) fn tick(&mut self) -> bool {
+        let active = self.current.is_some();
+        if self.current().is_none() {
+            self.current = None;
+        }
+        active
+    }
+}
+
+impl EditorState {
+    /// Shows a notification in the status line, replacing the previous one.
+    /// Plain strings are shown as informational notifications.
+    ///
+    /// The notification disappears after a timeout, see
+    /// [`EditorState::set_notification_timeout`]. Apps should call
+    /// [`EditorEventHandler::on_tick`] periodically and redraw when it
+    /// returns `true`.
+    ///
+    /// [`EditorEventHandler::on_tick`]: crate::EditorEventHandler::on_tick
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines, Notification};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// state.notify("Saved");
+    /// state.notify(Notification::error("Could not save"));
+    ///
+    /// assert_eq!(state.notification().unwrap().message, "Could not save");
+    /// ```
+    pub fn notify<N: Into<Notification>>(&mut self, notification: N) {
+        self.notifications.current = Some((notification.into(), Instant::now()));
+    }
+
+    /// Returns the notification that is currently shown, if any.
+    #[must_use]
+    pub fn notification(&self) -> Option<&Notification> {
+        self.notifications.current()
+    }
+
+    /// Removes the notification that is currently shown.
+    pub fn clear_notification(&mut self) {
+        self.notifications.current = None;
+    }
+
+    /// Sets how long notifications are shown. Defaults to 3 seconds.
+    pub fn set_notification_timeout(&mut self, timeout: Duration) {
+        self.notifications.timeout = timeout;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{CopySelection, FindNext, InsertChar, SwitchMode};
+    use crate::clipboard::InternalClipboard;
+    use crate::state::selection::Selection;
+    use crate::{EditorMode, Field, Index2, Lines};
+
+    #[test]
+    fn test_notifications() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\na"));
+        state.set_clipboard(InternalClipboard::default());
+        state.notify("Hello");
+        assert_eq!(state.notification(), Some(&Notification::info("Hello")));
+
+        state.set_notification_timeout(Duration::ZERO);
+        assert_eq!(state.notification(), None);
+        assert!(state.notifications.tick());
+        assert!(!state.notifications.tick());
+        state.set_notification_timeout(Duration::from_secs(60));
+
+        state.selection = Some(Selection::new(Index2::new(0, 0), Index2::new(2, 0)));
+        state.execute(CopySelection);
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::info("3 lines yanked"))
+        );
+
+        state.set_search_pattern("a");
+        state.execute(FindNext);
+        state.execute(FindNext);
+        assert_eq!(
+            state.notification(),
+            Some(&Notification::warning(
+                "Search hit BOTTOM, continuing at TOP"
+            ))
+        );
+
+        state.set_fields(vec![Field::new("b", Index2::new(1, 0), Index2::new(1, 0))]);
+        state.cursor = Index2::new(0, 0);
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.execute(InsertChar('!'));
+        assert_eq!(
+            state.notification().map(|n| n.level),
+            Some(NotificationLevel::Warning)
+        );
+    }
+}
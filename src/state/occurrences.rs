@@ -0,0 +1,170 @@
+//! The word under the cursor and its occurrences.
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use jagged::index::RowIndex;
+
+use crate::{EditorState, Index2, Lines};
+
+/// Whether a character is part of a word.
+pub(crate) fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// State for highlighting the occurrences of the word under the cursor.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OccurrenceState {
+    /// How long the cursor must rest on a word before its occurrences are
+    /// highlighted. `None` disables the highlighting.
+    pub(crate) debounce: Option<Duration>,
+    /// The last word under the cursor and when the cursor moved onto it.
+    word: Option<(String, Instant)>,
+}
+
+impl OccurrenceState {
+    /// Whether the cursor rests on a word whose occurrences are not
+    /// highlighted yet.
+    pub(crate) fn is_pending(&self) -> bool {
+        match (self.debounce, &self.word) {
+            (Some(debounce), Some((_, since))) => since.elapsed() < debounce,
+            _ => false,
+        }
+    }
+
+    /// Records the word under the cursor. Returns whether the occurrences of
+    /// the word should be highlighted.
+    pub(crate) fn update(&mut self, word: Option<&String>) -> bool {
+        let (Some(debounce), Some(word)) = (self.debounce, word) else {
+            self.word = None;
+            return false;
+        };
+
+        match &self.word {
+            Some((last, since)) if last == word => since.elapsed() >= debounce,
+            _ => {
+                self.word = Some((word.clone(), Instant::now()));
+                debounce.is_zero()
+            }
+        }
+    }
+}
+
+impl EditorState {
+    /// Returns the word under the cursor, if any. A word consists of
+    /// alphanumeric characters and underscores.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("let my_var = 1;"));
+    /// state.cursor = Index2::new(0, 6);
+    ///
+    /// assert_eq!(state.word_under_cursor(), Some(String::from("my_var")));
+    /// ```
+    #[must_use]
+    pub fn word_under_cursor(&self) -> Option<String> {
+        let line = self.lines.get(RowIndex::new(self.cursor.row))?;
+        let range = word_range(line, self.cursor.col)?;
+        Some(line[range].iter().collect())
+    }
+}
+
+/// Returns the columns of the word at `col`.
+pub(crate) fn word_range(line: &[char], col: usize) -> Option<Range<usize>> {
+    if !is_word_char(*line.get(col)?) {
+        return None;
+    }
+
+    let start = line[..col]
+        .iter()
+        .rposition(|&ch| !is_word_char(ch))
+        .map_or(0, |i| i + 1);
+    let end = line[col..]
+        .iter()
+        .position(|&ch| !is_word_char(ch))
+        .map_or(line.len(), |i| col + i);
+
+    Some(start..end)
+}
+
+/// Finds the whole word occurrences of `word` within the given rows. Returns
+/// the start position of each occurrence.
+pub(crate) fn find_word_occurrences(
+    lines: &Lines,
+    word: &[char],
+    rows: Range<usize>,
+) -> Vec<Index2> {
+    let mut occurrences = Vec::new();
+    if word.is_empty() {
+        return occurrences;
+    }
+
+    for (row, line) in lines
+        .iter_row()
+        .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
+    {
+        let mut col = 0;
+        while col + word.len() <= line.len() {
+            let end = col + word.len();
+            let is_boundary_before = col == 0 || !is_word_char(line[col - 1]);
+            let is_boundary_after = end == line.len() || !is_word_char(line[end]);
+            if is_boundary_before && is_boundary_after && &line[col..end] == word {
+                occurrences.push(Index2::new(row, col));
+                col = end;
+            } else {
+                col += 1;
+            }
+        }
+    }
+
+    occurrences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_under_cursor() {
+        let mut state = EditorState::new(Lines::from("foo(bar_1, baz)"));
+
+        state.cursor = Index2::new(0, 6);
+        assert_eq!(state.word_under_cursor(), Some(String::from("bar_1")));
+
+        state.cursor = Index2::new(0, 3);
+        assert_eq!(state.word_under_cursor(), None);
+
+        state.cursor = Index2::new(0, 99);
+        assert_eq!(state.word_under_cursor(), None);
+    }
+
+    #[test]
+    fn test_find_word_occurrences() {
+        let lines = Lines::from("foo foobar\nbar foo\nfoo");
+        let word: Vec<char> = "foo".chars().collect();
+
+        let occurrences = find_word_occurrences(&lines, &word, 0..2);
+
+        assert_eq!(occurrences, vec![Index2::new(0, 0), Index2::new(1, 4)]);
+    }
+
+    #[test]
+    fn test_occurrence_debounce() {
+        let mut occurrences = OccurrenceState::default();
+        let word = String::from("foo");
+        assert!(!occurrences.update(Some(&word)));
+
+        occurrences.debounce = Some(Duration::from_secs(60));
+        assert!(!occurrences.update(Some(&word)));
+        assert!(!occurrences.update(Some(&word)));
+
+        occurrences.debounce = Some(Duration::ZERO);
+        assert!(occurrences.update(Some(&word)));
+    }
+}
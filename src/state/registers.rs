@@ -0,0 +1,119 @@
+//! Registers that keep yanked and deleted texts apart from the clipboard,
+//! similar to the registers of vim.
+use std::collections::{HashMap, VecDeque};
+
+use crate::clipboard::ClipboardTrait;
+use crate::EditorState;
+
+/// The number of numbered registers, `"1` to `"9`.
+const NUMBERED: usize = 9;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Registers {
+    /// The last deletions of one or more lines, the most recent first.
+    numbered: VecDeque<String>,
+    /// The last deletion within a line.
+    small_delete: Option<String>,
+    /// The named registers `"a` to `"z`.
+    named: HashMap<char, String>,
+    /// The register chosen for the next yank, deletion or paste.
+    pub(crate) selected: Option<char>,
+}
+
+impl Registers {
+    fn get(&self, register: char) -> Option<&str> {
+        match register {
+            '1'..='9' => self
+                .numbered
+                .get(register as usize - '1' as usize)
+                .map(String::as_str),
+            '-' => self.small_delete.as_deref(),
+            _ => self.named.get(&register).map(String::as_str),
+        }
+    }
+}
+
+impl EditorState {
+    /// Copies a deleted text to the clipboard, the yank ring and the
+    /// registers. Deletions of whole or several lines shift through the
+    /// numbered registers, smaller deletions replace the `"-` register.
+    pub(crate) fn yank_deleted(&mut self, text: String) {
+        if text.contains('\n') {
+            self.registers.numbered.push_front(text.clone());
+            self.registers.numbered.truncate(NUMBERED);
+        } else if !text.is_empty() {
+            self.registers.small_delete = Some(text.clone());
+        }
+        self.yank(text);
+    }
+
+    /// Stores a yanked text in the selected named register, if any.
+    pub(crate) fn yank_to_register(&mut self, text: &str) {
+        if let Some(register) = self.registers.selected.take() {
+            if register.is_ascii_lowercase() {
+                self.registers.named.insert(register, text.to_string());
+            }
+        }
+    }
+
+    /// Returns the text to paste: the content of the selected register, or
+    /// the clipboard if no register is selected.
+    pub(crate) fn paste_text(&mut self) -> String {
+        match self.registers.selected.take() {
+            Some(register) => self.register(register).unwrap_or_default().to_string(),
+            None => self.clip.get_text(),
+        }
+    }
+
+    /// Returns the content of a register: `'1'` to `'9'` hold the last
+    /// deletions of whole or several lines, the most recent first, `'-'`
+    /// holds the last deletion within a line and `'a'` to `'z'` hold the
+    /// texts yanked into them, e.g. with `"ayy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{delete::DeleteToEndOfLine, DeleteLine, MoveForward};
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("first\nsecond"));
+    /// state.execute(DeleteLine(1));
+    /// state.execute(MoveForward(3));
+    /// state.execute(DeleteToEndOfLine);
+    ///
+    /// assert_eq!(state.register('1'), Some("\nfirst"));
+    /// assert_eq!(state.register('-'), Some("ond"));
+    /// ```
+    #[must_use]
+    pub fn register(&self, register: char) -> Option<&str> {
+        self.registers.get(register)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clipboard::InternalClipboard;
+    use crate::{EditorEventHandler, EditorState, Lines};
+
+    #[test]
+    fn test_registers() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc\nd"));
+        state.set_clipboard(InternalClipboard::default());
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("dddd", &mut state).unwrap();
+        assert_eq!(state.register('1'), Some("\nb"));
+        assert_eq!(state.register('2'), Some("\na"));
+
+        handler.feed_keys("\"2p", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("c\na\nd"));
+
+        handler.feed_keys("\"ayyk\"ap", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("c\na\na\nd"));
+        assert_eq!(state.register('a'), Some("\na"));
+
+        handler.feed_keys("$D\"-p", &mut state).unwrap();
+        assert_eq!(state.register('-'), Some("a"));
+        assert_eq!(state.lines, Lines::from("c\na\na\nd"));
+    }
+}
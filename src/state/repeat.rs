@@ -0,0 +1,116 @@
+//! The last change, repeated with `.` like in vim.
+//!
+//! Actions executed in normal mode by the key handler or by
+//! [`EditorState::apply_actions`] are recorded if they change the text or
+//! enter insert mode. For the latter, the text typed until insert mode is
+//! left is recorded as well.
+use crate::actions::{Action, Execute, InsertChar};
+use crate::{EditorMode, EditorState};
+
+/// The last change, see [`EditorState::last_change`].
+#[derive(Debug, Clone)]
+pub(crate) struct LastChange {
+    /// The action that made the change.
+    pub(crate) action: Action,
+    /// The text typed after the action entered insert mode.
+    pub(crate) inserted: String,
+    /// Whether insert mode was not left since the action entered it.
+    typing: bool,
+}
+
+impl EditorState {
+    /// Returns the action that made the last change in normal mode, as
+    /// repeated by [`RepeatLastChange`](crate::actions::RepeatLastChange).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::Action;
+    /// use edtui::{EditorEventHandler, EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// let mut event_handler = EditorEventHandler::default();
+    /// event_handler.feed_keys("lx.", &mut state).unwrap();
+    /// assert_eq!(state.lines, Lines::from("Hlo"));
+    /// assert!(matches!(state.last_change(), Some(Action::RemoveChar(_))));
+    /// ```
+    #[must_use]
+    pub fn last_change(&self) -> Option<&Action> {
+        self.last_change.as_ref().map(|change| &change.action)
+    }
+
+    /// Executes an action and records it as the last change if it changed
+    /// the text or entered insert mode from normal mode.
+    pub(crate) fn execute_change(&mut self, action: &mut Action) {
+        if let Action::InsertChar(InsertChar(ch)) = action {
+            if self.insert_into_gap(*ch) {
+                return;
+            }
+        }
+        self.close_gap();
+
+        let repeatable = self.mode == EditorMode::Normal
+            && !matches!(
+                action,
+                Action::Undo(_)
+                    | Action::Redo(_)
+                    | Action::UndoN(_)
+                    | Action::RedoN(_)
+                    | Action::RepeatLastChange(_)
+                    | Action::PlayMacro(_)
+            );
+        if !repeatable {
+            action.execute(self);
+            return;
+        }
+
+        self.sync_edits();
+        let edits = self.edit_tracker.edits;
+        action.execute(self);
+        self.sync_edits();
+        let typing = self.mode == EditorMode::Insert;
+        if typing || self.edit_tracker.edits != edits {
+            self.last_change = Some(LastChange {
+                action: action.clone(),
+                inserted: String::new(),
+                typing,
+            });
+        }
+    }
+
+    /// Records the text typed since the last change entered insert mode.
+    /// Called when insert mode is left.
+    pub(crate) fn finish_change_insert(&mut self, inserted: &str) {
+        if let Some(change) = self.last_change.as_mut().filter(|change| change.typing) {
+            change.inserted = inserted.to_string();
+            change.typing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EditorEventHandler, EditorState, Lines};
+
+    #[test]
+    fn test_repeat_last_change() {
+        let mut state = EditorState::new(Lines::from("one two three\nfour five six"));
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("ciwzero<Esc>w.", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("zero zero three\nfour five six"));
+
+        handler.feed_keys("A!<Esc>j.", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("zero zero three!\nfour five six!"));
+
+        // Motions and undo do not replace the last change
+        handler.feed_keys("0wu.", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("zero zero three!\nfour five six!"));
+
+        handler.feed_keys("0x..", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("zero zero three!\nr five six!"));
+
+        handler.feed_keys("ggdd.", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from(""));
+    }
+}
@@ -1,40 +1,233 @@
+use std::ops::Range;
+
 use jagged::Index2;
 
-use crate::Lines;
+use crate::{helper::map_lines, Lines};
 
 use super::selection::Selection;
 
+/// The part of the text that search and replace operate on.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// The entire text (default).
+    #[default]
+    Buffer,
+    /// A range of rows, e.g. `:3,7s/…/…/` in vim.
+    Rows(Range<usize>),
+    /// The current visual selection or, outside of visual mode, the last
+    /// visual selection, e.g. `:'<,'>s/…/…/` in vim.
+    Selection,
+}
+
+/// Where the cursor lands relative to a search match, like the offset in
+/// `/pattern/e` in vim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchOffset {
+    /// A number of characters from the start of the match, e.g. `/foo/s+1`.
+    /// `Start(0)` places the cursor at the start of the match (default).
+    Start(isize),
+    /// A number of characters from the last character of the match, e.g.
+    /// `/foo/e` or `/foo/e-1`.
+    End(isize),
+    /// A number of lines below or above the match, in the first column, e.g.
+    /// `/foo/+1` or `/foo/-2`.
+    Lines(isize),
+}
+
+impl Default for SearchOffset {
+    fn default() -> Self {
+        Self::Start(0)
+    }
+}
+
+impl SearchOffset {
+    /// Parses an offset as written after the pattern in vim, such as `e`,
+    /// `e-1`, `s+2`, `b`, `+1` or `-3`. An empty offset is `Start(0)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::SearchOffset;
+    ///
+    /// assert_eq!(SearchOffset::parse("e-1"), Some(SearchOffset::End(-1)));
+    /// assert_eq!(SearchOffset::parse("+"), Some(SearchOffset::Lines(1)));
+    /// assert_eq!(SearchOffset::parse("x"), None);
+    /// ```
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        if let Some(rest) = text.strip_prefix('e') {
+            return parse_count(rest).map(Self::End);
+        }
+        if let Some(rest) = text.strip_prefix(['s', 'b']) {
+            return parse_count(rest).map(Self::Start);
+        }
+        match text {
+            "" => Some(Self::Start(0)),
+            _ => parse_count(text).map(Self::Lines),
+        }
+    }
+
+    /// Returns the cursor position for a match of `len` characters at
+    /// `start`, clamped to the text.
+    pub(crate) fn apply(self, lines: &Lines, start: Index2, len: usize) -> Index2 {
+        let line_len = |row| lines.len_col(row).unwrap_or_default();
+        let clamp_col = |row, col: isize| {
+            let max = line_len(row).saturating_sub(1);
+            col.clamp(0, max as isize) as usize
+        };
+        match self {
+            Self::Start(n) => Index2::new(start.row, clamp_col(start.row, start.col as isize + n)),
+            Self::End(n) => {
+                let end = start.col + len.saturating_sub(1);
+                Index2::new(start.row, clamp_col(start.row, end as isize + n))
+            }
+            Self::Lines(n) => {
+                let last = lines.len().saturating_sub(1) as isize;
+                let row = (start.row as isize + n).clamp(0, last);
+                Index2::new(row as usize, 0)
+            }
+        }
+    }
+}
+
+/// Parses the count of an offset: empty is `0`, a lone sign is `1` or `-1`.
+fn parse_count(text: &str) -> Option<isize> {
+    match text {
+        "" => Some(0),
+        "+" => Some(1),
+        "-" => Some(-1),
+        _ => text.strip_prefix('+').unwrap_or(text).parse().ok(),
+    }
+}
+
 /// Represents the state of a search operation
 /// Including the search pattern, matched indices and selected index.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct SearchState {
     pub(crate) start_cursor: Index2,
     pub(crate) pattern: String,
+    /// The text that replaces the pattern.
+    pub(crate) replacement: String,
     pub(crate) matches: Vec<Index2>,
+    /// The number of characters of each match.
+    pub(crate) lengths: Vec<usize>,
     pub(crate) selected_index: Option<usize>,
+    pub(crate) scope: SearchScope,
+    /// The bounds of the scope when the search was started. Matches outside
+    /// of them are ignored.
+    pub(crate) bounds: Option<Selection>,
+    /// Whether searches wrap around the end of the text.
+    pub(crate) wrap_scan: bool,
+    /// Whether searches ignore the case of letters.
+    pub(crate) ignore_case: bool,
+    /// Whether the last jump to a match wrapped around.
+    pub(crate) wrapped: bool,
+    /// Whether patterns are regular expressions unless they start with `\V`.
+    pub(crate) regex: bool,
+    /// Where the cursor lands relative to a match.
+    pub(crate) offset: SearchOffset,
+    /// The offset typed after the pattern in search mode, e.g. `e` in
+    /// `foo/e`, or `None` if no `/` was typed.
+    pub(crate) offset_query: Option<String>,
+    /// The offset of the last confirmed search, which takes precedence over
+    /// [`SearchState::offset`].
+    pub(crate) typed_offset: Option<SearchOffset>,
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        Self {
+            start_cursor: Index2::default(),
+            pattern: String::new(),
+            replacement: String::new(),
+            matches: Vec::new(),
+            lengths: Vec::new(),
+            selected_index: None,
+            scope: SearchScope::default(),
+            bounds: None,
+            wrap_scan: true,
+            ignore_case: false,
+            wrapped: false,
+            regex: false,
+            offset: SearchOffset::default(),
+            offset_query: None,
+            typed_offset: None,
+        }
+    }
 }
 
 impl SearchState {
-    pub(crate) fn len(&self) -> usize {
-        self.pattern.len()
+    /// Returns the number of characters of the selected match.
+    pub(crate) fn selected_len(&self) -> usize {
+        self.selected_index
+            .and_then(|i| self.lengths.get(i))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether the pattern is matched as a regular expression: with a `\v`
+    /// prefix, or by default unless it has a `\V` prefix. Always `false`
+    /// without the `regex` feature.
+    pub(crate) fn is_regex(&self) -> bool {
+        let (magic, _) = split_magic(&self.pattern);
+        cfg!(feature = "regex") && magic.unwrap_or(self.regex)
+    }
+
+    /// Returns the pattern without its `\v` or `\V` prefix.
+    pub(crate) fn pattern_without_magic(&self) -> &str {
+        split_magic(&self.pattern).1
     }
 
     pub(crate) fn start(&mut self, start_cursor: Index2) {
         self.clear();
         self.start_cursor = start_cursor;
+        self.offset_query = None;
+        self.typed_offset = None;
+    }
+
+    /// Returns the query as typed in search mode, i.e. the pattern and the
+    /// offset, if any.
+    pub(crate) fn query(&self) -> String {
+        match &self.offset_query {
+            Some(offset) => format!("{}/{offset}", self.pattern),
+            None => self.pattern.clone(),
+        }
+    }
+
+    /// Returns the offset that applies to jumps to matches.
+    pub(crate) fn effective_offset(&self) -> SearchOffset {
+        self.typed_offset.unwrap_or(self.offset)
     }
 
     pub(crate) fn clear(&mut self) {
         self.pattern.clear();
         self.matches.clear();
+        self.lengths.clear();
     }
 
     pub(crate) fn trigger_search(&mut self, lines: &Lines) {
-        let pattern: Vec<char> = self.pattern.chars().collect();
-        self.matches = lines
-            .match_indices(&pattern)
-            .map(|(_, index)| index)
-            .collect();
+        let (bounds, ignore_case) = (self.bounds.as_ref(), self.ignore_case);
+
+        #[cfg(feature = "regex")]
+        if self.is_regex() {
+            // An incomplete expression, e.g. while it is typed, has no matches
+            let regex = regex::RegexBuilder::new(self.pattern_without_magic())
+                .case_insensitive(ignore_case)
+                .build();
+            (self.matches, self.lengths) = match regex {
+                Ok(regex) => find_matches(lines, bounds, |line| regex_line_matches(line, &regex)),
+                Err(_) => (Vec::new(), Vec::new()),
+            };
+            return;
+        }
+
+        let pattern: Vec<char> = self.pattern_without_magic().chars().collect();
+        (self.matches, self.lengths) = find_matches(lines, bounds, |line| {
+            line_matches(line, &pattern, ignore_case)
+                .into_iter()
+                .map(|col| (col, pattern.len()))
+                .collect()
+        });
     }
 
     pub(crate) fn push_char(&mut self, ch: char) {
@@ -46,19 +239,21 @@ impl SearchState {
     }
 
     pub(crate) fn first(&mut self) -> Option<&Index2> {
-        for (i, index) in self.matches.iter().enumerate() {
-            if index >= &self.start_cursor {
-                self.selected_index = Some(i);
-                return Some(index);
-            }
+        self.wrapped = false;
+        if let Some(i) = self
+            .matches
+            .iter()
+            .position(|index| index >= &self.start_cursor)
+        {
+            self.selected_index = Some(i);
+            return self.matches.get(i);
         }
-        match self.matches.first() {
-            Some(index) => {
-                self.selected_index = Some(0);
-                Some(index)
-            }
-            None => None,
+        if self.matches.is_empty() || !self.wrap_scan {
+            return None;
         }
+        self.wrapped = true;
+        self.selected_index = Some(0);
+        self.matches.first()
     }
 
     pub(crate) fn current(&self) -> Option<&Index2> {
@@ -66,16 +261,14 @@ impl SearchState {
     }
 
     pub(crate) fn next(&mut self) -> Option<&Index2> {
-        if let Some(selected) = self.selected_index {
-            let new_selected = if selected + 1 >= self.matches.len() {
-                0
-            } else {
-                selected + 1
-            };
-            self.selected_index = Some(new_selected);
-            return self.matches.get(new_selected);
+        let selected = self.selected_index?;
+        self.wrapped = selected + 1 >= self.matches.len();
+        if self.wrapped && !self.wrap_scan {
+            return None;
         }
-        None
+        let new_selected = if self.wrapped { 0 } else { selected + 1 };
+        self.selected_index = Some(new_selected);
+        self.matches.get(new_selected)
     }
 
     pub(crate) fn previous(&mut self) -> Option<&Index2> {
@@ -84,6 +277,10 @@ impl SearchState {
             return None;
         }
 
+        self.wrapped = self.selected_index == Some(0);
+        if self.wrapped && !self.wrap_scan {
+            return None;
+        }
         let new_selected = match self.selected_index {
             Some(0) | None => len - 1,
             Some(i) => i - 1,
@@ -94,14 +291,213 @@ impl SearchState {
     }
 }
 
+/// Returns the start columns of the non-overlapping matches of `pattern`
+/// in a line.
+pub(crate) fn line_matches(line: &[char], pattern: &[char], ignore_case: bool) -> Vec<usize> {
+    let mut matches = Vec::new();
+    if pattern.is_empty() {
+        return matches;
+    }
+    let is_match = |text: &[char]| {
+        if ignore_case {
+            text.iter()
+                .zip(pattern)
+                .all(|(&a, &b)| fold_case(a) == fold_case(b))
+        } else {
+            text == pattern
+        }
+    };
+    let mut col = 0;
+    while col + pattern.len() <= line.len() {
+        if is_match(&line[col..col + pattern.len()]) {
+            matches.push(col);
+            col += pattern.len();
+        } else {
+            col += 1;
+        }
+    }
+    matches
+}
+
+/// Returns the starts and the lengths of the matches in all lines that lie
+/// within `bounds`. `line_matches` returns the start columns and the lengths
+/// of the matches in a line.
+fn find_matches<F>(
+    lines: &Lines,
+    bounds: Option<&Selection>,
+    line_matches: F,
+) -> (Vec<Index2>, Vec<usize>)
+where
+    F: Fn(&[char]) -> Vec<(usize, usize)> + Sync,
+{
+    let matches = map_lines(lines, |row, line| {
+        line_matches(line)
+            .into_iter()
+            .map(|(col, len)| (Index2::new(row, col), len))
+            .filter(|&(index, len)| is_within(bounds, index, len))
+            .collect::<Vec<_>>()
+    });
+    matches.into_iter().flatten().unzip()
+}
+
+/// Returns the start columns and the lengths of the non-empty matches of a
+/// regular expression in a line.
+#[cfg(feature = "regex")]
+fn regex_line_matches(line: &[char], regex: &regex::Regex) -> Vec<(usize, usize)> {
+    let text: String = line.iter().collect();
+    let col = |byte: usize| text[..byte].chars().count();
+    regex
+        .find_iter(&text)
+        .filter(|matched| !matched.is_empty())
+        .map(|matched| {
+            let start = col(matched.start());
+            (start, col(matched.end()) - start)
+        })
+        .collect()
+}
+
+/// Splits the `\v` (regular expression) or `\V` (literal) prefix off a
+/// search pattern, like the very magic and very nomagic modes of vim.
+pub(crate) fn split_magic(pattern: &str) -> (Option<bool>, &str) {
+    if let Some(rest) = pattern.strip_prefix("\\v") {
+        return (Some(true), rest);
+    }
+    if let Some(rest) = pattern.strip_prefix("\\V") {
+        return (Some(false), rest);
+    }
+    (None, pattern)
+}
+
+/// Returns the lowercase of a character, keeping characters whose
+/// lowercase consists of multiple characters.
+fn fold_case(ch: char) -> char {
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => ch,
+    }
+}
+
+/// Whether a match of `len` characters at `start` lies within `bounds`.
+pub(crate) fn is_within(bounds: Option<&Selection>, start: Index2, len: usize) -> bool {
+    let end = Index2::new(start.row, start.col + len.saturating_sub(1));
+    bounds.is_none_or(|bounds| bounds.contains(&start) && bounds.contains(&end))
+}
+
 impl From<&SearchState> for Option<Selection> {
     fn from(value: &SearchState) -> Self {
-        value
-            .selected_index
-            .and_then(|index| value.matches.get(index))
-            .map(|&start| {
-                let end = Index2::new(start.row, start.col + value.len().saturating_sub(1));
-                Selection::new(start, end)
-            })
+        value.current().map(|&start| {
+            let end = Index2::new(
+                start.row,
+                start.col + value.selected_len().saturating_sub(1),
+            );
+            Selection::new(start, end)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_matches() {
+        let line: Vec<char> = "aaa Foo foo".chars().collect();
+        let pattern = |text: &str| text.chars().collect::<Vec<_>>();
+
+        assert_eq!(line_matches(&line, &pattern("aa"), false), vec![0]);
+        assert_eq!(line_matches(&line, &pattern("foo"), false), vec![8]);
+        assert_eq!(line_matches(&line, &pattern("foo"), true), vec![4, 8]);
+        assert!(line_matches(&line, &[], false).is_empty());
+    }
+
+    #[test]
+    fn test_parse_search_offset() {
+        assert_eq!(SearchOffset::parse(""), Some(SearchOffset::Start(0)));
+        assert_eq!(SearchOffset::parse("e"), Some(SearchOffset::End(0)));
+        assert_eq!(SearchOffset::parse("e+2"), Some(SearchOffset::End(2)));
+        assert_eq!(SearchOffset::parse("b-"), Some(SearchOffset::Start(-1)));
+        assert_eq!(SearchOffset::parse("s1"), Some(SearchOffset::Start(1)));
+        assert_eq!(SearchOffset::parse("-3"), Some(SearchOffset::Lines(-3)));
+        assert_eq!(SearchOffset::parse("2"), Some(SearchOffset::Lines(2)));
+        assert_eq!(SearchOffset::parse("e+x"), None);
+        assert_eq!(SearchOffset::parse("x"), None);
+    }
+
+    #[test]
+    fn test_apply_search_offset() {
+        let lines = Lines::from("foo bar\nbaz");
+        let start = Index2::new(0, 4);
+        assert_eq!(
+            SearchOffset::End(0).apply(&lines, start, 3),
+            Index2::new(0, 6)
+        );
+        assert_eq!(
+            SearchOffset::End(5).apply(&lines, start, 3),
+            Index2::new(0, 6)
+        );
+        assert_eq!(
+            SearchOffset::Start(-1).apply(&lines, start, 3),
+            Index2::new(0, 3)
+        );
+        assert_eq!(
+            SearchOffset::Lines(1).apply(&lines, start, 3),
+            Index2::new(1, 0)
+        );
+        assert_eq!(
+            SearchOffset::Lines(-1).apply(&lines, start, 3),
+            Index2::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_split_magic() {
+        assert_eq!(split_magic("\\vfo+"), (Some(true), "fo+"));
+        assert_eq!(split_magic("\\Va.b"), (Some(false), "a.b"));
+        assert_eq!(split_magic("a\\v"), (None, "a\\v"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_search() {
+        let lines = Lines::from("föö fo\nf");
+        let mut search = SearchState {
+            pattern: String::from("\\vfo*"),
+            ..SearchState::default()
+        };
+        search.trigger_search(&lines);
+        assert_eq!(
+            search.matches,
+            vec![Index2::new(0, 0), Index2::new(0, 4), Index2::new(1, 0)]
+        );
+        assert_eq!(search.lengths, vec![1, 2, 1]);
+
+        search.regex = true;
+        search.pattern = String::from("\\Vfo*");
+        search.trigger_search(&lines);
+        assert!(search.matches.is_empty());
+
+        search.pattern = String::from("(");
+        search.trigger_search(&lines);
+        assert!(search.matches.is_empty());
+    }
+
+    #[test]
+    fn test_search_large_text() {
+        let text = (0..10_000)
+            .map(|row| if row % 3 == 0 { "x foo foo" } else { "bar" })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut search = SearchState {
+            pattern: String::from("foo"),
+            ..SearchState::default()
+        };
+        search.trigger_search(&Lines::from(text.as_str()));
+
+        let expected: Vec<Index2> = (0..10_000)
+            .step_by(3)
+            .flat_map(|row| [Index2::new(row, 2), Index2::new(row, 6)])
+            .collect();
+        assert_eq!(search.matches, expected);
     }
 }
@@ -5,10 +5,17 @@ use jagged::index::RowIndex;
 use crate::{Index2, Lines};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Selection {
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::Index2Def"))]
     pub start: Index2,
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::Index2Def"))]
     pub end: Index2,
     pub line_mode: bool,
+    /// Whether the selection is a rectangle between `start` and `end`, as
+    /// selected with `Ctrl+v`.
+    pub block_mode: bool,
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::option"))]
     pub anchor: Option<Index2>,
 }
 
@@ -19,6 +26,7 @@ impl Selection {
             start,
             end,
             line_mode: false,
+            block_mode: false,
             anchor: None,
         }
     }
@@ -29,11 +37,55 @@ impl Selection {
         self
     }
 
+    /// Makes the selection a rectangle between `start` and `end`.
+    #[must_use]
+    pub fn block_mode(mut self) -> Self {
+        self.block_mode = true;
+        self
+    }
+
+    /// Returns the first and the last selected column of a block
+    /// selection.
+    #[must_use]
+    pub(crate) fn block_columns(&self) -> (usize, usize) {
+        (
+            self.start.col.min(self.end.col),
+            self.start.col.max(self.end.col),
+        )
+    }
+
+    /// Returns the end of the selection that stays in place while the
+    /// cursor extends the selection.
+    #[must_use]
+    pub fn anchor_position(&self) -> Index2 {
+        if self.line_mode {
+            return self.anchor.unwrap_or(self.start);
+        }
+        self.start
+    }
+
+    /// Makes `cursor` the new anchor of the selection. Returns the previous
+    /// anchor, to which the cursor should be moved.
+    pub fn swap_anchor(&mut self, cursor: Index2) -> Index2 {
+        let anchor = self.anchor_position();
+        if self.line_mode {
+            self.anchor = Some(cursor);
+        } else {
+            self.start = cursor;
+            self.end = anchor;
+        }
+        anchor
+    }
+
     #[must_use]
     pub fn contains(&self, pos: &Index2) -> bool {
         if self.line_mode {
             return self.contains_row(pos.row);
         }
+        if self.block_mode {
+            let (first, last) = self.block_columns();
+            return self.contains_row(pos.row) && pos.col >= first && pos.col <= last;
+        }
 
         let (start, end) = if self.start < self.end {
             (&self.start, &self.end)
@@ -67,16 +119,28 @@ impl Selection {
         false
     }
 
+    /// Returns the first position of the selection. For a block selection,
+    /// this is its top left corner.
     #[must_use]
     pub fn start(&self) -> Index2 {
+        if self.block_mode {
+            let row = self.start.row.min(self.end.row);
+            return Index2::new(row, self.block_columns().0);
+        }
         if self.is_reversed() {
             return self.end;
         }
         self.start
     }
 
+    /// Returns the last position of the selection. For a block selection,
+    /// this is its bottom right corner.
     #[must_use]
     pub fn end(&self) -> Index2 {
+        if self.block_mode {
+            let row = self.start.row.max(self.end.row);
+            return Index2::new(row, self.block_columns().1);
+        }
         if self.is_reversed() {
             return self.start;
         }
@@ -107,21 +171,61 @@ impl Selection {
 
             return lines;
         }
+        if self.block_mode {
+            let (first, last) = self.block_columns();
+            let rows: Vec<_> = (self.start().row..=self.end().row)
+                .filter_map(|row| lines.get(RowIndex::new(row)))
+                .map(|line| {
+                    let end = (last + 1).min(line.len());
+                    line.get(first..end)
+                        .map(<[char]>::to_vec)
+                        .unwrap_or_default()
+                })
+                .collect();
+            return Lines::new(rows);
+        }
 
         lines.copy_range(self.start()..=self.end())
     }
 
-    /// Extracts a selection from `Lines`.
+    /// Extracts a selection from `Lines`. In line mode, the selected rows
+    /// are removed entirely. In block mode, the selected columns are removed
+    /// from each row and rows that end before the block are kept unchanged.
     #[must_use]
     pub fn extract_from(&self, lines: &mut Lines) -> Lines {
         if self.line_mode {
-            let st = Index2::new(self.start().row, 0);
-            let en = Index2::new(self.end().row, lines.last_col_index(self.end().row));
+            let mut extracted = Lines::default();
+            if lines.is_empty() {
+                return extracted;
+            }
+            let last = self.end().row.min(lines.last_row_index());
+            let first = self.start().row.min(last);
 
-            let mut lines = lines.extract(st..=en);
-            lines.insert(RowIndex::new(0), vec![]);
+            extracted.push(vec![]);
+            for _ in first..=last {
+                extracted.push(lines.remove(RowIndex::new(first)));
+            }
+            if lines.is_empty() {
+                lines.push(vec![]);
+            }
 
-            return lines;
+            return extracted;
+        }
+        if self.block_mode {
+            let (first, last) = self.block_columns();
+            let mut extracted = Lines::default();
+            for row in self.start().row..=self.end().row.min(lines.last_row_index()) {
+                let Some(line) = lines.get_mut(RowIndex::new(row)) else {
+                    continue;
+                };
+                let end = (last + 1).min(line.len());
+                extracted.push(if first < end {
+                    line.drain(first..end).collect()
+                } else {
+                    vec![]
+                });
+            }
+            return extracted;
         }
 
         lines.extract(self.start()..=self.end())
@@ -136,6 +240,12 @@ impl Selection {
         row_len: usize,
     ) -> Option<(usize, usize)> {
         let (start, end) = (self.start(), self.end());
+        if self.block_mode {
+            if !self.contains_row(row_index) || start.col >= row_len {
+                return None;
+            }
+            return Some((start.col, end.col.min(row_len)));
+        }
 
         let start_col = match start.row.cmp(&row_index) {
             Ordering::Less => 0,
@@ -209,6 +319,30 @@ mod tests {
         assert_eq!(selection.copy_from(&data), Lines::from("\nWo"));
     }
 
+    #[test]
+    fn test_extract_lines_from_empty_buffer() {
+        let mut data = Lines::default();
+        let selection = Selection::new(Index2::new(0, 0), Index2::new(0, 0)).line_mode();
+
+        assert_eq!(selection.extract_from(&mut data), Lines::default());
+        assert_eq!(data, Lines::default());
+    }
+
+    #[test]
+    fn test_block_selection() {
+        let mut data = Lines::from("Hello\nWo\nWorld");
+        let selection = Selection::new(Index2::new(2, 3), Index2::new(0, 2)).block_mode();
+
+        assert!(selection.contains(&Index2::new(1, 2)));
+        assert!(!selection.contains(&Index2::new(1, 4)));
+        assert_eq!(selection.get_selected_columns_in_row(1, 2), None);
+        assert_eq!(selection.get_selected_columns_in_row(2, 5), Some((2, 3)));
+        assert_eq!(selection.copy_from(&data), Lines::from("ll\n\nrl"));
+
+        assert_eq!(selection.extract_from(&mut data), Lines::from("ll\n\nrl"));
+        assert_eq!(data, Lines::from("Heo\nWo\nWod"));
+    }
+
     #[test]
     fn test_selection_columns_in_row() {
         // given
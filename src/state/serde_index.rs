@@ -0,0 +1,87 @@
+//! Serde support for positions. [`Index2`] does not implement the serde
+//! traits, so fields holding positions refer to these modules with
+//! `#[serde(with = "...")]`. A position is stored as `{ row, col }`.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use jagged::Index2;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Mirrors the fields of [`Index2`].
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Index2")]
+pub(crate) struct Index2Def {
+    row: usize,
+    col: usize,
+}
+
+/// A position that implements the serde traits.
+#[derive(Serialize, Deserialize)]
+struct Position(#[serde(with = "Index2Def")] Index2);
+
+/// An optional position.
+pub(crate) mod option {
+    use super::{Deserialize, Deserializer, Index2, Position, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        index: &Option<Index2>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        index.map(Position).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Index2>, D::Error> {
+        Ok(Option::<Position>::deserialize(deserializer)?.map(|position| position.0))
+    }
+}
+
+/// A list of positions.
+pub(crate) mod vec {
+    use super::{Deserialize, Deserializer, Index2, Position, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        indices: &[Index2],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(indices.iter().map(|&index| Position(index)))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Index2>, D::Error> {
+        let positions = Vec::<Position>::deserialize(deserializer)?;
+        Ok(positions.into_iter().map(|position| position.0).collect())
+    }
+}
+
+/// A map of positions.
+pub(crate) mod map {
+    use super::{
+        Deserialize, Deserializer, Hash, HashMap, Index2, Position, Serialize, Serializer,
+    };
+
+    pub(crate) fn serialize<K, S>(
+        indices: &HashMap<K, Index2>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(indices.iter().map(|(key, &index)| (key, Position(index))))
+    }
+
+    pub(crate) fn deserialize<'de, K, D>(deserializer: D) -> Result<HashMap<K, Index2>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        D: Deserializer<'de>,
+    {
+        let positions = HashMap::<K, Position>::deserialize(deserializer)?;
+        Ok(positions
+            .into_iter()
+            .map(|(key, position)| (key, position.0))
+            .collect())
+    }
+}
@@ -0,0 +1,199 @@
+//! Saving and restoring the position of the user within a document.
+//!
+//! A [`Session`] is a lightweight snapshot of everything but the text itself:
+//! the cursor, the viewport, the selection, marks, bookmarks, the jump list
+//! and the last search. Apps can store it when a document is closed and restore it when the
+//! document is reopened. With the `serde` feature, a session can be serialized, e.g. to keep
+//! it on disk between runs.
+use std::collections::{BTreeSet, HashMap};
+
+use jagged::Index2;
+
+use super::selection::Selection;
+use crate::{EditorMode, EditorState};
+
+/// The maximum number of positions kept in the jump list.
+const MAX_JUMPS: usize = 100;
+
+/// A snapshot of the users position within a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    /// The cursor position.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::Index2Def"))]
+    pub cursor: Index2,
+    /// The top-left position of the viewport (`row` is the vertical, `col`
+    /// the horizontal scroll offset).
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::Index2Def"))]
+    pub viewport: Index2,
+    /// The selection, if any.
+    pub selection: Option<Selection>,
+    /// The named marks.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::map"))]
+    pub marks: HashMap<char, Index2>,
+    /// The bookmarked rows.
+    pub bookmarks: BTreeSet<usize>,
+    /// The jump list, ordered from oldest to newest.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_index::vec"))]
+    pub jumps: Vec<Index2>,
+    /// The last search pattern.
+    pub search: String,
+}
+
+impl EditorState {
+    /// Returns a [`Session`] describing the current position within the document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello\nWorld"));
+    /// state.cursor = Index2::new(1, 2);
+    ///
+    /// let session = state.session();
+    ///
+    /// let mut reopened = EditorState::new(Lines::from("Hello\nWorld"));
+    /// reopened.restore_session(session);
+    /// assert_eq!(reopened.cursor, Index2::new(1, 2));
+    /// ```
+    #[must_use]
+    pub fn session(&self) -> Session {
+        Session {
+            cursor: self.cursor,
+            viewport: Index2::new(self.view.viewport.y, self.view.viewport.x),
+            selection: self.selection.clone(),
+            marks: self.marks.clone(),
+            bookmarks: self.bookmarks.clone(),
+            jumps: self.jumps.clone(),
+            search: self.search.pattern.clone(),
+        }
+    }
+
+    /// Restores a [`Session`] previously obtained with [`EditorState::session`].
+    ///
+    /// Positions are clamped to the current buffer, so restoring a session
+    /// on a document that was modified in the meantime is safe. Restoring a
+    /// session with a selection switches the editor into visual mode.
+    pub fn restore_session(&mut self, session: Session) {
+        self.selection = session.selection.map(|mut selection| {
+            selection.start = self.clamp_index(selection.start);
+            selection.end = self.clamp_index(selection.end);
+            selection.anchor = selection.anchor.map(|anchor| self.clamp_index(anchor));
+            selection
+        });
+        self.mode = if self.selection.is_some() {
+            EditorMode::Visual
+        } else {
+            EditorMode::Normal
+        };
+
+        self.cursor = self.clamp_index(session.cursor);
+        self.clamp_column();
+
+        self.view.viewport.y = session.viewport.row.min(self.lines.last_row_index());
+        self.view.viewport.x = session.viewport.col;
+
+        self.marks = session
+            .marks
+            .into_iter()
+            .map(|(name, mark)| (name, self.clamp_index(mark)))
+            .collect();
+        self.bookmarks = session.bookmarks;
+        self.bookmarks.retain(|&row| row < self.lines.len());
+        self.jumps = session
+            .jumps
+            .into_iter()
+            .map(|jump| self.clamp_index(jump))
+            .collect();
+        self.jumps.dedup();
+
+        self.search.pattern = session.search;
+        self.search.trigger_search(&self.lines);
+    }
+
+    /// Sets a named mark at the current cursor position.
+    pub fn set_mark(&mut self, name: char) {
+        self.marks.insert(name, self.cursor);
+    }
+
+    /// Returns the position of a named mark, if it exists.
+    #[must_use]
+    pub fn mark(&self, name: char) -> Option<Index2> {
+        self.marks.get(&name).copied()
+    }
+
+    /// Returns the jump list, ordered from oldest to newest.
+    #[must_use]
+    pub fn jumps(&self) -> &[Index2] {
+        &self.jumps
+    }
+
+    /// Records a position in the jump list. Called by motions that move the
+    /// cursor far away, such as `gg`, `G` or a search.
+    pub(crate) fn push_jump(&mut self, from: Index2) {
+        if self.jumps.last() == Some(&from) {
+            return;
+        }
+        self.jumps.push(from);
+        if self.jumps.len() > MAX_JUMPS {
+            self.jumps.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lines;
+
+    fn test_state() -> EditorState {
+        EditorState::new(Lines::from("Hello World!\n\n123."))
+    }
+
+    #[test]
+    fn test_session_roundtrip() {
+        let mut state = test_state();
+        state.cursor = Index2::new(2, 1);
+        state.set_mark('a');
+        state.toggle_bookmark(1);
+        state.push_jump(state.cursor);
+        state.view.viewport.y = 1;
+        state.search.pattern = String::from("World");
+
+        let session = state.session();
+        let mut restored = test_state();
+        restored.restore_session(session);
+
+        assert_eq!(restored.cursor, Index2::new(2, 1));
+        assert_eq!(restored.mark('a'), Some(Index2::new(2, 1)));
+        assert_eq!(restored.bookmarks().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(restored.jumps(), &[Index2::new(2, 1)]);
+        assert_eq!(restored.view.viewport.y, 1);
+        assert_eq!(restored.search_pattern(), "World");
+        assert_eq!(restored.search.matches, vec![Index2::new(0, 6)]);
+        assert_eq!(restored.mode, EditorMode::Normal);
+    }
+
+    #[test]
+    fn test_restore_session_clamps_positions() {
+        let mut state = test_state();
+        state.cursor = Index2::new(2, 3);
+        state.selection = Some(Selection::new(Index2::new(2, 3), Index2::new(0, 0)).line_mode());
+        state.set_mark('a');
+        state.push_jump(Index2::new(1, 0));
+        state.push_jump(Index2::new(2, 3));
+        let session = state.session();
+
+        let mut restored = EditorState::new(Lines::from("Hi"));
+        restored.restore_session(session);
+
+        assert_eq!(restored.cursor, Index2::new(0, 2));
+        let selection = restored.selection.clone().unwrap();
+        assert_eq!(selection.start, Index2::new(0, 2));
+        assert_eq!(selection.anchor, Some(Index2::new(0, 2)));
+        assert_eq!(restored.mode, EditorMode::Visual);
+        assert_eq!(restored.mark('a'), Some(Index2::new(0, 2)));
+        assert_eq!(restored.jumps(), &[Index2::new(0, 0), Index2::new(0, 2)]);
+    }
+}
@@ -0,0 +1,240 @@
+//! How the characters of the row being typed into are stored.
+//!
+//! Every row is a vector of characters, so inserting into the middle of a
+//! row shifts all characters after the cursor. With [`LineStorage::GapBuffer`],
+//! the row is instead split at the cursor while typing: the characters after
+//! the cursor are kept aside in reverse order, and typed characters are
+//! pushed onto the end of the row. The row is joined again before anything
+//! else reads or edits the text.
+use jagged::index::RowIndex;
+
+use super::edit::Edit;
+use crate::{EditorMode, EditorState, Index2};
+
+/// How the characters of the row being typed into are stored, see
+/// [`EditorState::set_line_storage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineStorage {
+    /// Characters are inserted into the row directly, shifting the rest of
+    /// the row (default).
+    #[default]
+    Contiguous,
+    /// Characters typed in insert mode are inserted into a gap at the
+    /// cursor, so that typing costs the same regardless of the length of
+    /// the row. Suited for heavy editing within very long lines, such as
+    /// logs or minified JSON.
+    GapBuffer,
+}
+
+/// A row split at the gap. The characters before the gap stay in the row.
+#[derive(Debug, Clone)]
+pub(crate) struct Gap {
+    /// The split row.
+    row: usize,
+    /// The characters after the gap, in reverse order.
+    tail: Vec<char>,
+    /// The column where the characters typed since the last tracked edit
+    /// start.
+    start: usize,
+}
+
+impl EditorState {
+    /// Sets how the characters of the row being typed into are stored.
+    ///
+    /// With [`LineStorage::GapBuffer`], the row at the cursor ends at the
+    /// cursor while characters are typed in insert mode. The row is joined
+    /// again before any other key, action or event is handled, before the
+    /// editor is rendered, and by [`EditorState::close_gap`]. Apps that read
+    /// [`EditorState::lines`] between key events, or that execute actions
+    /// directly through [`Execute::execute`], call `close_gap` first.
+    ///
+    /// [`Execute::execute`]: crate::actions::Execute::execute
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorEventHandler, EditorState, LineStorage, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("{\"a\":1}"));
+    /// state.set_line_storage(LineStorage::GapBuffer);
+    ///
+    /// let mut event_handler = EditorEventHandler::default();
+    /// event_handler.feed_keys("$i2", &mut state).unwrap();
+    /// state.close_gap();
+    /// assert_eq!(state.lines, Lines::from("{\"a\":12}"));
+    /// ```
+    pub fn set_line_storage(&mut self, storage: LineStorage) {
+        self.close_gap();
+        self.line_storage = storage;
+    }
+
+    /// Returns how the characters of the row being typed into are stored,
+    /// see [`EditorState::set_line_storage`].
+    #[must_use]
+    pub fn line_storage(&self) -> LineStorage {
+        self.line_storage
+    }
+
+    /// Joins the row that is split while typing with
+    /// [`LineStorage::GapBuffer`], so that [`EditorState::lines`] holds the
+    /// entire text. Does nothing if no row is split.
+    pub fn close_gap(&mut self) {
+        let Some(gap) = self.gap.take() else {
+            return;
+        };
+        let Some(line) = self.lines.get_mut(RowIndex::new(gap.row)) else {
+            return;
+        };
+        if gap.start < line.len() {
+            // Characters typed since the last tracked edit are tracked with
+            // the other edits of the action that closes the gap.
+            let mut old = line[..gap.start].to_vec();
+            old.extend(gap.tail.iter().rev());
+            line.extend(gap.tail.into_iter().rev());
+            self.touch_edited_row(gap.row, old);
+        } else {
+            line.extend(gap.tail.into_iter().rev());
+        }
+    }
+
+    /// Inserts a character typed in insert mode into the gap at the cursor,
+    /// splitting the cursor row if needed. Returns `false` if the character
+    /// must be inserted into the row directly instead.
+    pub(crate) fn insert_into_gap(&mut self, ch: char) -> bool {
+        if self.line_storage != LineStorage::GapBuffer
+            || self.mode != EditorMode::Insert
+            || ch == '\n'
+            // Wrapping at the text width and reverting edits outside of
+            // fields need the entire row
+            || self.text_width.is_some()
+            || !self.fields.is_empty()
+        {
+            return false;
+        }
+
+        let cursor = self.cursor;
+        let at_gap = self.gap.as_ref().is_some_and(|gap| {
+            gap.row == cursor.row && self.lines.len_col(gap.row) == Some(cursor.col)
+        });
+        if !at_gap {
+            self.close_gap();
+            if !self.edit_tracker.is_clean() {
+                return false;
+            }
+            let Some(line) = self.lines.get_mut(RowIndex::new(cursor.row)) else {
+                return false;
+            };
+            if cursor.col > line.len() {
+                return false;
+            }
+            let mut tail = line.split_off(cursor.col);
+            tail.reverse();
+            self.gap = Some(Gap {
+                row: cursor.row,
+                tail,
+                start: cursor.col,
+            });
+        }
+
+        if let Some(line) = self.lines.get_mut(RowIndex::new(cursor.row)) {
+            line.push(ch);
+            self.cursor.col += 1;
+            self.inserted.push(ch);
+        }
+        true
+    }
+
+    /// Returns the edit made by the characters typed into the gap since the
+    /// last call, if any.
+    pub(crate) fn take_gap_edit(&mut self) -> Option<Edit> {
+        let gap = self.gap.as_mut()?;
+        let len = self.lines.len_col(gap.row)?;
+        if gap.start >= len {
+            return None;
+        }
+        let start = Index2::new(gap.row, gap.start);
+        let old_len = gap.start + gap.tail.len();
+        gap.start = len;
+        Some(Edit::insertion(start, len - start.col, old_len))
+    }
+
+    /// Returns the row of the gap if characters were typed into it since
+    /// the last tracked edit.
+    #[cfg(test)]
+    pub(crate) fn gap_edit_row(&self) -> Option<usize> {
+        let gap = self.gap.as_ref()?;
+        (self.lines.len_col(gap.row)? > gap.start).then_some(gap.row)
+    }
+
+    /// Returns the entire text, including the characters after the gap.
+    #[cfg(test)]
+    pub(crate) fn whole_lines(&self) -> crate::Lines {
+        let mut state = self.clone();
+        state.close_gap();
+        state.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EditorEventHandler, EditorView, Highlight, Lines};
+    use ratatui_core::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+    fn test_state(text: &str) -> EditorState {
+        let mut state = EditorState::new(Lines::from(text));
+        state.set_line_storage(LineStorage::GapBuffer);
+        state
+    }
+
+    #[test]
+    fn test_gap_buffer_typing() {
+        let mut state = test_state("ac\nd");
+        let mut handler = EditorEventHandler::default();
+
+        handler.feed_keys("lib", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ab\nd"));
+        assert_eq!(state.cursor, Index2::new(0, 2));
+
+        handler.feed_keys("bb", &mut state).unwrap();
+        state.close_gap();
+        assert_eq!(state.lines, Lines::from("abbbc\nd"));
+
+        handler.feed_keys("x<Left>y<Esc>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("abbbyxc\nd"));
+
+        handler.feed_keys("u", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("ac\nd"));
+    }
+
+    #[test]
+    fn test_gap_buffer_tracks_edits() {
+        let mut state = test_state("abc\nd");
+        let mut handler = EditorEventHandler::default();
+        let highlight = Highlight::new(Index2::new(0, 1), Index2::new(0, 2), Style::default());
+        state.set_highlights("group", vec![highlight]);
+        let generation = state.generation();
+
+        handler.feed_keys("ixy", &mut state).unwrap();
+        let highlight = &state.highlights("group")[0];
+        assert_eq!(highlight.start, Index2::new(0, 3));
+        assert_eq!(highlight.end, Index2::new(0, 4));
+        assert!(state.generation() > generation);
+
+        handler.feed_keys("<Esc>", &mut state).unwrap();
+        assert_eq!(state.lines, Lines::from("xyabc\nd"));
+        assert_eq!(state.highlights("group")[0].start, Index2::new(0, 3));
+    }
+
+    #[test]
+    fn test_gap_closed_before_render() {
+        let mut state = test_state("ac");
+        let mut handler = EditorEventHandler::default();
+        handler.feed_keys("lib", &mut state).unwrap();
+
+        let area = Rect::new(0, 0, 10, 1);
+        let mut buf = Buffer::empty(area);
+        EditorView::new(&mut state).render(area, &mut buf);
+        assert_eq!(state.lines, Lines::from("abc"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Custom text objects, such as the number under the cursor.
+use std::collections::HashMap;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::EditorState;
+
+type TextObjectFn = Rc<dyn Fn(&[char], usize) -> Option<Range<usize>>>;
+
+/// The registered text objects by their key. Contains the number object
+/// `n` by default.
+#[derive(Clone)]
+pub(crate) struct TextObjects(HashMap<char, TextObjectFn>);
+
+impl Default for TextObjects {
+    fn default() -> Self {
+        let mut objects: HashMap<char, TextObjectFn> = HashMap::new();
+        objects.insert('n', Rc::new(number_range));
+        Self(objects)
+    }
+}
+
+impl TextObjects {
+    /// Returns the columns of the object with the given key in a line.
+    pub(crate) fn find(&self, key: char, line: &[char], col: usize) -> Option<Range<usize>> {
+        let range = self.0.get(&key)?(line, col)?;
+        (range.start < range.end && range.end <= line.len()).then_some(range)
+    }
+}
+
+impl EditorState {
+    /// Registers a text object under `key`, replacing any object with the
+    /// same key. The object is found by `find`, which receives the line of
+    /// the cursor together with the cursor column and returns the columns of
+    /// the object, typically the match under or after the cursor.
+    ///
+    /// The object is selected with [`SelectTextObject`]. Bind it in visual
+    /// mode and to the operators with [`KeyEventHandler::bind_text_object`].
+    /// The number object `n` is registered by default.
+    ///
+    /// [`SelectTextObject`]: crate::actions::SelectTextObject
+    /// [`KeyEventHandler::bind_text_object`]: crate::events::KeyEventHandler::bind_text_object
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{DeleteSelection, SelectTextObject};
+    /// use edtui::{EditorState, Index2, Lines};
+    ///
+    /// // A date such as `2024-01-31`
+    /// let mut state = EditorState::new(Lines::from("due 2024-01-31!"));
+    /// state.set_text_object('d', |line, col| {
+    ///     let is_date_char = |ch: &char| ch.is_ascii_digit() || *ch == '-';
+    ///     let start = line[..=col].iter().rposition(|ch| !is_date_char(ch))? + 1;
+    ///     let len = line[start..].iter().take_while(|ch| is_date_char(ch)).count();
+    ///     Some(start..start + len)
+    /// });
+    ///
+    /// state.cursor = Index2::new(0, 6);
+    /// state.execute(SelectTextObject('d'));
+    /// state.execute(DeleteSelection);
+    /// assert_eq!(state.lines, Lines::from("due !"));
+    /// ```
+    pub fn set_text_object<F>(&mut self, key: char, find: F)
+    where
+        F: Fn(&[char], usize) -> Option<Range<usize>> + 'static,
+    {
+        self.text_objects.0.insert(key, Rc::new(find));
+    }
+
+    /// Removes the text object registered under `key`.
+    pub fn remove_text_object(&mut self, key: char) {
+        self.text_objects.0.remove(&key);
+    }
+}
+
+/// Finds the number under the cursor, or the next number in the line. A
+/// number is a decimal, optionally with a sign and a fraction, or a
+/// hexadecimal number such as `0xff`.
+fn number_range(line: &[char], col: usize) -> Option<Range<usize>> {
+    let col = col.min(line.len());
+    // Go back to the start of the word under the cursor
+    let mut search = col;
+    while search > 0 && (line[search - 1].is_ascii_alphanumeric() || line[search - 1] == '.') {
+        search -= 1;
+    }
+
+    loop {
+        let start = search + line[search..].iter().position(char::is_ascii_digit)?;
+        let hex = line[start] == '0' && matches!(line.get(start + 1), Some('x' | 'X'));
+        let digit = |ch: &&char| {
+            if hex {
+                ch.is_ascii_hexdigit()
+            } else {
+                ch.is_ascii_digit()
+            }
+        };
+        let mut end = if hex { start + 2 } else { start };
+        end += line[end..].iter().take_while(digit).count();
+        if !hex
+            && line.get(end) == Some(&'.')
+            && line.get(end + 1).is_some_and(char::is_ascii_digit)
+        {
+            end += 1 + line[end + 1..].iter().take_while(digit).count();
+        }
+
+        // Skip numbers before the cursor
+        if end <= col {
+            search = end;
+            continue;
+        }
+        let start = if start > 0 && line[start - 1] == '-' {
+            start - 1
+        } else {
+            start
+        };
+        return Some(start..end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_range() {
+        let range = |text: &str, col| {
+            let line: Vec<char> = text.chars().collect();
+            number_range(&line, col)
+        };
+
+        assert_eq!(range("x = -12.5;", 0), Some(4..9));
+        assert_eq!(range("x = -12.5;", 7), Some(4..9));
+        assert_eq!(range("abc 0xFF1 d", 6), Some(4..9));
+        assert_eq!(range("v2 9", 0), Some(1..2));
+        assert_eq!(range("12ab34", 5), Some(4..6));
+        assert_eq!(range("no numbers", 3), None);
+    }
+}
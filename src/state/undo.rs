@@ -1,14 +1,19 @@
-//! This module contains a "brute-force" implementation of undo and redo.
-//! It stores the entire editor state at each action.
+//! This module contains the undo and redo history.
 //!
-//! This approach works for basic undo/redo needs, but its not the most efficient.
-//! In the long run, this should be replaced with an action-based mechanism.
+//! The most recent entry holds a full snapshot of the text. Older entries
+//! only store the rows that differ from the entry after them, so that the
+//! history stays small even for large buffers. The history is capped by a
+//! number of entries and optionally by an estimate of its memory usage, in
+//! which case the oldest entries are dropped first.
 use crate::{EditorState, Index2, Lines};
+use std::ops::{Deref, DerefMut};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Stack {
     inner: Vec<UndoState>,
-    max_size: usize,
+    pub(super) max_size: usize,
+    pub(super) max_bytes: Option<usize>,
+    bytes: usize,
 }
 
 impl Stack {
@@ -16,65 +21,488 @@ impl Stack {
         Self {
             inner: Vec::new(),
             max_size: 100,
+            max_bytes: None,
+            bytes: 0,
         }
     }
 
     pub(crate) fn pop(&mut self) -> Option<UndoState> {
-        self.inner.pop()
+        let value = self.inner.pop()?;
+        self.bytes -= value.size();
+        Some(value)
     }
 
     pub(crate) fn push(&mut self, value: UndoState) {
+        self.bytes += value.size();
         self.inner.push(value);
-        if self.len() > self.max_size {
-            self.remove(0);
-        }
+        self.evict();
     }
 
-    fn len(&mut self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.inner.len()
     }
 
+    pub(crate) fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.pop();
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Sets the maximum number of entries and the maximum estimated memory
+    /// usage in bytes.
+    pub(crate) fn set_limits(&mut self, max_size: usize, max_bytes: Option<usize>) {
+        self.max_size = max_size;
+        self.max_bytes = max_bytes;
+        self.evict();
+    }
+
+    /// Replaces the full snapshot of the latest entry by the rows that
+    /// differ from `lines`, the text the entry is undone from.
+    fn compact(&mut self, lines: &Lines) {
+        if let Some(last) = self.inner.last_mut() {
+            if let Snapshot::Full(full) = &last.snapshot {
+                self.bytes -= last.size();
+                last.snapshot = Snapshot::diff(lines, full);
+                self.bytes += last.size();
+            }
+        }
+    }
+
+    /// Returns the text of the entry `steps` entries below the top, given
+    /// the current text.
+    fn preview(&self, lines: &Lines, steps: usize) -> Option<Lines> {
+        let start = self.len().checked_sub(steps)?;
+        let mut text = lines.clone();
+        for entry in self.inner[start..].iter().rev() {
+            text = entry.snapshot.clone().apply(&text);
+        }
+        Some(text)
+    }
+
+    /// Drops the oldest entries until the history fits its limits. The
+    /// latest entry is kept regardless of its size.
+    fn evict(&mut self) {
+        while self.len() > self.max_size
+            || (self.len() > 1 && self.max_bytes.is_some_and(|max| self.bytes > max))
+        {
+            self.remove(0);
+        }
+    }
+
     fn remove(&mut self, index: usize) {
-        self.inner.remove(index);
+        let value = self.inner.remove(index);
+        self.bytes -= value.size();
     }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct UndoState {
-    lines: Lines,
+    snapshot: Snapshot,
     cursor: Index2,
 }
 
+impl UndoState {
+    /// Returns an estimate of the memory used by the entry.
+    fn size(&self) -> usize {
+        self.snapshot.size()
+    }
+}
+
+/// The text of a history entry.
+#[derive(Debug, Clone)]
+enum Snapshot {
+    /// The entire text.
+    Full(Lines),
+    /// The text of the next entry with the rows starting at `row` replaced.
+    Delta {
+        row: usize,
+        len: usize,
+        rows: Vec<Vec<char>>,
+    },
+}
+
+impl Snapshot {
+    /// Returns the rows that must be replaced to get from `base` to `target`.
+    fn diff(base: &Lines, target: &Lines) -> Self {
+        let prefix = base
+            .iter_row()
+            .zip(target.iter_row())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let max_suffix = base.len().min(target.len()) - prefix;
+        let suffix = base
+            .iter_row()
+            .rev()
+            .zip(target.iter_row().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        Self::Delta {
+            row: prefix,
+            len: base.len() - prefix - suffix,
+            rows: target
+                .iter_row()
+                .skip(prefix)
+                .take(target.len() - prefix - suffix)
+                .map(|row| row.to_vec())
+                .collect(),
+        }
+    }
+
+    /// Returns the text of the snapshot, given the text of the next entry.
+    fn apply(self, base: &Lines) -> Lines {
+        match self {
+            Self::Full(lines) => lines,
+            Self::Delta { row, len, rows } => {
+                let mut data: Vec<Vec<char>> = base.iter_row().map(|row| row.to_vec()).collect();
+                let row = row.min(data.len());
+                let end = (row + len).min(data.len());
+                data.splice(row..end, rows);
+                Lines::new(data)
+            }
+        }
+    }
+
+    /// Returns an estimate of the memory used by the snapshot.
+    fn size(&self) -> usize {
+        let row_size = |row: &[char]| std::mem::size_of::<Vec<char>>() + std::mem::size_of_val(row);
+        std::mem::size_of::<Self>()
+            + match self {
+                Self::Full(lines) => lines.iter_row().map(|row| row_size(row)).sum::<usize>(),
+                Self::Delta { rows, .. } => rows.iter().map(|row| row_size(row)).sum(),
+            }
+    }
+}
+
+/// Suppresses undo snapshots while it is alive, see
+/// [`EditorState::pause_history`].
+///
+/// The guard dereferences to the [`EditorState`], so that it can be mutated
+/// through the guard. When the guard is dropped, snapshots are taken again.
+pub struct HistoryPause<'a> {
+    state: &'a mut EditorState,
+}
+
+impl Deref for HistoryPause<'_> {
+    type Target = EditorState;
+
+    fn deref(&self) -> &Self::Target {
+        self.state
+    }
+}
+
+impl DerefMut for HistoryPause<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.state
+    }
+}
+
+impl Drop for HistoryPause<'_> {
+    fn drop(&mut self) {
+        self.state.history_paused -= 1;
+    }
+}
+
 impl EditorState {
-    pub(crate) fn capture(&mut self) {
-        let editor_state = UndoState {
-            lines: self.lines.clone(),
+    /// Takes a snapshot of the text and the cursor for the undo history,
+    /// such that the next undo returns to the current state.
+    ///
+    /// Actions capture the state before they change the text, so this is
+    /// only needed when mutating [`EditorState::lines`] directly. Capturing
+    /// is idempotent: if the text did not change since the last snapshot, no
+    /// new entry is added. A new entry clears the redo history. While the
+    /// history is paused, capturing does nothing.
+    pub fn capture(&mut self) {
+        if self.history_paused > 0 {
+            return;
+        }
+        self.close_gap();
+        if let Some(UndoState {
+            snapshot: Snapshot::Full(lines),
+            cursor,
+        }) = self.undo.inner.last_mut()
+        {
+            if *lines == self.lines {
+                *cursor = self.cursor;
+                return;
+            }
+        }
+        self.undo.compact(&self.lines);
+        self.undo.push(UndoState {
+            snapshot: Snapshot::Full(self.lines.clone()),
             cursor: self.cursor,
+        });
+        self.redo.clear();
+    }
+
+    /// Pauses the undo history until the returned guard is dropped, so that
+    /// all changes made in the meantime are undone at once.
+    ///
+    /// The state is captured once when pausing. Pauses can be nested.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::actions::{AppendNewline, InsertChar};
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello"));
+    /// {
+    ///     let mut state = state.pause_history();
+    ///     state.execute(AppendNewline(1));
+    ///     state.execute(InsertChar('!'));
+    /// }
+    /// assert_eq!(state.lines, Lines::from("Hello\n!"));
+    ///
+    /// state.undo();
+    /// assert_eq!(state.lines, Lines::from("Hello"));
+    /// ```
+    pub fn pause_history(&mut self) -> HistoryPause<'_> {
+        self.capture();
+        self.history_paused += 1;
+        HistoryPause { state: self }
+    }
+
+    /// Sets the maximum number of changes kept in the undo history, 100 by
+    /// default. The oldest changes are dropped first.
+    pub fn set_undo_limit(&mut self, max_entries: usize) {
+        let max_bytes = self.undo.max_bytes;
+        self.undo.set_limits(max_entries, max_bytes);
+        self.redo.set_limits(max_entries, max_bytes);
+    }
+
+    /// Sets the maximum estimated memory in bytes used by the undo history,
+    /// unlimited by default. The oldest changes are dropped first, though the
+    /// latest change can always be undone.
+    pub fn set_undo_memory_limit(&mut self, max_bytes: Option<usize>) {
+        let max_entries = self.undo.max_size;
+        self.undo.set_limits(max_entries, max_bytes);
+        self.redo.set_limits(max_entries, max_bytes);
+    }
+
+    /// Reverts the last change. The cursor is placed where the change
+    /// occurred. Returns whether there was a change to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(prev) = self.undo.pop() else {
+            return false;
+        };
+        let current = self.restore(prev);
+        self.redo.push(current);
+        self.generation += 1;
+        true
+    }
+
+    /// Reapplies the last undone change. The cursor is placed where the
+    /// change occurred. Returns whether there was a change to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
         };
-        self.undo.push(editor_state);
-    }
-
-    pub fn undo(&mut self) {
-        if let Some(prev) = self.undo.pop() {
-            let current = UndoState {
-                lines: self.lines.clone(),
-                cursor: self.cursor,
-            };
-            self.lines = prev.lines;
-            self.cursor = prev.cursor;
-            self.redo.push(current);
+        let current = self.restore(next);
+        self.undo.push(current);
+        self.generation += 1;
+        true
+    }
+
+    /// Returns how many changes can be undone.
+    #[must_use]
+    pub fn undo_count(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Returns how many undone changes can be redone.
+    #[must_use]
+    pub fn redo_count(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Returns the text as it would be after undoing `steps` changes,
+    /// without changing the state, e.g. to preview the entries of an undo
+    /// history browser. Returns `None` if fewer changes can be undone, see
+    /// [`EditorState::undo_count`]. Zero steps return the current text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Lines};
+    ///
+    /// let mut state = EditorState::new(Lines::from("one"));
+    /// state.capture();
+    /// state.lines = Lines::from("two");
+    /// state.capture();
+    /// state.lines = Lines::from("three");
+    ///
+    /// assert_eq!(state.undo_preview(1), Some(Lines::from("two")));
+    /// assert_eq!(state.undo_preview(2), Some(Lines::from("one")));
+    /// assert_eq!(state.undo_preview(3), None);
+    /// assert_eq!(state.lines, Lines::from("three"));
+    /// ```
+    #[must_use]
+    pub fn undo_preview(&self, steps: usize) -> Option<Lines> {
+        self.undo.preview(&self.lines, steps)
+    }
+
+    /// Returns the text as it would be after redoing `steps` undone changes,
+    /// without changing the state. Returns `None` if fewer changes can be
+    /// redone, see [`EditorState::redo_count`].
+    #[must_use]
+    pub fn redo_preview(&self, steps: usize) -> Option<Lines> {
+        self.redo.preview(&self.lines, steps)
+    }
+
+    /// Replaces the text with a snapshot and moves the cursor to the start
+    /// of the text that differs. Returns the replaced state.
+    fn restore(&mut self, state: UndoState) -> UndoState {
+        let lines = state.snapshot.apply(&self.lines);
+        let current = UndoState {
+            snapshot: Snapshot::diff(&lines, &self.lines),
+            cursor: self.cursor,
+        };
+        self.cursor = match self.replace_lines(lines) {
+            None => state.cursor,
+            Some(edit) => {
+                let mut cursor = edit.start;
+                // Restored lines start after the line break at the end of a row
+                let row_len = self.lines.len_col(cursor.row).unwrap_or_default();
+                if edit.new_end > edit.start
+                    && cursor.col >= row_len
+                    && cursor.row + 1 < self.lines.len()
+                {
+                    cursor = Index2::new(cursor.row + 1, 0);
+                }
+                cursor
+            }
+        };
+        self.clamp_column();
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{DeleteLine, Execute, InsertChar, RedoN, SwitchMode, Undo, UndoN};
+    use crate::EditorMode;
+
+    #[test]
+    fn test_undo_restores_cursor_to_change() {
+        let mut state = EditorState::new(Lines::from("foo\nbar\nbaz"));
+        state.cursor = Index2::new(1, 1);
+        state.execute(SwitchMode(EditorMode::Insert));
+        state.execute(InsertChar('!'));
+        state.execute(SwitchMode(EditorMode::Normal));
+        state.cursor = Index2::new(2, 0);
+        state.execute(DeleteLine(1));
+        state.cursor = Index2::new(0, 0);
+
+        Undo.execute(&mut state);
+        assert_eq!(state.cursor, Index2::new(2, 0));
+        assert_eq!(state.notification().unwrap().message, "1 change undone");
+
+        UndoN(5).execute(&mut state);
+        assert_eq!(state.lines, Lines::from("foo\nbar\nbaz"));
+        assert_eq!(state.cursor, Index2::new(1, 1));
+        assert_eq!(state.notification().unwrap().message, "1 change undone");
+
+        Undo.execute(&mut state);
+        assert_eq!(
+            state.notification().unwrap().message,
+            "Already at oldest change"
+        );
+
+        RedoN(2).execute(&mut state);
+        assert_eq!(state.lines, Lines::from("foo\nb!ar"));
+        assert_eq!(state.notification().unwrap().message, "2 changes redone");
+    }
+
+    #[test]
+    fn test_undo_history_limits() {
+        let mut state = EditorState::new(Lines::from("a\nb\nc"));
+        for ch in ['x', 'y', 'z'] {
+            state.capture();
+            state.lines.push(vec![ch]);
         }
+        assert!(matches!(
+            state.undo.inner[0].snapshot,
+            Snapshot::Delta { .. }
+        ));
+        assert!(matches!(state.undo.inner[2].snapshot, Snapshot::Full(_)));
+
+        state.set_undo_limit(2);
+        assert_eq!(state.undo.len(), 2);
+
+        state.set_undo_memory_limit(Some(0));
+        assert_eq!(state.undo.len(), 1);
+
+        assert!(state.undo());
+        assert!(!state.undo());
+        assert_eq!(state.lines, Lines::from("a\nb\nc\nx\ny"));
+        assert!(state.redo());
+        assert_eq!(state.lines, Lines::from("a\nb\nc\nx\ny\nz"));
+    }
+
+    #[test]
+    fn test_undo_deltas() {
+        let mut state = EditorState::new(Lines::from("foo\nbar"));
+        state.capture();
+        state.lines = Lines::from("foo\nbaz\nbar");
+        state.capture();
+        state.lines = Lines::from("");
+        state.capture();
+        state.lines = Lines::from("qux");
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from(""));
+        state.undo();
+        assert_eq!(state.lines, Lines::from("foo\nbaz\nbar"));
+        state.undo();
+        assert_eq!(state.lines, Lines::from("foo\nbar"));
+        state.redo();
+        state.redo();
+        state.redo();
+        assert_eq!(state.lines, Lines::from("qux"));
     }
 
-    pub fn redo(&mut self) {
-        if let Some(prev) = self.redo.pop() {
-            let current = UndoState {
-                lines: self.lines.clone(),
-                cursor: self.cursor,
-            };
-            self.lines = prev.lines;
-            self.cursor = prev.cursor;
-            self.undo.push(current);
+    #[test]
+    fn test_history_preview() {
+        let mut state = EditorState::new(Lines::from("foo\nbar"));
+        state.capture();
+        state.lines = Lines::from("foo\nbaz\nbar");
+        state.capture();
+        state.lines = Lines::from("qux\nbaz\nbar");
+        state.undo();
+
+        assert_eq!((state.undo_count(), state.redo_count()), (1, 1));
+        assert_eq!(state.undo_preview(0), Some(Lines::from("foo\nbaz\nbar")));
+        assert_eq!(state.undo_preview(1), Some(Lines::from("foo\nbar")));
+        assert_eq!(state.redo_preview(1), Some(Lines::from("qux\nbaz\nbar")));
+        assert_eq!(state.redo_preview(2), None);
+        assert_eq!(state.lines, Lines::from("foo\nbaz\nbar"));
+    }
+
+    #[test]
+    fn test_capture_coalesces() {
+        let mut state = EditorState::new(Lines::from("foo"));
+        state.capture();
+        state.capture();
+        assert_eq!(state.undo.len(), 1);
+
+        {
+            let mut state = state.pause_history();
+            state.lines.push(vec!['a']);
+            state.capture();
+            state.pause_history().lines.push(vec!['b']);
+            state.capture();
         }
+        assert_eq!(state.undo.len(), 1);
+        assert_eq!(state.history_paused, 0);
+
+        state.undo();
+        assert_eq!(state.lines, Lines::from("foo"));
     }
 }
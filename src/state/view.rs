@@ -1,10 +1,14 @@
+use super::occurrences::OccurrenceState;
 use crate::{
-    helper::{char_width, chars_width},
-    view::line_wrapper::LineWrapper,
-    view::LineNumbers,
-    Lines,
+    helper::char_width,
+    view::cache::RenderCache,
+    view::line_wrapper::{LineLayout, LineWrapper, WrapOptions},
+    view::{Focus, LineNumbers},
+    EditorState, Lines,
 };
+use jagged::index::RowIndex;
 use ratatui_core::layout::Rect;
+use std::time::{Duration, Instant};
 
 /// Represents the (x, y) offset of the editor's viewport.
 /// It represents the top-left local editor coordinate.
@@ -21,10 +25,61 @@ pub(crate) struct ViewState {
     pub(crate) screen_area: Rect,
     /// Whether the lines are wrapped.
     pub(crate) wrap: bool,
+    /// How the lines are wrapped.
+    pub(crate) wrap_options: WrapOptions,
     /// The number of spaces used to display a tab.
     pub(crate) tab_width: usize,
     /// Line numbers configuration.
     pub(crate) line_numbers: LineNumbers,
+    /// The number of rows that consecutive pages overlap when scrolling
+    /// by a full page.
+    pub(crate) page_overlap: usize,
+    /// The minimum number of rows kept above and below the cursor.
+    pub(crate) scroll_off: usize,
+    /// Highlighting of the word under the cursor.
+    pub(crate) occurrences: OccurrenceState,
+    /// The columns that are highlighted to mark a line length.
+    pub(crate) color_columns: Vec<usize>,
+    /// Lines starting with this character are rendered as comments.
+    pub(crate) comment_char: Option<char>,
+    /// Whether markdown markup is rendered.
+    pub(crate) markdown: bool,
+    /// The lines that are not dimmed, if any are.
+    pub(crate) focus: Option<Focus>,
+    /// How long scrolling to a distant row is animated, if at all.
+    pub(crate) smooth_scroll: Option<Duration>,
+    /// The in-progress scroll animation.
+    scroll: Option<Scroll>,
+    /// The vertical offset that was rendered last.
+    pub(crate) rendered_y: usize,
+    /// Increases whenever the view changed, see
+    /// [`EditorState::view_generation`].
+    pub(crate) generation: u64,
+    /// The view as of the last render.
+    rendered: Option<RenderedView>,
+    /// Whether the last frame is reused if nothing changed.
+    pub(crate) cache_renders: bool,
+    /// The last rendered frame, if caching is enabled.
+    pub(crate) render_cache: Option<RenderCache>,
+}
+
+/// The parts of the view that are compared to detect a change.
+#[derive(Debug, Clone, PartialEq)]
+struct RenderedView {
+    viewport: Offset,
+    screen_area: Rect,
+    wrap: bool,
+    wrap_options: WrapOptions,
+    tab_width: usize,
+    line_numbers: LineNumbers,
+}
+
+/// An animation of the vertical offset between two rows.
+#[derive(Debug, Clone, Copy)]
+struct Scroll {
+    from: usize,
+    to: usize,
+    since: Instant,
 }
 
 impl Default for ViewState {
@@ -34,12 +89,75 @@ impl Default for ViewState {
             num_rows: 0,
             screen_area: Rect::default(),
             wrap: true,
+            wrap_options: WrapOptions::default(),
             tab_width: 2,
             line_numbers: LineNumbers::None,
+            page_overlap: 2,
+            scroll_off: 0,
+            occurrences: OccurrenceState::default(),
+            color_columns: Vec::new(),
+            comment_char: None,
+            markdown: false,
+            focus: None,
+            smooth_scroll: None,
+            scroll: None,
+            rendered_y: 0,
+            generation: 0,
+            rendered: None,
+            cache_renders: false,
+            render_cache: None,
         }
     }
 }
 
+impl EditorState {
+    /// Returns how a line is laid out on screen as of the last render: the
+    /// columns shown on each display row and the width of the prefix of
+    /// continuation rows. Without wrapping, every line occupies a single
+    /// row. Returns `None` if the line does not exist.
+    ///
+    /// Hosts can use the layout to position a scrollbar or hit-test mouse
+    /// events consistently with the editor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, EditorView, Lines};
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    ///
+    /// let mut state = EditorState::new(Lines::from("Hello World"));
+    /// let area = Rect::new(0, 0, 8, 4);
+    /// EditorView::new(&mut state).render(area, &mut Buffer::empty(area));
+    ///
+    /// let layout = state.line_layout(0).unwrap();
+    /// assert_eq!(layout.rows, vec![0..6, 6..11]);
+    /// ```
+    #[must_use]
+    pub fn line_layout(&self, row: usize) -> Option<LineLayout> {
+        let line = self.lines.get(RowIndex::new(row))?;
+        if !self.view.wrap {
+            return Some(LineLayout {
+                rows: std::iter::once(0..line.len()).collect(),
+                prefix_width: 0,
+            });
+        }
+        let width = self.view.screen_area.width.into();
+        Some(LineWrapper::layout(
+            line,
+            width,
+            self.view.tab_width,
+            &self.view.wrap_options,
+        ))
+    }
+
+    /// Forces the next render to redraw the editor, e.g. after modifying
+    /// the text directly or after changing the theme. Increases the
+    /// [`EditorState::generation`].
+    pub fn invalidate_render_cache(&mut self) {
+        self.generation += 1;
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
 pub(crate) struct Offset {
     /// The x-offset.
@@ -72,6 +190,13 @@ impl ViewState {
         self.screen_area = area.into();
     }
 
+    /// Returns the number of rows to scroll by a full page.
+    pub(crate) fn page_rows(&self) -> usize {
+        (self.screen_area.height as usize)
+            .saturating_sub(self.page_overlap)
+            .max(1)
+    }
+
     /// Updates the viewports horizontal offset.
     pub(crate) fn update_viewport_horizontal(
         &mut self,
@@ -161,6 +286,63 @@ impl ViewState {
         self.viewport.y
     }
 
+    /// Returns the vertical offset to render, given the offset of the
+    /// viewport. With smooth scrolling, the offset moves towards the viewport
+    /// over time when the viewport jumped by more than one row.
+    pub(crate) fn animate_viewport_vertical(&mut self) -> usize {
+        let target = self.viewport.y;
+        let Some(duration) = self.smooth_scroll else {
+            self.scroll = None;
+            self.rendered_y = target;
+            return target;
+        };
+
+        if self.scroll.map(|scroll| scroll.to) != Some(target) {
+            self.scroll = (self.rendered_y.abs_diff(target) > 1).then(|| Scroll {
+                from: self.rendered_y,
+                to: target,
+                since: Instant::now(),
+            });
+        }
+
+        let y = match self.scroll {
+            Some(scroll) if scroll.since.elapsed() < duration => {
+                let progress = scroll.since.elapsed().as_secs_f64() / duration.as_secs_f64();
+                // Ease out, so that the animation slows down towards the target
+                let progress = 1.0 - (1.0 - progress).powi(2);
+                let distance = (target as f64 - scroll.from as f64) * progress;
+                (scroll.from as f64 + distance).round() as usize
+            }
+            _ => {
+                self.scroll = None;
+                target
+            }
+        };
+        self.rendered_y = y;
+        y
+    }
+
+    /// Increases the generation if the view changed since the last render.
+    pub(crate) fn finish_render(&mut self) {
+        let rendered = RenderedView {
+            viewport: self.viewport,
+            screen_area: self.screen_area,
+            wrap: self.wrap,
+            wrap_options: self.wrap_options.clone(),
+            tab_width: self.tab_width,
+            line_numbers: self.line_numbers,
+        };
+        if self.rendered.as_ref() != Some(&rendered) {
+            self.generation += 1;
+            self.rendered = Some(rendered);
+        }
+    }
+
+    /// Whether a scroll animation is in progress.
+    pub(crate) fn is_scrolling(&self) -> bool {
+        self.scroll.is_some()
+    }
+
     /// Updates the number of rows that are currently shown on the viewport.
     /// Refers to the number of editor lines, not visual lines.
     pub(crate) fn update_num_rows(&mut self, num_rows: usize) {
@@ -194,8 +376,8 @@ impl ViewState {
 
         let skip = lines.len().saturating_sub(cursor_row + 1);
         for (i, line) in lines.iter_row().rev().skip(skip).enumerate() {
-            let line_width = chars_width(line, self.tab_width);
-            let current_row_height = LineWrapper::determine_split(line_width, max_width).len();
+            let current_row_height =
+                LineWrapper::layout(line, max_width, self.tab_width, &self.wrap_options).num_rows();
 
             // If we run out of height or exceed it, scroll the viewport.
             if remaining_height < current_row_height {
@@ -308,4 +490,24 @@ mod tests {
             expected: 1
         }
     );
+
+    #[test]
+    fn test_smooth_scroll() {
+        let mut view = ViewState::default();
+        view.viewport.y = 40;
+        assert_eq!(view.animate_viewport_vertical(), 40);
+
+        view.smooth_scroll = Some(Duration::from_secs(60));
+        view.viewport.y = 41;
+        assert_eq!(view.animate_viewport_vertical(), 41);
+        assert!(!view.is_scrolling());
+
+        view.viewport.y = 0;
+        assert_eq!(view.animate_viewport_vertical(), 41);
+        assert!(view.is_scrolling());
+
+        view.smooth_scroll = Some(Duration::ZERO);
+        assert_eq!(view.animate_viewport_vertical(), 0);
+        assert!(!view.is_scrolling());
+    }
 }
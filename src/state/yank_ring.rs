@@ -0,0 +1,100 @@
+//! The last yanked and deleted texts, which a paste can cycle through.
+use std::collections::VecDeque;
+
+use crate::clipboard::ClipboardTrait;
+use crate::{EditorState, Index2};
+
+/// The number of texts kept by default.
+const DEFAULT_SIZE: usize = 10;
+
+/// The last yanked and deleted texts, the most recent first.
+#[derive(Debug, Clone)]
+pub(crate) struct YankRing {
+    entries: VecDeque<String>,
+    max_size: usize,
+    last_paste: Option<LastPaste>,
+}
+
+/// A paste that can still be replaced by another text of the ring.
+#[derive(Debug, Clone)]
+struct LastPaste {
+    /// The index of the pasted text, or `None` if it was not in the ring.
+    index: Option<usize>,
+    /// The cursor before the paste.
+    cursor: Index2,
+    /// The length of the undo history and the cursor after the paste. If
+    /// either changed, the paste is no longer the last command.
+    after: (usize, Index2),
+}
+
+impl Default for YankRing {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_size: DEFAULT_SIZE,
+            last_paste: None,
+        }
+    }
+}
+
+impl YankRing {
+    fn push(&mut self, text: &str) {
+        if text.is_empty() || self.entries.front().is_some_and(|entry| entry == text) {
+            return;
+        }
+        self.entries.push_front(text.to_string());
+        self.entries.truncate(self.max_size);
+    }
+
+    /// Remembers that the entry at `index` was pasted at `cursor`. `after`
+    /// holds the length of the undo history and the cursor after the paste.
+    pub(crate) fn pasted(&mut self, index: Option<usize>, cursor: Index2, after: (usize, Index2)) {
+        self.last_paste = Some(LastPaste {
+            index,
+            cursor,
+            after,
+        });
+    }
+
+    /// Returns the index of `text` in the ring.
+    pub(crate) fn position(&self, text: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry == text)
+    }
+
+    /// Returns the entry that replaces the last paste, its index and the
+    /// cursor before the paste, if the paste was the last command. `after`
+    /// holds the current length of the undo history and the cursor.
+    pub(crate) fn cycle(&mut self, after: (usize, Index2)) -> Option<(String, usize, Index2)> {
+        let last = self.last_paste.take().filter(|last| last.after == after)?;
+        if self.entries.is_empty() {
+            return None;
+        }
+        let index = last
+            .index
+            .map_or(0, |index| (index + 1) % self.entries.len());
+        Some((self.entries[index].clone(), index, last.cursor))
+    }
+}
+
+impl EditorState {
+    /// Copies a yanked or deleted text to the clipboard and the yank ring.
+    pub(crate) fn yank(&mut self, text: String) {
+        self.yank_to_register(&text);
+        self.yank_ring.push(&text);
+        self.clip.set_text(text);
+    }
+
+    /// Returns the last yanked and deleted texts, the most recent first.
+    /// A paste can be replaced by an older text with [`PasteCycleNext`].
+    ///
+    /// [`PasteCycleNext`]: crate::actions::PasteCycleNext
+    pub fn yank_ring(&self) -> impl Iterator<Item = &str> {
+        self.yank_ring.entries.iter().map(String::as_str)
+    }
+
+    /// Sets how many yanked and deleted texts are kept, 10 by default.
+    pub fn set_yank_ring_size(&mut self, size: usize) {
+        self.yank_ring.max_size = size;
+        self.yank_ring.entries.truncate(size);
+    }
+}
@@ -0,0 +1,241 @@
+//! Validation of structured text, such as JSON or YAML.
+//!
+//! A [`Validate`] implementation checks the text after it was edited and
+//! returns the errors it found. To avoid validating on every keystroke, the
+//! validation runs once the text has not changed for a debounce duration.
+//! The view underlines the errors and shows the message of the error under
+//! the cursor in the status line. `]d` jumps to the next error.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use edtui::validation::{Validate, ValidationError};
+//! use edtui::{EditorState, Index2, Lines};
+//!
+//! struct NoTabs;
+//!
+//! impl Validate for NoTabs {
+//!     fn validate(&self, lines: &Lines) -> Vec<ValidationError> {
+//!         lines
+//!             .iter()
+//!             .filter(|(ch, _)| *ch == Some(&'\t'))
+//!             .map(|(_, index)| ValidationError::new(index, index, "Tabs are not allowed"))
+//!             .collect()
+//!     }
+//! }
+//!
+//! let mut state = EditorState::new(Lines::from("a\tb"));
+//! state.set_validator(NoTabs, Duration::ZERO);
+//! state.validate();
+//!
+//! assert_eq!(state.validation_errors()[0].start, Index2::new(0, 1));
+//! ```
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    state::{edit::Edit, selection::Selection},
+    EditorState, Index2, Lines,
+};
+
+/// Checks the text of the editor for errors.
+pub trait Validate {
+    /// Returns the errors in the text.
+    fn validate(&self, lines: &Lines) -> Vec<ValidationError>;
+}
+
+/// An error in the text, reported by a [`Validate`] implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The first position of the error.
+    pub start: Index2,
+    /// The last position of the error (inclusive).
+    pub end: Index2,
+    /// A description of the error.
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Creates a new error from `start` to `end` (inclusive).
+    #[must_use]
+    pub fn new<S: Into<String>>(start: Index2, end: Index2, message: S) -> Self {
+        Self {
+            start,
+            end,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn as_selection(&self) -> Selection {
+        Selection::new(self.start, self.end)
+    }
+}
+
+/// The validator of an editor and the errors it reported.
+#[derive(Clone, Default)]
+pub(crate) struct ValidationState {
+    validator: Option<Rc<dyn Validate>>,
+    debounce: Duration,
+    /// When the text was last changed, if it was not validated since.
+    changed: Option<Instant>,
+    pub(crate) errors: Vec<ValidationError>,
+}
+
+impl ValidationState {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.validator.is_some()
+    }
+
+    /// Records that the text was changed, which restarts the debounce.
+    pub(crate) fn mark_changed(&mut self) {
+        if self.is_enabled() {
+            self.changed = Some(Instant::now());
+        }
+    }
+
+    /// Validates the text if it changed and the debounce elapsed.
+    pub(crate) fn update(&mut self, lines: &Lines) {
+        if self
+            .changed
+            .is_some_and(|changed| changed.elapsed() >= self.debounce)
+        {
+            self.run(lines);
+        }
+    }
+
+    /// Validates the text if the debounce elapsed. Returns whether a
+    /// validation was pending.
+    pub(crate) fn tick(&mut self, lines: &Lines) -> bool {
+        let pending = self.changed.is_some();
+        self.update(lines);
+        pending
+    }
+
+    /// Moves the errors along with an edit, until the text is validated
+    /// again. Errors whose text was deleted entirely are removed.
+    pub(crate) fn adjust(&mut self, edit: &Edit) {
+        self.errors.retain_mut(|error| {
+            if edit.is_deleted(error.start, error.end) {
+                return false;
+            }
+            error.start = edit.map(error.start);
+            error.end = edit.map(error.end);
+            true
+        });
+        self.mark_changed();
+    }
+
+    fn run(&mut self, lines: &Lines) {
+        if let Some(validator) = &self.validator {
+            self.errors = validator.validate(lines);
+            self.errors
+                .sort_by_key(|error| (error.start.row, error.start.col));
+        }
+        self.changed = None;
+    }
+}
+
+impl EditorState {
+    /// Sets a validator that checks the text once it has not changed for
+    /// `debounce`. The text is validated on the next render.
+    ///
+    /// The validation runs during rendering, so apps should redraw
+    /// periodically when using a debounce.
+    pub fn set_validator<V: Validate + 'static>(&mut self, validator: V, debounce: Duration) {
+        self.validation = ValidationState {
+            validator: Some(Rc::new(validator)),
+            debounce,
+            changed: Some(Instant::now()),
+            errors: Vec::new(),
+        };
+    }
+
+    /// Removes the validator and its errors.
+    pub fn clear_validator(&mut self) {
+        self.validation = ValidationState::default();
+    }
+
+    /// Validates the text immediately.
+    pub fn validate(&mut self) {
+        self.validation.run(&self.lines);
+    }
+
+    /// Returns the errors of the last validation, ordered by position.
+    #[must_use]
+    pub fn validation_errors(&self) -> &[ValidationError] {
+        &self.validation.errors
+    }
+
+    /// Returns the error under the cursor, if any.
+    #[must_use]
+    pub fn validation_error_at_cursor(&self) -> Option<&ValidationError> {
+        self.validation
+            .errors
+            .iter()
+            .find(|error| error.as_selection().contains(&self.cursor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{InsertChar, NextError};
+    use crate::EditorMode;
+
+    struct NoDigits;
+
+    impl Validate for NoDigits {
+        fn validate(&self, lines: &Lines) -> Vec<ValidationError> {
+            lines
+                .iter()
+                .filter(|(ch, _)| ch.is_some_and(char::is_ascii_digit))
+                .map(|(_, index)| ValidationError::new(index, index, "Digit"))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_validation_is_debounced() {
+        let mut state = EditorState::new(Lines::from("a1\nb2"));
+        state.set_validator(NoDigits, Duration::from_secs(60));
+
+        state.validation.update(&state.lines);
+        assert!(state.validation_errors().is_empty());
+
+        state.validate();
+        assert_eq!(state.validation_errors().len(), 2);
+    }
+
+    #[test]
+    fn test_errors_move_with_edits() {
+        let mut state = EditorState::new(Lines::from("a1\nb2"));
+        state.set_validator(NoDigits, Duration::from_secs(60));
+        state.validate();
+
+        state.mode = EditorMode::Insert;
+        state.execute(InsertChar('x'));
+
+        let error = &state.validation_errors()[0];
+        assert_eq!(error.start, Index2::new(0, 2));
+    }
+
+    #[test]
+    fn test_next_error() {
+        let mut state = EditorState::new(Lines::from("a1\nb2"));
+        state.set_validator(NoDigits, Duration::ZERO);
+        state.validate();
+
+        state.execute(NextError);
+        assert_eq!(state.cursor, Index2::new(0, 1));
+        assert_eq!(state.validation_error_at_cursor().unwrap().message, "Digit");
+
+        state.execute(NextError);
+        assert_eq!(state.cursor, Index2::new(1, 1));
+
+        state.execute(NextError);
+        assert_eq!(state.cursor, Index2::new(0, 1));
+    }
+}
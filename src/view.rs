@@ -1,26 +1,43 @@
+#[cfg(feature = "bidi")]
+mod bidi;
+pub(crate) mod cache;
 mod internal;
+pub mod keymap_help;
 pub(crate) mod line_wrapper;
+mod markdown;
 mod render_line;
+pub mod search_bar;
+pub mod snapshot;
 pub mod status_line;
 #[cfg(feature = "syntax-highlighting")]
 pub mod syntax_higlighting;
 pub mod theme;
 
+use cache::{RenderCache, RenderKey};
+use markdown::Conceal;
 use render_line::RenderLine;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Duration;
 #[cfg(feature = "syntax-highlighting")]
 use syntax_higlighting::SyntaxHighlighter;
 
 use crate::{
-    helper::{max_col, rect_indent_y},
-    state::{selection::Selection, EditorState},
-    EditorMode, Index2,
+    helper::rect_indent_y,
+    state::{
+        notification::NotificationLevel, occurrences::find_word_occurrences, selection::Selection,
+        EditorState,
+    },
+    EditorMode, Index2, Lines,
 };
 
 #[cfg(feature = "syntax-highlighting")]
 use internal::line_into_highlighted_spans_with_selections;
 use internal::line_into_spans_with_selections;
 use jagged::index::RowIndex;
+pub use keymap_help::KeymapHelp;
 use line_wrapper::LineWrapper;
+pub use line_wrapper::{LineLayout, WrapOptions};
 use ratatui_core::{
     buffer::Buffer,
     layout::{Constraint, Layout, Position, Rect},
@@ -28,6 +45,7 @@ use ratatui_core::{
     text::Span,
     widgets::Widget,
 };
+pub use search_bar::{EditorSearchBar, SearchBarPosition};
 pub use status_line::EditorStatusLine;
 use theme::EditorTheme;
 
@@ -43,6 +61,44 @@ pub enum LineNumbers {
     Relative,
 }
 
+/// The lines that stay in focus while all other lines are dimmed, see
+/// [`EditorView::focus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Focus {
+    /// The paragraph of the cursor, i.e. the lines between the blank lines
+    /// around it.
+    Paragraph,
+    /// The rows in the range, e.g. the section a writing app is editing.
+    Rows(Range<usize>),
+}
+
+impl Focus {
+    /// Returns the rows in focus, given the row of the cursor.
+    fn rows(&self, lines: &Lines, cursor_row: usize) -> Range<usize> {
+        match self {
+            Self::Paragraph => {
+                let is_blank = |row: usize| {
+                    lines
+                        .get(RowIndex::new(row))
+                        .is_none_or(|line| line.iter().all(|ch| ch.is_whitespace()))
+                };
+                if is_blank(cursor_row) {
+                    return cursor_row..cursor_row + 1;
+                }
+                let start = (0..cursor_row)
+                    .rev()
+                    .find(|&row| is_blank(row))
+                    .map_or(0, |row| row + 1);
+                let end = (cursor_row + 1..lines.len())
+                    .find(|&row| is_blank(row))
+                    .unwrap_or(lines.len());
+                start..end
+            }
+            Self::Rows(rows) => rows.clone(),
+        }
+    }
+}
+
 /// Creates the view for the editor. [`EditorView`] and [`EditorState`] are
 /// the core classes of edtui.
 ///
@@ -122,6 +178,25 @@ impl<'a, 'b> EditorView<'a, 'b> {
         self
     }
 
+    /// Sets how overflowing lines are wrapped, e.g. whether they break at
+    /// word boundaries and how their continuation rows are indented. See
+    /// [`WrapOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorState, EditorView, WrapOptions};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// EditorView::new(&mut state).wrap_options(WrapOptions::default().indicator("↪ "));
+    /// ```
+    #[must_use]
+    pub fn wrap_options(self, options: WrapOptions) -> Self {
+        self.state.view.wrap_options = options;
+        self
+    }
+
     pub(super) fn get_wrap(&self) -> bool {
         self.state.view.wrap
     }
@@ -159,6 +234,150 @@ impl<'a, 'b> EditorView<'a, 'b> {
         self
     }
 
+    /// Highlights the other occurrences of the word under the cursor within
+    /// the viewport, once the cursor rested on the word for `debounce`. Pass
+    /// `None` to disable the highlighting (default).
+    ///
+    /// The highlighting appears on the first render after the debounce
+    /// elapsed, so apps should redraw periodically when using it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use edtui::{EditorState, EditorView};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// EditorView::new(&mut state).highlight_word_occurrences(Some(Duration::from_millis(300)));
+    /// ```
+    #[must_use]
+    pub fn highlight_word_occurrences(self, debounce: Option<Duration>) -> Self {
+        self.state.view.occurrences.debounce = debounce;
+        self
+    }
+
+    /// Animates the scrolling over the given duration when the cursor jumps
+    /// to a distant row, e.g. with `G` or a search, instead of snapping to
+    /// it. Pass `None` to disable the animation (default).
+    ///
+    /// The animation advances on every render, so apps should redraw while
+    /// [`EditorEventHandler::on_tick`] returns `true`.
+    ///
+    /// [`EditorEventHandler::on_tick`]: crate::EditorEventHandler::on_tick
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use edtui::{EditorState, EditorView};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// EditorView::new(&mut state).smooth_scroll(Some(Duration::from_millis(150)));
+    /// ```
+    #[must_use]
+    pub fn smooth_scroll(self, duration: Option<Duration>) -> Self {
+        self.state.view.smooth_scroll = duration;
+        self
+    }
+
+    /// Reuses the last rendered frame if the [`EditorState::generation`] and
+    /// the area did not change, to save CPU in apps that redraw
+    /// at a fixed rate. Disabled by default.
+    ///
+    /// Only the state is compared, not the theme or the syntax highlighter.
+    /// Call [`EditorState::invalidate_render_cache`] after changing them,
+    /// or after modifying the text directly instead of through actions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorState, EditorView};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// EditorView::new(&mut state).render_cache(true);
+    /// ```
+    #[must_use]
+    pub fn render_cache(self, enabled: bool) -> Self {
+        self.state.view.cache_renders = enabled;
+        if !enabled {
+            self.state.view.render_cache = None;
+        }
+        self
+    }
+
+    /// Sets the number of rows that consecutive pages overlap when
+    /// scrolling by a full page with `PageUp` and `PageDown`. Defaults to 2.
+    #[must_use]
+    pub fn page_overlap(self, page_overlap: usize) -> Self {
+        self.state.view.page_overlap = page_overlap;
+        self
+    }
+
+    /// Highlights the given columns in every line, e.g. to mark the maximum
+    /// line length. Columns are zero-based, so that a color column at 72
+    /// marks the first character beyond 72 characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorState, EditorView};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// EditorView::new(&mut state).color_columns(vec![50, 72]);
+    /// ```
+    #[must_use]
+    pub fn color_columns(self, color_columns: Vec<usize>) -> Self {
+        self.state.view.color_columns = color_columns;
+        self
+    }
+
+    /// Renders lines that start with `comment_char` in the comment style of
+    /// the theme. Pass `None` to disable it (default).
+    #[must_use]
+    pub fn comment_char(self, comment_char: Option<char>) -> Self {
+        self.state.view.comment_char = comment_char;
+        self
+    }
+
+    /// Dims all lines but the ones in focus with the dim style of the theme,
+    /// e.g. for writing apps. Pass `None` to disable it (default).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorState, EditorView, Focus};
+    ///
+    /// let mut state = EditorState::default();
+    ///
+    /// // Dim all lines outside of the paragraph of the cursor
+    /// EditorView::new(&mut state).focus(Some(Focus::Paragraph));
+    ///
+    /// // Dim all lines outside of the first ten rows
+    /// EditorView::new(&mut state).focus(Some(Focus::Rows(0..10)));
+    /// ```
+    #[must_use]
+    pub fn focus(self, focus: Option<Focus>) -> Self {
+        self.state.view.focus = focus;
+        self
+    }
+
+    /// Renders markdown markup: headings, emphasis, inline code and list
+    /// bullets are styled, and their markup characters are concealed on all
+    /// lines but the cursor line. The text itself is not modified. Disabled
+    /// by default.
+    ///
+    /// # Note
+    /// Mouse positions on lines with concealed characters are not adjusted.
+    #[must_use]
+    pub fn markdown(self, markdown: bool) -> Self {
+        self.state.view.markdown = markdown;
+        self
+    }
+
     /// Returns the line numbers configuration.
     pub(super) fn get_line_numbers(&self) -> LineNumbers {
         self.state.view.line_numbers
@@ -188,9 +407,33 @@ impl<'a, 'b> EditorView<'a, 'b> {
     }
 }
 
+/// The sign shown in the sign column for bookmarked lines.
+const BOOKMARK_SIGN: &str = "▌";
+
 impl Widget for EditorView<'_, '_> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        self.state.close_gap();
+        if !self.state.view.cache_renders {
+            self.render_editor(area, buf);
+            return;
+        }
+
+        let key = RenderKey::new(self.state, area);
+        let cache = &self.state.view.render_cache;
+        if let (Some(key), Some(cache)) = (&key, cache) {
+            if cache.restore(key, buf) {
+                return;
+            }
+        }
+        self.render_editor(area, buf);
+        self.state.view.render_cache =
+            RenderKey::new(self.state, area).map(|key| RenderCache::new(key, buf));
+    }
+}
+
+impl EditorView<'_, '_> {
     #[allow(clippy::too_many_lines)]
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    fn render_editor(&mut self, area: Rect, buf: &mut Buffer) {
         // Draw the border.
         buf.set_style(area, self.theme.base);
         let area = match &self.theme.block {
@@ -209,13 +452,16 @@ impl Widget for EditorView<'_, '_> {
         ])
         .areas(area);
 
-        // Calculate line number gutter width and split area
+        // Calculate the gutter width (sign column and line numbers) and split area
         let line_number_width = self.line_number_width();
+        let sign_width = u16::from(!self.state.bookmarks.is_empty());
         let line_numbers_style = self.theme.line_numbers_style;
-        let (gutter_area, content_main) = if line_number_width > 0 {
-            let [gutter, content] =
-                Layout::horizontal([Constraint::Length(line_number_width), Constraint::Min(0)])
-                    .areas(main);
+        let (gutter_area, content_main) = if sign_width + line_number_width > 0 {
+            let [gutter, content] = Layout::horizontal([
+                Constraint::Length(sign_width + line_number_width),
+                Constraint::Min(0),
+            ])
+            .areas(main);
             // Fill the entire gutter with the line numbers style
             buf.set_style(gutter, line_numbers_style);
             (Some(gutter), content)
@@ -232,9 +478,16 @@ impl Widget for EditorView<'_, '_> {
 
         // Retrieve the displayed cursor position. The column of the displayed
         // cursor is clamped to the maximum line length.
-        let max_col = max_col(&self.state.lines, &self.state.cursor, self.state.mode);
+        let max_col = self.state.max_cursor_col();
         let cursor = Index2::new(self.state.cursor.row, self.state.cursor.col.min(max_col));
 
+        // An in-progress IME composition is displayed in front of the cursor.
+        let composition: Vec<char> = self.state.composition.chars().collect();
+        let composition_col = cursor
+            .col
+            .min(lines.len_col(cursor.row).unwrap_or_default());
+        let cursor = Index2::new(cursor.row, cursor.col + composition.len());
+
         // Store the coordinates of the current editor.
         // Use content_main (not main) so mouse events are calculated relative to text area.
         self.state.view.set_screen_area(content_main);
@@ -242,27 +495,116 @@ impl Widget for EditorView<'_, '_> {
         // Update the view offset. Requires the screen size and the position
         // of the cursor. Updates the view offset only if the cursor is out
         // side of the view port. The state is stored in the `ViewOffset`.
+        // The rows within the scroll-off margin are brought into view first,
+        // the cursor row last so that it stays visible on small viewports.
         let view_state = &mut self.state.view;
-        let (offset_x, offset_y) = if wrap_lines {
-            (
-                0,
-                view_state.update_viewport_vertical_wrap(width, height, cursor.row, lines),
-            )
+        let scroll_off = view_state.scroll_off.min(height.saturating_sub(1) / 2);
+        let rows = [
+            (cursor.row + scroll_off).min(lines.len().saturating_sub(1)),
+            cursor.row.saturating_sub(scroll_off),
+            cursor.row,
+        ];
+        let offset_x = if wrap_lines {
+            for row in rows {
+                view_state.update_viewport_vertical_wrap(width, height, row, lines);
+            }
+            0
         } else {
+            for row in rows {
+                view_state.update_viewport_vertical(height, row);
+            }
             let line = lines.get(RowIndex::new(cursor.row));
-            (
-                view_state.update_viewport_horizontal(width, cursor.col, line),
-                view_state.update_viewport_vertical(height, cursor.row),
-            )
+            view_state.update_viewport_horizontal(width, cursor.col, line)
         };
 
+        // Jumps of the viewport may be animated.
+        let offset_y = view_state.animate_viewport_vertical();
+
         // Predetermine highlighted sections.
         let mut search_selection: Option<Selection> = None;
         if self.state.mode == EditorMode::Search {
             search_selection = (&self.state.search).into();
         };
         let selections = vec![&self.state.selection, &search_selection];
+        let mut highlights: Vec<(Selection, Style)> = Vec::new();
+        if let Some(comment_char) = self.state.view.comment_char {
+            let style = self.theme.base.patch(self.theme.comment_style);
+            for (row, line) in lines.iter_row().enumerate().skip(offset_y).take(height) {
+                if line.first() == Some(&comment_char) {
+                    let end = Index2::new(row, line.len() - 1);
+                    highlights.push((Selection::new(Index2::new(row, 0), end), style));
+                }
+            }
+        }
+        let mut conceals: HashMap<usize, Vec<Conceal>> = HashMap::new();
+        if self.state.view.markdown {
+            for (row, line) in lines.iter_row().enumerate().skip(offset_y).take(height) {
+                let markup = markdown::parse(line, &self.theme.markdown);
+                for (start, end, style) in markup.styles {
+                    let selection = Selection::new(Index2::new(row, start), Index2::new(row, end));
+                    highlights.push((selection, self.theme.base.patch(style)));
+                }
+                if row != cursor.row && !markup.conceals.is_empty() {
+                    conceals.insert(row, markup.conceals);
+                }
+            }
+        }
+        let word = self.state.word_under_cursor();
+        if self.state.view.occurrences.update(word.as_ref()) {
+            let word: Vec<char> = word.unwrap_or_default().chars().collect();
+            let rows = offset_y..offset_y + height;
+            for start in find_word_occurrences(lines, &word, rows) {
+                let end = Index2::new(start.row, start.col + word.len() - 1);
+                let occurrence = Selection::new(start, end);
+                if !occurrence.contains(&self.state.cursor) {
+                    highlights.push((occurrence, self.theme.word_occurrence_style));
+                }
+            }
+        }
+        highlights.extend(
+            self.state
+                .highlights
+                .sorted()
+                .into_iter()
+                .map(|highlight| (highlight.as_selection(), highlight.style)),
+        );
+        self.state.validation.update(&self.state.lines);
+        highlights.extend(self.state.validation_errors().iter().map(|error| {
+            let style = self.theme.base.patch(self.theme.error_style);
+            (error.as_selection(), style)
+        }));
+        if let Some(row) = self.state.locations.flashed_row() {
+            let flash = Selection::new(Index2::new(row, 0), Index2::new(row, 0)).line_mode();
+            highlights.push((flash, self.theme.base.patch(self.theme.flash_style)));
+        }
+        if let Some(yanked) = self.state.yank_flash.region() {
+            highlights.push((yanked.clone(), self.theme.base.patch(self.theme.yank_style)));
+        }
+        if !composition.is_empty() {
+            let start = Index2::new(cursor.row, composition_col);
+            let end = Index2::new(cursor.row, composition_col + composition.len() - 1);
+            let style = self.theme.base.patch(self.theme.composition_style);
+            highlights.push((Selection::new(start, end), style));
+        }
 
+        let anchor = self
+            .state
+            .selection
+            .as_ref()
+            .filter(|_| self.theme.selection_anchor_style.is_some())
+            .map(|selection| {
+                let anchor = selection.anchor_position();
+                let max_col = lines.len_col(anchor.row).unwrap_or_default();
+                Index2::new(anchor.row, anchor.col.min(max_col.saturating_sub(1)))
+            })
+            .filter(|anchor| *anchor != cursor);
+        let focus = self
+            .state
+            .view
+            .focus
+            .as_ref()
+            .map(|focus| focus.rows(lines, cursor.row));
+        let mut anchor_position: Option<Position> = None;
         let mut cursor_position: Option<Position> = None;
         let mut content_area = content_main;
         let mut gutter_row_area = gutter_area;
@@ -277,12 +619,33 @@ impl Widget for EditorView<'_, '_> {
                 break;
             }
 
+            let composed: Vec<char>;
+            let line: &[char] = if row_index == cursor.row && !composition.is_empty() {
+                composed = [
+                    &line[..composition_col],
+                    &composition,
+                    &line[composition_col..],
+                ]
+                .concat();
+                &composed
+            } else {
+                line
+            };
+
             let col_skips = offset_x;
             num_rendered_rows += 1;
 
+            // Lines with right-to-left text are reordered for display, which
+            // requires the spans of the whole line.
+            #[cfg(feature = "bidi")]
+            let visual_order = bidi::visual_order(line);
+            #[cfg(feature = "bidi")]
+            let col_skips = if visual_order.is_some() { 0 } else { col_skips };
+
             let spans = generate_spans(
                 line,
                 &selections,
+                &highlights,
                 row_index,
                 col_skips,
                 &self.theme.base,
@@ -290,16 +653,36 @@ impl Widget for EditorView<'_, '_> {
                 #[cfg(feature = "syntax-highlighting")]
                 self.syntax_highlighter.as_ref(),
             );
+            // Markup is concealed only on lines that are not reordered.
+            #[cfg(feature = "bidi")]
+            let conceal = conceals.get(&row_index).filter(|_| visual_order.is_none());
+            #[cfg(not(feature = "bidi"))]
+            let conceal = conceals.get(&row_index);
+            let spans = match conceal {
+                Some(conceal) => markdown::conceal_spans(spans, conceal, col_skips),
+                None => spans,
+            };
+            #[cfg(feature = "bidi")]
+            let spans = match &visual_order {
+                Some(order) => bidi::reorder_spans(spans, order, offset_x),
+                None => spans,
+            };
 
             let render_line = if wrap_lines {
-                RenderLine::Wrapped(LineWrapper::wrap_spans(spans, width, tab_width))
+                let options = &self.state.view.wrap_options;
+                let (lines, prefix) = LineWrapper::wrap_spans(spans, width, tab_width, options);
+                RenderLine::Wrapped(lines, Span::styled(prefix, self.theme.wrap_indicator_style))
             } else {
                 RenderLine::Single(spans)
             };
 
-            // Render line number in the gutter
-            if line_numbers_enabled {
-                if let Some(gutter) = gutter_row_area {
+            // Render the sign and the line number in the gutter
+            if let Some(gutter) = gutter_row_area {
+                if self.state.bookmarks.contains(&row_index) {
+                    let sign = Span::styled(BOOKMARK_SIGN, self.theme.bookmark_style);
+                    buf.set_span(gutter.x, gutter.y, &sign, sign_width);
+                }
+                if line_numbers_enabled {
                     let is_cursor_line = row_index == cursor.row;
                     let line_num = if is_relative {
                         if is_cursor_line {
@@ -327,71 +710,166 @@ impl Widget for EditorView<'_, '_> {
                     };
                     let num_span = Span::styled(num_str, line_numbers_style);
 
-                    let line_num_area = Rect::new(gutter.x, gutter.y, gutter.width, 1);
+                    let line_num_area = Rect::new(
+                        gutter.x + sign_width,
+                        gutter.y,
+                        gutter.width.saturating_sub(sign_width),
+                        1,
+                    );
                     buf.set_span(
                         line_num_area.x,
                         line_num_area.y,
                         &num_span,
                         line_num_area.width,
                     );
-
-                    let num_lines = render_line.num_lines() as u16;
-                    gutter_row_area = Some(Rect::new(
-                        gutter.x,
-                        gutter.y.saturating_add(num_lines),
-                        gutter.width,
-                        gutter.height.saturating_sub(num_lines),
-                    ));
                 }
+
+                let num_lines = render_line.num_lines() as u16;
+                gutter_row_area = Some(Rect::new(
+                    gutter.x,
+                    gutter.y.saturating_add(num_lines),
+                    gutter.width,
+                    gutter.height.saturating_sub(num_lines),
+                ));
             }
 
-            // Determine the cursor position.
-            if row_index == cursor.row {
-                cursor_position = Some(render_line.data_coordinate_to_screen_coordinate(
-                    cursor.col.saturating_sub(offset_x),
+            // Determine the cursor position and the position of the anchor
+            // of the selection.
+            let screen_position = |col: usize| {
+                #[cfg(feature = "bidi")]
+                let col = match &visual_order {
+                    Some(order) => bidi::visual_col(order, col),
+                    None => col,
+                };
+                let mut position = render_line.data_coordinate_to_screen_coordinate(
+                    col.saturating_sub(offset_x),
                     content_area,
                     tab_width,
-                ));
+                );
+                // With virtual editing, the cursor may be beyond the end of the line.
+                let beyond_line = col.saturating_sub(line.len().max(offset_x));
+                position.x = (position.x as usize + beyond_line)
+                    .min(content_area.right().saturating_sub(1) as usize)
+                    as u16;
+                position
+            };
+            if row_index == cursor.row {
+                cursor_position = Some(screen_position(cursor.col));
+            }
+            if let Some(anchor) = anchor.filter(|anchor| anchor.row == row_index) {
+                anchor_position = Some(screen_position(anchor.col));
             }
 
             // Render the current line.
             content_area = {
                 let num_lines = render_line.num_lines();
-                render_line.render(content_area, buf, tab_width);
+                render_line.render(content_area, buf, tab_width, self.theme.control_char_style);
+                let num_rows = (num_lines as u16).min(content_area.height);
+                // Dimming is layered on top of all other styles but the cursor.
+                if focus
+                    .as_ref()
+                    .is_some_and(|rows| !rows.contains(&row_index))
+                {
+                    let rows =
+                        Rect::new(content_area.x, content_area.y, content_area.width, num_rows);
+                    buf.set_style(rows, self.theme.dim_style);
+                }
+                for y in content_area.top()..content_area.top() + num_rows {
+                    for col in &self.state.view.color_columns {
+                        let x = content_area.x as usize + col.saturating_sub(offset_x);
+                        if *col < offset_x || x >= content_area.right() as usize {
+                            continue;
+                        }
+                        if let Some(cell) = buf.cell_mut(Position::new(x as u16, y)) {
+                            cell.set_style(self.theme.color_column_style);
+                        }
+                    }
+                }
                 rect_indent_y(content_area, num_lines)
             };
 
             row_index += 1;
         }
 
-        // Render the cursor on top.
-        if let Some(cell) = buf.cell_mut(cursor_position.unwrap_or(Position::new(
-            content_main.left(),
-            content_main.top() + self.state.cursor.row as u16,
-        ))) {
-            cell.set_style(self.theme.cursor_style);
+        // Render the cursor on top. While scrolling, the cursor may be
+        // outside of the rendered rows.
+        let cursor_position = cursor_position.or_else(|| {
+            (!self.state.view.is_scrolling()).then(|| {
+                Position::new(
+                    content_main.left(),
+                    content_main.top() + self.state.cursor.row as u16,
+                )
+            })
+        });
+        if let Some(style) = self.theme.selection_anchor_style {
+            if let Some(cell) = anchor_position.and_then(|position| buf.cell_mut(position)) {
+                cell.set_style(style);
+            }
+        }
+        if let Some(cell) = cursor_position.and_then(|position| buf.cell_mut(position)) {
+            cell.set_style(self.theme.cursor_style_for(self.state.mode));
         }
 
         // Save the total number of lines that are currently displayed on the viewport.
         // Required to handle scrolling.
         self.state.view.update_num_rows(num_rendered_rows);
+        self.state.view.finish_render();
+
+        // Render the search bar on top of the text.
+        let search_bar = self
+            .theme
+            .search_bar
+            .take()
+            .filter(|_| self.state.mode == EditorMode::Search);
+        let has_search_bar = search_bar.is_some();
+        if let Some(search_bar) = search_bar {
+            let search = &self.state.search;
+            search_bar
+                .query(search.query())
+                .matches(search.selected_index, search.matches.len())
+                .regex(search.is_regex())
+                .render(main, buf);
+        }
 
         // Render the status line.
-        if let Some(s) = self.theme.status_line {
+        if let Some(s) = self.theme.status_line.take() {
+            let (message, level) = match self.state.notification() {
+                Some(notification) => (Some(notification.message.clone()), notification.level),
+                None => match self.state.validation_error_at_cursor() {
+                    Some(error) => (Some(error.message.clone()), NotificationLevel::Error),
+                    None => (
+                        self.state
+                            .current_location()
+                            .filter(|location| location.position.row == cursor.row)
+                            .map(|location| location.message.clone()),
+                        NotificationLevel::Info,
+                    ),
+                },
+            };
             s.mode(self.state.mode.name())
-                .search(if self.state.mode == EditorMode::Search {
-                    Some(self.state.search_pattern())
-                } else {
-                    None
-                })
+                .search(
+                    if self.state.mode == EditorMode::Search && !has_search_bar {
+                        Some(self.state.search.query())
+                    } else {
+                        None
+                    },
+                )
+                .command(
+                    (self.state.mode == EditorMode::Command)
+                        .then(|| self.state.command_line.clone()),
+                )
+                .message(message)
+                .level(level)
                 .render(status, buf);
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_spans<'a>(
     line: &[char],
     selections: &[&Option<Selection>],
+    highlights: &[(Selection, Style)],
     row_index: usize,
     col_skips: usize,
     base_style: &Style,
@@ -403,6 +881,7 @@ fn generate_spans<'a>(
         return line_into_highlighted_spans_with_selections(
             line,
             selections,
+            highlights,
             syntax,
             row_index,
             col_skips,
@@ -413,9 +892,131 @@ fn generate_spans<'a>(
     line_into_spans_with_selections(
         line,
         selections,
+        highlights,
         row_index,
         col_skips,
         base_style,
         highlight_style,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lines;
+
+    #[test]
+    fn test_render_wrapped_at_words() {
+        let mut state = EditorState::new(Lines::from("  foo barbaz qux"));
+        state.cursor = Index2::new(0, 13);
+        let area = Rect::new(0, 0, 10, 4);
+        let mut buf = Buffer::empty(area);
+        let theme = EditorTheme::default().hide_status_line();
+        EditorView::new(&mut state)
+            .theme(theme)
+            .wrap_options(WrapOptions::default().indicator("> ").indent(true))
+            .render(area, &mut buf);
+
+        let row = |y: u16| -> String { (0..10).map(|x| buf[(x, y)].symbol()).collect() };
+        assert_eq!(row(0), "  foo     ");
+        assert_eq!(row(1), "  > barbaz");
+        assert_eq!(row(2), "  >  qux  ");
+        assert_eq!(buf[(5, 2)].style().bg, Some(theme::WHITE));
+    }
+
+    #[test]
+    fn test_render_mode_cursor_and_anchor_styles() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        let area = Rect::new(0, 0, 10, 1);
+        let render = |state: &mut EditorState| {
+            let theme = EditorTheme::default()
+                .hide_status_line()
+                .mode_cursor_style(EditorMode::Insert, Style::default().bg(theme::RED))
+                .selection_anchor_style(Style::default().bg(theme::GRAY));
+            let mut buf = Buffer::empty(area);
+            EditorView::new(state).theme(theme).render(area, &mut buf);
+            buf
+        };
+
+        let buf = render(&mut state);
+        assert_eq!(buf[(0, 0)].style().bg, Some(theme::WHITE));
+
+        state.mode = EditorMode::Insert;
+        let buf = render(&mut state);
+        assert_eq!(buf[(0, 0)].style().bg, Some(theme::RED));
+
+        state.mode = EditorMode::Normal;
+        state.execute(crate::actions::SwitchMode(EditorMode::Visual));
+        state.execute(crate::actions::MoveForward(3));
+        let buf = render(&mut state);
+        assert_eq!(buf[(0, 0)].style().bg, Some(theme::GRAY));
+        assert_eq!(buf[(1, 0)].style().bg, Some(theme::YELLOW));
+        assert_eq!(buf[(3, 0)].style().bg, Some(theme::WHITE));
+    }
+
+    #[test]
+    fn test_render_focus() {
+        let mut state = EditorState::new(Lines::from("one\ntwo\n\nthree"));
+        state.cursor = Index2::new(1, 0);
+        let area = Rect::new(0, 0, 10, 4);
+        let render = |state: &mut EditorState, focus: Focus| {
+            let theme = EditorTheme::default().hide_status_line();
+            let mut buf = Buffer::empty(area);
+            EditorView::new(state)
+                .theme(theme)
+                .focus(Some(focus))
+                .render(area, &mut buf);
+            buf
+        };
+        let is_dimmed = |buf: &Buffer, y: u16| {
+            buf[(1, y)]
+                .modifier
+                .contains(ratatui_core::style::Modifier::DIM)
+        };
+
+        let buf = render(&mut state, Focus::Paragraph);
+        assert!(!is_dimmed(&buf, 0));
+        assert!(!is_dimmed(&buf, 1));
+        assert!(is_dimmed(&buf, 2));
+        assert!(is_dimmed(&buf, 3));
+
+        let buf = render(&mut state, Focus::Rows(3..4));
+        assert!(is_dimmed(&buf, 0));
+        assert!(!is_dimmed(&buf, 3));
+    }
+
+    #[test]
+    fn test_render_cache() {
+        let mut state = EditorState::new(Lines::from("Hello"));
+        let area = Rect::new(0, 0, 10, 2);
+        let render = |state: &mut EditorState, buf: &mut Buffer| {
+            let theme = EditorTheme::default().hide_status_line();
+            EditorView::new(state)
+                .theme(theme)
+                .render_cache(true)
+                .render(area, buf);
+        };
+        let row = |buf: &Buffer| -> String { (0..10).map(|x| buf[(x, 0)].symbol()).collect() };
+
+        let mut buf = Buffer::empty(area);
+        render(&mut state, &mut buf);
+        assert_eq!(row(&buf), "Hello     ");
+
+        // The cached frame is reused, even though the text changed
+        // behind the back of the editor.
+        state.lines = Lines::from("World");
+        let mut buf = Buffer::empty(area);
+        render(&mut state, &mut buf);
+        assert_eq!(row(&buf), "Hello     ");
+
+        state.invalidate_render_cache();
+        let mut buf = Buffer::empty(area);
+        render(&mut state, &mut buf);
+        assert_eq!(row(&buf), "World     ");
+
+        state.execute(crate::actions::DeleteCharForward(1));
+        let mut buf = Buffer::empty(area);
+        render(&mut state, &mut buf);
+        assert_eq!(row(&buf), "orld      ");
+    }
+}
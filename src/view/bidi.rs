@@ -0,0 +1,83 @@
+//! Bidirectional text rendering.
+//!
+//! Lines that contain right-to-left text, such as Arabic or Hebrew, are
+//! reordered for display using the Unicode Bidirectional Algorithm. The text
+//! itself stays in logical order, so that the cursor moves through the text
+//! in the order it is read. Each line is treated as a separate paragraph.
+use ratatui_core::text::Span;
+use unicode_bidi::ParagraphBidiInfo;
+
+/// Returns the logical index of each character of a line in visual order,
+/// or `None` if the line contains no right-to-left text.
+pub(super) fn visual_order(line: &[char]) -> Option<Vec<usize>> {
+    let text: String = line.iter().collect();
+    let info = ParagraphBidiInfo::new(&text, None);
+    if !info.has_rtl() {
+        return None;
+    }
+    let levels = info.reordered_levels_per_char(0..text.len());
+    Some(ParagraphBidiInfo::reorder_visual(&levels))
+}
+
+/// Returns the visual column of the character at the logical column `col`.
+pub(super) fn visual_col(order: &[usize], col: usize) -> usize {
+    order.iter().position(|&i| i == col).unwrap_or(col)
+}
+
+/// Reorders the characters of `spans` into visual order and skips the first
+/// `col_skips` visual columns. The style of each character is kept.
+pub(super) fn reorder_spans<'a>(
+    spans: Vec<Span<'a>>,
+    order: &[usize],
+    col_skips: usize,
+) -> Vec<Span<'a>> {
+    let chars: Vec<(char, _)> = spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(|ch| (ch, span.style)))
+        .collect();
+
+    let mut reordered: Vec<Span> = Vec::new();
+    for &(ch, style) in order.iter().skip(col_skips).filter_map(|&i| chars.get(i)) {
+        match reordered.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push(ch),
+            _ => reordered.push(Span::styled(String::from(ch), style)),
+        }
+    }
+    reordered
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui_core::style::Style;
+
+    use super::*;
+
+    #[test]
+    fn test_visual_order() {
+        let line: Vec<char> = "ab אבג".chars().collect();
+
+        let order = visual_order(&line).unwrap();
+
+        assert_eq!(order, vec![0, 1, 2, 5, 4, 3]);
+        assert_eq!(visual_col(&order, 3), 5);
+        assert_eq!(visual_order(&['a', 'b']), None);
+    }
+
+    #[test]
+    fn test_reorder_spans_keeps_styles() {
+        let selected = Style::default().red();
+        let spans = vec![
+            Span::raw("ab "),
+            Span::styled("אב", selected),
+            Span::raw("ג"),
+        ];
+        let order = vec![0, 1, 2, 5, 4, 3];
+
+        let reordered = reorder_spans(spans, &order, 1);
+
+        assert_eq!(
+            reordered,
+            vec![Span::raw("b ג"), Span::styled("בא", selected)]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Reuses the last rendered frame if nothing changed since.
+use ratatui_core::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+};
+
+use super::LineNumbers;
+use crate::{state::selection::Selection, EditorMode, EditorState, Index2};
+
+/// Everything a rendered frame depends on. The text is covered by the
+/// [`EditorState::generation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RenderKey {
+    generation: u64,
+    area: Rect,
+    cursor: Index2,
+    mode: EditorMode,
+    selection: Option<Selection>,
+    viewport: (usize, usize),
+    wrap: bool,
+    tab_width: usize,
+    line_numbers: LineNumbers,
+}
+
+impl RenderKey {
+    /// Returns the key of a frame in `area`, or `None` if the frame changes
+    /// over time, e.g. while a notification or a yank flash is shown.
+    pub(crate) fn new(state: &EditorState, area: Rect) -> Option<Self> {
+        let animated = state.view.is_scrolling()
+            || state.view.occurrences.is_pending()
+            || state.yank_flash.region().is_some()
+            || state.locations.flashed_row().is_some()
+            || state.notifications.current().is_some();
+        (!animated).then(|| Self {
+            generation: state.generation,
+            area,
+            cursor: state.cursor,
+            mode: state.mode,
+            selection: state.selection.clone(),
+            viewport: (state.view.viewport.x, state.view.viewport.y),
+            wrap: state.view.wrap,
+            tab_width: state.view.tab_width,
+            line_numbers: state.view.line_numbers,
+        })
+    }
+}
+
+/// The last rendered frame.
+#[derive(Debug, Clone)]
+pub(crate) struct RenderCache {
+    key: RenderKey,
+    buffer: Buffer,
+}
+
+impl RenderCache {
+    /// Copies the area of the frame from `buf`.
+    pub(crate) fn new(key: RenderKey, buf: &Buffer) -> Self {
+        let area = key.area.intersection(buf.area);
+        let mut buffer = Buffer::empty(area);
+        for position in area.positions() {
+            copy_cell(buf, &mut buffer, position);
+        }
+        Self { key, buffer }
+    }
+
+    /// Copies the frame into `buf` if it was rendered with the same key.
+    /// Returns whether the frame was reused.
+    pub(crate) fn restore(&self, key: &RenderKey, buf: &mut Buffer) -> bool {
+        if self.key != *key || self.buffer.area != key.area.intersection(buf.area) {
+            return false;
+        }
+        for position in self.buffer.area.positions() {
+            copy_cell(&self.buffer, buf, position);
+        }
+        true
+    }
+}
+
+fn copy_cell(from: &Buffer, to: &mut Buffer, position: Position) {
+    if let (Some(from), Some(to)) = (from.cell(position), to.cell_mut(position)) {
+        *to = from.clone();
+    }
+}
@@ -161,61 +161,65 @@ impl From<InternalSpan> for Span<'_> {
     }
 }
 
-/// Converts a line into a vector of `Span`s, applying styles based on the given selections.
+/// Converts a line into a vector of `Span`s, applying styles based on the given selections
+/// and highlights. Highlights must be ordered from lowest to highest priority.
 pub(crate) fn line_into_spans_with_selections<'a>(
     line: &[char],
     selections: &[&Option<Selection>],
+    highlights: &[(Selection, Style)],
     row_index: usize,
     col_skips: usize,
     base_style: &Style,
     highlight_style: &Style,
 ) -> Vec<Span<'a>> {
     let mut spans = Vec::new();
-    let mut push_span = |span: String, is_selected: bool| {
-        let style = if is_selected {
-            highlight_style
-        } else {
-            base_style
-        };
-
-        spans.push(Span::styled(span, *style));
-    };
-
     let mut current_span = String::new();
-    let mut previous_is_selected = false;
+    let mut previous_style = *base_style;
 
     // Iterate over the line's characters, starting from the offset
     for (i, &ch) in line.iter().skip(col_skips).enumerate() {
         let position = Index2::new(row_index, col_skips + i);
 
         // Check if the current position is selected by any selection
-        let current_is_selected = selections
+        let is_selected = selections
             .iter()
             .filter_map(|selection| selection.as_ref())
             .any(|selection| selection.contains(&position));
 
-        // If the selection state has changed, push the current span and start a new one
-        if i != 0 && previous_is_selected != current_is_selected {
-            push_span(current_span.clone(), previous_is_selected);
+        let current_style = if is_selected {
+            *highlight_style
+        } else {
+            highlights
+                .iter()
+                .rev()
+                .find(|(selection, _)| selection.contains(&position))
+                .map_or(*base_style, |(_, style)| *style)
+        };
+
+        // If the style has changed, push the current span and start a new one
+        if i != 0 && previous_style != current_style {
+            spans.push(Span::styled(current_span.clone(), previous_style));
             current_span.clear();
         }
 
-        previous_is_selected = current_is_selected;
+        previous_style = current_style;
         current_span.push(ch);
     }
 
     // Push the final span
-    push_span(current_span.clone(), previous_is_selected);
+    spans.push(Span::styled(current_span, previous_style));
 
     spans
 }
 
-/// Converts a line into a vector of `Span`s, applying styles based on the given selections
-/// and syntax highlighting.
+/// Converts a line into a vector of `Span`s, applying styles based on the given selections,
+/// highlights and syntax highlighting.
 #[cfg(feature = "syntax-highlighting")]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn line_into_highlighted_spans_with_selections<'a>(
     line: &[char],
     selections: &[&Option<Selection>],
+    highlights: &[(Selection, Style)],
     syntax_highligher: &SyntaxHighlighter,
     row_index: usize,
     col_skips: usize,
@@ -225,6 +229,17 @@ pub(crate) fn line_into_highlighted_spans_with_selections<'a>(
     let line: String = line.iter().collect();
     let mut internal_spans = syntax_highligher.highlight_line(&line, base_style);
 
+    for (highlight, style) in highlights
+        .iter()
+        .filter(|(highlight, _)| highlight.contains_row(row_index))
+    {
+        if let Some(new_span) =
+            InternalSpan::split_at_selection(&internal_spans, row_index, highlight, style)
+        {
+            internal_spans = new_span;
+        }
+    }
+
     let selections = selections
         .iter()
         .filter_map(|selection| selection.as_ref().filter(|s| s.contains_row(row_index)));
@@ -250,7 +265,7 @@ pub(crate) fn line_into_highlighted_spans_with_selections<'a>(
 /// # Example
 /// ```ignore
 /// let wrapped_spans = vec![vec![Span::from("hello")], vec![Span::from("world")]];
-/// let index = find_position_in_wrapped_spans(&wrapped_spans, 6, 5);
+/// let index = find_position_in_wrapped_spans(&wrapped_spans, 6, 5, 0, 0);
 /// assert_eq!(index, Index2::new(1, 1));
 /// ```
 pub(super) fn find_position_in_wrapped_spans(
@@ -258,11 +273,14 @@ pub(super) fn find_position_in_wrapped_spans(
     col_index: usize,
     max_width: usize,
     tab_width: usize,
+    prefix_width: usize,
 ) -> Index2 {
     if wrapped_spans.is_empty() {
         return Index2::new(0, col_index);
     }
 
+    // Continuation rows start after the prefix
+    let indent = |row: usize| if row > 0 { prefix_width } else { 0 };
     let mut char_pos = col_index;
 
     for (row, spans) in wrapped_spans.iter().enumerate() {
@@ -271,7 +289,7 @@ pub(super) fn find_position_in_wrapped_spans(
 
         if char_pos <= max_char_pos {
             let col = unicode_width_position_in_spans(spans, char_pos, tab_width);
-            return Index2::new(row, col);
+            return Index2::new(row, indent(row) + col);
         }
 
         if row + 1 < wrapped_spans.len() {
@@ -279,15 +297,16 @@ pub(super) fn find_position_in_wrapped_spans(
         }
     }
 
+    let last_row = wrapped_spans.len() - 1;
     let last_span_width = match wrapped_spans.last() {
         Some(span) => spans_width(span, tab_width),
         None => 0,
     };
 
-    if last_span_width >= max_width {
-        Index2::new(wrapped_spans.len(), 0)
+    if indent(last_row) + last_span_width >= max_width {
+        Index2::new(wrapped_spans.len(), prefix_width)
     } else {
-        Index2::new(wrapped_spans.len().saturating_sub(1), last_span_width)
+        Index2::new(last_row, indent(last_row) + last_span_width)
     }
 }
 
@@ -340,19 +359,43 @@ mod tests {
         // given a highlighted line
         let base = Style::default();
         let hightlighted = Style::default().red();
-        let line = "Hello".chars().into_iter().collect::<Vec<char>>();
+        let line = "Hello".chars().collect::<Vec<char>>();
 
         let selection = Some(Selection::new(Index2::new(0, 0), Index2::new(0, 2)));
         let selections = vec![&selection];
 
         // when `line_into_spans_with_selections` is called
-        let spans = line_into_spans_with_selections(&line, &selections, 0, 0, &base, &hightlighted);
+        let spans =
+            line_into_spans_with_selections(&line, &selections, &[], 0, 0, &base, &hightlighted);
 
         // then span is split into highlighted spans
         assert_eq!(spans[0], Span::styled("Hel", hightlighted));
         assert_eq!(spans[1], Span::styled("lo", base));
     }
 
+    #[test]
+    fn test_internal_line_into_spans_with_highlights() {
+        // given a line with overlapping highlights
+        let base = Style::default();
+        let low = Style::default().red();
+        let high = Style::default().blue();
+        let line = "Hello".chars().collect::<Vec<char>>();
+
+        let highlights = vec![
+            (Selection::new(Index2::new(0, 0), Index2::new(0, 3)), low),
+            (Selection::new(Index2::new(0, 2), Index2::new(0, 2)), high),
+        ];
+
+        // when `line_into_spans_with_selections` is called
+        let spans = line_into_spans_with_selections(&line, &[], &highlights, 0, 0, &base, &base);
+
+        // then the highlight with the higher priority is drawn on top
+        assert_eq!(spans[0], Span::styled("He", low));
+        assert_eq!(spans[1], Span::styled("l", high));
+        assert_eq!(spans[2], Span::styled("l", low));
+        assert_eq!(spans[3], Span::styled("o", base));
+    }
+
     #[test]
     fn test_internal_span_split_spans() {
         // given a highlighted line
@@ -364,7 +407,7 @@ mod tests {
         ];
 
         // when `split_spans` is called
-        let new_spans = InternalSpan::split_spans(&spans, 1, 1, &hightlighted);
+        let new_spans = InternalSpan::split_spans(&spans, 1, 1, hightlighted);
 
         // then the span is split correctly
         assert_eq!(new_spans[0], InternalSpan::new("H", base));
@@ -373,7 +416,7 @@ mod tests {
         assert_eq!(new_spans[3], InternalSpan::new("lo!", base));
 
         // when
-        let new_spans = InternalSpan::split_spans(&spans, 1, 2, &hightlighted);
+        let new_spans = InternalSpan::split_spans(&spans, 1, 2, hightlighted);
 
         // then
         assert_eq!(new_spans[0], InternalSpan::new("H", base));
@@ -381,7 +424,7 @@ mod tests {
         assert_eq!(new_spans[2], InternalSpan::new("lo!", base));
 
         // when
-        let new_spans = InternalSpan::split_spans(&spans, 1, 3, &hightlighted);
+        let new_spans = InternalSpan::split_spans(&spans, 1, 3, hightlighted);
 
         // then
         assert_eq!(new_spans[0], InternalSpan::new("H", base));
@@ -390,7 +433,7 @@ mod tests {
         assert_eq!(new_spans[3], InternalSpan::new("o!", base));
 
         // when
-        let new_spans = InternalSpan::split_spans(&spans, 1, 10, &hightlighted);
+        let new_spans = InternalSpan::split_spans(&spans, 1, 10, hightlighted);
 
         // then
         assert_eq!(new_spans[0], InternalSpan::new("H", base));
@@ -406,7 +449,7 @@ mod tests {
         let spans = vec![InternalSpan::new("Hell🙂!", base)];
 
         // when `split_spans` is called
-        let new_spans = InternalSpan::split_spans(&spans, 2, 4, &hightlighted);
+        let new_spans = InternalSpan::split_spans(&spans, 2, 4, hightlighted);
 
         // then the span is split correctly
         assert_eq!(new_spans[0], InternalSpan::new("He", base));
@@ -464,7 +507,7 @@ mod tests {
         // when `split_at_selection` is called
         let selection = Selection::new(Index2::new(0, 1), Index2::new(0, 3));
         let new_spans =
-            InternalSpan::split_at_selection(&spans, 0, &selection, &hightlighted).unwrap();
+            InternalSpan::split_at_selection(&spans, 0, &selection, hightlighted).unwrap();
 
         // then spans are correctly split
         assert_eq!(new_spans[0], InternalSpan::new("H", base));
@@ -486,7 +529,7 @@ mod tests {
         // when `split_at_selection` is called
         let selection = Selection::new(Index2::new(0, 3), Index2::new(0, 5));
         let new_spans =
-            InternalSpan::split_at_selection(&spans, 0, &selection, &hightlighted).unwrap();
+            InternalSpan::split_at_selection(&spans, 0, &selection, hightlighted).unwrap();
 
         // then spans are correctly split
         assert_eq!(new_spans[0], InternalSpan::new("Hel", base));
@@ -514,16 +557,16 @@ mod tests {
         let line_2 = vec![Span::from("def")];
         let spans = vec![line_1, line_2];
 
-        let position = find_position_in_wrapped_spans(&spans, 2, 3, 0);
+        let position = find_position_in_wrapped_spans(&spans, 2, 3, 0, 0);
         assert_eq!(position, Index2::new(0, 2));
 
-        let position = find_position_in_wrapped_spans(&spans, 3, 3, 0);
+        let position = find_position_in_wrapped_spans(&spans, 3, 3, 0, 0);
         assert_eq!(position, Index2::new(1, 0));
 
-        let position = find_position_in_wrapped_spans(&spans, 5, 3, 0);
+        let position = find_position_in_wrapped_spans(&spans, 5, 3, 0, 0);
         assert_eq!(position, Index2::new(1, 2));
 
-        let position = find_position_in_wrapped_spans(&spans, 6, 3, 0);
+        let position = find_position_in_wrapped_spans(&spans, 6, 3, 0, 0);
         assert_eq!(position, Index2::new(2, 0));
     }
 
@@ -533,16 +576,16 @@ mod tests {
         let line_2 = vec![Span::from("c😀")];
         let spans = vec![line_1, line_2];
 
-        let position = find_position_in_wrapped_spans(&spans, 2, 4, 0);
+        let position = find_position_in_wrapped_spans(&spans, 2, 4, 0, 0);
         assert_eq!(position, Index2::new(0, 3));
 
-        let position = find_position_in_wrapped_spans(&spans, 3, 4, 0);
+        let position = find_position_in_wrapped_spans(&spans, 3, 4, 0, 0);
         assert_eq!(position, Index2::new(1, 0));
 
-        let position = find_position_in_wrapped_spans(&spans, 4, 4, 0);
+        let position = find_position_in_wrapped_spans(&spans, 4, 4, 0, 0);
         assert_eq!(position, Index2::new(1, 1));
 
-        let position = find_position_in_wrapped_spans(&spans, 5, 4, 0);
+        let position = find_position_in_wrapped_spans(&spans, 5, 4, 0, 0);
         assert_eq!(position, Index2::new(1, 3));
     }
 }
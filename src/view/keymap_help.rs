@@ -0,0 +1,210 @@
+//! A cheat-sheet of the key bindings.
+use std::collections::BTreeMap;
+
+use ratatui_core::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+use ratatui_widgets::{block::Block, clear::Clear, paragraph::Paragraph};
+
+use super::theme::{BLACK, GRAY, WHITE, YELLOW};
+use crate::{
+    actions::{Action, ActionCategory},
+    events::{KeyEvent, KeyEventHandler},
+    EditorMode,
+};
+
+/// A help overlay that lists the bindings of a [`KeyEventHandler`], grouped
+/// by mode and category. It is generated from the handler, so it stays in
+/// sync with customized bindings.
+///
+/// # Example
+///
+/// ```
+/// use edtui::events::KeyEventHandler;
+/// use edtui::{EditorMode, KeymapHelp};
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+///
+/// let handler = KeyEventHandler::vim_mode();
+/// let area = Rect::new(0, 0, 60, 20);
+/// let mut buf = Buffer::empty(area);
+/// KeymapHelp::new(&handler)
+///     .modes(vec![EditorMode::Normal])
+///     .render(area, &mut buf);
+/// ```
+pub struct KeymapHelp<'a> {
+    handler: &'a KeyEventHandler,
+    modes: Vec<EditorMode>,
+    block: Option<Block<'a>>,
+    style: Style,
+    header_style: Style,
+    key_style: Style,
+}
+
+impl<'a> KeymapHelp<'a> {
+    /// Creates a help overlay for the bindings of `handler`.
+    #[must_use]
+    pub fn new(handler: &'a KeyEventHandler) -> Self {
+        Self {
+            handler,
+            modes: vec![
+                EditorMode::Normal,
+                EditorMode::Visual,
+                EditorMode::Insert,
+                EditorMode::Search,
+                EditorMode::Command,
+            ],
+            block: None,
+            style: Style::default().bg(BLACK).fg(WHITE),
+            header_style: Style::default().fg(YELLOW).add_modifier(Modifier::BOLD),
+            key_style: Style::default().fg(GRAY),
+        }
+    }
+
+    /// Sets the modes whose bindings are listed, all modes by default.
+    #[must_use]
+    pub fn modes(mut self, modes: Vec<EditorMode>) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    /// Sets the surrounding block.
+    #[must_use]
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the base style.
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the mode and category headers.
+    #[must_use]
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Sets the style of the keys.
+    #[must_use]
+    pub fn key_style(mut self, style: Style) -> Self {
+        self.key_style = style;
+        self
+    }
+
+    /// Returns the keys and descriptions of a mode, grouped by category.
+    fn entries(&self, mode: EditorMode) -> BTreeMap<ActionCategory, Vec<(String, String)>> {
+        let mut entries: BTreeMap<ActionCategory, Vec<(String, String)>> = BTreeMap::new();
+        for (key, action) in self.handler.bindings(mode) {
+            entries
+                .entry(action.category())
+                .or_default()
+                .push((format_keys(key.keys()), describe(action)));
+        }
+        for (key, action) in self.handler.char_bindings(mode) {
+            let action = action(' ');
+            entries
+                .entry(action.category())
+                .or_default()
+                .push((format_keys(key.keys()) + "{char}", describe(&action)));
+        }
+        entries
+    }
+}
+
+fn format_keys(keys: &[KeyEvent]) -> String {
+    keys.iter().map(ToString::to_string).collect()
+}
+
+fn describe(action: &Action) -> String {
+    action
+        .description()
+        .map_or_else(|| action.to_string(), String::from)
+}
+
+impl Widget for KeymapHelp<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines: Vec<Line> = Vec::new();
+        for &mode in &self.modes {
+            let entries = self.entries(mode);
+            if entries.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(mode.name(), self.header_style));
+            for (category, bindings) in entries {
+                lines.push(Line::styled(format!("  {category}"), self.header_style));
+                let width = bindings.iter().map(|(keys, _)| keys.len()).max();
+                for (keys, description) in bindings {
+                    let keys = format!("    {keys:<width$}  ", width = width.unwrap_or(0));
+                    lines.push(Line::from(vec![
+                        Span::styled(keys, self.key_style),
+                        Span::raw(description),
+                    ]));
+                }
+            }
+        }
+
+        Clear.render(area, buf);
+        let mut paragraph = Paragraph::new(lines).style(self.style);
+        if let Some(block) = self.block {
+            paragraph = paragraph.block(block);
+        }
+        paragraph.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::actions::{DeleteLine, MoveDown, ReplaceChar};
+    use crate::events::KeyEventRegister;
+
+    #[test]
+    fn test_render_keymap_help() {
+        let mut handler = KeyEventHandler::new(HashMap::new(), false);
+        handler.insert(KeyEventRegister::n(vec![KeyEvent::Char('j')]), MoveDown(1));
+        handler.insert(
+            KeyEventRegister::n(vec![KeyEvent::Char('d'), KeyEvent::Char('d')]),
+            DeleteLine(1),
+        );
+        handler.insert_char_action(KeyEventRegister::n(vec![KeyEvent::Char('r')]), |c| {
+            ReplaceChar(c).into()
+        });
+
+        let area = Rect::new(0, 0, 60, 6);
+        let mut buf = Buffer::empty(area);
+        KeymapHelp::new(&handler).render(area, &mut buf);
+
+        let rendered: Vec<String> = (0..area.height)
+            .map(|y| {
+                let line: String = (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "Normal",
+                "  Motion",
+                "    j  Move the cursor down",
+                "  Edit",
+                "    dd       Delete the current line",
+                "    r{char}  Replace the character under the cursor",
+            ]
+        );
+    }
+}
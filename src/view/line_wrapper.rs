@@ -1,90 +1,243 @@
-use crate::helper::{char_width, span_width, split_str_at};
+use crate::helper::{char_width, chars_width, split_str_at};
 use ratatui_core::text::Span;
-use std::borrow::Cow;
-
-#[derive(Default)]
-pub(crate) struct LineWrapper;
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// Options for wrapping lines, see [`EditorView::wrap_options`].
+///
+/// [`EditorView::wrap_options`]: crate::EditorView::wrap_options
+///
+/// # Example
+///
+/// ```rust
+/// use edtui::{EditorState, EditorView, WrapOptions};
+///
+/// let mut state = EditorState::default();
+///
+/// EditorView::new(&mut state).wrap_options(
+///     WrapOptions::default()
+///         .indicator("↪ ")
+///         .list_indent(true),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// Whether lines break after whitespace instead of within words. Words
+    /// that are longer than a row are still broken. Enabled by default.
+    pub words: bool,
+    /// The indicator shown at the start of continuation rows, similar to
+    /// `showbreak` in vim. Empty by default.
+    pub indicator: String,
+    /// Whether continuation rows are indented like the line itself, similar
+    /// to `breakindent` in vim. Disabled by default.
+    pub indent: bool,
+    /// Whether continuation rows of list items are aligned with the text
+    /// after the bullet or number, such as `- ` or `1. `, similar to
+    /// `breakindentopt=list` in vim. Implies `indent`. Disabled by default.
+    pub list_indent: bool,
+}
 
-impl LineWrapper {
-    /// Splits a given line width into multiple smaller widths, ensuring each width
-    /// is no larger than the specified maximum width.
-    pub(crate) fn determine_split(line_width: usize, max_width: usize) -> Vec<usize> {
-        if line_width == 0 {
-            return vec![0];
+impl Default for WrapOptions {
+    fn default() -> Self {
+        Self {
+            words: true,
+            indicator: String::new(),
+            indent: false,
+            list_indent: false,
         }
+    }
+}
 
-        let mut remaining_width = line_width;
-        let mut split_widths = Vec::new();
+impl WrapOptions {
+    /// Sets whether lines break after whitespace instead of within words.
+    #[must_use]
+    pub fn words(mut self, words: bool) -> Self {
+        self.words = words;
+        self
+    }
 
-        while remaining_width > 0 {
-            let current_chunk = std::cmp::min(remaining_width, max_width);
-            split_widths.push(current_chunk);
-            remaining_width = remaining_width.saturating_sub(max_width);
-        }
+    /// Sets the indicator shown at the start of continuation rows.
+    #[must_use]
+    pub fn indicator<S: Into<String>>(mut self, indicator: S) -> Self {
+        self.indicator = indicator.into();
+        self
+    }
 
-        split_widths
+    /// Sets whether continuation rows are indented like the line itself.
+    #[must_use]
+    pub fn indent(mut self, indent: bool) -> Self {
+        self.indent = indent;
+        self
     }
 
-    pub(crate) fn wrap_line(line: &[char], max_width: usize, tab_width: usize) -> Vec<Vec<char>> {
-        let mut lines = Vec::new();
-        let mut line_width = 0;
-        let mut current_line = Vec::new();
+    /// Sets whether continuation rows of list items are aligned with the
+    /// text after the bullet or number.
+    #[must_use]
+    pub fn list_indent(mut self, list_indent: bool) -> Self {
+        self.list_indent = list_indent;
+        self
+    }
+}
 
-        for &ch in line {
-            let char_width = char_width(ch, tab_width);
+/// How a line is laid out on screen, see [`EditorState::line_layout`].
+///
+/// [`EditorState::line_layout`]: crate::EditorState::line_layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineLayout {
+    /// The columns of the line that are shown on each display row. An empty
+    /// line occupies a single, empty row.
+    pub rows: Vec<Range<usize>>,
+    /// The width of the prefix in front of continuation rows, consisting of
+    /// the wrap indent and the wrap indicator.
+    pub prefix_width: usize,
+}
 
-            if line_width + char_width > max_width {
-                lines.push(current_line.clone());
-                current_line.clear();
-                line_width = 0;
+impl LineLayout {
+    /// Returns the number of display rows the line occupies.
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the display row that shows the given column. Columns beyond
+    /// the end of the line are on the last row.
+    #[must_use]
+    pub fn row_of(&self, col: usize) -> usize {
+        self.rows
+            .iter()
+            .position(|row| col < row.end)
+            .unwrap_or(self.rows.len().saturating_sub(1))
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct LineWrapper;
+
+impl LineWrapper {
+    /// Returns the prefix of the continuation rows of a line, consisting of
+    /// the hanging indent and the wrap indicator. The prefix takes at most
+    /// half of the width, so that continuation rows keep room for text.
+    pub(crate) fn prefix(
+        line: &[char],
+        max_width: usize,
+        tab_width: usize,
+        options: &WrapOptions,
+    ) -> String {
+        let indicator_width = options.indicator.width();
+        let Some(available) = (max_width / 2).checked_sub(indicator_width) else {
+            return String::new();
+        };
+
+        let indent = if options.indent || options.list_indent {
+            let mut leading = line.iter().take_while(|ch| ch.is_whitespace()).count();
+            if options.list_indent {
+                leading += list_marker_len(&line[leading..]);
             }
+            chars_width(&line[..leading], tab_width).min(available)
+        } else {
+            0
+        };
+        " ".repeat(indent) + &options.indicator
+    }
 
-            current_line.push(ch);
-            line_width += char_width;
-        }
+    /// Returns the number of characters on each row of a wrapped line.
+    /// Continuation rows are narrower by the width of their prefix. Empty
+    /// lines have no rows.
+    pub(crate) fn row_lengths(
+        line: &[char],
+        max_width: usize,
+        tab_width: usize,
+        prefix_width: usize,
+        words: bool,
+    ) -> Vec<usize> {
+        let mut rows = Vec::new();
+        let mut start = 0;
+        let mut row_width = max_width;
+
+        while start < line.len() {
+            let mut width = 0;
+            let mut end = start;
+            for &ch in &line[start..] {
+                let char_width = char_width(ch, tab_width);
+                if width + char_width > row_width && end > start {
+                    break;
+                }
+                width += char_width;
+                end += 1;
+            }
 
-        if !current_line.is_empty() {
-            lines.push(current_line);
+            // Break after the last whitespace instead of within a word
+            if words && end < line.len() && !line[end].is_whitespace() {
+                if let Some(i) = line[start..end].iter().rposition(|ch| ch.is_whitespace()) {
+                    end = start + i + 1;
+                }
+            }
+
+            rows.push(end - start);
+            start = end;
+            row_width = max_width.saturating_sub(prefix_width).max(1);
         }
 
-        lines
+        rows
+    }
+
+    /// Lays out a line on rows of at most `max_width`.
+    pub(crate) fn layout(
+        line: &[char],
+        max_width: usize,
+        tab_width: usize,
+        options: &WrapOptions,
+    ) -> LineLayout {
+        let prefix_width = Self::prefix(line, max_width, tab_width, options).width();
+        let mut start = 0;
+        let mut rows: Vec<Range<usize>> =
+            Self::row_lengths(line, max_width, tab_width, prefix_width, options.words)
+                .into_iter()
+                .map(|len| {
+                    start += len;
+                    start - len..start
+                })
+                .collect();
+        if rows.is_empty() {
+            rows.push(0..0);
+        }
+        LineLayout { rows, prefix_width }
     }
 
-    pub(crate) fn wrap_spans(
-        spans: Vec<Span<'_>>,
+    /// Wraps the spans of a line. Returns the rows together with the prefix
+    /// of the continuation rows.
+    pub(crate) fn wrap_spans<'a>(
+        spans: Vec<Span<'a>>,
         max_width: usize,
         tab_width: usize,
-    ) -> Vec<Vec<Span<'_>>> {
+        options: &WrapOptions,
+    ) -> (Vec<Vec<Span<'a>>>, String) {
+        let line: Vec<char> = spans.iter().flat_map(|span| span.content.chars()).collect();
+        let prefix = Self::prefix(&line, max_width, tab_width, options);
+        let prefix_width = prefix.width();
+        let row_lengths =
+            Self::row_lengths(&line, max_width, tab_width, prefix_width, options.words);
+
         let mut wrapped_lines = Vec::new();
         let mut current_line = Vec::new();
-        let mut current_line_width = 0;
+        let mut row_lengths = row_lengths.into_iter();
+        let mut remaining = row_lengths.next().unwrap_or(usize::MAX);
 
         for span in spans {
-            // If adding this span exceeds the max width, handle wrapping
-            if current_line_width + span_width(&span, tab_width) > max_width {
-                let mut remaining_span = span.clone();
-                let mut split_at = max_width - current_line_width;
-
-                while span_width(&remaining_span, tab_width) > split_at {
-                    let (fitting_part, rest) =
-                        Self::split_span_at(remaining_span, split_at, tab_width);
-                    current_line.push(fitting_part.clone());
-                    wrapped_lines.push(current_line.clone());
-
-                    // Prepare for the next line
-                    current_line.clear();
-                    remaining_span = rest;
-                    split_at = max_width;
+            let mut span = span;
+            // Split the span while it extends beyond the current row
+            while span.content.chars().count() > remaining {
+                let (fitting_part, rest) = split_str_at(&span.content, remaining);
+                if !fitting_part.is_empty() {
+                    current_line.push(Span::styled(fitting_part, span.style));
                 }
-
-                // Add remaining part to the current line
-                current_line_width = span_width(&remaining_span, tab_width);
-                current_line.push(remaining_span);
-            } else {
-                // No wrapping needed, just add the span
-                current_line_width += span_width(&span, tab_width);
-                current_line.push(span);
+                wrapped_lines.push(std::mem::take(&mut current_line));
+                span = Span::styled(rest, span.style);
+                remaining = row_lengths.next().unwrap_or(usize::MAX);
             }
+
+            remaining -= span.content.chars().count();
+            current_line.push(span);
         }
 
         // Add any remaining content as the last line
@@ -92,36 +245,45 @@ impl LineWrapper {
             wrapped_lines.push(current_line);
         }
 
-        wrapped_lines
+        (wrapped_lines, prefix)
     }
+}
 
-    fn split_str_at(s: Cow<'_, str>, split_at: usize, tab_width: usize) -> (String, String) {
-        let mut current_width = 0;
-        for (i, ch) in s.chars().enumerate() {
-            current_width += char_width(ch, tab_width);
-            if current_width > split_at {
-                let (a, b) = split_str_at(s, i);
-                return (a.to_string(), b.to_string());
+/// Returns the length of the list marker at the start of `text` including
+/// the whitespace after it, such as `- ` or `12. `, or 0 if there is none.
+fn list_marker_len(text: &[char]) -> usize {
+    let marker = match text {
+        ['-' | '*' | '+', ..] => 1,
+        _ => {
+            let digits = text.iter().take_while(|ch| ch.is_ascii_digit()).count();
+            match text.get(digits) {
+                Some('.' | ')') if digits > 0 => digits + 1,
+                _ => return 0,
             }
         }
-
-        (s.to_string(), String::new())
-    }
-
-    fn split_span_at(span: Span, split_at: usize, tab_width: usize) -> (Span, Span) {
-        let (a, b) = Self::split_str_at(span.content, split_at, tab_width);
-        (Span::styled(a, span.style), Span::styled(b, span.style))
+    };
+    let spaces = text[marker..].iter().take_while(|&&ch| ch == ' ').count();
+    if spaces == 0 {
+        return 0;
     }
+    marker + spaces
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn char_wrap() -> WrapOptions {
+        WrapOptions {
+            words: false,
+            ..WrapOptions::default()
+        }
+    }
+
     #[test]
     fn test_wrap_spans() {
         let spans = vec![Span::raw("Hello"), Span::raw("World")];
-        let wrapped_spans = LineWrapper::wrap_spans(spans, 3, 0);
+        let (wrapped_spans, _) = LineWrapper::wrap_spans(spans, 3, 0, &char_wrap());
 
         assert_eq!(wrapped_spans[0], vec![Span::raw("Hel")]);
         assert_eq!(wrapped_spans[1], vec![Span::raw("lo"), Span::raw("W")]);
@@ -132,30 +294,75 @@ mod tests {
     #[test]
     fn test_wrap_spans_with_emoji() {
         let spans = vec![Span::raw("Hell🙂!")];
-        let wrapped_spans = LineWrapper::wrap_spans(spans, 4, 0);
+        let (wrapped_spans, _) = LineWrapper::wrap_spans(spans, 4, 0, &char_wrap());
 
         assert_eq!(wrapped_spans[0], vec![Span::raw("Hell")]);
         assert_eq!(wrapped_spans[1], vec![Span::raw("🙂!")]);
     }
 
     #[test]
-    fn test_split_span_at_with_emoji() {
-        let span = Span::raw("🙂!");
-        let (left, right) = LineWrapper::split_span_at(span, 2, 0);
-
-        assert_eq!(left, Span::raw("🙂"));
-        assert_eq!(right, Span::raw("!"));
+    fn test_wrap_spans_at_words() {
+        let options = WrapOptions {
+            indicator: String::from("> "),
+            indent: true,
+            ..WrapOptions::default()
+        };
+        let spans = vec![Span::raw("  foo "), Span::raw("barbaz qux")];
+        let (wrapped_spans, prefix) = LineWrapper::wrap_spans(spans, 10, 0, &options);
+
+        assert_eq!(prefix, "  > ");
+        assert_eq!(wrapped_spans[0], vec![Span::raw("  foo ")]);
+        assert_eq!(wrapped_spans[1], vec![Span::raw("barbaz")]);
+        assert_eq!(wrapped_spans[2], vec![Span::raw(" qux")]);
     }
 
-    fn test_line_wrapper_determine_split() {
-        let line_widths = LineWrapper::determine_split(5, 3);
-
-        assert_eq!(line_widths[0], 3);
-        assert_eq!(line_widths[1], 2);
+    #[test]
+    fn test_line_layout() {
+        let line: Vec<char> = "ab cdef".chars().collect();
+        let options = WrapOptions::default().indicator("|");
+        let layout = LineWrapper::layout(&line, 5, 0, &options);
+
+        assert_eq!(layout.rows, vec![0..3, 3..7]);
+        assert_eq!(layout.prefix_width, 1);
+        assert_eq!(layout.row_of(2), 0);
+        assert_eq!(layout.row_of(3), 1);
+        assert_eq!(layout.row_of(9), 1);
+        assert_eq!(LineWrapper::layout(&[], 5, 0, &options).rows, vec![0..0]);
+    }
 
-        let line_widths = LineWrapper::determine_split(6, 3);
+    #[test]
+    fn test_line_wrapper_row_lengths() {
+        let line: Vec<char> = "Hello".chars().collect();
+        assert_eq!(LineWrapper::row_lengths(&line, 3, 0, 0, false), vec![3, 2]);
+
+        let line: Vec<char> = "Hello!".chars().collect();
+        assert_eq!(LineWrapper::row_lengths(&line, 3, 0, 0, false), vec![3, 3]);
+
+        let line: Vec<char> = "ab cdef".chars().collect();
+        assert_eq!(LineWrapper::row_lengths(&line, 5, 0, 0, true), vec![3, 4]);
+        assert_eq!(LineWrapper::row_lengths(&line, 5, 0, 2, false), vec![5, 2]);
+        assert_eq!(
+            LineWrapper::row_lengths(&line, 5, 0, 2, true),
+            vec![3, 3, 1]
+        );
+        assert!(LineWrapper::row_lengths(&[], 5, 0, 0, true).is_empty());
+    }
 
-        assert_eq!(line_widths[0], 3);
-        assert_eq!(line_widths[1], 3);
+    #[test]
+    fn test_wrap_list_items() {
+        let options = WrapOptions {
+            list_indent: true,
+            ..WrapOptions::default()
+        };
+        let prefix = |line: &str| {
+            let line: Vec<char> = line.chars().collect();
+            LineWrapper::prefix(&line, 40, 2, &options)
+        };
+
+        assert_eq!(prefix("- foo bar"), "  ");
+        assert_eq!(prefix("  12. foo bar"), "      ");
+        assert_eq!(prefix("3)  foo"), "    ");
+        assert_eq!(prefix("  -foo"), "  ");
+        assert_eq!(prefix("1.5 foo"), "");
     }
 }
@@ -0,0 +1,194 @@
+//! Markdown-aware rendering.
+//!
+//! Headings, emphasis, inline code and list bullets are styled in place. On
+//! all lines but the cursor line, the markup characters are concealed, e.g.
+//! `**bold**` is displayed as `bold`. The text itself is not modified.
+use ratatui_core::{style::Style, text::Span};
+
+use super::theme::MarkdownTheme;
+
+/// A character that is hidden, or replaced by another character, for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Conceal {
+    pub(super) col: usize,
+    pub(super) replacement: Option<char>,
+}
+
+impl Conceal {
+    fn hide(col: usize) -> Self {
+        Self {
+            col,
+            replacement: None,
+        }
+    }
+}
+
+/// The styled ranges (inclusive) and the concealed characters of a line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Markup {
+    pub(super) styles: Vec<(usize, usize, Style)>,
+    pub(super) conceals: Vec<Conceal>,
+}
+
+/// Parses the markdown markup of a single line.
+pub(super) fn parse(line: &[char], theme: &MarkdownTheme) -> Markup {
+    let mut markup = Markup::default();
+    let indent = line.iter().take_while(|ch| ch.is_whitespace()).count();
+    let rest = &line[indent..];
+
+    // Headings: `# Heading`
+    let level = rest.iter().take_while(|&&ch| ch == '#').count();
+    if (1..=6).contains(&level) && rest.get(level) == Some(&' ') {
+        markup.styles.push((0, line.len() - 1, theme.heading));
+        markup
+            .conceals
+            .extend((indent..=indent + level).map(Conceal::hide));
+        return markup;
+    }
+
+    // Code fences: ```rust
+    if rest.starts_with(&['`', '`', '`']) {
+        markup.styles.push((0, line.len() - 1, theme.code));
+        return markup;
+    }
+
+    // Bullets: `- item`, `* item` or `+ item`
+    let mut start = indent;
+    if matches!(rest, ['-' | '*' | '+', ' ', ..]) {
+        markup.styles.push((indent, indent, theme.bullet));
+        markup.conceals.push(Conceal {
+            col: indent,
+            replacement: Some('•'),
+        });
+        start += 2;
+    }
+
+    parse_inline(line, start, theme, &mut markup);
+    markup
+}
+
+/// Parses inline code and emphasis, starting at column `start`.
+fn parse_inline(line: &[char], start: usize, theme: &MarkdownTheme, markup: &mut Markup) {
+    let mut i = start;
+    while i < line.len() {
+        let (marker, style) = match line[i] {
+            '\\' => {
+                i += 2;
+                continue;
+            }
+            '`' => (&line[i..=i], theme.code),
+            '*' | '_' if i > 0 && line[i] == '_' && line[i - 1].is_alphanumeric() => {
+                i += 1;
+                continue;
+            }
+            '*' | '_' if line.get(i + 1) == Some(&line[i]) => (&line[i..i + 2], theme.bold),
+            '*' | '_' => (&line[i..=i], theme.italic),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let open_end = i + marker.len();
+        let closing = (open_end + 1..=line.len().saturating_sub(marker.len()))
+            .find(|&j| line[j..j + marker.len()] == *marker)
+            .filter(|_| !line[open_end].is_whitespace());
+        let Some(close) = closing else {
+            i = open_end;
+            continue;
+        };
+
+        let end = close + marker.len() - 1;
+        markup.styles.push((i, end, style));
+        markup
+            .conceals
+            .extend((i..open_end).chain(close..=end).map(Conceal::hide));
+        i = end + 1;
+    }
+}
+
+/// Removes or replaces the concealed characters of `spans`, whose first
+/// character is at column `col_skips`.
+pub(super) fn conceal_spans<'a>(
+    spans: Vec<Span<'a>>,
+    conceals: &[Conceal],
+    col_skips: usize,
+) -> Vec<Span<'a>> {
+    let mut col = col_skips;
+    let mut concealed: Vec<Span> = Vec::new();
+    for span in spans {
+        let content: String = span
+            .content
+            .chars()
+            .filter_map(|ch| {
+                let conceal = conceals.iter().find(|conceal| conceal.col == col);
+                col += 1;
+                conceal.map_or(Some(ch), |conceal| conceal.replacement)
+            })
+            .collect();
+        if !content.is_empty() {
+            concealed.push(Span::styled(content, span.style));
+        }
+    }
+    concealed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(line: &str) -> Markup {
+        let line: Vec<char> = line.chars().collect();
+        parse(&line, &MarkdownTheme::default())
+    }
+
+    fn concealed(line: &str) -> String {
+        let markup = parse_str(line);
+        let spans = vec![Span::raw(line)];
+        conceal_spans(spans, &markup.conceals, 0)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_heading() {
+        let markup = parse_str("## Title");
+
+        assert_eq!(
+            markup.styles,
+            vec![(0, 7, MarkdownTheme::default().heading)]
+        );
+        assert_eq!(concealed("## Title"), "Title");
+    }
+
+    #[test]
+    fn test_parse_inline() {
+        let theme = MarkdownTheme::default();
+        let markup = parse_str("a **b** _c_ `d`");
+
+        assert_eq!(
+            markup.styles,
+            vec![
+                (2, 6, theme.bold),
+                (8, 10, theme.italic),
+                (12, 14, theme.code)
+            ]
+        );
+        assert_eq!(concealed("a **b** _c_ `d`"), "a b c d");
+    }
+
+    #[test]
+    fn test_parse_ignores_unmatched_markers() {
+        assert_eq!(
+            concealed("2 * 3 = snake_case_name"),
+            "2 * 3 = snake_case_name"
+        );
+        assert_eq!(concealed("\\*not italic*"), "\\*not italic*");
+    }
+
+    #[test]
+    fn test_parse_bullet() {
+        assert_eq!(concealed("  - *item*"), "  • item");
+    }
+}
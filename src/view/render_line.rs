@@ -1,26 +1,27 @@
 use ratatui_core::{
     buffer::Buffer,
     layout::{Position, Rect},
+    style::Style,
     text::{Line, Span},
     widgets::Widget,
 };
 
-use crate::helper::{rect_indent_y, replace_tabs_in_span};
+use crate::helper::{display_spans, rect_indent_y};
 
 use super::internal::{find_position_in_spans, find_position_in_wrapped_spans};
 
 /// An internal data type that represents a line for rendering.
 /// A vector of spans represents a line. Wrapped lines consist
-/// of an array of lines.
+/// of an array of lines and the prefix of their continuation rows.
 pub(super) enum RenderLine<'a> {
-    Wrapped(Vec<Vec<Span<'a>>>),
+    Wrapped(Vec<Vec<Span<'a>>>, Span<'a>),
     Single(Vec<Span<'a>>),
 }
 
 impl RenderLine<'_> {
     pub(super) fn num_lines(&self) -> usize {
         match self {
-            RenderLine::Wrapped(lines) => lines.len().max(1),
+            RenderLine::Wrapped(lines, _) => lines.len().max(1),
             RenderLine::Single(_) => 1,
         }
     }
@@ -33,9 +34,13 @@ impl RenderLine<'_> {
         tab_width: usize,
     ) -> Position {
         let index2 = match self {
-            RenderLine::Wrapped(lines) => {
-                find_position_in_wrapped_spans(lines, data_col, area.width as usize, tab_width)
-            }
+            RenderLine::Wrapped(lines, prefix) => find_position_in_wrapped_spans(
+                lines,
+                data_col,
+                area.width as usize,
+                tab_width,
+                prefix.width(),
+            ),
 
             RenderLine::Single(line) => find_position_in_spans(line, data_col, tab_width),
         };
@@ -46,27 +51,49 @@ impl RenderLine<'_> {
         )
     }
 
-    pub(super) fn render(self, mut area: Rect, buf: &mut Buffer, tab_width: usize) {
+    pub(super) fn render(
+        self,
+        mut area: Rect,
+        buf: &mut Buffer,
+        tab_width: usize,
+        control_char_style: Style,
+    ) {
         match self {
-            RenderLine::Wrapped(lines) => {
-                for line in lines {
+            RenderLine::Wrapped(lines, prefix) => {
+                let prefix_width = (prefix.width() as u16).min(area.width);
+                for (i, line) in lines.into_iter().enumerate() {
                     if area.height == 0 {
                         break;
                     }
 
-                    render_line(area, buf, line, tab_width);
+                    let mut line_area = area;
+                    if i > 0 {
+                        buf.set_span(area.x, area.y, &prefix, prefix_width);
+                        line_area.x += prefix_width;
+                        line_area.width -= prefix_width;
+                    }
+                    render_line(line_area, buf, &line, tab_width, control_char_style);
                     area = rect_indent_y(area, 1);
                 }
             }
-            RenderLine::Single(line) => render_line(area, buf, line, tab_width),
+            RenderLine::Single(line) => {
+                render_line(area, buf, &line, tab_width, control_char_style);
+            }
         }
     }
 }
 
-fn render_line(area: Rect, buf: &mut Buffer, mut line: Vec<Span>, tab_width: usize) {
-    for span in &mut line {
-        replace_tabs_in_span(span, tab_width);
-    }
+fn render_line(
+    area: Rect,
+    buf: &mut Buffer,
+    line: &[Span],
+    tab_width: usize,
+    control_char_style: Style,
+) {
+    let spans: Vec<Span> = line
+        .iter()
+        .flat_map(|span| display_spans(span, tab_width, control_char_style))
+        .collect();
 
-    Line::from(line).render(area, buf);
+    Line::from(spans).render(area, buf);
 }
@@ -0,0 +1,239 @@
+use super::theme::{DARK_GRAY, GRAY, WHITE};
+use ratatui_core::layout::Rect;
+use ratatui_core::{
+    buffer::Buffer,
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// Where the [`EditorSearchBar`] is placed within the editor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchBarPosition {
+    /// Above the first visible line.
+    Top,
+    /// Below the last visible line (default).
+    #[default]
+    Bottom,
+}
+
+/// An optional one-line overlay that shows the search query in search mode.
+///
+/// The search bar is drawn on top of the text, at the top or the bottom of
+/// the editor area. It shows a prefix, the query followed by a cursor, and
+/// the number of matches. While it is shown, the status line does not repeat
+/// the query.
+///
+/// # Example
+///
+/// ```rust
+/// use edtui::{EditorSearchBar, EditorTheme, SearchBarPosition};
+///
+/// let theme = EditorTheme::default()
+///     .search_bar(EditorSearchBar::default().position(SearchBarPosition::Top));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditorSearchBar {
+    /// The search query.
+    query: String,
+    /// The selected match and the total number of matches.
+    matches: (Option<usize>, usize),
+    /// Whether the query is a regular expression.
+    regex: bool,
+    /// The text shown in front of the query.
+    prefix: String,
+    /// The placement within the editor.
+    position: SearchBarPosition,
+    /// The style for the prefix
+    style_prefix: Style,
+    /// The style for the query
+    style_query: Style,
+    /// The style for the cursor after the query
+    style_cursor: Style,
+    /// The style for the match counter
+    style_counter: Style,
+    /// The style for the line itself
+    style_line: Style,
+}
+
+impl Default for EditorSearchBar {
+    /// Creates a new instance of [`EditorSearchBar`].
+    ///
+    /// This constructor initializes with default style.
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            matches: (None, 0),
+            regex: false,
+            prefix: "/".to_string(),
+            position: SearchBarPosition::default(),
+            style_prefix: Style::default().fg(WHITE).bg(DARK_GRAY).bold(),
+            style_query: Style::default().fg(WHITE).bg(DARK_GRAY),
+            style_cursor: Style::default().fg(DARK_GRAY).bg(WHITE),
+            style_counter: Style::default().fg(GRAY).bg(DARK_GRAY),
+            style_line: Style::default().fg(WHITE).bg(DARK_GRAY),
+        }
+    }
+}
+
+impl EditorSearchBar {
+    /// Overwrite the text shown in front of the query, `/` by default.
+    #[must_use]
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the placement of the search bar within the editor.
+    #[must_use]
+    pub fn position(mut self, position: SearchBarPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Overwrite the style for the prefix.
+    #[must_use]
+    pub fn style_prefix(mut self, style: Style) -> Self {
+        self.style_prefix = style;
+        self
+    }
+
+    /// Overwrite the style for the query.
+    #[must_use]
+    pub fn style_query(mut self, style: Style) -> Self {
+        self.style_query = style;
+        self
+    }
+
+    /// Overwrite the style for the cursor after the query.
+    #[must_use]
+    pub fn style_cursor(mut self, style: Style) -> Self {
+        self.style_cursor = style;
+        self
+    }
+
+    /// Overwrite the style for the match counter.
+    #[must_use]
+    pub fn style_counter(mut self, style: Style) -> Self {
+        self.style_counter = style;
+        self
+    }
+
+    /// Overwrite the style for the search bar.
+    #[must_use]
+    pub fn style_line(mut self, style: Style) -> Self {
+        self.style_line = style;
+        self
+    }
+
+    /// Overwrite the query of the search bar.
+    ///
+    /// This method is used internally to set the current search pattern.
+    #[must_use]
+    pub fn query<S: Into<String>>(mut self, query: S) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Overwrite the match counter of the search bar.
+    ///
+    /// This method is used internally to set the selected match and the
+    /// number of matches.
+    #[must_use]
+    pub fn matches(mut self, selected: Option<usize>, total: usize) -> Self {
+        self.matches = (selected, total);
+        self
+    }
+
+    /// Marks the query as a regular expression, which is shown in front of
+    /// the match counter.
+    ///
+    /// This method is used internally to show how the search pattern is
+    /// matched.
+    #[must_use]
+    pub fn regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// Returns the row of `area` the search bar is drawn on.
+    pub(crate) fn area(&self, area: Rect) -> Rect {
+        let y = match self.position {
+            SearchBarPosition::Top => area.top(),
+            SearchBarPosition::Bottom => area.bottom().saturating_sub(1),
+        };
+        Rect::new(area.x, y, area.width, area.height.min(1))
+    }
+}
+
+impl Widget for EditorSearchBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = self.area(area);
+        buf.set_style(area, self.style_line);
+
+        let mut counter = match self.matches {
+            (_, 0) if self.query.is_empty() => String::new(),
+            (_, 0) => "no matches ".to_string(),
+            (Some(selected), total) => format!("{}/{total} ", selected + 1),
+            (None, total) => format!("{total} matches "),
+        };
+        if self.regex {
+            counter.insert_str(0, "regex ");
+        }
+        let counter_width = counter.chars().count() as u16;
+        let counter_area = Rect {
+            x: area.right().saturating_sub(counter_width).max(area.x),
+            width: counter_width.min(area.width),
+            ..area
+        };
+        Span::styled(counter, self.style_counter).render(counter_area, buf);
+
+        let query_area = Rect {
+            width: area.width.saturating_sub(counter_area.width),
+            ..area
+        };
+        Line::from(vec![
+            Span::styled(self.prefix, self.style_prefix),
+            Span::styled(self.query, self.style_query),
+            Span::styled(" ", self.style_cursor),
+        ])
+        .render(query_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_bar() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut buf = Buffer::empty(area);
+        EditorSearchBar::default()
+            .query("foo")
+            .matches(Some(1), 4)
+            .render(area, &mut buf);
+
+        let row = |y: u16| -> String { (0..20).map(|x| buf[(x, y)].symbol()).collect() };
+        assert_eq!(row(0), " ".repeat(20));
+        assert_eq!(row(2), "/foo            2/4 ");
+        assert_eq!(buf[(4, 2)].style().bg, Some(WHITE));
+
+        let mut buf = Buffer::empty(area);
+        EditorSearchBar::default()
+            .position(SearchBarPosition::Top)
+            .query("bar")
+            .render(area, &mut buf);
+        let row: String = (0..20).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(row, "/bar     no matches ");
+
+        let mut buf = Buffer::empty(area);
+        EditorSearchBar::default()
+            .query("\\vb.")
+            .matches(Some(0), 3)
+            .regex(true)
+            .render(area, &mut buf);
+        let row: String = (0..20).map(|x| buf[(x, 2)].symbol()).collect();
+        assert_eq!(row, "/\\vb.     regex 1/3 ");
+    }
+}
@@ -0,0 +1,143 @@
+//! Rendering the editor into plain text, e.g. for snapshot tests.
+use std::fmt;
+
+use ratatui_core::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::Style,
+    widgets::Widget,
+};
+
+use super::EditorView;
+
+/// A frame of the editor rendered without a terminal, see
+/// [`EditorView::snapshot`]. Formatting the snapshot prints its rows with
+/// trailing whitespace removed.
+///
+/// # Example
+///
+/// ```
+/// use edtui::{EditorState, EditorTheme, EditorView, Index2, Lines};
+/// use ratatui::style::{Color, Style};
+///
+/// let mut state = EditorState::new(Lines::from("Hello world"));
+/// state.cursor = Index2::new(0, 7);
+/// let theme = EditorTheme::default()
+///     .cursor_style(Style::default().bg(Color::Red))
+///     .hide_status_line();
+/// let snapshot = EditorView::new(&mut state).theme(theme).wrap(true).snapshot(6, 2);
+///
+/// assert_eq!(snapshot.to_string(), "Hello\nworld");
+/// assert_eq!(
+///     snapshot.style_map(|style| style.bg == Some(Color::Red), '^'),
+///     "      \n ^    ",
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    buffer: Buffer,
+}
+
+impl Snapshot {
+    /// Returns the rows of the frame, each as wide as the frame.
+    #[must_use]
+    pub fn rows(&self) -> Vec<String> {
+        let area = self.buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| self.buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the style of the cell at column `x` and row `y`, or `None`
+    /// if the position is outside of the frame.
+    #[must_use]
+    pub fn style(&self, x: u16, y: u16) -> Option<Style> {
+        self.buffer
+            .cell(Position::new(x, y))
+            .map(|cell| cell.style())
+    }
+
+    /// Returns a grid as large as the frame in which the cells whose style
+    /// matches `filter` are replaced by `marker` and all others by spaces,
+    /// e.g. to assert where the selection or a highlight is drawn.
+    #[must_use]
+    pub fn style_map<F>(&self, filter: F, marker: char) -> String
+    where
+        F: Fn(Style) -> bool,
+    {
+        let area = self.buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| {
+                        if filter(self.buffer[(x, y)].style()) {
+                            marker
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the buffer the frame was rendered into.
+    #[must_use]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let rows: Vec<&str> = rows.iter().map(|row| row.trim_end()).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+impl EditorView<'_, '_> {
+    /// Renders the editor into a frame of `width` columns and `height` rows
+    /// without a terminal. The state is updated as by a regular render,
+    /// e.g. the viewport follows the cursor.
+    #[must_use]
+    pub fn snapshot(self, width: u16, height: u16) -> Snapshot {
+        let area = Rect::new(0, 0, width, height);
+        let mut buffer = Buffer::empty(area);
+        self.render(area, &mut buffer);
+        Snapshot { buffer }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{MoveForward, SwitchMode};
+    use crate::view::theme::YELLOW;
+    use crate::{EditorMode, EditorState, EditorTheme, Lines};
+
+    #[test]
+    fn test_snapshot() {
+        let mut state = EditorState::new(Lines::from("foo bar\nbaz"));
+        state.execute(SwitchMode(EditorMode::Visual));
+        state.execute(MoveForward(2));
+        let snapshot = EditorView::new(&mut state).snapshot(12, 3);
+
+        assert_eq!(snapshot.rows()[0], "foo bar     ");
+        assert_eq!(snapshot.to_string(), "foo bar\nbaz\n  Visual");
+        assert_eq!(
+            snapshot.style_map(|style| style.bg == Some(YELLOW), '#'),
+            "##          \n            \n            "
+        );
+        assert_eq!(snapshot.style(12, 0), None);
+
+        let theme = EditorTheme::default().hide_status_line();
+        let snapshot = EditorView::new(&mut state).theme(theme).snapshot(12, 3);
+        assert_eq!(snapshot.to_string(), "foo bar\nbaz\n");
+    }
+}
@@ -1,4 +1,5 @@
-use super::theme::{DARK_GRAY, WHITE};
+use super::theme::{DARK_GRAY, RED, WHITE, YELLOW};
+use crate::state::notification::NotificationLevel;
 use ratatui_core::layout::{Constraint, HorizontalAlignment, Layout};
 use ratatui_core::{buffer::Buffer, layout::Rect, style::Style, text::Span, widgets::Widget};
 use ratatui_widgets::block::Block;
@@ -10,10 +11,21 @@ pub struct EditorStatusLine {
     mode: String,
     /// The current search buffer. Shown only in search mode.
     search: Option<String>,
+    /// The current command line. Shown only in command mode.
+    command: Option<String>,
+    /// A message, such as the validation error under the cursor. Shown
+    /// outside of search mode.
+    message: Option<String>,
+    /// The severity of the message.
+    level: NotificationLevel,
     /// The style for the mode of the status line
     style_mode: Option<Style>,
     /// The style for the search of the status line
     style_search: Option<Style>,
+    /// The style for warning messages
+    style_warning: Style,
+    /// The style for error messages
+    style_error: Style,
     /// The style for the line itself
     style_line: Style,
     /// Horizontal alignment of the status bar
@@ -28,8 +40,13 @@ impl Default for EditorStatusLine {
         Self {
             mode: String::new(),
             search: None,
+            command: None,
+            message: None,
+            level: NotificationLevel::Info,
             style_mode: Some(Style::default().fg(WHITE).bg(DARK_GRAY).bold()),
             style_search: Some(Style::default().fg(WHITE).bg(DARK_GRAY)),
+            style_warning: Style::default().fg(YELLOW).bg(DARK_GRAY),
+            style_error: Style::default().fg(RED).bg(DARK_GRAY),
             style_line: Style::default().fg(WHITE).bg(DARK_GRAY),
             alignment: HorizontalAlignment::Left,
         }
@@ -72,6 +89,22 @@ impl EditorStatusLine {
         self
     }
 
+    /// Overwrite the style for warning messages, such as warning
+    /// notifications.
+    #[must_use]
+    pub fn style_warning(mut self, style: Style) -> Self {
+        self.style_warning = style;
+        self
+    }
+
+    /// Overwrite the style for error messages, such as error notifications
+    /// and validation errors.
+    #[must_use]
+    pub fn style_error(mut self, style: Style) -> Self {
+        self.style_error = style;
+        self
+    }
+
     /// Overwrite the style for the status lines.
     ///
     /// This method allows you to customize the appearance of the
@@ -100,6 +133,35 @@ impl EditorStatusLine {
         self
     }
 
+    /// Overwrite the command line content for the status line.
+    ///
+    /// This method is used internally to show the command line in command
+    /// mode.
+    #[must_use]
+    pub fn command<S: Into<String>>(mut self, command: Option<S>) -> Self {
+        self.command = command.map(Into::into);
+        self
+    }
+
+    /// Overwrite the message content for the status line.
+    ///
+    /// This method is used internally to show the validation error under
+    /// the cursor.
+    #[must_use]
+    pub fn message<S: Into<String>>(mut self, message: Option<S>) -> Self {
+        self.message = message.map(Into::into);
+        self
+    }
+
+    /// Overwrite the severity of the message for the status line.
+    ///
+    /// This method is used internally to style notifications.
+    #[must_use]
+    pub fn level(mut self, level: NotificationLevel) -> Self {
+        self.level = level;
+        self
+    }
+
     #[deprecated(
         since = "0.10.4",
         note = "Please use `alignment(HorizontalAlignment::Left)` or `alignment(HorizontalAlignment::Right)` instead"
@@ -134,15 +196,21 @@ impl Widget for EditorStatusLine {
 
         let layout = Layout::horizontal(constraints).split(area);
 
-        let search_text = match self.search {
-            None => String::new(),
-            Some(search) => format!("/{search}"),
+        let style_search = self.style_search.unwrap_or(self.style_line);
+        let (search_text, style_search) = match (self.search, self.command, self.message) {
+            (Some(search), _, _) => (format!("/{search}"), style_search),
+            (None, Some(command), _) => (format!(":{command}"), style_search),
+            (None, None, Some(message)) => match self.level {
+                NotificationLevel::Info => (message, style_search),
+                NotificationLevel::Warning => (message, self.style_warning),
+                NotificationLevel::Error => (message, self.style_error),
+            },
+            (None, None, None) => (String::new(), style_search),
         };
 
         let mode_span = Span::raw(format!("{:^10}", self.mode))
             .style(self.style_mode.unwrap_or(self.style_line));
-        let search_span =
-            Span::raw(search_text).style(self.style_search.unwrap_or(self.style_line));
+        let search_span = Span::raw(search_text).style(style_search);
 
         let line_block = Block::new().style(self.style_line);
 
@@ -2,9 +2,13 @@ use super::internal::InternalSpan;
 use crate::syntect::{
     easy::HighlightLines,
     highlighting::{Theme, ThemeSet},
-    parsing::{SyntaxReference, SyntaxSet},
+    parsing::{
+        BasicScopeStackOp, ParseState, Scope, ScopeStack, ScopeStackOp, SyntaxReference, SyntaxSet,
+    },
 };
 use crate::view::syntax_higlighting::SyntaxHighlighterError::{ExtensionNotFound, ThemeNotFound};
+use crate::EditorState;
+use jagged::index::RowIndex;
 use once_cell::sync::Lazy;
 use ratatui_core::style::{Color, Style};
 use std::error::Error;
@@ -28,6 +32,106 @@ pub struct SyntaxHighlighter {
     syntax_set: Arc<SyntaxSet>,
 }
 
+/// A definition that encloses the cursor, see [`SyntaxHighlighter::breadcrumbs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    /// The kind of the definition as named by the syntax, e.g. "function",
+    /// "impl" or "module".
+    pub kind: String,
+    /// The name of the definition, e.g. the name of the function.
+    pub name: String,
+}
+
+/// A breadcrumb while the text is parsed, see [`Breadcrumbs`].
+struct Crumb {
+    /// The index of the `meta` scope of the definition in the scope stack.
+    depth: usize,
+    /// The `meta` scope of the definition, e.g. `meta.function`.
+    scope: Scope,
+    breadcrumb: Breadcrumb,
+    /// Whether the body of the definition was entered, after which the end
+    /// of the `meta` scope ends the definition.
+    has_body: bool,
+}
+
+/// Collects the definitions that enclose a position from the scopes of the
+/// syntax. A definition is a `meta` scope such as `meta.function` that
+/// contains an `entity.name` scope, e.g. `entity.name.function`. Syntaxes
+/// often end and restart the `meta` scope around the name, so a definition
+/// only ends with its `meta` scope once its body, a `meta.block`, was
+/// entered.
+#[derive(Default)]
+struct Breadcrumbs {
+    crumbs: Vec<Crumb>,
+    /// The start of a name whose end is not yet known.
+    pending_name: Option<usize>,
+}
+
+impl Breadcrumbs {
+    /// Handles the push of `scope` at `start`, `stack` ends with `scope`.
+    fn push(&mut self, scope: Scope, stack: &[Scope], start: usize) {
+        let depth = stack.len() - 1;
+        self.crumbs.retain(|crumb| {
+            crumb.depth < depth
+                || crumb.depth == depth && !crumb.has_body && is_same_kind(crumb.scope, scope)
+        });
+        if is_scope(scope, "meta.block") {
+            if let Some(crumb) = self.crumbs.last_mut().filter(|c| c.depth + 1 == depth) {
+                crumb.has_body = true;
+            }
+        } else if is_scope(scope, "entity.name") {
+            self.push_definition(scope, &stack[..depth], start);
+        }
+    }
+
+    /// Handles a pop, `stack` holds the remaining scopes.
+    fn pop(&mut self, stack: &[Scope]) {
+        let depth = stack.len();
+        self.crumbs
+            .retain(|crumb| crumb.depth < depth || crumb.depth == depth && !crumb.has_body);
+    }
+
+    /// Adds the definition of the name that starts at `start`, `stack` holds
+    /// the scopes that enclose the name.
+    fn push_definition(&mut self, name: Scope, stack: &[Scope], start: usize) {
+        let Some(&meta) = stack.last() else {
+            return;
+        };
+        if !is_scope(meta, "meta") || is_scope(meta, "meta.block") {
+            return;
+        }
+        // The outermost of nested scopes of the same kind is the definition.
+        let depth = stack
+            .iter()
+            .rposition(|&scope| !is_same_kind(scope, meta))
+            .map_or(0, |i| i + 1);
+        let kind = name.build_string();
+        let kind = kind.split('.').nth(2).unwrap_or_default().to_string();
+        self.crumbs.retain(|crumb| crumb.depth < depth);
+        self.crumbs.push(Crumb {
+            depth,
+            scope: meta,
+            breadcrumb: Breadcrumb {
+                kind,
+                name: String::new(),
+            },
+            has_body: false,
+        });
+        self.pending_name = Some(start);
+    }
+}
+
+/// Whether `scope` is `prefix` or a more specific scope, e.g. `meta.block`
+/// is a prefix of `meta.block.rust`.
+fn is_scope(scope: Scope, prefix: &str) -> bool {
+    Scope::new(prefix).is_ok_and(|prefix| prefix.is_prefix_of(scope))
+}
+
+/// Whether two scopes share their first two atoms, e.g. `meta.function`.
+fn is_same_kind(a: Scope, b: Scope) -> bool {
+    a.len() >= 2 && b.len() >= 2 && (0..2).all(|i| a.atom_at(i) == b.atom_at(i))
+}
+
 #[derive(Debug)]
 pub enum SyntaxHighlighterError<'a> {
     ThemeNotFound(&'a str),
@@ -195,6 +299,81 @@ impl SyntaxHighlighter {
         Ok(self)
     }
 
+    /// Returns the definitions that enclose the cursor, the outermost
+    /// first, e.g. to show a breadcrumb such as `impl Foo > fn bar` above the
+    /// editor. Which definitions are found depends on the syntax; in Rust
+    /// these are modules, impls, traits, structs and functions among others.
+    ///
+    /// The text is parsed from the start up to the cursor on every call.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use edtui::{EditorState, Index2, Lines, SyntaxHighlighter};
+    ///
+    /// let mut state = EditorState::new(Lines::from("impl Foo {\n    fn bar() {\n        baz();\n    }\n}"));
+    /// state.cursor = Index2::new(2, 8);
+    ///
+    /// let syntax_highlighter = SyntaxHighlighter::new("dracula", "rs").unwrap();
+    /// let breadcrumb: Vec<String> = syntax_highlighter
+    ///     .breadcrumbs(&state)
+    ///     .into_iter()
+    ///     .map(|crumb| format!("{} {}", crumb.kind, crumb.name))
+    ///     .collect();
+    /// assert_eq!(breadcrumb.join(" > "), "impl Foo > function bar");
+    /// ```
+    #[must_use]
+    pub fn breadcrumbs(&self, state: &EditorState) -> Vec<Breadcrumb> {
+        let mut parse_state = ParseState::new(&self.syntax_ref);
+        let mut stack = ScopeStack::new();
+        let mut breadcrumbs = Breadcrumbs::default();
+
+        for row in 0..=state.cursor.row.min(state.lines.last_row_index()) {
+            let Some(chars) = state.lines.get(RowIndex::new(row)) else {
+                break;
+            };
+            let mut line: String = chars.iter().collect();
+            line.push('\n');
+            let Ok(ops) = parse_state.parse_line(&line, &self.syntax_set) else {
+                break;
+            };
+            let end = if row == state.cursor.row {
+                chars
+                    .iter()
+                    .take(state.cursor.col)
+                    .map(|ch| ch.len_utf8())
+                    .sum()
+            } else {
+                line.len()
+            };
+
+            for (i, (index, op)) in ops.iter().enumerate() {
+                if *index > end {
+                    break;
+                }
+                let result = stack.apply_with_hook(op, |op, scopes| match op {
+                    BasicScopeStackOp::Push(scope) => breadcrumbs.push(scope, scopes, *index),
+                    BasicScopeStackOp::Pop => breadcrumbs.pop(scopes),
+                });
+                if result.is_err() {
+                    return Vec::new();
+                }
+                if let Some(start) = breadcrumbs.pending_name.take() {
+                    let name_end = name_end(&stack, &ops[i + 1..]).unwrap_or(line.len());
+                    if let Some(crumb) = breadcrumbs.crumbs.last_mut() {
+                        crumb.breadcrumb.name = line[start..name_end].trim().to_string();
+                    }
+                }
+            }
+        }
+
+        breadcrumbs
+            .crumbs
+            .into_iter()
+            .map(|crumb| crumb.breadcrumb)
+            .collect()
+    }
+
     pub(super) fn highlight_line(&self, line: &str, base_style: &Style) -> Vec<InternalSpan> {
         // Highlight lines
         let mut highlight_lines = HighlightLines::new(&self.syntax_ref, &self.theme);
@@ -220,6 +399,19 @@ impl SyntaxHighlighter {
     }
 }
 
+/// Returns the position at which the scope on top of `stack` ends.
+fn name_end(stack: &ScopeStack, ops: &[(usize, ScopeStackOp)]) -> Option<usize> {
+    let depth = stack.len();
+    let mut stack = stack.clone();
+    for (index, op) in ops {
+        stack.apply(op).ok()?;
+        if stack.len() < depth {
+            return Some(*index);
+        }
+    }
+    None
+}
+
 impl Display for SyntaxHighlighterError<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -230,3 +422,41 @@ impl Display for SyntaxHighlighterError<'_> {
 }
 
 impl Error for SyntaxHighlighterError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index2, Lines};
+
+    fn breadcrumb_at(text: &str, cursor: Index2) -> Vec<String> {
+        let mut state = EditorState::new(Lines::from(text));
+        state.cursor = cursor;
+        SyntaxHighlighter::new("dracula", "rs")
+            .unwrap()
+            .breadcrumbs(&state)
+            .into_iter()
+            .map(|crumb| format!("{} {}", crumb.kind, crumb.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_breadcrumbs() {
+        let text = "mod a {\n    fn first() {}\n\n    fn second(x: u8) {\n        x;\n    }\n}\nfn main() {}";
+
+        assert_eq!(breadcrumb_at(text, Index2::new(2, 0)), vec!["module a"]);
+        assert_eq!(
+            breadcrumb_at(text, Index2::new(4, 8)),
+            vec!["module a", "function second"]
+        );
+        assert_eq!(
+            breadcrumb_at(text, Index2::new(3, 8)),
+            vec!["module a", "function second"]
+        );
+        assert_eq!(breadcrumb_at(text, Index2::new(6, 0)), vec!["module a"]);
+        assert_eq!(
+            breadcrumb_at(text, Index2::new(7, 11)),
+            vec!["function main"]
+        );
+        assert!(breadcrumb_at(text, Index2::new(0, 0)).is_empty());
+    }
+}
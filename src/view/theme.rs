@@ -1,5 +1,8 @@
-use super::EditorStatusLine;
-use ratatui_core::style::{Color, Style};
+use std::collections::HashMap;
+
+use super::{EditorSearchBar, EditorStatusLine};
+use crate::EditorMode;
+use ratatui_core::style::{Color, Modifier, Style};
 use ratatui_widgets::block::Block;
 
 /// The theme data of the Editor.
@@ -8,14 +11,45 @@ pub struct EditorTheme<'a> {
     pub base: Style,
     /// The cursor style
     pub cursor_style: Style,
+    /// Cursor styles that replace the cursor style in a mode
+    pub mode_cursor_styles: HashMap<EditorMode, Style>,
     /// The text style in visual mode when a text is selected
     pub selection_style: Style,
+    /// The style of the cell at the anchor of the selection, the end that
+    /// stays in place while the cursor extends it
+    pub selection_anchor_style: Option<Style>,
     /// The surrounding block
     pub block: Option<Block<'a>>,
     /// An optional [`StatusLine`] displaying the editor mode
     pub status_line: Option<EditorStatusLine>,
+    /// An optional [`EditorSearchBar`] displaying the query in search mode
+    pub search_bar: Option<EditorSearchBar>,
     /// Style for line numbers (subdued by default)
     pub line_numbers_style: Style,
+    /// Style for the occurrences of the word under the cursor
+    pub word_occurrence_style: Style,
+    /// Style for the in-progress IME composition
+    pub composition_style: Style,
+    /// Style for the placeholders of control characters, such as `^[`
+    pub control_char_style: Style,
+    /// Style for the color columns
+    pub color_column_style: Style,
+    /// Style for comment lines
+    pub comment_style: Style,
+    /// Style for validation errors
+    pub error_style: Style,
+    /// Style for the line flashed after jumping to a location
+    pub flash_style: Style,
+    /// Style for the text highlighted after yanking
+    pub yank_style: Style,
+    /// Style for the bookmark sign in the sign column
+    pub bookmark_style: Style,
+    /// Style for the indicator of wrapped lines
+    pub wrap_indicator_style: Style,
+    /// Style for the lines outside of the focus
+    pub dim_style: Style,
+    /// Styles for markdown rendering
+    pub markdown: MarkdownTheme,
 }
 
 impl Default for EditorTheme<'_> {
@@ -27,9 +61,24 @@ impl Default for EditorTheme<'_> {
             base: Style::default().bg(BLACK).fg(WHITE),
             block: None,
             cursor_style: Style::default().bg(WHITE).fg(BLACK),
+            mode_cursor_styles: HashMap::new(),
             selection_style: Style::default().bg(YELLOW).fg(BLACK),
+            selection_anchor_style: None,
             status_line: Some(EditorStatusLine::default()),
+            search_bar: None,
             line_numbers_style: Style::default().bg(BLACK).fg(GRAY),
+            word_occurrence_style: Style::default().bg(GRAY).fg(WHITE),
+            composition_style: Style::default().add_modifier(Modifier::UNDERLINED),
+            control_char_style: Style::default().fg(GRAY),
+            color_column_style: Style::default().bg(DARK_GRAY),
+            comment_style: Style::default().fg(GRAY),
+            error_style: Style::default().fg(RED).add_modifier(Modifier::UNDERLINED),
+            flash_style: Style::default().bg(YELLOW).fg(BLACK),
+            yank_style: Style::default().bg(YELLOW).fg(BLACK),
+            bookmark_style: Style::default().fg(YELLOW),
+            wrap_indicator_style: Style::default().fg(GRAY),
+            dim_style: Style::default().add_modifier(Modifier::DIM),
+            markdown: MarkdownTheme::default(),
         }
     }
 }
@@ -109,6 +158,36 @@ impl<'a> EditorTheme<'a> {
         self
     }
 
+    /// Sets the style of the cursor in `mode`, replacing the
+    /// [`EditorTheme::cursor_style`] in that mode, e.g. to tell the modes
+    /// apart without a status line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorMode, EditorTheme};
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let theme = EditorTheme::default()
+    ///     .mode_cursor_style(EditorMode::Normal, Style::default().bg(Color::Yellow))
+    ///     .mode_cursor_style(EditorMode::Insert, Style::default().bg(Color::Green))
+    ///     .mode_cursor_style(EditorMode::Visual, Style::default().bg(Color::LightRed));
+    /// ```
+    #[must_use]
+    pub fn mode_cursor_style(mut self, mode: EditorMode, style: Style) -> Self {
+        self.mode_cursor_styles.insert(mode, style);
+        self
+    }
+
+    /// Returns the style of the cursor in `mode`.
+    #[must_use]
+    pub fn cursor_style_for(&self, mode: EditorMode) -> Style {
+        self.mode_cursor_styles
+            .get(&mode)
+            .copied()
+            .unwrap_or(self.cursor_style)
+    }
+
     /// Hides the cursor.
     ///
     /// # Example
@@ -122,6 +201,7 @@ impl<'a> EditorTheme<'a> {
     #[must_use]
     pub fn hide_cursor(mut self) -> Self {
         self.cursor_style = self.base;
+        self.mode_cursor_styles.clear();
         self
     }
 
@@ -143,6 +223,15 @@ impl<'a> EditorTheme<'a> {
         self
     }
 
+    /// Sets the style of the cell at the anchor of the selection, the end
+    /// that stays in place while the cursor extends the selection. By
+    /// default the anchor is styled like the rest of the selection.
+    #[must_use]
+    pub fn selection_anchor_style(mut self, style: Style) -> Self {
+        self.selection_anchor_style = Some(style);
+        self
+    }
+
     /// This method allows you to customize the style of the [`StatusLine`]
     /// of the Editor. See [`StatusLine`] on how to modify its appearance.
     /// Use `hide_status_line` to hide the status line.
@@ -179,6 +268,24 @@ impl<'a> EditorTheme<'a> {
         self
     }
 
+    /// Shows the search query in an [`EditorSearchBar`] overlay while in
+    /// search mode, instead of in the status line. See [`EditorSearchBar`]
+    /// on how to modify its appearance and placement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::{EditorSearchBar, EditorTheme};
+    ///
+    /// let theme = EditorTheme::default()
+    ///     .search_bar(EditorSearchBar::default().prefix("Search: "));
+    /// ```
+    #[must_use]
+    pub fn search_bar(mut self, search_bar: EditorSearchBar) -> Self {
+        self.search_bar = Some(search_bar);
+        self
+    }
+
     /// Customize the style of the line numbers.
     /// By default, line numbers are displayed in a subdued gray color.
     ///
@@ -196,6 +303,198 @@ impl<'a> EditorTheme<'a> {
         self.line_numbers_style = style;
         self
     }
+
+    /// Customize the style of the occurrences of the word under the cursor.
+    /// See [`EditorView::highlight_word_occurrences`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use edtui::EditorTheme;
+    /// use ratatui::style::{Style, Modifier};
+    ///
+    /// let theme = EditorTheme::default()
+    ///     .word_occurrence_style(Style::default().add_modifier(Modifier::UNDERLINED));
+    /// ```
+    ///
+    /// [`EditorView::highlight_word_occurrences`]: crate::EditorView::highlight_word_occurrences
+    #[must_use]
+    pub fn word_occurrence_style(mut self, style: Style) -> Self {
+        self.word_occurrence_style = style;
+        self
+    }
+
+    /// Sets the style of the in-progress IME composition, see
+    /// [`EditorState::set_composition`]. Underlined by default.
+    ///
+    /// [`EditorState::set_composition`]: crate::EditorState::set_composition
+    #[must_use]
+    pub fn composition_style(mut self, style: Style) -> Self {
+        self.composition_style = style;
+        self
+    }
+
+    /// Sets the style of control characters. Control characters are shown
+    /// as placeholders, e.g. `^[` for escape or `<85>` for next line.
+    #[must_use]
+    pub fn control_char_style(mut self, style: Style) -> Self {
+        self.control_char_style = style;
+        self
+    }
+
+    /// Sets the style of the color columns, see
+    /// [`EditorView::color_columns`].
+    ///
+    /// [`EditorView::color_columns`]: crate::EditorView::color_columns
+    #[must_use]
+    pub fn color_column_style(mut self, style: Style) -> Self {
+        self.color_column_style = style;
+        self
+    }
+
+    /// Sets the style of comment lines, see [`EditorView::comment_char`].
+    /// Dimmed by default.
+    ///
+    /// [`EditorView::comment_char`]: crate::EditorView::comment_char
+    #[must_use]
+    pub fn comment_style(mut self, style: Style) -> Self {
+        self.comment_style = style;
+        self
+    }
+
+    /// Sets the style of validation errors, see
+    /// [`EditorState::set_validator`]. Underlined in red by default.
+    ///
+    /// [`EditorState::set_validator`]: crate::EditorState::set_validator
+    #[must_use]
+    pub fn error_style(mut self, style: Style) -> Self {
+        self.error_style = style;
+        self
+    }
+
+    /// Sets the style of the line that is flashed briefly after jumping to
+    /// an entry of the location list, see [`EditorState::set_locations`].
+    ///
+    /// [`EditorState::set_locations`]: crate::EditorState::set_locations
+    #[must_use]
+    pub fn flash_style(mut self, style: Style) -> Self {
+        self.flash_style = style;
+        self
+    }
+
+    /// Sets the style of the text that is highlighted briefly after
+    /// yanking, see [`EditorState::set_yank_flash`].
+    ///
+    /// [`EditorState::set_yank_flash`]: crate::EditorState::set_yank_flash
+    #[must_use]
+    pub fn yank_style(mut self, style: Style) -> Self {
+        self.yank_style = style;
+        self
+    }
+
+    /// Sets the style of the indicator at the start of the continuation rows
+    /// of wrapped lines, see [`WrapOptions::indicator`].
+    ///
+    /// [`WrapOptions::indicator`]: crate::WrapOptions::indicator
+    #[must_use]
+    pub fn wrap_indicator_style(mut self, style: Style) -> Self {
+        self.wrap_indicator_style = style;
+        self
+    }
+
+    /// Sets the style of the lines outside of the focus, see
+    /// [`EditorView::focus`]. The style is applied on top of the other
+    /// styles of these lines, and dims them by default.
+    ///
+    /// [`EditorView::focus`]: crate::EditorView::focus
+    #[must_use]
+    pub fn dim_style(mut self, style: Style) -> Self {
+        self.dim_style = style;
+        self
+    }
+
+    /// Sets the style of the bookmark sign in the sign column, see
+    /// [`EditorState::toggle_bookmark`].
+    ///
+    /// [`EditorState::toggle_bookmark`]: crate::EditorState::toggle_bookmark
+    #[must_use]
+    pub fn bookmark_style(mut self, style: Style) -> Self {
+        self.bookmark_style = style;
+        self
+    }
+
+    /// Sets the styles for markdown rendering, see [`EditorView::markdown`].
+    ///
+    /// [`EditorView::markdown`]: crate::EditorView::markdown
+    #[must_use]
+    pub fn markdown(mut self, markdown: MarkdownTheme) -> Self {
+        self.markdown = markdown;
+        self
+    }
+}
+
+/// The styles for markdown rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkdownTheme {
+    /// Style for headings
+    pub heading: Style,
+    /// Style for bold text
+    pub bold: Style,
+    /// Style for italic text
+    pub italic: Style,
+    /// Style for inline code and code fences
+    pub code: Style,
+    /// Style for list bullets
+    pub bullet: Style,
+}
+
+impl Default for MarkdownTheme {
+    fn default() -> Self {
+        Self {
+            heading: Style::default().fg(YELLOW).add_modifier(Modifier::BOLD),
+            bold: Style::default().add_modifier(Modifier::BOLD),
+            italic: Style::default().add_modifier(Modifier::ITALIC),
+            code: Style::default().fg(GRAY),
+            bullet: Style::default().fg(YELLOW),
+        }
+    }
+}
+
+impl MarkdownTheme {
+    /// Sets the style for headings.
+    #[must_use]
+    pub fn heading(mut self, style: Style) -> Self {
+        self.heading = style;
+        self
+    }
+
+    /// Sets the style for bold text.
+    #[must_use]
+    pub fn bold(mut self, style: Style) -> Self {
+        self.bold = style;
+        self
+    }
+
+    /// Sets the style for italic text.
+    #[must_use]
+    pub fn italic(mut self, style: Style) -> Self {
+        self.italic = style;
+        self
+    }
+
+    /// Sets the style for inline code and code fences.
+    #[must_use]
+    pub fn code(mut self, style: Style) -> Self {
+        self.code = style;
+        self
+    }
+
+    /// Sets the style for list bullets.
+    #[must_use]
+    pub fn bullet(mut self, style: Style) -> Self {
+        self.bullet = style;
+        self
+    }
 }
 
 pub(crate) const WHITE: Color = Color::Rgb(255, 255, 255);
@@ -203,3 +502,4 @@ pub(crate) const BLACK: Color = Color::Rgb(0, 0, 0);
 pub(crate) const DARK_GRAY: Color = Color::Rgb(16, 17, 22);
 pub(crate) const YELLOW: Color = Color::Rgb(250, 204, 21);
 pub(crate) const GRAY: Color = Color::Rgb(100, 100, 100);
+pub(crate) const RED: Color = Color::Rgb(239, 68, 68);